@@ -23,6 +23,15 @@ use crate::error::Error;
 use crate::platform::application as platform;
 use crate::util;
 
+/// Whether the system prefers light or dark colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Appearance {
+    /// Dark content on a light background.
+    Light,
+    /// Light content on a dark background.
+    Dark,
+}
+
 /// A top-level handler that is not associated with any window.
 ///
 /// This is most important on macOS, where it is entirely normal for
@@ -208,4 +217,16 @@ impl Application {
     pub fn get_locale() -> String {
         platform::Application::get_locale()
     }
+
+    /// Returns whether the system prefers light or dark colors.
+    ///
+    /// This is [`Appearance::Light`] on platforms that have no such preference, and
+    /// on X11, where the desktop's preference isn't read yet. Changes are reported
+    /// to every window with [`WinHandler::appearance_changed`], except on the web,
+    /// where the preference is only read when this is called.
+    ///
+    /// [`WinHandler::appearance_changed`]: crate::WinHandler::appearance_changed
+    pub fn get_appearance() -> Appearance {
+        platform::Application::get_appearance()
+    }
 }
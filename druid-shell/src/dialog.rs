@@ -21,7 +21,8 @@ use std::path::{Path, PathBuf};
 /// This path might point to a file or a directory.
 #[derive(Debug, Clone)]
 pub struct FileInfo {
-    pub(crate) path: PathBuf,
+    /// The chosen paths; never empty.
+    pub(crate) paths: Vec<PathBuf>,
 }
 
 /// Type of file dialog.
@@ -167,9 +168,32 @@ pub struct FileSpec {
 }
 
 impl FileInfo {
+    /// Create a `FileInfo` from the paths returned by a dialog, or `None` if there are none.
+    #[allow(dead_code)]
+    pub(crate) fn from_paths(paths: Vec<PathBuf>) -> Option<FileInfo> {
+        if paths.is_empty() {
+            None
+        } else {
+            Some(FileInfo { paths })
+        }
+    }
+
     /// Returns the underlying path.
+    ///
+    /// If several paths were chosen in a [`multi_selection`] dialog, this is the first one.
+    ///
+    /// [`multi_selection`]: FileDialogOptions::multi_selection
     pub fn path(&self) -> &Path {
-        &self.path
+        &self.paths[0]
+    }
+
+    /// Returns all the chosen paths.
+    ///
+    /// This has more than one path only if the dialog allowed [`multi_selection`].
+    ///
+    /// [`multi_selection`]: FileDialogOptions::multi_selection
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
     }
 }
 
@@ -207,7 +231,10 @@ impl FileDialogOptions {
 
     /// Set multiple items to be selectable.
     ///
-    /// This is only relevant for open dialogs.
+    /// This is only relevant for open dialogs. All the chosen paths are available
+    /// from [`FileInfo::paths`].
+    ///
+    /// [`FileInfo::paths`]: crate::FileInfo::paths
     pub fn multi_selection(mut self) -> Self {
         self.multi_selection = true;
         self
@@ -18,7 +18,7 @@ use std::borrow::Borrow;
 
 use tracing::warn;
 
-use crate::{IntoKey, KbKey, KeyEvent, Modifiers};
+use crate::{Code, IntoKey, KbKey, KeyEvent, Modifiers};
 
 // TODO: fix docstring
 
@@ -59,6 +59,8 @@ use crate::{IntoKey, KbKey, KeyEvent, Modifiers};
 pub struct HotKey {
     pub(crate) mods: RawMods,
     pub(crate) key: KbKey,
+    /// If set, the physical key to match instead of `key`.
+    pub(crate) code: Option<Code>,
 }
 
 impl HotKey {
@@ -88,10 +90,38 @@ impl HotKey {
         HotKey {
             mods: mods.into().unwrap_or(RawMods::None),
             key: key.into_key(),
+            code: None,
         }
         .warn_if_needed()
     }
 
+    /// Create a new hotkey that matches a physical key, whatever it is
+    /// labelled with in the current keyboard layout.
+    ///
+    /// Hotkeys for letters should usually use [`HotKey::new`], so that they
+    /// follow the layout. This is for keys that are chosen for where they
+    /// are, such as the key left of `1` (`` ` `` on a US keyboard).
+    ///
+    /// Such hotkeys can't be shown on menu items.
+    ///
+    /// # Examples
+    /// ```
+    /// use druid_shell::{Code, HotKey, KeyEvent, RawMods};
+    ///
+    /// let toggle_console = HotKey::from_code(RawMods::Ctrl, Code::Backquote);
+    ///
+    /// let mut event = KeyEvent::for_test(RawMods::Ctrl, "ö");
+    /// event.code = Code::Backquote;
+    /// assert!(toggle_console.matches(event));
+    /// ```
+    pub fn from_code(mods: impl Into<Option<RawMods>>, code: Code) -> Self {
+        HotKey {
+            mods: mods.into().unwrap_or(RawMods::None),
+            key: KbKey::Unidentified,
+            code: Some(code),
+        }
+    }
+
     //TODO: figure out if we need to be normalizing case or something?
     fn warn_if_needed(self) -> Self {
         if let KbKey::Character(s) = &self.key {
@@ -114,7 +144,11 @@ impl HotKey {
         // Should be a const but const bit_or doesn't work here.
         let base_mods = Modifiers::SHIFT | Modifiers::CONTROL | Modifiers::ALT | Modifiers::META;
         let event = event.borrow();
-        self.mods == event.mods & base_mods && self.key == event.key
+        let key_matches = match self.code {
+            Some(code) => code == event.code,
+            None => self.key == event.key,
+        };
+        self.mods == event.mods & base_mods && key_matches
     }
 }
 
@@ -63,7 +63,7 @@ mod window;
 
 pub mod text;
 
-pub use application::{AppHandler, Application};
+pub use application::{AppHandler, Appearance, Application};
 pub use clipboard::{Clipboard, ClipboardFormat, FormatId};
 pub use common_util::Counter;
 pub use dialog::{FileDialogOptions, FileInfo, FileSpec};
@@ -254,14 +254,23 @@ pub enum Cursor {
     Arrow,
     /// A vertical I-beam, for indicating insertion points in text.
     IBeam,
+    /// A pointing hand, for indicating links and other clickable things.
     Pointer,
+    /// A crosshair, for precise selection of a point.
     Crosshair,
 
     #[deprecated(note = "this will be removed in future because it is not available on windows")]
     OpenHand,
+    /// A circle with a line through it, for indicating that an action isn't available.
     NotAllowed,
+    /// A horizontal double arrow, for indicating that something can be resized horizontally.
     ResizeLeftRight,
+    /// A vertical double arrow, for indicating that something can be resized vertically.
     ResizeUpDown,
+    /// A custom cursor, created with [`WindowHandle::make_cursor`].
+    ///
+    /// [`WindowHandle::make_cursor`]: crate::WindowHandle::make_cursor
+    //
     // The platform cursor should be small. Any image data that it uses should be shared (i.e.
     // behind an `Arc` or using a platform API that does the sharing).
     Custom(platform::window::CustomCursor),
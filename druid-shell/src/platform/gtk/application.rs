@@ -16,9 +16,9 @@
 
 use gio::prelude::ApplicationExtManual;
 use gio::{ApplicationExt, ApplicationFlags, Cancellable};
-use gtk::{Application as GtkApplication, GtkApplicationExt};
+use gtk::{Application as GtkApplication, GtkApplicationExt, Settings, SettingsExt};
 
-use crate::application::AppHandler;
+use crate::application::{AppHandler, Appearance};
 
 use super::clipboard::Clipboard;
 use super::error::Error;
@@ -84,4 +84,25 @@ impl Application {
     pub fn get_locale() -> String {
         glib::get_language_names()[0].as_str().into()
     }
+
+    pub fn get_appearance() -> Appearance {
+        Settings::get_default()
+            .map(|settings| appearance_from_settings(&settings))
+            .unwrap_or(Appearance::Light)
+    }
+}
+
+/// GTK has no single setting for dark mode: either the application asks for
+/// the dark variant of the theme, or the user picked a dark theme, which by
+/// convention has a name like `Adwaita-dark`.
+pub(crate) fn appearance_from_settings(settings: &Settings) -> Appearance {
+    let theme_name = settings
+        .get_property_gtk_theme_name()
+        .map(|name| name.to_lowercase())
+        .unwrap_or_default();
+    if settings.get_property_gtk_application_prefer_dark_theme() || theme_name.ends_with("-dark") {
+        Appearance::Dark
+    } else {
+        Appearance::Light
+    }
 }
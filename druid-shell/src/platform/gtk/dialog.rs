@@ -14,7 +14,7 @@
 
 //! File open/save dialogs, GTK implementation.
 
-use std::ffi::OsString;
+use std::path::PathBuf;
 
 use anyhow::anyhow;
 use gtk::{FileChooserAction, FileChooserExt, FileFilter, NativeDialogExt, ResponseType, Window};
@@ -35,7 +35,7 @@ pub(crate) fn get_file_dialog_path(
     window: &Window,
     ty: FileDialogType,
     options: FileDialogOptions,
-) -> Result<OsString, Error> {
+) -> Result<Vec<PathBuf>, Error> {
     // TODO: support message localization
 
     let (title, action) = match (ty, options.select_directories) {
@@ -96,9 +96,9 @@ pub(crate) fn get_file_dialog_path(
     let result = dialog.run();
 
     let result = match result {
-        ResponseType::Accept => match dialog.get_filename() {
-            Some(path) => Ok(path.into_os_string()),
-            None => Err(anyhow!("No path received for filename")),
+        ResponseType::Accept => match dialog.get_filenames() {
+            paths if !paths.is_empty() => Ok(paths),
+            _ => Err(anyhow!("No path received for filename")),
         },
         ResponseType::Cancel => Err(anyhow!("Dialog was deleted")),
         _ => {
@@ -14,6 +14,7 @@
 
 //! GTK Monitors and Screen information.
 
+use crate::scale::Scale;
 use crate::screen::Monitor;
 use gdk::Display;
 use kurbo::{Point, Rect, Size};
@@ -27,6 +28,7 @@ fn translate_gdk_rectangle(r: gdk::Rectangle) -> Rect {
 
 fn translate_gdk_monitor(mon: gdk::Monitor) -> Monitor {
     let area = translate_gdk_rectangle(mon.get_geometry());
+    let scale = mon.get_scale_factor() as f64;
     Monitor::new(
         mon.is_primary(),
         area,
@@ -34,6 +36,7 @@ fn translate_gdk_monitor(mon: gdk::Monitor) -> Monitor {
             .map(translate_gdk_rectangle)
             .unwrap_or(area),
     )
+    .with_scale(Scale::new(scale, scale))
 }
 
 pub(crate) fn get_monitors() -> Vec<Monitor> {
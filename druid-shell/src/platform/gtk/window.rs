@@ -19,6 +19,7 @@ use std::convert::{TryFrom, TryInto};
 use std::ffi::c_void;
 use std::os::raw::{c_int, c_uint};
 use std::panic::Location;
+use std::path::PathBuf;
 use std::ptr;
 use std::slice;
 use std::sync::{Arc, Mutex, Weak};
@@ -26,10 +27,15 @@ use std::time::Instant;
 
 use anyhow::anyhow;
 use cairo::Surface;
-use gdk::{EventKey, EventMask, ModifierType, ScrollDirection, WindowExt, WindowTypeHint};
+use gdk::{
+    DragAction, EventKey, EventMask, ModifierType, ScrollDirection, WindowExt, WindowTypeHint,
+};
 use gio::ApplicationExt;
 use gtk::prelude::*;
-use gtk::{AccelGroup, ApplicationWindow, DrawingArea, SettingsExt};
+use gtk::{
+    AccelGroup, ApplicationWindow, DestDefaults, DrawingArea, Settings, SettingsExt, TargetEntry,
+    TargetFlags,
+};
 use tracing::{error, warn};
 
 #[cfg(feature = "raw-win-handle")]
@@ -51,7 +57,7 @@ use crate::window::{
     self, FileDialogToken, IdleToken, TextFieldToken, TimerToken, WinHandler, WindowLevel,
 };
 
-use super::application::Application;
+use super::application::{appearance_from_settings, Application};
 use super::dialog;
 use super::keycodes;
 use super::menu::Menu;
@@ -177,6 +183,18 @@ pub(crate) struct WindowState {
     click_counter: ClickCounter,
     active_text_input: Cell<Option<TextFieldToken>>,
     deferred_queue: RefCell<Vec<DeferredOp>>,
+    file_drag: RefCell<FileDrag>,
+}
+
+/// The files that are being dragged over the window.
+#[derive(Default)]
+struct FileDrag {
+    /// The paths of the files, once GTK has given them to us.
+    paths: Option<Vec<PathBuf>>,
+    /// The position of the cursor, in display points.
+    position: Point,
+    /// Whether the files have been dropped, and are waiting for their paths.
+    dropped: bool,
 }
 
 #[derive(Clone, PartialEq)]
@@ -296,6 +314,7 @@ impl WindowBuilder {
             click_counter: ClickCounter::default(),
             active_text_input: Cell::new(None),
             deferred_queue: RefCell::new(Vec::new()),
+            file_drag: RefCell::new(FileDrag::default()),
         });
 
         self.app
@@ -671,6 +690,139 @@ impl WindowBuilder {
                 Inhibit(true)
             }));
 
+        // The settings are shared by all windows; once this window is gone,
+        // its handlers do nothing.
+        if let Some(settings) = Settings::get_default() {
+            let appearance_changed = clone!(handle => move |settings: &Settings| {
+                if let Some(state) = handle.state.upgrade() {
+                    let appearance = appearance_from_settings(settings);
+                    state.with_handler(|h| h.appearance_changed(appearance));
+                }
+            });
+            settings.connect_property_gtk_theme_name_notify(appearance_changed.clone());
+            settings.connect_property_gtk_application_prefer_dark_theme_notify(appearance_changed);
+        }
+
+        // Files are dragged in as a list of URIs. We don't use any of the default behaviors,
+        // because we want to know where the cursor is while the files are dragged.
+        let uri_list = TargetEntry::new("text/uri-list", TargetFlags::OTHER_APP, 0);
+        win_state
+            .drawing_area
+            .drag_dest_set(DestDefaults::empty(), &[uri_list], DragAction::COPY);
+
+        win_state.drawing_area.connect_drag_motion(
+            clone!(handle => move |widget, context, x, y, time| {
+                if let Some(state) = handle.state.upgrade() {
+                    let position = Point::new(x as f64, y as f64).to_dp(state.scale.get());
+                    let paths = match state.file_drag.try_borrow_mut() {
+                        Ok(mut drag) => {
+                            drag.position = position;
+                            drag.paths.clone()
+                        }
+                        Err(_) => {
+                            error!("file drag state already borrowed");
+                            return Inhibit(false);
+                        }
+                    };
+                    match paths {
+                        Some(paths) => {
+                            state.with_handler(|h| h.file_drop_hover(position, &paths));
+                        }
+                        // we get the paths, and send the hover, in drag-data-received
+                        None => request_uri_list(widget, context, time),
+                    }
+                    context.drag_status(DragAction::COPY, time);
+                }
+                Inhibit(true)
+            }),
+        );
+
+        win_state.drawing_area.connect_drag_leave(
+            clone!(handle => move |_widget, _context, _time| {
+                // GTK also sends drag-leave right before drag-drop, so we wait to see whether the
+                // files are dropped before reporting that the drag was cancelled.
+                let handle = handle.clone();
+                glib::idle_add_local(move || {
+                    if let Some(state) = handle.state.upgrade() {
+                        let cancelled = match state.file_drag.try_borrow_mut() {
+                            Ok(mut drag) if !drag.dropped => {
+                                *drag = FileDrag::default();
+                                true
+                            }
+                            Ok(_) => false,
+                            Err(_) => {
+                                error!("file drag state already borrowed");
+                                false
+                            }
+                        };
+                        if cancelled {
+                            state.with_handler(|h| h.file_drop_cancelled());
+                        }
+                    }
+                    glib::Continue(false)
+                });
+            }),
+        );
+
+        win_state.drawing_area.connect_drag_drop(
+            clone!(handle => move |widget, context, x, y, time| {
+                if let Some(state) = handle.state.upgrade() {
+                    let position = Point::new(x as f64, y as f64).to_dp(state.scale.get());
+                    let paths = match state.file_drag.try_borrow_mut() {
+                        Ok(mut drag) => {
+                            drag.position = position;
+                            drag.dropped = true;
+                            drag.paths.take()
+                        }
+                        Err(_) => {
+                            error!("file drag state already borrowed");
+                            return Inhibit(false);
+                        }
+                    };
+                    match paths {
+                        Some(paths) => {
+                            finish_file_drop(&state, paths);
+                            context.drag_finish(true, false, time);
+                        }
+                        // we finish the drop once we have the paths, in drag-data-received
+                        None => request_uri_list(widget, context, time),
+                    }
+                }
+                Inhibit(true)
+            }),
+        );
+
+        win_state.drawing_area.connect_drag_data_received(
+            clone!(handle => move |_widget, context, _x, _y, selection, _info, time| {
+                if let Some(state) = handle.state.upgrade() {
+                    let paths: Vec<PathBuf> = selection
+                        .get_uris()
+                        .iter()
+                        .filter_map(|uri| glib::filename_from_uri(uri).ok())
+                        .map(|(path, _hostname)| path)
+                        .collect();
+                    let (dropped, position) = match state.file_drag.try_borrow_mut() {
+                        Ok(mut drag) => {
+                            if !drag.dropped {
+                                drag.paths = Some(paths.clone());
+                            }
+                            (drag.dropped, drag.position)
+                        }
+                        Err(_) => {
+                            error!("file drag state already borrowed");
+                            return;
+                        }
+                    };
+                    if dropped {
+                        finish_file_drop(&state, paths);
+                        context.drag_finish(true, false, time);
+                    } else {
+                        state.with_handler(|h| h.file_drop_hover(position, &paths));
+                    }
+                }
+            }),
+        );
+
         win_state
             .window
             .connect_delete_event(clone!(handle => move |_widget, _ev| {
@@ -715,6 +867,16 @@ impl WindowBuilder {
 
 impl WindowState {
     #[track_caller]
+    fn take_file_drag(&self) -> Option<FileDrag> {
+        match self.file_drag.try_borrow_mut() {
+            Ok(mut drag) => Some(std::mem::take(&mut *drag)),
+            Err(_) => {
+                error!("file drag state already borrowed");
+                None
+            }
+        }
+    }
+
     fn with_handler<T, F: FnOnce(&mut dyn WinHandler) -> T>(&self, f: F) -> Option<T> {
         if self.invalid.try_borrow_mut().is_err() || self.surface.try_borrow_mut().is_err() {
             error!("other RefCells were borrowed when calling into the handler");
@@ -812,7 +974,7 @@ impl WindowState {
                         options,
                     )
                     .ok()
-                    .map(|s| FileInfo { path: s.into() });
+                    .and_then(FileInfo::from_paths);
                     self.with_handler(|h| h.open_file(token, file_info));
                 }
                 DeferredOp::SaveAs(options, token) => {
@@ -822,7 +984,7 @@ impl WindowState {
                         options,
                     )
                     .ok()
-                    .map(|s| FileInfo { path: s.into() });
+                    .and_then(FileInfo::from_paths);
                     self.with_handler(|h| h.save_as(token, file_info));
                 }
                 DeferredOp::ContextMenu(menu, handle) => {
@@ -1255,6 +1417,18 @@ fn run_idle(state: &Arc<WindowState>) -> glib::source::Continue {
     glib::source::Continue(false)
 }
 
+/// Asks the source of a drag for the list of files that are being dragged.
+fn request_uri_list(widget: &DrawingArea, context: &gdk::DragContext, time: u32) {
+    widget.drag_get_data(context, &gdk::Atom::intern("text/uri-list"), time);
+}
+
+/// Sends the dropped files to the handler, and forgets about the drag.
+fn finish_file_drop(state: &WindowState, paths: Vec<PathBuf>) {
+    if let Some(drag) = state.take_file_drag() {
+        state.with_handler(|h| h.file_dropped(drag.position, paths));
+    }
+}
+
 fn make_gdk_cursor(cursor: &Cursor, gdk_window: &gdk::Window) -> Option<gdk::Cursor> {
     if let Cursor::Custom(custom) = cursor {
         Some(custom.0.clone())
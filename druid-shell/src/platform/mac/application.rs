@@ -21,14 +21,14 @@ use std::ffi::c_void;
 use std::rc::Rc;
 
 use cocoa::appkit::{NSApp, NSApplication, NSApplicationActivationPolicyRegular};
-use cocoa::base::{id, nil, NO, YES};
+use cocoa::base::{id, nil, BOOL, NO, YES};
 use cocoa::foundation::{NSArray, NSAutoreleasePool};
 use lazy_static::lazy_static;
 use objc::declare::ClassDecl;
 use objc::runtime::{Class, Object, Sel};
 use objc::{class, msg_send, sel, sel_impl};
 
-use crate::application::AppHandler;
+use crate::application::{AppHandler, Appearance};
 
 use super::clipboard::Clipboard;
 use super::error::Error;
@@ -140,6 +140,36 @@ impl Application {
             locale
         }
     }
+
+    pub fn get_appearance() -> Appearance {
+        unsafe {
+            let app = NSApp();
+            // `effectiveAppearance` is only available since macOS 10.14,
+            // which also introduced dark mode.
+            let responds: BOOL = msg_send![app, respondsToSelector: sel!(effectiveAppearance)];
+            if responds == NO {
+                return Appearance::Light;
+            }
+            let appearance: id = msg_send![app, effectiveAppearance];
+            appearance_from_nsappearance(appearance)
+        }
+    }
+}
+
+/// Returns whether an `NSAppearance` is one of the dark ones, like `NSAppearanceNameDarkAqua`.
+pub(crate) fn appearance_from_nsappearance(appearance: id) -> Appearance {
+    if appearance == nil {
+        return Appearance::Light;
+    }
+    let name = unsafe {
+        let name: id = msg_send![appearance, name];
+        util::from_nsstring(name)
+    };
+    if name.contains("Dark") {
+        Appearance::Dark
+    } else {
+        Appearance::Light
+    }
 }
 
 struct DelegateState {
@@ -16,7 +16,7 @@
 
 #![allow(non_upper_case_globals, clippy::clippy::upper_case_acronyms)]
 
-use std::ffi::OsString;
+use std::path::PathBuf;
 
 use cocoa::base::{id, nil, NO, YES};
 use cocoa::foundation::{NSArray, NSAutoreleasePool, NSInteger, NSURL};
@@ -29,13 +29,31 @@ pub(crate) type NSModalResponse = NSInteger;
 const NSModalResponseOK: NSInteger = 1;
 const NSModalResponseCancel: NSInteger = 0;
 
-pub(crate) unsafe fn get_path(panel: id, result: NSModalResponse) -> Option<OsString> {
+pub(crate) unsafe fn get_paths(
+    panel: id,
+    ty: FileDialogType,
+    result: NSModalResponse,
+) -> Option<Vec<PathBuf>> {
     match result {
         NSModalResponseOK => {
-            let url: id = msg_send![panel, URL];
-            let path: id = msg_send![url, path];
-            let path: OsString = from_nsstring(path).into();
-            Some(path)
+            let urls: Vec<id> = match ty {
+                // an open panel can have several urls, if multiple selection is enabled
+                FileDialogType::Open => {
+                    let urls: id = msg_send![panel, URLs];
+                    (0..urls.count())
+                        .map(|idx| urls.objectAtIndex(idx))
+                        .collect()
+                }
+                FileDialogType::Save => vec![msg_send![panel, URL]],
+            };
+            let paths = urls
+                .into_iter()
+                .map(|url| {
+                    let path: id = msg_send![url, path];
+                    PathBuf::from(from_nsstring(path))
+                })
+                .collect();
+            Some(paths)
         }
         NSModalResponseCancel => None,
         _ => unreachable!(),
@@ -18,6 +18,7 @@
 
 use std::ffi::c_void;
 use std::mem;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex, Weak};
 use std::time::Instant;
 
@@ -48,12 +49,12 @@ use crate::piet::{Piet, PietText, RenderContext};
 use super::appkit::{
     NSRunLoopCommonModes, NSTrackingArea, NSTrackingAreaOptions, NSView as NSViewExt,
 };
-use super::application::Application;
+use super::application::{appearance_from_nsappearance, Application};
 use super::dialog;
 use super::keyboard::{make_modifiers, KeyboardState};
 use super::menu::Menu;
 use super::text_input::NSRange;
-use super::util::{assert_main_thread, make_nsstring};
+use super::util::{assert_main_thread, from_nsstring, make_nsstring};
 use crate::common_util::IdleCallback;
 use crate::dialog::{FileDialogOptions, FileDialogType, FileInfo};
 use crate::keyboard_types::KeyState;
@@ -360,6 +361,10 @@ lazy_static! {
             sel!(windowDidResignKey:),
             window_did_resign_key as extern "C" fn(&mut Object, Sel, id),
         );
+        decl.add_method(
+            sel!(viewDidChangeEffectiveAppearance),
+            view_did_change_effective_appearance as extern "C" fn(&mut Object, Sel),
+        );
         decl.add_method(
             sel!(setFrameSize:),
             set_frame_size as extern "C" fn(&mut Object, Sel, NSSize),
@@ -453,6 +458,24 @@ lazy_static! {
             window_will_close as extern "C" fn(&mut Object, Sel, id),
         );
 
+        // methods for NSDraggingDestination
+        decl.add_method(
+            sel!(draggingEntered:),
+            dragging_updated as extern "C" fn(&mut Object, Sel, id) -> NSUInteger,
+        );
+        decl.add_method(
+            sel!(draggingUpdated:),
+            dragging_updated as extern "C" fn(&mut Object, Sel, id) -> NSUInteger,
+        );
+        decl.add_method(
+            sel!(draggingExited:),
+            dragging_exited as extern "C" fn(&mut Object, Sel, id),
+        );
+        decl.add_method(
+            sel!(performDragOperation:),
+            perform_drag_operation as extern "C" fn(&mut Object, Sel, id) -> BOOL,
+        );
+
         // methods for NSTextInputClient
         decl.add_method(sel!(hasMarkedText), super::text_input::has_marked_text as extern fn(&mut Object, Sel) -> BOOL);
         decl.add_method(
@@ -551,6 +574,9 @@ fn make_view(handler: Box<dyn WinHandler>) -> (id, Weak<Mutex<Vec<IdleKind>>>) {
             .autorelease();
         view.addTrackingArea(tracking_area);
 
+        let dragged_types = NSArray::arrayWithObjects(nil, &[make_nsstring(FILENAMES_PBOARD_TYPE)]);
+        let () = msg_send![view, registerForDraggedTypes: dragged_types];
+
         (view.autorelease(), queue_handle)
     }
 }
@@ -736,6 +762,70 @@ extern "C" fn mouse_leave(this: &mut Object, _: Sel, _nsevent: id) {
     }
 }
 
+/// The pasteboard type of a list of file paths.
+const FILENAMES_PBOARD_TYPE: &str = "NSFilenamesPboardType";
+/// `NSDragOperationNone`.
+const DRAG_OPERATION_NONE: NSUInteger = 0;
+/// `NSDragOperationCopy`.
+const DRAG_OPERATION_COPY: NSUInteger = 1;
+
+/// Gets the position of a drag, and the paths of the files being dragged.
+fn dragged_files(view: id, sender: id) -> (Point, Vec<PathBuf>) {
+    unsafe {
+        let point: NSPoint = msg_send![sender, draggingLocation];
+        let view_point = view.convertPoint_fromView_(point, nil);
+        let pos = Point::new(view_point.x as f64, view_point.y as f64);
+
+        let pasteboard: id = msg_send![sender, draggingPasteboard];
+        let filenames: id = msg_send![
+            pasteboard,
+            propertyListForType: make_nsstring(FILENAMES_PBOARD_TYPE)
+        ];
+        let paths = if filenames == nil {
+            Vec::new()
+        } else {
+            (0..filenames.count())
+                .map(|i| PathBuf::from(from_nsstring(filenames.objectAtIndex(i))))
+                .collect()
+        };
+        (pos, paths)
+    }
+}
+
+extern "C" fn dragging_updated(this: &mut Object, _: Sel, sender: id) -> NSUInteger {
+    unsafe {
+        let view_state: *mut c_void = *this.get_ivar("viewState");
+        let view_state = &mut *(view_state as *mut ViewState);
+        let (pos, paths) = dragged_files(this as id, sender);
+        if paths.is_empty() {
+            return DRAG_OPERATION_NONE;
+        }
+        (*view_state).handler.file_drop_hover(pos, &paths);
+        DRAG_OPERATION_COPY
+    }
+}
+
+extern "C" fn dragging_exited(this: &mut Object, _: Sel, _sender: id) {
+    unsafe {
+        let view_state: *mut c_void = *this.get_ivar("viewState");
+        let view_state = &mut *(view_state as *mut ViewState);
+        (*view_state).handler.file_drop_cancelled();
+    }
+}
+
+extern "C" fn perform_drag_operation(this: &mut Object, _: Sel, sender: id) -> BOOL {
+    unsafe {
+        let view_state: *mut c_void = *this.get_ivar("viewState");
+        let view_state = &mut *(view_state as *mut ViewState);
+        let (pos, paths) = dragged_files(this as id, sender);
+        if paths.is_empty() {
+            return NO;
+        }
+        (*view_state).handler.file_dropped(pos, paths);
+        YES
+    }
+}
+
 extern "C" fn scroll_wheel(this: &mut Object, _: Sel, nsevent: id) {
     unsafe {
         let view_state: *mut c_void = *this.get_ivar("viewState");
@@ -966,6 +1056,17 @@ extern "C" fn window_did_resign_key(this: &mut Object, _: Sel, _notification: id
     }
 }
 
+extern "C" fn view_did_change_effective_appearance(this: &mut Object, _: Sel) {
+    unsafe {
+        let view_state: *mut c_void = *this.get_ivar("viewState");
+        let view_state = &mut *(view_state as *mut ViewState);
+        let appearance: id = msg_send![this, effectiveAppearance];
+        (*view_state)
+            .handler
+            .appearance_changed(appearance_from_nsappearance(appearance));
+    }
+}
+
 extern "C" fn window_should_close(this: &mut Object, _: Sel, _window: id) -> BOOL {
     unsafe {
         let view_state: *mut c_void = *this.get_ivar("viewState");
@@ -1170,7 +1271,7 @@ impl WindowHandle {
         unsafe {
             let panel = dialog::build_panel(ty, opts);
             let block = ConcreteBlock::new(move |response: dialog::NSModalResponse| {
-                let url = dialog::get_path(panel, response).map(|s| FileInfo { path: s.into() });
+                let url = dialog::get_paths(panel, ty, response).and_then(FileInfo::from_paths);
                 let view = self_clone.nsview.load();
                 if let Some(view) = (*view).as_ref() {
                     let view_state: *mut c_void = *view.get_ivar("viewState");
@@ -14,7 +14,7 @@
 
 //! Web implementation of features at the application scope.
 
-use crate::application::AppHandler;
+use crate::application::{AppHandler, Appearance};
 
 use super::clipboard::Clipboard;
 use super::error::Error;
@@ -40,4 +40,15 @@ impl Application {
             .and_then(|w| w.navigator().language())
             .unwrap_or_else(|| "en-US".into())
     }
+
+    pub fn get_appearance() -> Appearance {
+        let dark = web_sys::window()
+            .and_then(|w| w.match_media("(prefers-color-scheme: dark)").ok().flatten())
+            .map_or(false, |query| query.matches());
+        if dark {
+            Appearance::Dark
+        } else {
+            Appearance::Light
+        }
+    }
 }
@@ -23,7 +23,7 @@
 #![allow(non_upper_case_globals)]
 
 use std::convert::TryInto;
-use std::ffi::OsString;
+use std::path::PathBuf;
 use std::ptr::null_mut;
 
 use winapi::ctypes::c_void;
@@ -76,7 +76,7 @@ pub(crate) unsafe fn get_file_dialog_path(
     hwnd_owner: HWND,
     ty: FileDialogType,
     options: FileDialogOptions,
-) -> Result<OsString, Error> {
+) -> Result<Vec<PathBuf>, Error> {
     let mut pfd: *mut IFileDialog = null_mut();
     let (class, id) = match ty {
         FileDialogType::Open => (&CLSID_FileOpenDialog, IFileOpenDialog::uuidof()),
@@ -99,7 +99,8 @@ pub(crate) unsafe fn get_file_dialog_path(
     if options.select_directories {
         flags |= FOS_PICKFOLDERS;
     }
-    if options.multi_selection {
+    let multi_selection = ty == FileDialogType::Open && options.multi_selection;
+    if multi_selection {
         flags |= FOS_ALLOWMULTISELECT;
     }
 
@@ -165,13 +166,35 @@ pub(crate) unsafe fn get_file_dialog_path(
 
     // show the dialog
     as_result(file_dialog.Show(hwnd_owner))?;
+
+    // `GetResult` only returns the first item of a multiple selection
+    if multi_selection {
+        let open_dialog = file_dialog.cast::<IFileOpenDialog>()?;
+        let mut results_ptr: *mut IShellItemArray = null_mut();
+        as_result(open_dialog.GetResults(&mut results_ptr))?;
+        let results = ComPtr::from_raw(results_ptr);
+        let mut count: DWORD = 0;
+        as_result(results.GetCount(&mut count))?;
+        let mut paths = Vec::with_capacity(count as usize);
+        for idx in 0..count {
+            let mut item_ptr: *mut IShellItem = null_mut();
+            as_result(results.GetItemAt(idx, &mut item_ptr))?;
+            paths.push(shell_item_path(&ComPtr::from_raw(item_ptr))?);
+        }
+        return Ok(paths);
+    }
+
     let mut result_ptr: *mut IShellItem = null_mut();
     as_result(file_dialog.GetResult(&mut result_ptr))?;
     let shell_item = ComPtr::from_raw(result_ptr);
+    Ok(vec![shell_item_path(&shell_item)?])
+}
+
+/// Returns the file system path of a shell item.
+unsafe fn shell_item_path(shell_item: &ComPtr<IShellItem>) -> Result<PathBuf, Error> {
     let mut display_name: LPWSTR = null_mut();
     as_result(shell_item.GetDisplayName(SIGDN_FILESYSPATH, &mut display_name))?;
     let filename = display_name.to_os_string();
     CoTaskMemFree(display_name as LPVOID);
-
-    Ok(filename)
+    Ok(filename.into())
 }
@@ -22,9 +22,12 @@ use winapi::shared::minwindef::*;
 use winapi::shared::windef::*;
 use winapi::shared::winerror::*;
 use winapi::um::errhandlingapi::GetLastError;
+use winapi::um::shellscalingapi::MDT_EFFECTIVE_DPI;
 use winapi::um::winuser::*;
 
+use super::util::OPTIONAL_FUNCTIONS;
 use crate::kurbo::Rect;
+use crate::scale::Scale;
 use crate::screen::Monitor;
 
 unsafe extern "system" fn monitorenumproc(
@@ -64,8 +67,19 @@ unsafe extern "system" fn monitorenumproc(
         info.rcWork.right as f64,
         info.rcWork.bottom as f64,
     );
+    // 96 DPI is a scale of 1.0; without GetDpiForMonitor (before Windows 8.1)
+    // every monitor uses the system DPI, which we don't know here.
+    let mut scale = Scale::default();
+    if let Some(func) = OPTIONAL_FUNCTIONS.GetDpiForMonitor {
+        let mut dpi_x = 0;
+        let mut dpi_y = 0;
+        func(hmonitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y);
+        if dpi_x != 0 && dpi_y != 0 {
+            scale = Scale::new(dpi_x as f64 / 96.0, dpi_y as f64 / 96.0);
+        }
+    }
     let monitors = _lparam as *mut Vec<Monitor>;
-    (*monitors).push(Monitor::new(primary, rect, work_rect));
+    (*monitors).push(Monitor::new(primary, rect, work_rect).with_scale(scale));
     TRUE
 }
 
@@ -19,6 +19,7 @@
 use std::cell::{Cell, RefCell};
 use std::mem;
 use std::panic::Location;
+use std::path::PathBuf;
 use std::ptr::{null, null_mut};
 use std::rc::{Rc, Weak};
 use std::sync::{Arc, Mutex};
@@ -37,6 +38,7 @@ use winapi::shared::winerror::*;
 use winapi::um::dcomp::{IDCompositionDevice, IDCompositionTarget, IDCompositionVisual};
 use winapi::um::dwmapi::DwmExtendFrameIntoClientArea;
 use winapi::um::errhandlingapi::GetLastError;
+use winapi::um::shellapi::{DragFinish, DragQueryFileW, DragQueryPoint, HDROP};
 use winapi::um::shellscalingapi::MDT_EFFECTIVE_DPI;
 use winapi::um::unknwnbase::*;
 use winapi::um::uxtheme::*;
@@ -601,9 +603,7 @@ impl MyWndProc {
                     let info = unsafe {
                         get_file_dialog_path(hwnd, FileDialogType::Save, options)
                             .ok()
-                            .map(|os_str| FileInfo {
-                                path: os_str.into(),
-                            })
+                            .and_then(FileInfo::from_paths)
                     };
                     self.with_wnd_state(|s| s.handler.save_as(token, info));
                 }
@@ -611,7 +611,7 @@ impl MyWndProc {
                     let info = unsafe {
                         get_file_dialog_path(hwnd, FileDialogType::Open, options)
                             .ok()
-                            .map(|s| FileInfo { path: s.into() })
+                            .and_then(FileInfo::from_paths)
                     };
                     self.with_wnd_state(|s| s.handler.open_file(token, info));
                 }
@@ -1182,6 +1182,38 @@ impl WndProc for MyWndProc {
 
                 Some(0)
             }
+            WM_DROPFILES => {
+                let hdrop = wparam as HDROP;
+                let (pos, paths) = unsafe {
+                    let mut point = POINT { x: 0, y: 0 };
+                    DragQueryPoint(hdrop, &mut point);
+                    let count = DragQueryFileW(hdrop, 0xFFFF_FFFF, null_mut(), 0);
+                    let paths: Vec<PathBuf> = (0..count)
+                        .map(|i| {
+                            let len = DragQueryFileW(hdrop, i, null_mut(), 0);
+                            let mut buf = vec![0u16; len as usize + 1];
+                            DragQueryFileW(hdrop, i, buf.as_mut_ptr(), buf.len() as UINT);
+                            PathBuf::from(buf[..len as usize].to_os_string())
+                        })
+                        .collect();
+                    DragFinish(hdrop);
+                    let pos = Point::new(point.x as f64, point.y as f64).to_dp(self.scale());
+                    (pos, paths)
+                };
+                self.with_wnd_state(|s| s.handler.file_dropped(pos, paths));
+                Some(0)
+            }
+            WM_SETTINGCHANGE => {
+                // This is sent with "ImmersiveColorSet" when the user switches
+                // between light and dark mode.
+                let setting = lparam as LPWSTR;
+                if !setting.is_null() && setting.from_wide().as_deref() == Some("ImmersiveColorSet")
+                {
+                    let appearance = Application::get_appearance();
+                    self.with_wnd_state(|s| s.handler.appearance_changed(appearance));
+                }
+                None
+            }
             WM_CLOSE => self
                 .with_wnd_state(|s| s.handler.request_close())
                 .map(|_| 0),
@@ -1354,7 +1386,8 @@ impl WindowBuilder {
             };
 
             let mut dwStyle = WS_OVERLAPPEDWINDOW;
-            let mut dwExStyle: DWORD = 0;
+            // Windows only tells us about files when they are dropped, so there's no hovering.
+            let mut dwExStyle: DWORD = WS_EX_ACCEPTFILES;
             let mut focusable = true;
             if let Some(level) = self.level {
                 match level {
@@ -15,7 +15,7 @@
 //! X11 implementation of features at the application scope.
 
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::convert::{TryFrom, TryInto};
 use std::os::unix::io::RawFd;
 use std::rc::Rc;
@@ -32,7 +32,7 @@ use x11rb::protocol::Event;
 use x11rb::resource_manager::Database as ResourceDb;
 use x11rb::xcb_ffi::XCBConnection;
 
-use crate::application::AppHandler;
+use crate::application::{AppHandler, Appearance};
 
 use super::clipboard::Clipboard;
 use super::util;
@@ -90,6 +90,11 @@ pub(crate) struct Application {
     idle_write: RawFd,
     /// The major opcode of the Present extension, if it is supported.
     present_opcode: Option<u8>,
+    /// The system clipboard, which is owned by the `Application`'s window.
+    clipboard: Clipboard,
+    /// Events that were read from the connection while the clipboard was waiting for
+    /// a reply, and that still need to be handled.
+    pending_events: Rc<RefCell<VecDeque<Event>>>,
 }
 
 /// The mutable `Application` state.
@@ -172,6 +177,9 @@ impl Application {
             .ok_or_else(|| anyhow!("Couldn't get visual from screen"))?;
         let argb_visual_type = util::get_argb_visual_type(&*connection, &screen)?;
 
+        let pending_events = Rc::new(RefCell::new(VecDeque::new()));
+        let clipboard = Clipboard::new(connection.clone(), window_id, pending_events.clone())?;
+
         Ok(Application {
             connection,
             rdb,
@@ -184,6 +192,8 @@ impl Application {
             present_opcode,
             root_visual_type,
             argb_visual_type,
+            clipboard,
+            pending_events,
             marker: std::marker::PhantomData,
         })
     }
@@ -265,8 +275,9 @@ impl Application {
             WindowClass::INPUT_ONLY,
             // Visual ID
             x11rb::COPY_FROM_PARENT,
-            // Window properties mask
-            &CreateWindowAux::new().event_mask(EventMask::STRUCTURE_NOTIFY),
+            // Window properties mask; the clipboard needs to know about property changes
+            &CreateWindowAux::new()
+                .event_mask(EventMask::STRUCTURE_NOTIFY | EventMask::PROPERTY_CHANGE),
         )?
         .check()
         .context("create input-only window")?;
@@ -434,6 +445,18 @@ impl Application {
                 w.handle_idle_notify(ev)
                     .context("IDLE_NOTIFY - failed to handle")?;
             }
+            Event::SelectionRequest(ev) => {
+                if ev.owner == self.window_id {
+                    self.clipboard
+                        .handle_selection_request(ev)
+                        .context("SELECTION_REQUEST - failed to handle")?;
+                }
+            }
+            Event::SelectionClear(ev) => {
+                if ev.owner == self.window_id {
+                    self.clipboard.handle_selection_clear(ev);
+                }
+            }
             Event::Error(e) => {
                 // TODO: if an error is caused by the present extension, disable it and fall back
                 // to copying pixels. This is blocked on
@@ -474,8 +497,9 @@ impl Application {
 
             // Before we poll on the connection's file descriptor, check whether there are any
             // events ready. It could be that XCB has some events in its internal buffers because
-            // of something that happened during the idle loop.
-            let mut event = self.connection.poll_for_event()?;
+            // of something that happened during the idle loop, or that the clipboard queued
+            // some events while it was waiting for a reply.
+            let mut event = self.next_event()?;
 
             if event.is_none() {
                 poll_with_timeout(
@@ -498,7 +522,7 @@ impl Application {
                         tracing::error!("Error handling event: {:#}", e);
                     }
                 }
-                event = self.connection.poll_for_event()?;
+                event = self.next_event()?;
             }
 
             let now = Instant::now();
@@ -528,6 +552,14 @@ impl Application {
         }
     }
 
+    /// Returns the next event that is ready to be handled, if any.
+    fn next_event(&self) -> Result<Option<Event>, Error> {
+        if let Some(event) = borrow_mut!(self.pending_events)?.pop_front() {
+            return Ok(Some(event));
+        }
+        Ok(self.connection.poll_for_event()?)
+    }
+
     pub fn run(self, _handler: Option<Box<dyn AppHandler>>) {
         if let Err(e) = self.run_inner() {
             tracing::error!("{}", e);
@@ -565,9 +597,7 @@ impl Application {
     }
 
     pub fn clipboard(&self) -> Clipboard {
-        // TODO(x11/clipboard): implement Application::clipboard
-        tracing::warn!("Application::clipboard is currently unimplemented for X11 platforms.");
-        Clipboard {}
+        self.clipboard.clone()
     }
 
     pub fn get_locale() -> String {
@@ -589,6 +619,11 @@ impl Application {
             .unwrap_or_else(|| "en-US".to_string())
     }
 
+    pub fn get_appearance() -> Appearance {
+        // TODO: read the preference from the XSETTINGS of the desktop environment
+        Appearance::Light
+    }
+
     pub(crate) fn idle_pipe(&self) -> RawFd {
         self.idle_write
     }
@@ -13,45 +13,424 @@
 // limitations under the License.
 
 //! Interactions with the system pasteboard on X11.
+//!
+//! The X11 clipboard is the `CLIPBOARD` selection. Putting data on the clipboard
+//! makes our application window the owner of the selection; other clients then
+//! ask us for the data in a particular format (a "target") with `SelectionRequest`
+//! events, which are answered from the event loop.
+//!
+//! Getting data from the clipboard means asking the current owner to convert the
+//! selection into a property on our window, and waiting for it to notify us that
+//! it has done so. That wait happens inside the clipboard call, so it is bounded
+//! by a timeout; events that arrive in the meantime are queued and handled by the
+//! event loop afterwards.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Error};
+use x11rb::atom_manager;
+use x11rb::connection::{Connection, RequestConnection};
+use x11rb::protocol::xproto::{
+    Atom, AtomEnum, ConnectionExt, EventMask, PropMode, Property, SelectionClearEvent,
+    SelectionNotifyEvent, SelectionRequestEvent, Window, SELECTION_NOTIFY_EVENT,
+};
+use x11rb::protocol::Event;
+use x11rb::wrapper::ConnectionExt as _;
+use x11rb::xcb_ffi::XCBConnection;
 
 use crate::clipboard::{ClipboardFormat, FormatId};
-use tracing::warn;
 
-#[derive(Debug, Clone, Default)]
-pub struct Clipboard;
+/// How long to wait for the owner of the clipboard to send us its contents.
+///
+/// This is a timeout between replies, so a large transfer can take longer than this
+/// as long as it keeps making progress.
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The other names that text is commonly requested with.
+const TEXT_ALIASES: [&str; 4] = ["TEXT", "STRING", "text/plain;charset=utf-8", "text/plain"];
+
+atom_manager! {
+    ClipboardAtoms: ClipboardAtomsCookie {
+        CLIPBOARD,
+        TARGETS,
+        INCR,
+        // The property on our window that clipboard owners put the contents in.
+        DRUID_CLIPBOARD_CONTENTS,
+    }
+}
+
+/// The system clipboard.
+#[derive(Clone)]
+pub struct Clipboard {
+    state: Rc<RefCell<ClipboardState>>,
+}
+
+struct ClipboardState {
+    connection: Rc<XCBConnection>,
+    /// The window that owns the selection when we put something on the clipboard, and
+    /// that receives the contents of the clipboard when we get something from it.
+    ///
+    /// This is the `Application`'s window, which lives as long as the event loop.
+    window_id: Window,
+    atoms: ClipboardAtoms,
+    /// Events that arrived while we were waiting for the clipboard owner, which the
+    /// event loop still has to handle.
+    pending_events: Rc<RefCell<VecDeque<Event>>>,
+    /// The data we have put on the clipboard, as long as we own it.
+    contents: Option<Vec<(Atom, Vec<u8>)>>,
+}
+
+impl std::fmt::Debug for Clipboard {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Clipboard").finish()
+    }
+}
 
 impl Clipboard {
-    pub fn put_string(&mut self, _s: impl AsRef<str>) {
-        // TODO(x11/clipboard): implement Clipboard::put_string
-        warn!("Clipboard::put_string is currently unimplemented for X11 platforms.");
+    pub(crate) fn new(
+        connection: Rc<XCBConnection>,
+        window_id: Window,
+        pending_events: Rc<RefCell<VecDeque<Event>>>,
+    ) -> Result<Clipboard, Error> {
+        let atoms = ClipboardAtoms::new(connection.as_ref())?
+            .reply()
+            .context("get clipboard atoms")?;
+        let state = ClipboardState {
+            connection,
+            window_id,
+            atoms,
+            pending_events,
+            contents: None,
+        };
+        Ok(Clipboard {
+            state: Rc::new(RefCell::new(state)),
+        })
     }
 
-    pub fn put_formats(&mut self, _formats: &[ClipboardFormat]) {
-        // TODO(x11/clipboard): implement Clipboard::put_formats
-        warn!("Clipboard::put_formats is currently unimplemented for X11 platforms.");
+    pub fn put_string(&mut self, s: impl AsRef<str>) {
+        self.put_formats(&[ClipboardFormat::from(s.as_ref())]);
+    }
+
+    pub fn put_formats(&mut self, formats: &[ClipboardFormat]) {
+        if let Err(e) = self.state.borrow_mut().put_formats(formats) {
+            tracing::error!("failed to put formats on the clipboard: {:#}", e);
+        }
     }
 
     pub fn get_string(&self) -> Option<String> {
-        // TODO(x11/clipboard): implement Clipboard::get_string
-        warn!("Clipboard::set_string is currently unimplemented for X11 platforms.");
-        None
+        std::iter::once(ClipboardFormat::TEXT)
+            .chain(TEXT_ALIASES.iter().copied())
+            .find_map(|format| self.get_format(format))
+            .and_then(|data| String::from_utf8(data).ok())
     }
 
-    pub fn preferred_format(&self, _formats: &[FormatId]) -> Option<FormatId> {
-        // TODO(x11/clipboard): implement Clipboard::preferred_format
-        warn!("Clipboard::preferred_format is currently unimplemented for X11 platforms.");
-        None
+    pub fn preferred_format(&self, formats: &[FormatId]) -> Option<FormatId> {
+        let state = self.state.borrow();
+        let result = state.get_targets().and_then(|targets| {
+            let format_atoms = formats
+                .iter()
+                .map(|format| state.intern(format))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(targets.iter().find_map(|target| {
+                format_atoms
+                    .iter()
+                    .position(|atom| atom == target)
+                    .map(|idx| formats[idx])
+            }))
+        });
+        result
+            .map_err(|e| tracing::error!("failed to get clipboard formats: {:#}", e))
+            .ok()
+            .flatten()
     }
 
-    pub fn get_format(&self, _format: FormatId) -> Option<Vec<u8>> {
-        // TODO(x11/clipboard): implement Clipboard::get_format
-        warn!("Clipboard::get_format is currently unimplemented for X11 platforms.");
-        None
+    pub fn get_format(&self, format: FormatId) -> Option<Vec<u8>> {
+        let state = self.state.borrow();
+        let result = state
+            .intern(format)
+            .and_then(|target| state.get_contents(target));
+        result
+            .map_err(|e| tracing::error!("failed to get clipboard contents: {:#}", e))
+            .ok()
+            .flatten()
     }
 
     pub fn available_type_names(&self) -> Vec<String> {
-        // TODO(x11/clipboard): implement Clipboard::available_type_names
-        warn!("Clipboard::available_type_names is currently unimplemented for X11 platforms.");
-        vec![]
+        let state = self.state.borrow();
+        let result = state.get_targets().and_then(|targets| {
+            targets
+                .into_iter()
+                .map(|atom| Ok(format!("{} ({})", state.atom_name(atom)?, atom)))
+                .collect::<Result<Vec<_>, Error>>()
+        });
+        result
+            .map_err(|e| tracing::error!("failed to get clipboard formats: {:#}", e))
+            .unwrap_or_default()
+    }
+
+    /// Answer another client asking for the contents of the clipboard.
+    pub(crate) fn handle_selection_request(&self, ev: &SelectionRequestEvent) -> Result<(), Error> {
+        self.state.borrow().handle_selection_request(ev)
+    }
+
+    /// Another client has taken ownership of the clipboard.
+    pub(crate) fn handle_selection_clear(&self, ev: &SelectionClearEvent) {
+        let mut state = self.state.borrow_mut();
+        if ev.selection == state.atoms.CLIPBOARD {
+            state.contents = None;
+        }
+    }
+}
+
+impl ClipboardState {
+    fn intern(&self, name: &str) -> Result<Atom, Error> {
+        Ok(self
+            .connection
+            .intern_atom(false, name.as_bytes())?
+            .reply()
+            .with_context(|| format!("intern atom {}", name))?
+            .atom)
+    }
+
+    fn atom_name(&self, atom: Atom) -> Result<String, Error> {
+        let reply = self
+            .connection
+            .get_atom_name(atom)?
+            .reply()
+            .context("get atom name")?;
+        Ok(String::from_utf8_lossy(&reply.name).into_owned())
+    }
+
+    /// Whether our window currently owns the clipboard.
+    fn is_owner(&self) -> Result<bool, Error> {
+        let owner = self
+            .connection
+            .get_selection_owner(self.atoms.CLIPBOARD)?
+            .reply()
+            .context("get clipboard owner")?
+            .owner;
+        Ok(owner == self.window_id)
+    }
+
+    fn put_formats(&mut self, formats: &[ClipboardFormat]) -> Result<(), Error> {
+        let mut contents = Vec::with_capacity(formats.len());
+        for format in formats {
+            contents.push((self.intern(format.identifier)?, format.data.clone()));
+            if format.identifier == ClipboardFormat::TEXT {
+                for alias in &TEXT_ALIASES {
+                    contents.push((self.intern(alias)?, format.data.clone()));
+                }
+            }
+        }
+        // the previous contents are replaced entirely, even if we already owned the clipboard
+        self.contents = Some(contents);
+
+        self.connection
+            .set_selection_owner(self.window_id, self.atoms.CLIPBOARD, x11rb::CURRENT_TIME)?
+            .check()
+            .context("set clipboard owner")?;
+        if !self.is_owner()? {
+            self.contents = None;
+            return Err(anyhow!("another client kept ownership of the clipboard"));
+        }
+        Ok(())
+    }
+
+    /// Returns the formats that the clipboard's contents are available in.
+    fn get_targets(&self) -> Result<Vec<Atom>, Error> {
+        let targets = match self.get_contents(self.atoms.TARGETS)? {
+            Some(targets) => targets,
+            None => return Ok(Vec::new()),
+        };
+        Ok(targets
+            .chunks_exact(4)
+            .map(|bytes| u32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .collect())
+    }
+
+    /// Returns the contents of the clipboard in the `target` format, if the owner of the
+    /// clipboard can provide them.
+    fn get_contents(&self, target: Atom) -> Result<Option<Vec<u8>>, Error> {
+        if let Some(contents) = &self.contents {
+            // We can't answer our own requests while we are waiting for the answer,
+            // so use our own contents directly.
+            if self.is_owner()? {
+                if target == self.atoms.TARGETS {
+                    let targets = self.targets(contents);
+                    return Ok(Some(
+                        targets
+                            .iter()
+                            .flat_map(|t| t.to_ne_bytes().to_vec())
+                            .collect(),
+                    ));
+                }
+                let data = contents.iter().find(|(atom, _)| *atom == target);
+                return Ok(data.map(|(_, data)| data.clone()));
+            }
+        }
+
+        let conn = &self.connection;
+        let property = self.atoms.DRUID_CLIPBOARD_CONTENTS;
+        conn.convert_selection(
+            self.window_id,
+            self.atoms.CLIPBOARD,
+            target,
+            property,
+            x11rb::CURRENT_TIME,
+        )?;
+        conn.flush()?;
+
+        let notified_property = self.wait_for_event(|ev| match ev {
+            Event::SelectionNotify(ev)
+                if ev.requestor == self.window_id && ev.selection == self.atoms.CLIPBOARD =>
+            {
+                Some(ev.property)
+            }
+            _ => None,
+        })?;
+        if notified_property == x11rb::NONE {
+            // the owner can't convert the clipboard to this format, or there is no owner
+            return Ok(None);
+        }
+
+        let reply = conn
+            .get_property(true, self.window_id, property, AtomEnum::ANY, 0, u32::MAX)?
+            .reply()
+            .context("get clipboard contents")?;
+        if reply.type_ != self.atoms.INCR {
+            return Ok(Some(reply.value));
+        }
+
+        // The contents are too large for a single property, so the owner sends them in
+        // chunks. Deleting the property (which we just did) asks for the next chunk, and
+        // an empty chunk marks the end.
+        let mut data = Vec::new();
+        loop {
+            self.wait_for_event(|ev| match ev {
+                Event::PropertyNotify(ev)
+                    if ev.window == self.window_id
+                        && ev.atom == property
+                        && ev.state == Property::NEW_VALUE =>
+                {
+                    Some(())
+                }
+                _ => None,
+            })?;
+            let reply = conn
+                .get_property(true, self.window_id, property, AtomEnum::ANY, 0, u32::MAX)?
+                .reply()
+                .context("get clipboard contents chunk")?;
+            if reply.value.is_empty() {
+                return Ok(Some(data));
+            }
+            data.extend_from_slice(&reply.value);
+        }
+    }
+
+    /// The formats we can provide our `contents` in, including `TARGETS` itself.
+    fn targets(&self, contents: &[(Atom, Vec<u8>)]) -> Vec<Atom> {
+        std::iter::once(self.atoms.TARGETS)
+            .chain(contents.iter().map(|(atom, _)| *atom))
+            .collect()
+    }
+
+    /// Waits until `matches` returns `Some` for an event, queueing the other events
+    /// for the event loop.
+    fn wait_for_event<T>(&self, mut matches: impl FnMut(&Event) -> Option<T>) -> Result<T, Error> {
+        let deadline = Instant::now() + TIMEOUT;
+        loop {
+            if let Some(event) = self.connection.poll_for_event()? {
+                match matches(&event) {
+                    Some(result) => return Ok(result),
+                    None => self.pending_events.borrow_mut().push_back(event),
+                }
+                continue;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(anyhow!("timed out waiting for the clipboard owner"));
+            }
+            poll_connection(&self.connection, deadline - now)?;
+        }
+    }
+
+    fn handle_selection_request(&self, ev: &SelectionRequestEvent) -> Result<(), Error> {
+        // obsolete clients don't specify a property, and expect the target to be used
+        let property = if ev.property == x11rb::NONE {
+            ev.target
+        } else {
+            ev.property
+        };
+        let conn = &self.connection;
+
+        let converted = match &self.contents {
+            Some(contents) if ev.selection == self.atoms.CLIPBOARD => {
+                if ev.target == self.atoms.TARGETS {
+                    conn.change_property32(
+                        PropMode::REPLACE,
+                        ev.requestor,
+                        property,
+                        AtomEnum::ATOM,
+                        &self.targets(contents),
+                    )?;
+                    true
+                } else {
+                    match contents.iter().find(|(atom, _)| *atom == ev.target) {
+                        // Sending large data in chunks (the INCR protocol) is not supported,
+                        // so refuse anything that doesn't fit in a single request.
+                        Some((_, data)) if data.len() + 64 > conn.maximum_request_bytes() => {
+                            tracing::warn!(
+                                "clipboard contents of {} bytes are too large to send",
+                                data.len()
+                            );
+                            false
+                        }
+                        Some((_, data)) => {
+                            conn.change_property8(
+                                PropMode::REPLACE,
+                                ev.requestor,
+                                property,
+                                ev.target,
+                                data,
+                            )?;
+                            true
+                        }
+                        None => false,
+                    }
+                }
+            }
+            _ => false,
+        };
+
+        let notify = SelectionNotifyEvent {
+            response_type: SELECTION_NOTIFY_EVENT,
+            sequence: 0,
+            time: ev.time,
+            requestor: ev.requestor,
+            selection: ev.selection,
+            target: ev.target,
+            property: if converted { property } else { x11rb::NONE },
+        };
+        conn.send_event(false, ev.requestor, EventMask::NO_EVENT, notify)?;
+        Ok(())
+    }
+}
+
+/// Waits for up to `timeout` for the connection to have something to read.
+fn poll_connection(conn: &XCBConnection, timeout: Duration) -> Result<(), Error> {
+    use nix::poll::{poll, PollFd, PollFlags};
+    use std::os::raw::c_int;
+    use std::os::unix::io::AsRawFd;
+
+    let mut poll_fds = [PollFd::new(conn.as_raw_fd(), PollFlags::POLLIN)];
+    // round up, so that we don't wake up just before the deadline
+    let millis = c_int::try_from(timeout.as_millis()).unwrap_or(c_int::max_value() - 1) + 1;
+    match poll(&mut poll_fds, millis) {
+        Ok(_) | Err(nix::Error::Sys(nix::errno::Errno::EINTR)) => Ok(()),
+        Err(e) => Err(e.into()),
     }
 }
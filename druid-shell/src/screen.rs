@@ -16,6 +16,7 @@
 
 use crate::kurbo::Rect;
 use crate::platform;
+use crate::scale::Scale;
 use std::fmt;
 use std::fmt::Display;
 
@@ -32,6 +33,7 @@ pub struct Monitor {
     // https://docs.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-monitorinfo
     // Unsure about x11
     work_rect: Rect,
+    scale: Scale,
 }
 
 impl Monitor {
@@ -41,8 +43,16 @@ impl Monitor {
             primary,
             rect,
             work_rect,
+            scale: Scale::default(),
         }
     }
+
+    #[allow(dead_code)]
+    pub(crate) fn with_scale(mut self, scale: Scale) -> Self {
+        self.scale = scale;
+        self
+    }
+
     /// Returns true if the monitor is the primary monitor.
     /// The primary monitor has its origin at (0, 0) in virtual screen coordinates.
     pub fn is_primary(&self) -> bool {
@@ -59,6 +69,16 @@ impl Monitor {
     pub fn virtual_work_rect(&self) -> Rect {
         self.work_rect
     }
+
+    /// Returns the scale that windows on this monitor are drawn with.
+    ///
+    /// This is the platform's scale factor for the monitor; a window's own
+    /// scale is available from [`WindowHandle::get_scale`].
+    ///
+    /// [`WindowHandle::get_scale`]: crate::WindowHandle::get_scale
+    pub fn scale(&self) -> Scale {
+        self.scale
+    }
 }
 
 impl Display for Monitor {
@@ -15,9 +15,10 @@
 //! Platform independent window types.
 
 use std::any::Any;
+use std::path::PathBuf;
 use std::time::Duration;
 
-use crate::application::Application;
+use crate::application::{Appearance, Application};
 use crate::common_util::Counter;
 use crate::dialog::{FileDialogOptions, FileInfo};
 use crate::error::Error;
@@ -377,6 +378,12 @@ impl WindowHandle {
         self.0.set_cursor(cursor)
     }
 
+    /// Create a [`Cursor`] from an image, for use with [`set_cursor`].
+    ///
+    /// Returns `None` if the platform doesn't support custom cursors, or if the
+    /// cursor couldn't be created.
+    ///
+    /// [`set_cursor`]: WindowHandle::set_cursor
     pub fn make_cursor(&self, desc: &CursorDesc) -> Option<Cursor> {
         self.0.make_cursor(desc)
     }
@@ -549,6 +556,12 @@ pub trait WinHandler {
     #[allow(unused_variables)]
     fn scale(&mut self, scale: Scale) {}
 
+    /// Called when the system switches between light and dark colors.
+    ///
+    /// This is not called on platforms that have no such preference.
+    #[allow(unused_variables)]
+    fn appearance_changed(&mut self, appearance: Appearance) {}
+
     /// Request the handler to prepare to paint the window contents.  In particular, if there are
     /// any regions that need to be repainted on the next call to `paint`, the handler should
     /// invalidate those regions by calling [`WindowHandle::invalidate_rect`] or
@@ -659,6 +672,30 @@ pub trait WinHandler {
     /// Called when the mouse cursor has left the application window
     fn mouse_leave(&mut self) {}
 
+    /// Called while files are dragged over the window, every time the cursor moves.
+    ///
+    /// `position` is the position of the cursor in the window, and `paths` are the
+    /// paths of the dragged files.
+    ///
+    /// This is currently supported on GTK and macOS.
+    #[allow(unused_variables)]
+    fn file_drop_hover(&mut self, position: Point, paths: &[PathBuf]) {}
+
+    /// Called when files that were dragged over the window leave it, or when the drag
+    /// is cancelled.
+    ///
+    /// This is currently supported on GTK and macOS.
+    fn file_drop_cancelled(&mut self) {}
+
+    /// Called when files are dropped on the window.
+    ///
+    /// `position` is the position of the cursor in the window, and `paths` are the
+    /// paths of the dropped files.
+    ///
+    /// This is currently supported on GTK, macOS and Windows.
+    #[allow(unused_variables)]
+    fn file_dropped(&mut self, position: Point, paths: Vec<PathBuf>) {}
+
     /// Called on timer event.
     ///
     /// This is called at (approximately) the requested deadline by a
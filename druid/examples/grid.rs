@@ -0,0 +1,88 @@
+// Copyright 2021 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Demonstrates the `Grid` widget with a settings form: the labels and the
+//! fields line up in columns, and each section has a header spanning both.
+
+// On Windows platform, don't show a console when opening the app.
+#![windows_subsystem = "windows"]
+
+use druid::widget::{Checkbox, Flex, Grid, GridParams, GridTrack, Label, Stepper, TextBox};
+use druid::{
+    AppLauncher, Data, FontDescriptor, FontWeight, Lens, UnitPoint, Widget, WidgetExt, WindowDesc,
+};
+
+#[derive(Clone, Data, Lens)]
+struct Settings {
+    name: String,
+    email: String,
+    server: String,
+    port: f64,
+    use_tls: bool,
+}
+
+pub fn main() {
+    let window = WindowDesc::new(build_root_widget())
+        .title("Grid")
+        .window_size((400.0, 300.0));
+
+    let settings = Settings {
+        name: "Ferris".into(),
+        email: "ferris@example.com".into(),
+        server: "mail.example.com".into(),
+        port: 993.0,
+        use_tls: true,
+    };
+
+    AppLauncher::with_window(window)
+        .log_to_console()
+        .launch(settings)
+        .expect("Failed to launch application");
+}
+
+fn header(text: &str) -> impl Widget<Settings> {
+    Label::new(text)
+        .with_font(FontDescriptor::default().with_weight(FontWeight::BOLD))
+        .padding((0.0, 8.0, 0.0, 0.0))
+}
+
+fn build_root_widget() -> impl Widget<Settings> {
+    let port = Label::dynamic(|port: &f64, _| port.to_string())
+        .lens(Settings::port)
+        .padding((0.0, 0.0, 8.0, 0.0));
+
+    Grid::new()
+        .with_column(GridTrack::Fit)
+        .with_column(GridTrack::Flex(1.0))
+        .column_spacing(10.0)
+        .row_spacing(6.0)
+        .cell_alignment(UnitPoint::LEFT)
+        .with_child(header("Account"), GridParams::new(0, 0).span(1, 2))
+        .with_child(Label::new("Name"), (1, 0))
+        .with_child(TextBox::new().expand_width().lens(Settings::name), (1, 1))
+        .with_child(Label::new("Email address"), (2, 0))
+        .with_child(TextBox::new().expand_width().lens(Settings::email), (2, 1))
+        .with_child(header("Server"), GridParams::new(3, 0).span(1, 2))
+        .with_child(Label::new("Host"), (4, 0))
+        .with_child(TextBox::new().expand_width().lens(Settings::server), (4, 1))
+        .with_child(Label::new("Port"), (5, 0))
+        .with_child(
+            Flex::row()
+                .with_child(port)
+                .with_child(Stepper::new().with_range(1.0, 65535.0).lens(Settings::port)),
+            (5, 1),
+        )
+        .with_child(Checkbox::new("Use TLS").lens(Settings::use_tls), (6, 1))
+        .padding(10.0)
+}
@@ -13,20 +13,28 @@
 // limitations under the License.
 
 //! Demos basic list widget and list manipulations.
+//!
+//! Files dropped on the bottom list are added to it as new items, on the
+//! platforms that support dropping files (GTK, macOS and Windows).
 
 use druid::im::{vector, Vector};
-use druid::lens::{self, LensExt};
-use druid::widget::{Button, CrossAxisAlignment, Flex, Label, List, Scroll};
+use druid::widget::{Button, Controller, CrossAxisAlignment, Flex, Label, List, Scroll};
 use druid::{
-    AppLauncher, Color, Data, Lens, LocalizedString, UnitPoint, Widget, WidgetExt, WindowDesc,
+    AppLauncher, Color, Data, Env, Event, EventCtx, Lens, LocalizedString, Selector, UnitPoint,
+    Widget, WidgetExt, WindowDesc,
 };
 
+/// Sent by a row of the right list when its delete button is pressed.
+const DELETE_ITEM: Selector<u32> = Selector::new("list-demo.delete-item");
+
 #[derive(Clone, Data, Lens)]
 struct AppData {
     left: Vector<u32>,
     right: Vector<u32>,
     l_index: usize,
     r_index: usize,
+    /// The names of the files dropped on the window.
+    files: Vector<String>,
 }
 
 pub fn main() {
@@ -40,6 +48,7 @@ pub fn main() {
         r_index: right.len(),
         left,
         right,
+        files: Vector::new(),
     };
     AppLauncher::with_window(main_window)
         .log_to_console()
@@ -83,24 +92,22 @@ fn ui_builder() -> impl Widget<AppData> {
         1.0,
     );
 
-    // Build a list with shared data
+    // Build a list whose rows can delete themselves
     lists.add_flex_child(
         Scroll::new(
             List::new(|| {
                 Flex::row()
                     .with_child(
-                        Label::new(|(_, item): &(Vector<u32>, u32), _env: &_| {
-                            format!("List item #{}", item)
-                        })
-                        .align_vertical(UnitPoint::LEFT),
+                        Label::new(|item: &u32, _env: &_| format!("List item #{}", item))
+                            .align_vertical(UnitPoint::LEFT),
                     )
                     .with_flex_spacer(1.0)
                     .with_child(
                         Button::new("Delete")
-                            .on_click(|_ctx, (shared, item): &mut (Vector<u32>, u32), _env| {
-                                // We have access to both child's data and shared data.
-                                // Remove element from right list.
-                                shared.retain(|v| v != item);
+                            .on_click(|ctx, item: &mut u32, _env| {
+                                // A row only has its own item, so it asks the
+                                // list to remove it.
+                                ctx.submit_notification(DELETE_ITEM.with(*item));
                             })
                             .fix_size(80.0, 20.0)
                             .align_vertical(UnitPoint::CENTER),
@@ -109,17 +116,11 @@ fn ui_builder() -> impl Widget<AppData> {
                     .background(Color::rgb(0.5, 0.0, 0.5))
                     .fix_height(50.0)
             })
-            .with_spacing(10.),
+            .with_spacing(10.)
+            .controller(DeleteItems),
         )
         .vertical()
-        .lens(lens::Identity.map(
-            // Expose shared data with children data
-            |d: &AppData| (d.right.clone(), d.right.clone()),
-            |d: &mut AppData, x: (Vector<u32>, Vector<u32>)| {
-                // If shared data was changed reflect the changes in our AppData
-                d.right = x.0
-            },
-        )),
+        .lens(AppData::right),
         1.0,
     );
 
@@ -140,5 +141,46 @@ fn ui_builder() -> impl Widget<AppData> {
             )
             .horizontal(),
         )
+        .with_child(Label::new("dropped files"))
+        .with_child(
+            Scroll::new(List::new(|| {
+                Label::new(|item: &String, _env: &_| item.clone())
+                    .padding(10.0)
+                    .background(Color::rgb(0.0, 0.3, 0.5))
+            }))
+            .vertical()
+            .lens(AppData::files)
+            .on_file_drop(|_, paths, data: &mut AppData, _| {
+                for path in paths {
+                    let name = path.file_name().unwrap_or_else(|| path.as_os_str());
+                    data.files.push_back(name.to_string_lossy().into_owned());
+                }
+            })
+            .fix_height(100.0)
+            .expand_width(),
+        )
         .debug_paint_layout()
 }
+
+/// Removes items from the list when one of its rows asks for it.
+struct DeleteItems;
+
+impl<W: Widget<Vector<u32>>> Controller<Vector<u32>, W> for DeleteItems {
+    fn event(
+        &mut self,
+        child: &mut W,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut Vector<u32>,
+        env: &Env,
+    ) {
+        if let Event::Notification(notification) = event {
+            if let Some(item) = notification.get(DELETE_ITEM) {
+                data.retain(|v| v != item);
+                ctx.set_handled();
+                return;
+            }
+        }
+        child.event(ctx, event, data, env)
+    }
+}
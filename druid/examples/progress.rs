@@ -0,0 +1,103 @@
+// Copyright 2021 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An example of reporting progress from a background thread.
+//!
+//! The thread gets an `ExtEventSink` before the window is even open, and sends
+//! a command with the progress so far after every step; a `Controller` receives
+//! the commands and puts the progress in the app data.
+
+use std::thread;
+use std::time::Duration;
+
+use druid::widget::prelude::*;
+use druid::widget::{Controller, Flex, Label, ProgressBar};
+use druid::{AppLauncher, Data, ExtEventSink, Lens, Selector, Target, WidgetExt, WindowDesc};
+
+const SET_PROGRESS: Selector<f64> = Selector::new("progress-example.set-progress");
+
+const STEPS: u32 = 50;
+
+#[derive(Clone, Default, Data, Lens)]
+struct AppState {
+    progress: f64,
+}
+
+pub fn main() {
+    let window = WindowDesc::new(make_ui()).title("Progress Demo");
+    let launcher = AppLauncher::with_window(window);
+
+    // The sink can be handed out before the app is running.
+    let sink = launcher.get_external_handle();
+    thread::spawn(move || do_work(sink));
+
+    launcher
+        .log_to_console()
+        .launch(AppState::default())
+        .expect("launch failed");
+}
+
+/// Pretend to do some slow work, reporting progress after every step.
+fn do_work(sink: ExtEventSink) {
+    for step in 1..=STEPS {
+        thread::sleep(Duration::from_millis(100));
+        let progress = step as f64 / STEPS as f64;
+        if sink
+            .submit_command(SET_PROGRESS, progress, Target::Auto)
+            .is_err()
+        {
+            // the app has been closed, so there is nobody left to tell.
+            return;
+        }
+    }
+}
+
+/// Stores the progress sent by the worker thread.
+struct ProgressController;
+
+impl<W: Widget<AppState>> Controller<AppState, W> for ProgressController {
+    fn event(
+        &mut self,
+        child: &mut W,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut AppState,
+        env: &Env,
+    ) {
+        match event {
+            Event::Command(cmd) if cmd.is(SET_PROGRESS) => {
+                data.progress = *cmd.get_unchecked(SET_PROGRESS);
+                ctx.set_handled();
+            }
+            _ => child.event(ctx, event, data, env),
+        }
+    }
+}
+
+fn make_ui() -> impl Widget<AppState> {
+    let label = Label::dynamic(|data: &AppState, _| {
+        if data.progress < 1.0 {
+            format!("Working... {:.0}%", data.progress * 100.0)
+        } else {
+            "Done!".to_string()
+        }
+    });
+
+    Flex::column()
+        .with_child(label)
+        .with_spacer(10.0)
+        .with_child(ProgressBar::new().lens(AppState::progress).fix_width(200.0))
+        .center()
+        .controller(ProgressController)
+}
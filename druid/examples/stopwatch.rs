@@ -0,0 +1,123 @@
+// Copyright 2021 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A stopwatch, showing how to use a repeating timer.
+//!
+//! The timer is requested with `request_interval` when the stopwatch starts,
+//! and cancelled with `cancel_timer` when it stops.
+
+use std::time::Duration;
+
+use instant::Instant;
+
+use druid::widget::prelude::*;
+use druid::widget::{Button, Controller, Flex, Label};
+use druid::{AppLauncher, Data, Lens, TimerToken, WidgetExt, WindowDesc};
+
+const TICK_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Clone, Default, Data, Lens)]
+struct Stopwatch {
+    running: bool,
+    /// The elapsed time, in seconds.
+    elapsed: f64,
+}
+
+pub fn main() {
+    let window = WindowDesc::new(make_ui())
+        .window_size((300., 150.))
+        .title("Stopwatch");
+
+    AppLauncher::with_window(window)
+        .log_to_console()
+        .launch(Stopwatch::default())
+        .expect("launch failed");
+}
+
+/// Runs the interval while the stopwatch is running, and adds the time that
+/// passed between ticks to the elapsed time.
+struct Ticker {
+    timer_id: TimerToken,
+    last_tick: Instant,
+}
+
+impl<W: Widget<Stopwatch>> Controller<Stopwatch, W> for Ticker {
+    fn event(
+        &mut self,
+        child: &mut W,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut Stopwatch,
+        env: &Env,
+    ) {
+        match event {
+            Event::Timer(id) if *id == self.timer_id => {
+                let now = Instant::now();
+                data.elapsed += now.duration_since(self.last_tick).as_secs_f64();
+                self.last_tick = now;
+            }
+            _ => child.event(ctx, event, data, env),
+        }
+    }
+
+    fn update(
+        &mut self,
+        child: &mut W,
+        ctx: &mut UpdateCtx,
+        old_data: &Stopwatch,
+        data: &Stopwatch,
+        env: &Env,
+    ) {
+        if data.running != old_data.running {
+            if data.running {
+                self.last_tick = Instant::now();
+                self.timer_id = ctx.request_interval(TICK_INTERVAL);
+            } else {
+                ctx.cancel_timer(self.timer_id);
+                self.timer_id = TimerToken::INVALID;
+            }
+        }
+        child.update(ctx, old_data, data, env)
+    }
+}
+
+fn make_ui() -> impl Widget<Stopwatch> {
+    let time = Label::dynamic(|data: &Stopwatch, _| {
+        let minutes = (data.elapsed / 60.).floor();
+        let seconds = data.elapsed - minutes * 60.;
+        format!("{:02}:{:05.2}", minutes, seconds)
+    })
+    .with_text_size(32.);
+
+    let start_stop =
+        Button::dynamic(|data: &Stopwatch, _| if data.running { "Stop" } else { "Start" }.into())
+            .on_click(|_, data: &mut Stopwatch, _| data.running = !data.running);
+
+    let reset = Button::new("Reset").on_click(|_, data: &mut Stopwatch, _| data.elapsed = 0.);
+
+    Flex::column()
+        .with_child(time)
+        .with_spacer(10.)
+        .with_child(
+            Flex::row()
+                .with_child(start_stop)
+                .with_spacer(10.)
+                .with_child(reset),
+        )
+        .center()
+        .controller(Ticker {
+            timer_id: TimerToken::INVALID,
+            last_tick: Instant::now(),
+        })
+}
@@ -0,0 +1,122 @@
+// Copyright 2021 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An example of asking the user before closing a window with unsaved changes.
+//!
+//! When the window is asked to close, a `Controller` handles
+//! `Event::WindowCloseRequested`, which keeps the window open, and shows a
+//! confirmation modal instead. If the user chooses to discard their changes,
+//! the modal submits `FORCE_CLOSE_WINDOW`, which closes the window without
+//! asking again.
+
+use druid::commands::FORCE_CLOSE_WINDOW;
+use druid::widget::prelude::*;
+use druid::widget::{Button, Controller, Flex, Label, ModalHost, TextBox};
+use druid::{AppLauncher, Color, Data, Lens, WidgetExt, WindowDesc};
+
+#[derive(Clone, Default, Data, Lens)]
+struct AppState {
+    text: String,
+    saved_text: String,
+}
+
+impl AppState {
+    fn has_unsaved_changes(&self) -> bool {
+        self.text != self.saved_text
+    }
+}
+
+pub fn main() {
+    let window = WindowDesc::new(make_ui())
+        .window_size((400., 200.))
+        .title("Unsaved changes");
+
+    AppLauncher::with_window(window)
+        .log_to_console()
+        .launch(AppState::default())
+        .expect("launch failed");
+}
+
+/// Asks for confirmation before closing the window while there are unsaved changes.
+struct ConfirmClose;
+
+impl<W: Widget<AppState>> Controller<AppState, W> for ConfirmClose {
+    fn event(
+        &mut self,
+        child: &mut W,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut AppState,
+        env: &Env,
+    ) {
+        match event {
+            Event::WindowCloseRequested if data.has_unsaved_changes() => {
+                // handling the event keeps the window open
+                ctx.set_handled();
+                ctx.submit_command(ModalHost::show_modal(confirm_dialog()));
+            }
+            _ => child.event(ctx, event, data, env),
+        }
+    }
+}
+
+fn confirm_dialog() -> impl Widget<AppState> {
+    let discard = Button::new("Discard").on_click(|ctx, _, _| {
+        // closes the window without sending `WindowCloseRequested` again
+        ctx.submit_command(FORCE_CLOSE_WINDOW);
+    });
+    let cancel = Button::new("Cancel").on_click(|ctx, _, _| {
+        ctx.submit_command(ModalHost::DISMISS_MODAL);
+    });
+
+    Flex::column()
+        .with_child(Label::new("Discard unsaved changes?"))
+        .with_spacer(10.)
+        .with_child(
+            Flex::row()
+                .with_child(discard)
+                .with_spacer(10.)
+                .with_child(cancel),
+        )
+        .padding(20.)
+        .background(Color::grey(0.2))
+        .rounded(4.)
+}
+
+fn make_ui() -> impl Widget<AppState> {
+    let status = Label::dynamic(|data: &AppState, _| {
+        if data.has_unsaved_changes() {
+            "Unsaved changes".to_string()
+        } else {
+            "Saved".to_string()
+        }
+    });
+
+    let save = Button::new("Save").on_click(|_, data: &mut AppState, _| {
+        data.saved_text = data.text.clone();
+    });
+
+    let content = Flex::column()
+        .with_child(TextBox::new().lens(AppState::text).fix_width(300.))
+        .with_spacer(10.)
+        .with_child(
+            Flex::row()
+                .with_child(save)
+                .with_spacer(10.)
+                .with_child(status),
+        )
+        .center();
+
+    ModalHost::new(content).controller(ConfirmClose)
+}
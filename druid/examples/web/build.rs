@@ -23,6 +23,7 @@ const EXCEPTIONS: &[&str] = &[
     "svg",               // usvg doesn't currently build as Wasm.
     "async_event",       // the web backend doesn't currently support spawning threads.
     "blocking_function", // the web backend doesn't currently support spawning threads.
+    "progress",          // the web backend doesn't currently support spawning threads.
     "sub_window",
 ];
 
@@ -84,4 +84,6 @@ impl_example!(textbox);
 impl_example!(transparency);
 impl_example!(view_switcher);
 impl_example!(widget_gallery);
+impl_example!(grid);
+impl_example!(wrap);
 impl_example!(text);
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use druid::{
+    commands::SET_APPEARANCE,
     im,
     kurbo::{Affine, BezPath, Circle, Point},
     piet::{FixedLinearGradient, GradientStop, InterpolationMode},
@@ -20,7 +21,7 @@ use druid::{
         prelude::*, Button, Checkbox, FillStrat, Flex, Image, Label, List, Painter, ProgressBar,
         RadioGroup, Scroll, Slider, Spinner, Stepper, Switch, TextBox,
     },
-    AppLauncher, Color, Data, ImageBuf, Lens, Widget, WidgetExt, WidgetPod, WindowDesc,
+    AppLauncher, Appearance, Color, Data, ImageBuf, Lens, Widget, WidgetExt, WidgetPod, WindowDesc,
 };
 
 #[cfg(feature = "svg")]
@@ -84,6 +85,7 @@ fn ui_builder() -> impl Widget<AppData> {
         SquaresGrid::new()
             .with_cell_size(Size::new(200.0, 240.0))
             .with_spacing(20.0)
+            .with_child(label_widget(theme_switcher(), "Theme"))
             .with_child(label_widget(
                 Label::new(|data: &AppData, _: &_| data.label_data.clone()),
                 "Label",
@@ -193,6 +195,21 @@ fn ui_builder() -> impl Widget<AppData> {
     .vertical()
 }
 
+/// Buttons that switch the whole app between the light and the dark theme.
+fn theme_switcher() -> impl Widget<AppData> {
+    let theme_button = |text: &'static str, appearance: Option<Appearance>| {
+        Button::new(text).on_click(move |ctx, _: &mut AppData, _: &_| {
+            ctx.submit_command(SET_APPEARANCE.with(appearance));
+        })
+    };
+    Flex::column()
+        .with_child(theme_button("Light", Some(Appearance::Light)))
+        .with_spacer(4.0)
+        .with_child(theme_button("Dark", Some(Appearance::Dark)))
+        .with_spacer(4.0)
+        .with_child(theme_button("Follow the system", None))
+}
+
 fn label_widget<T: Data>(widget: impl Widget<T> + 'static, label: &str) -> impl Widget<T> {
     Flex::column()
         .must_fill_main_axis(true)
@@ -0,0 +1,87 @@
+// Copyright 2021 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Demonstrates the `Wrap` widget. Drag the splitter, or resize the window, to
+//! see the tags flow into more or fewer lines.
+
+// On Windows platform, don't show a console when opening the app.
+#![windows_subsystem = "windows"]
+
+use druid::widget::{CrossAxisAlignment, Label, LineBreaking, Split, Wrap};
+use druid::{theme, AppLauncher, Color, Widget, WidgetExt, WindowDesc};
+
+const TAGS: &[&str] = &[
+    "rust",
+    "gui",
+    "druid",
+    "piet",
+    "kurbo",
+    "widgets",
+    "layout",
+    "wrapping",
+    "flex",
+    "lines",
+    "tags",
+    "chips",
+    "cross-platform",
+    "data",
+    "lenses",
+    "env",
+    "theme",
+    "text",
+    "scroll",
+    "split",
+    "resizable",
+];
+
+pub fn main() {
+    let window = WindowDesc::new(build_root_widget())
+        .title("Wrap")
+        .window_size((500.0, 300.0));
+
+    AppLauncher::with_window(window)
+        .log_to_console()
+        .launch(())
+        .expect("Failed to launch application");
+}
+
+fn tag(text: &str) -> impl Widget<()> {
+    Label::new(text)
+        .padding((8.0, 4.0))
+        .background(theme::BACKGROUND_LIGHT)
+        .rounded(10.0)
+        .border(Color::grey(0.5), 1.0)
+}
+
+fn build_root_widget() -> impl Widget<()> {
+    let mut tags = Wrap::row()
+        .spacing(6.0)
+        .run_spacing(6.0)
+        .cross_axis_alignment(CrossAxisAlignment::Center);
+    for (i, text) in TAGS.iter().enumerate() {
+        // mix in some taller tags, to show the alignment within lines
+        if i % 5 == 0 {
+            tags.add_child(tag(text).padding((0.0, 6.0)));
+        } else {
+            tags.add_child(tag(text));
+        }
+    }
+
+    let side = Label::new("Drag the splitter to change the width of the tags.")
+        .with_line_break_mode(LineBreaking::WordWrap)
+        .padding(10.0);
+    Split::columns(tags.padding(10.0).scroll().vertical(), side)
+        .split_point(0.7)
+        .draggable(true)
+}
@@ -0,0 +1,157 @@
+// Copyright 2021 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Animations driven by the window's animation frames.
+//!
+//! A widget starts an animation with [`EventCtx::request_animation`] (also
+//! available on [`UpdateCtx`] and [`LifeCycleCtx`]), which returns an
+//! [`AnimationToken`]. On every animation frame after that, the widget gets an
+//! [`Event::AnimProgress`] with that token and the eased progress of the
+//! animation, and once the animation has run for its whole duration, an
+//! [`Event::AnimEnded`]. A widget can run any number of animations at once.
+//!
+//! [`EventCtx::request_animation`]: crate::EventCtx::request_animation
+//! [`UpdateCtx`]: crate::UpdateCtx
+//! [`LifeCycleCtx`]: crate::LifeCycleCtx
+//! [`Event::AnimProgress`]: crate::Event::AnimProgress
+//! [`Event::AnimEnded`]: crate::Event::AnimEnded
+
+use std::f64::consts::PI;
+use std::time::Duration;
+
+use crate::shell::Counter;
+
+/// A token that uniquely identifies a running animation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+pub struct AnimationToken(u64);
+
+impl AnimationToken {
+    /// A token that does not correspond to any animation.
+    pub const INVALID: AnimationToken = AnimationToken(0);
+
+    pub(crate) fn next() -> AnimationToken {
+        static ANIMATION_COUNTER: Counter = Counter::new();
+        AnimationToken(ANIMATION_COUNTER.next())
+    }
+}
+
+/// How the progress of an animation changes over its duration.
+///
+/// A curve maps the fraction of the duration that has passed, from `0.0` to
+/// `1.0`, to the progress that is reported in [`Event::AnimProgress`]. Every
+/// curve starts at `0.0` and ends at `1.0`.
+///
+/// [`Event::AnimProgress`]: crate::Event::AnimProgress
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnimationCurve {
+    /// Progress at a constant speed.
+    Linear,
+    /// Start slowly and speed up.
+    EaseIn,
+    /// Start quickly and slow down.
+    EaseOut,
+    /// Start slowly, speed up, and slow down again.
+    EaseInOut,
+    /// Overshoot the end and settle back, like a spring.
+    Spring,
+}
+
+impl AnimationCurve {
+    /// Returns the progress of an animation with this curve, when the fraction `t`
+    /// of its duration has passed.
+    pub fn eval(self, t: f64) -> f64 {
+        let t = t.max(0.0).min(1.0);
+        match self {
+            AnimationCurve::Linear => t,
+            AnimationCurve::EaseIn => t * t * t,
+            AnimationCurve::EaseOut => 1.0 - (1.0 - t).powi(3),
+            AnimationCurve::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            // an underdamped oscillation, scaled down so that it is exactly at rest at the end
+            AnimationCurve::Spring => 1.0 - (-6.0 * t).exp() * (1.0 - t) * (3.0 * PI * t).cos(),
+        }
+    }
+}
+
+impl Default for AnimationCurve {
+    fn default() -> Self {
+        AnimationCurve::Linear
+    }
+}
+
+/// An animation that a widget is running.
+#[derive(Debug, Clone)]
+pub(crate) struct Animation {
+    pub(crate) token: AnimationToken,
+    duration: u64,
+    elapsed: u64,
+    curve: AnimationCurve,
+}
+
+impl Animation {
+    pub(crate) fn new(duration: Duration, curve: AnimationCurve) -> Animation {
+        Animation {
+            token: AnimationToken::next(),
+            duration: duration.as_nanos() as u64,
+            elapsed: 0,
+            curve,
+        }
+    }
+
+    /// Advances the animation by `interval` nanoseconds. Returns the eased
+    /// progress, and whether the animation is finished.
+    pub(crate) fn advance(&mut self, interval: u64) -> (f64, bool) {
+        self.elapsed = self.elapsed.saturating_add(interval);
+        if self.elapsed >= self.duration {
+            (1.0, true)
+        } else {
+            let t = self.elapsed as f64 / self.duration as f64;
+            (self.curve.eval(t), false)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn curves_start_at_zero_and_end_at_one() {
+        for curve in &[
+            AnimationCurve::Linear,
+            AnimationCurve::EaseIn,
+            AnimationCurve::EaseOut,
+            AnimationCurve::EaseInOut,
+            AnimationCurve::Spring,
+        ] {
+            assert!(approx_eq!(f64, curve.eval(0.0), 0.0, epsilon = 1e-9));
+            assert!(approx_eq!(f64, curve.eval(1.0), 1.0, epsilon = 1e-9));
+        }
+    }
+
+    #[test]
+    fn animation_finishes_after_its_duration() {
+        let mut animation = Animation::new(Duration::from_millis(10), AnimationCurve::Linear);
+        let (progress, finished) = animation.advance(4_000_000);
+        assert!(approx_eq!(f64, progress, 0.4));
+        assert!(!finished);
+        assert_eq!(animation.advance(6_000_000), (1.0, true));
+    }
+}
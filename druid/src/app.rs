@@ -17,16 +17,19 @@
 use crate::ext_event::{ExtEventHost, ExtEventSink};
 use crate::kurbo::{Point, Size};
 use crate::menu::MenuManager;
-use crate::shell::{Application, Error as PlatformError, WindowBuilder, WindowHandle, WindowLevel};
+use crate::shell::{
+    Appearance, Application, Error as PlatformError, HotKey, WindowBuilder, WindowHandle,
+    WindowLevel,
+};
 use crate::widget::LabelText;
 use crate::win_handler::{AppHandler, AppState};
 use crate::window::WindowId;
-use crate::{AppDelegate, Data, Env, LocalizedString, Menu, Widget};
+use crate::{theme, AppDelegate, Command, Data, Env, LocalizedString, Menu, Widget};
 
 use druid_shell::WindowState;
 
 /// A function that modifies the initial environment.
-type EnvSetupFn<T> = dyn FnOnce(&mut Env, &T);
+type EnvSetupFn<T> = dyn Fn(&mut Env, &T);
 
 /// Handles initial setup of an application, and starts the runloop.
 pub struct AppLauncher<T> {
@@ -35,6 +38,17 @@ pub struct AppLauncher<T> {
     l10n_resources: Option<(Vec<String>, String)>,
     delegate: Option<Box<dyn AppDelegate<T>>>,
     ext_event_host: ExtEventHost,
+    quit_on_last_window_closed: Option<bool>,
+    follow_system_appearance: bool,
+}
+
+/// Builds the root [`Env`], and builds it again when the theme changes.
+pub(crate) struct EnvBuilder<T> {
+    /// The environment before the theme colors and the setup function are applied.
+    base: Env,
+    setup: Option<Box<EnvSetupFn<T>>>,
+    appearance: Appearance,
+    follow_system_appearance: bool,
 }
 
 /// Defines how a windows size should be determined
@@ -83,6 +97,8 @@ pub struct PendingWindow<T> {
     pub(crate) title: LabelText<T>,
     pub(crate) transparent: bool,
     pub(crate) menu: Option<MenuManager<T>>,
+    pub(crate) focus_wraps: bool,
+    pub(crate) hotkeys: Vec<(HotKey, Command)>,
     pub(crate) size_policy: WindowSizePolicy, // This is copied over from the WindowConfig
                                               // when the native window is constructed.
 }
@@ -99,6 +115,8 @@ impl<T: Data> PendingWindow<T> {
             title: LocalizedString::new("app-name").into(),
             menu: MenuManager::platform_default(),
             transparent: false,
+            focus_wraps: true,
+            hotkeys: Vec::new(),
             size_policy: WindowSizePolicy::User,
         }
     }
@@ -120,6 +138,13 @@ impl<T: Data> PendingWindow<T> {
         self
     }
 
+    /// Set whether moving focus past the last focusable widget wraps around
+    /// to the first one, and vice versa. This is `true` by default.
+    pub fn focus_wraps(mut self, wraps: bool) -> Self {
+        self.focus_wraps = wraps;
+        self
+    }
+
     /// Set the menu for this window.
     ///
     /// `menu` is a callback for creating the menu. Its first argument is the id of the window that
@@ -132,6 +157,14 @@ impl<T: Data> PendingWindow<T> {
         self.menu = Some(MenuManager::new(menu));
         self
     }
+
+    /// Submit `cmd` whenever `hotkey` is pressed in this window.
+    ///
+    /// See [`WindowDesc::hotkey`] for details.
+    pub fn hotkey(mut self, hotkey: HotKey, cmd: impl Into<Command>) -> Self {
+        self.hotkeys.push((hotkey, cmd.into()));
+        self
+    }
 }
 
 impl<T: Data> AppLauncher<T> {
@@ -143,13 +176,19 @@ impl<T: Data> AppLauncher<T> {
             l10n_resources: None,
             delegate: None,
             ext_event_host: ExtEventHost::new(),
+            quit_on_last_window_closed: None,
+            follow_system_appearance: false,
         }
     }
 
     /// Provide an optional closure that will be given mutable access to
     /// the environment and immutable access to the app state before launch.
     ///
-    /// This can be used to set or override theme values.
+    /// This can be used to set or override theme values. The closure is called
+    /// again, with the current app state, whenever the theme is switched with
+    /// [`SET_APPEARANCE`], so the values it sets stay in place.
+    ///
+    /// [`SET_APPEARANCE`]: crate::commands::SET_APPEARANCE
     pub fn configure_env(mut self, f: impl Fn(&mut Env, &T) + 'static) -> Self {
         self.env_setup = Some(Box::new(f));
         self
@@ -163,6 +202,39 @@ impl<T: Data> AppLauncher<T> {
         self
     }
 
+    /// Set whether the application quits once its last window is closed.
+    ///
+    /// By default this follows the platform convention: the application quits
+    /// on Windows and X11, and keeps running on macOS. If this is `false`, the
+    /// application keeps running until [`Application::quit`] is called or a
+    /// [`QUIT_APP`] command is submitted, for instance from
+    /// [`AppDelegate::window_removed`].
+    ///
+    /// This has no effect on GTK, where the platform always ends the application
+    /// once its last window is closed.
+    ///
+    /// [`QUIT_APP`]: crate::commands::QUIT_APP
+    pub fn quit_on_last_window_closed(mut self, quit: bool) -> Self {
+        self.quit_on_last_window_closed = Some(quit);
+        self
+    }
+
+    /// Use the light or the dark theme, whichever the system prefers, and
+    /// switch between them whenever the system preference changes.
+    ///
+    /// By default the dark theme is used. The theme can also be switched at
+    /// any time with [`SET_APPEARANCE`].
+    ///
+    /// The X11 backend can't read the system preference yet, so there the light
+    /// theme is always used. On the web, the preference is read when the
+    /// application starts, but later changes aren't followed.
+    ///
+    /// [`SET_APPEARANCE`]: crate::commands::SET_APPEARANCE
+    pub fn follow_system_appearance(mut self) -> Self {
+        self.follow_system_appearance = true;
+        self
+    }
+
     /// Initialize a minimal logger with DEBUG max level for printing logs out to stderr.
     ///
     /// This is meant for use during development only.
@@ -239,21 +311,32 @@ impl<T: Data> AppLauncher<T> {
     pub fn launch(mut self, data: T) -> Result<(), PlatformError> {
         let app = Application::new()?;
 
-        let mut env = self
+        let base_env = self
             .l10n_resources
             .map(|it| Env::with_i10n(it.0, &it.1))
             .unwrap_or_default();
-
-        if let Some(f) = self.env_setup.take() {
-            f(&mut env, &data);
+        let mut env = EnvBuilder {
+            base: base_env,
+            setup: self.env_setup.take(),
+            appearance: Appearance::Dark,
+            follow_system_appearance: false,
+        };
+        if self.follow_system_appearance {
+            env.set_appearance(None);
         }
 
+        // GTK quits by itself, so this is left to the platform there
+        let quit_on_last_window_closed = !cfg!(all(target_os = "linux", not(feature = "x11")))
+            && self
+                .quit_on_last_window_closed
+                .unwrap_or(cfg!(any(target_os = "windows", feature = "x11")));
         let mut state = AppState::new(
             app.clone(),
             data,
             env,
             self.delegate.take(),
             self.ext_event_host,
+            quit_on_last_window_closed,
         );
 
         for desc in self.windows {
@@ -267,6 +350,48 @@ impl<T: Data> AppLauncher<T> {
     }
 }
 
+impl<T> EnvBuilder<T> {
+    pub(crate) fn build(&self, data: &T) -> Env {
+        let mut env = self.base.clone();
+        theme::set_appearance(&mut env, self.appearance);
+        if let Some(setup) = &self.setup {
+            setup(&mut env, data);
+        }
+        env
+    }
+
+    /// Switch to the theme for `appearance`, or, if it is `None`, to the one
+    /// for the system preference, following its changes from now on.
+    pub(crate) fn set_appearance(&mut self, appearance: Option<Appearance>) {
+        self.follow_system_appearance = appearance.is_none();
+        self.appearance = appearance.unwrap_or_else(Application::get_appearance);
+    }
+
+    /// Switch the localization to `locale`, or, if it is `None`, to the
+    /// locale of the system. Returns `false` if `locale` is not valid.
+    pub(crate) fn set_locale(&mut self, locale: Option<&str>) -> bool {
+        let locale = locale
+            .map(String::from)
+            .unwrap_or_else(Application::get_locale);
+        let valid = self.base.set_locale(&locale);
+        if !valid {
+            tracing::warn!("'{}' is not a valid locale", locale);
+        }
+        valid
+    }
+
+    /// Returns `true` if the environment has to be built again, because it
+    /// follows the system preference, which is now `appearance`.
+    pub(crate) fn system_appearance_changed(&mut self, appearance: Appearance) -> bool {
+        if self.follow_system_appearance && self.appearance != appearance {
+            self.appearance = appearance;
+            true
+        } else {
+            false
+        }
+    }
+}
+
 impl Default for WindowConfig {
     fn default() -> Self {
         WindowConfig {
@@ -486,6 +611,32 @@ impl<T: Data> WindowDesc<T> {
         self
     }
 
+    /// Submit `cmd` whenever `hotkey` is pressed in this window.
+    ///
+    /// Hotkeys are checked before the key event is sent to the focused widget,
+    /// so they work wherever the focus is. A matching key event is marked as
+    /// handled, and no widget sees it. If the command's target is
+    /// [`Target::Auto`], it is sent to this window.
+    ///
+    /// Hotkeys of menu items don't need to be added here, because the
+    /// platform's menu already responds to them before the window sees the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid::widget::Label;
+    /// use druid::{commands, HotKey, SysMods, WindowDesc};
+    ///
+    /// let window = WindowDesc::<()>::new(Label::new("Editor"))
+    ///     .hotkey(HotKey::new(SysMods::Cmd, "s"), commands::SAVE_FILE);
+    /// ```
+    ///
+    /// [`Target::Auto`]: crate::Target::Auto
+    pub fn hotkey(mut self, hotkey: HotKey, cmd: impl Into<Command>) -> Self {
+        self.pending = self.pending.hotkey(hotkey, cmd);
+        self
+    }
+
     /// Set the window size policy
     pub fn window_size_policy(mut self, size_policy: WindowSizePolicy) -> Self {
         #[cfg(windows)]
@@ -558,11 +709,30 @@ impl<T: Data> WindowDesc<T> {
         self
     }
 
+    /// Builder-style method to set whether moving focus with Tab past the last
+    /// focusable widget wraps around to the first one, and vice versa.
+    ///
+    /// This is `true` by default.
+    pub fn focus_wraps(mut self, wraps: bool) -> Self {
+        self.pending = self.pending.focus_wraps(wraps);
+        self
+    }
+
     /// Sets the initial window position in [display points], relative to the origin
-    /// of the [virtual screen].
+    /// of the [virtual screen]. The monitors' rectangles in this space, for placing
+    /// a window on a particular monitor, are available from [`Screen::get_monitors`].
+    ///
+    /// To restore the geometry of a window from a previous launch, save the values of
+    /// [`WindowHandle::get_position`] and [`WindowHandle::get_size`] (available from
+    /// `ctx.window()`) before the window closes, and pass them to this method and to
+    /// [`window_size`].
     ///
     /// [display points]: crate::Scale
     /// [virtual screen]: crate::Screen
+    /// [`Screen::get_monitors`]: crate::Screen::get_monitors
+    /// [`WindowHandle::get_position`]: crate::WindowHandle::get_position
+    /// [`WindowHandle::get_size`]: crate::WindowHandle::get_size
+    /// [`window_size`]: WindowDesc::window_size
     pub fn set_position(mut self, position: impl Into<Point>) -> Self {
         self.config = self.config.set_position(position.into());
         self
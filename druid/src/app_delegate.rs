@@ -18,7 +18,7 @@ use std::any::{Any, TypeId};
 
 use crate::{
     commands, core::CommandQueue, ext_event::ExtEventHost, Command, Data, Env, Event, ExtEventSink,
-    Handled, SingleUse, Target, WindowDesc, WindowId,
+    Handled, Menu, SingleUse, Target, WindowDesc, WindowId,
 };
 
 /// A context passed in to [`AppDelegate`] functions.
@@ -69,6 +69,24 @@ impl<'a> DelegateCtx<'a> {
             debug_panic!("DelegateCtx::new_window<T> - T must match the application data type.");
         }
     }
+
+    /// Replace the menu of a window.
+    /// `T` must be the application's root `Data` type (the type provided to [`AppLauncher::launch`]).
+    ///
+    /// The new menu is never rebuilt, but it is still refreshed when the data changes.
+    ///
+    /// [`AppLauncher::launch`]: struct.AppLauncher.html#method.launch
+    pub fn set_menu<T: Any>(&mut self, menu: Menu<T>, window: WindowId) {
+        if self.app_data_type == TypeId::of::<T>() {
+            self.submit_command(
+                commands::SET_MENU
+                    .with(SingleUse::new(Box::new(menu)))
+                    .to(Target::Window(window)),
+            );
+        } else {
+            debug_panic!("DelegateCtx::set_menu<T> - T must match the application data type.");
+        }
+    }
 }
 
 /// A type that provides hooks for handling and modifying top-level events.
@@ -79,6 +97,33 @@ impl<'a> DelegateCtx<'a> {
 /// It is a natural place for things like window and menu management.
 ///
 /// You customize the `AppDelegate` by implementing its methods on your own type.
+///
+/// # Examples
+///
+/// Keeping track of the open windows, to quit once the last one is closed:
+///
+/// ```
+/// use std::collections::HashSet;
+/// use druid::{commands, AppDelegate, Data, DelegateCtx, Env, WindowId};
+///
+/// #[derive(Default)]
+/// struct Delegate {
+///     windows: HashSet<WindowId>,
+/// }
+///
+/// impl<T: Data> AppDelegate<T> for Delegate {
+///     fn window_added(&mut self, id: WindowId, _: &mut T, _: &Env, _: &mut DelegateCtx) {
+///         self.windows.insert(id);
+///     }
+///
+///     fn window_removed(&mut self, id: WindowId, _: &mut T, _: &Env, ctx: &mut DelegateCtx) {
+///         self.windows.remove(&id);
+///         if self.windows.is_empty() {
+///             ctx.submit_command(commands::QUIT_APP);
+///         }
+///     }
+/// }
+/// ```
 #[allow(unused)]
 pub trait AppDelegate<T: Data> {
     /// The `AppDelegate`'s event handler. This function receives all
@@ -108,12 +153,17 @@ pub trait AppDelegate<T: Data> {
     /// If your implementation returns `Handled::No`, the command will be sent down
     /// the widget tree. Otherwise it will not.
     ///
+    /// Closing a window, including with the button in its title bar, goes
+    /// through [`commands::CLOSE_WINDOW`]; handling that command keeps the
+    /// window open, for instance to ask whether to save first.
+    ///
     /// To do anything fancier than this, you can submit arbitary commands
     /// via [`DelegateCtx::submit_command`].
     ///
     /// [`Target`]: enum.Target.html
     /// [`Command`]: struct.Command.html
     /// [`DelegateCtx::submit_command`]: struct.DelegateCtx.html#method.submit_command
+    /// [`commands::CLOSE_WINDOW`]: crate::commands::CLOSE_WINDOW
     fn command(
         &mut self,
         ctx: &mut DelegateCtx,
@@ -177,7 +177,9 @@ pub mod sys {
     use super::Selector;
     use crate::{
         sub_window::{SubWindowDesc, SubWindowUpdate},
-        FileDialogOptions, FileInfo, SingleUse, WidgetId, WindowConfig,
+        widget::Viewport,
+        Appearance, FileDialogOptions, FileInfo, Point, Rect, SingleUse, Vec2, WidgetId,
+        WindowConfig, WindowState,
     };
 
     /// Quit the running application. This command is handled by the druid library.
@@ -198,11 +200,59 @@ pub mod sys {
     /// The command must target a specific window.
     /// When calling `submit_command` on a `Widget`s context, passing `None` as target
     /// will automatically target the window containing the widget.
+    ///
+    /// Before the window closes, the [`AppDelegate`] and the widgets in the window
+    /// receive [`Event::WindowCloseRequested`], and either of them can cancel the
+    /// close. This is also what happens when the user clicks the close button in
+    /// the titlebar.
+    ///
+    /// [`AppDelegate`]: crate::AppDelegate
+    /// [`Event::WindowCloseRequested`]: crate::Event::WindowCloseRequested
     pub const CLOSE_WINDOW: Selector = Selector::new("druid-builtin.close-window");
 
+    /// The selector for a command to close a window without asking first.
+    ///
+    /// Unlike [`CLOSE_WINDOW`], this does not send [`Event::WindowCloseRequested`],
+    /// so the close cannot be cancelled. This is useful for closing the window
+    /// after the user has confirmed that they want to, for instance after
+    /// cancelling a close to ask about unsaved changes.
+    ///
+    /// The command must target a specific window.
+    ///
+    /// [`CLOSE_WINDOW`]: CLOSE_WINDOW
+    /// [`Event::WindowCloseRequested`]: crate::Event::WindowCloseRequested
+    pub const FORCE_CLOSE_WINDOW: Selector = Selector::new("druid-builtin.force-close-window");
+
     /// Close all windows.
     pub const CLOSE_ALL_WINDOWS: Selector = Selector::new("druid-builtin.close-all-windows");
 
+    /// Switch the whole application to the bundled light or dark theme.
+    ///
+    /// With `None`, the theme follows the system preference from then on, like
+    /// with [`AppLauncher::follow_system_appearance`]. In either case the root
+    /// [`Env`] is built again, including the changes made by
+    /// [`AppLauncher::configure_env`], and every widget gets an `update` in
+    /// which [`UpdateCtx::env_changed`] is `true`.
+    ///
+    /// [`AppLauncher::follow_system_appearance`]: crate::AppLauncher::follow_system_appearance
+    /// [`Env`]: crate::Env
+    /// [`AppLauncher::configure_env`]: crate::AppLauncher::configure_env
+    /// [`UpdateCtx::env_changed`]: crate::UpdateCtx::env_changed
+    pub const SET_APPEARANCE: Selector<Option<Appearance>> =
+        Selector::new("druid-builtin.set-appearance");
+
+    /// Switch the localization of the whole application to a locale, given as
+    /// a BCP47 language tag like `"de-DE"`.
+    ///
+    /// With `None`, the application goes back to the locale of the system. The
+    /// root [`Env`] is built again, and every widget gets an `update`, in which
+    /// each [`LocalizedString`] is resolved again for the new locale. A tag that
+    /// is not valid is logged and ignored.
+    ///
+    /// [`Env`]: crate::Env
+    /// [`LocalizedString`]: crate::LocalizedString
+    pub const SET_LOCALE: Selector<Option<String>> = Selector::new("druid-builtin.set-locale");
+
     /// The selector for a command to bring a window to the front, and give it focus.
     ///
     /// The command must target a specific window.
@@ -214,6 +264,29 @@ pub mod sys {
     pub const CONFIGURE_WINDOW: Selector<WindowConfig> =
         Selector::new("druid-builtin.configure-window");
 
+    /// Move a window. The payload is the new position of the window's top-left
+    /// corner, in virtual-screen coordinates.
+    ///
+    /// The current position is available from [`WindowHandle::get_position`], so an
+    /// app can save it on close and restore it with this command, or with
+    /// [`WindowDesc::set_position`] when it next launches.
+    ///
+    /// The command must target a specific window.
+    ///
+    /// [`WindowHandle::get_position`]: crate::WindowHandle::get_position
+    /// [`WindowDesc::set_position`]: crate::WindowDesc::set_position
+    pub const SET_POSITION: Selector<Point> = Selector::new("druid-builtin.set-position");
+
+    /// Maximize, minimize or restore a window.
+    ///
+    /// The current state is available from [`WindowHandle::get_window_state`].
+    ///
+    /// The command must target a specific window.
+    ///
+    /// [`WindowHandle::get_window_state`]: crate::WindowHandle::get_window_state
+    pub const SET_WINDOW_STATE: Selector<WindowState> =
+        Selector::new("druid-builtin.set-window-state");
+
     /// Display a context (right-click) menu. The payload must be the [`ContextMenu`]
     /// object to be displayed.
     ///
@@ -221,6 +294,13 @@ pub mod sys {
     pub(crate) const SHOW_CONTEXT_MENU: Selector<SingleUse<Box<dyn Any>>> =
         Selector::new("druid-builtin.show-context-menu");
 
+    /// Replace the menu of a window. The payload must be the [`Menu`] for the
+    /// application's data type.
+    ///
+    /// [`Menu`]: crate::Menu
+    pub(crate) const SET_MENU: Selector<SingleUse<Box<dyn Any>>> =
+        Selector::new("druid-builtin.set-menu");
+
     /// This is sent to the window handler to create a new sub window.
     pub(crate) const NEW_SUB_WINDOW: Selector<SingleUse<SubWindowDesc>> =
         Selector::new("druid-builtin.new-sub-window");
@@ -258,7 +338,11 @@ pub mod sys {
 
     /// Open a path, must be handled by the application.
     ///
+    /// If the open panel allowed [`multi_selection`], the [`FileInfo`] contains every
+    /// chosen path.
+    ///
     /// [`FileInfo`]: ../struct.FileInfo.html
+    /// [`multi_selection`]: crate::FileDialogOptions::multi_selection
     pub const OPEN_FILE: Selector<FileInfo> = Selector::new("druid-builtin.open-file-path");
 
     /// When submitted by the application, the system will show the 'save as' panel,
@@ -316,6 +400,46 @@ pub mod sys {
     /// Select all.
     pub const SELECT_ALL: Selector = Selector::new("druid-builtin.menu-select-all");
 
+    /// A notification sent by a [`Scroll`] widget whenever its scroll offset changes.
+    ///
+    /// The payload is the [`Viewport`] after the change, describing the new offset,
+    /// the size of the visible region and the size of the scrolled content.
+    ///
+    /// This is sent at most once per event pass, no matter how many times the offset
+    /// was adjusted during that pass.
+    ///
+    /// [`Scroll`]: crate::widget::Scroll
+    pub const SCROLLED: Selector<Viewport> = Selector::new("druid-builtin.scrolled");
+
+    /// Sent by a [`Scroll`] widget to its descendants whenever the visible part of its
+    /// content changes.
+    ///
+    /// The payload is the visible rect, in the coordinate space of the scrolled content.
+    /// Widgets such as a virtualized [`List`] use this to only build the children that
+    /// can be seen.
+    ///
+    /// [`Scroll`]: crate::widget::Scroll
+    /// [`List`]: crate::widget::List
+    pub const VIEWPORT_CHANGED: Selector<Rect> = Selector::new("druid-builtin.viewport-changed");
+
+    /// A notification asking the nearest enclosing [`Scroll`] to scroll by the
+    /// given amount.
+    ///
+    /// This lets a widget scroll its container without knowing where it is in the
+    /// scrolled content, for instance to autoscroll while the user drags something
+    /// near an edge.
+    ///
+    /// [`Scroll`]: crate::widget::Scroll
+    pub const SCROLL_BY: Selector<Vec2> = Selector::new("druid-builtin.scroll-by");
+
+    /// Move keyboard focus to the widget this command is targeted at.
+    ///
+    /// The command must be submitted with a [`Target::Widget`]; the target
+    /// still receives the command as usual.
+    ///
+    /// [`Target::Widget`]: crate::Target::Widget
+    pub const FOCUS_WIDGET: Selector = Selector::new("druid-builtin.focus-widget");
+
     /// Text input state has changed, and we need to notify the platform.
     pub(crate) const INVALIDATE_IME: Selector<ImeInvalidation> =
         Selector::new("druid-builtin.invalidate-ime");
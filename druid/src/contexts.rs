@@ -23,6 +23,7 @@ use std::{
 };
 use tracing::{error, trace, warn};
 
+use crate::animation::Animation;
 use crate::core::{CommandQueue, CursorChange, FocusChange, WidgetState};
 use crate::env::KeyLike;
 use crate::menu::ContextMenu;
@@ -31,9 +32,9 @@ use crate::shell::text::Event as ImeInvalidation;
 use crate::shell::Region;
 use crate::text::{ImeHandlerRef, TextFieldRegistration};
 use crate::{
-    commands, sub_window::SubWindowDesc, widget::Widget, Affine, Command, Cursor, Data, Env,
-    ExtEventSink, Insets, Menu, Notification, Point, Rect, SingleUse, Size, Target, TimerToken,
-    Vec2, WidgetId, WindowConfig, WindowDesc, WindowHandle, WindowId,
+    commands, sub_window::SubWindowDesc, widget::Widget, Affine, AnimationCurve, AnimationToken,
+    Command, Cursor, Data, Env, ExtEventSink, Insets, Menu, Notification, Point, Rect, SingleUse,
+    Size, Target, TimerToken, Vec2, WidgetId, WindowConfig, WindowDesc, WindowHandle, WindowId,
 };
 
 /// A macro for implementing methods on multiple contexts.
@@ -385,6 +386,37 @@ impl_context_method!(EventCtx<'_, '_>, UpdateCtx<'_, '_>, LifeCycleCtx<'_, '_>,
         self.widget_state.request_anim = true;
     }
 
+    /// Start an animation that lasts `duration`, with its progress eased by `curve`.
+    ///
+    /// From the next animation frame on, this widget receives an
+    /// [`Event::AnimProgress`] on every frame, and finally an [`Event::AnimEnded`],
+    /// both carrying the returned token. A widget can run several animations at once.
+    ///
+    /// [`Event::AnimProgress`]: crate::Event::AnimProgress
+    /// [`Event::AnimEnded`]: crate::Event::AnimEnded
+    pub fn request_animation(
+        &mut self,
+        duration: Duration,
+        curve: AnimationCurve,
+    ) -> AnimationToken {
+        trace!("request_animation {:?} {:?}", duration, curve);
+        let animation = Animation::new(duration, curve);
+        let token = animation.token;
+        self.widget_state.animations.push(animation);
+        self.widget_state.request_anim = true;
+        token
+    }
+
+    /// Stop a running animation, without sending [`Event::AnimEnded`].
+    ///
+    /// Does nothing if the animation has already ended.
+    ///
+    /// [`Event::AnimEnded`]: crate::Event::AnimEnded
+    pub fn cancel_animation(&mut self, token: AnimationToken) {
+        trace!("cancel_animation {:?}", token);
+        self.widget_state.animations.retain(|a| a.token != token);
+    }
+
     /// Indicate that your children have changed.
     ///
     /// Widgets must call this method after adding a new child.
@@ -487,11 +519,40 @@ impl_context_method!(
         /// Request a timer event.
         ///
         /// The return value is a token, which can be used to associate the
-        /// request with the event.
+        /// request with the event, or to cancel it with [`cancel_timer`].
+        ///
+        /// [`cancel_timer`]: EventCtx::cancel_timer
         pub fn request_timer(&mut self, deadline: Duration) -> TimerToken {
             trace!("request_timer deadline={:?}", deadline);
             self.state.request_timer(&mut self.widget_state, deadline)
         }
+
+        /// Request a timer event that repeats every `period`.
+        ///
+        /// The widget receives [`Event::Timer`] with the returned token every
+        /// time the interval fires, until it is cancelled with
+        /// [`cancel_timer`] or the widget is removed from the tree.
+        ///
+        /// Like any timer, an interval is not precise, and the delay of every
+        /// tick is added to the following ones.
+        ///
+        /// [`Event::Timer`]: crate::Event::Timer
+        /// [`cancel_timer`]: EventCtx::cancel_timer
+        pub fn request_interval(&mut self, period: Duration) -> TimerToken {
+            trace!("request_interval period={:?}", period);
+            self.state.request_interval(&mut self.widget_state, period)
+        }
+
+        /// Cancel a timer or interval requested by this widget.
+        ///
+        /// The widget won't receive any further [`Event::Timer`] with this
+        /// token. Cancelling a timer that has already fired does nothing.
+        ///
+        /// [`Event::Timer`]: crate::Event::Timer
+        pub fn cancel_timer(&mut self, token: TimerToken) {
+            trace!("cancel_timer token={:?}", token);
+            self.widget_state.cancelled_timers.push(token);
+        }
     }
 );
 
@@ -571,6 +632,25 @@ impl EventCtx<'_, '_> {
         }
     }
 
+    /// Replace the menu of the window containing the current widget.
+    /// `T` must be the application's root `Data` type (the type provided to [`AppLauncher::launch`]).
+    ///
+    /// The new menu is never rebuilt, but it is still refreshed when the data changes.
+    ///
+    /// [`AppLauncher::launch`]: struct.AppLauncher.html#method.launch
+    pub fn set_menu<T: Any>(&mut self, menu: Menu<T>) {
+        trace!("set_menu");
+        if self.state.root_app_data_type == TypeId::of::<T>() {
+            self.submit_command(
+                commands::SET_MENU
+                    .with(SingleUse::new(Box::new(menu)))
+                    .to(Target::Window(self.state.window_id)),
+            );
+        } else {
+            debug_panic!("EventCtx::set_menu<T> - T must match the application data type.");
+        }
+    }
+
     /// Set the event as "handled", which stops its propagation to other
     /// widgets.
     pub fn set_handled(&mut self) {
@@ -612,42 +692,30 @@ impl EventCtx<'_, '_> {
         self.widget_state.request_focus = Some(FocusChange::Focus(target));
     }
 
-    /// Transfer focus to the next focusable widget.
+    /// Transfer focus to the next focusable widget in the window's focus chain.
     ///
-    /// This should only be called by a widget that currently has focus.
+    /// This can be called by any widget, whether or not it has focus. If no
+    /// widget is focused, the first widget in the chain gets focus.
     ///
     /// See [`is_focused`] for more information about focus.
     ///
     /// [`is_focused`]: struct.EventCtx.html#method.is_focused
     pub fn focus_next(&mut self) {
         trace!("focus_next");
-        if self.has_focus() {
-            self.widget_state.request_focus = Some(FocusChange::Next);
-        } else {
-            warn!(
-                "focus_next can only be called by the currently \
-                            focused widget or one of its ancestors."
-            );
-        }
+        self.widget_state.request_focus = Some(FocusChange::Next);
     }
 
-    /// Transfer focus to the previous focusable widget.
+    /// Transfer focus to the previous focusable widget in the window's focus chain.
     ///
-    /// This should only be called by a widget that currently has focus.
+    /// This can be called by any widget, whether or not it has focus. If no
+    /// widget is focused, the last widget in the chain gets focus.
     ///
     /// See [`is_focused`] for more information about focus.
     ///
     /// [`is_focused`]: struct.EventCtx.html#method.is_focused
     pub fn focus_prev(&mut self) {
         trace!("focus_prev");
-        if self.has_focus() {
-            self.widget_state.request_focus = Some(FocusChange::Previous);
-        } else {
-            warn!(
-                "focus_prev can only be called by the currently \
-                            focused widget or one of its ancestors."
-            );
-        }
+        self.widget_state.request_focus = Some(FocusChange::Previous);
     }
 
     /// Give up focus.
@@ -749,6 +817,22 @@ impl LifeCycleCtx<'_, '_> {
         self.widget_state.focus_chain.push(self.widget_id());
     }
 
+    /// Set the tab index of this widget and its focusable descendants.
+    ///
+    /// When cycling focus with Tab and Shift+Tab, widgets are visited in
+    /// ascending order of tab index, and in tree order among widgets with the
+    /// same index. Widgets without an index have an index of `0`. A tab index
+    /// set on a descendant takes precedence over this one.
+    ///
+    /// This should be called in response to a [`LifeCycle::WidgetAdded`] event.
+    ///
+    /// [`LifeCycle::WidgetAdded`]: enum.Lifecycle.html#variant.WidgetAdded
+    pub fn set_tab_index(&mut self, index: i32) {
+        trace!("set_tab_index index={}", index);
+        self.widget_state.tab_index = Some(index);
+        self.widget_state.update_focus_chain = true;
+    }
+
     /// Register this widget as accepting text input.
     pub fn register_text_input(&mut self, document: impl ImeHandlerRef + 'static) {
         let registration = TextFieldRegistration {
@@ -910,7 +994,14 @@ impl<'a> ContextState<'a> {
     fn request_timer(&self, widget_state: &mut WidgetState, deadline: Duration) -> TimerToken {
         trace!("request_timer deadline={:?}", deadline);
         let timer_token = self.window.request_timer(deadline);
-        widget_state.add_timer(timer_token);
+        widget_state.add_timer(timer_token, timer_token, None);
+        timer_token
+    }
+
+    fn request_interval(&self, widget_state: &mut WidgetState, period: Duration) -> TimerToken {
+        trace!("request_interval period={:?}", period);
+        let timer_token = self.window.request_timer(period);
+        widget_state.add_timer(timer_token, timer_token, Some(period));
         timer_token
     }
 }
@@ -15,8 +15,11 @@
 //! The fundamental druid types.
 
 use std::collections::{HashMap, VecDeque};
+use std::rc::{Rc, Weak};
+use std::time::Duration;
 use tracing::{info_span, trace, warn};
 
+use crate::animation::Animation;
 use crate::bloom::Bloom;
 use crate::command::sys::{CLOSE_WINDOW, SUB_WINDOW_HOST_TO_PARENT, SUB_WINDOW_PARENT_TO_HOST};
 use crate::contexts::ContextState;
@@ -123,6 +126,9 @@ pub struct WidgetState {
 
     pub(crate) is_hot: bool,
 
+    /// `true` if files being dragged over the window were last over this widget.
+    pub(crate) is_drop_hovered: bool,
+
     pub(crate) is_active: bool,
 
     pub(crate) needs_layout: bool,
@@ -140,17 +146,31 @@ pub struct WidgetState {
     /// Any descendant has requested an animation frame.
     pub(crate) request_anim: bool,
 
+    /// The animations this widget is running.
+    pub(crate) animations: Vec<Animation>,
+
     /// Any descendant has requested update.
     pub(crate) request_update: bool,
 
     pub(crate) update_focus_chain: bool,
 
     pub(crate) focus_chain: Vec<WidgetId>,
+    /// The tab index set on this widget, which applies to every entry in its
+    /// focus chain that doesn't have one from a descendant.
+    pub(crate) tab_index: Option<i32>,
+    /// The explicit tab indices of the widgets in `focus_chain`.
+    pub(crate) tab_indices: HashMap<WidgetId, i32>,
     pub(crate) request_focus: Option<FocusChange>,
     pub(crate) children: Bloom<WidgetId>,
     pub(crate) children_changed: bool,
-    /// Associate timers with widgets that requested them.
-    pub(crate) timers: HashMap<TimerToken, WidgetId>,
+    /// Associate timers with widgets that requested them, keyed by the token
+    /// of the platform timer.
+    pub(crate) timers: HashMap<TimerToken, WidgetTimer>,
+    /// Timers that were cancelled, by the token the widget knows them by.
+    pub(crate) cancelled_timers: Vec<TimerToken>,
+    /// Shared with the timers of this widget, which are dropped by the window
+    /// once this state, and so the widget, is gone.
+    pub(crate) timer_owner: Option<Rc<()>>,
     /// The cursor that was set using one of the context methods.
     pub(crate) cursor_change: CursorChange,
     /// The result of merging up children cursors. This gets cleared when merging state up (unlike
@@ -163,6 +183,27 @@ pub struct WidgetState {
     pub(crate) text_registrations: Vec<TextFieldRegistration>,
 }
 
+/// A timer requested by a widget.
+#[derive(Debug, Clone)]
+pub(crate) struct WidgetTimer {
+    /// The widget that requested the timer.
+    pub(crate) widget_id: WidgetId,
+    /// Alive as long as the widget is part of the tree.
+    owner: Weak<()>,
+    /// The token the widget knows the timer by, which is delivered with
+    /// `Event::Timer`.
+    pub(crate) token: TimerToken,
+    /// The period, if this is an interval.
+    pub(crate) interval: Option<Duration>,
+}
+
+impl WidgetTimer {
+    /// Whether the widget that requested this timer still exists.
+    pub(crate) fn is_alive(&self) -> bool {
+        self.owner.strong_count() > 0
+    }
+}
+
 /// Methods by which a widget can attempt to change focus state.
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum FocusChange {
@@ -230,6 +271,11 @@ impl<T, W: Widget<T>> WidgetPod<T, W> {
         self.state.has_active
     }
 
+    /// Returns `true` if this widget or any descendant has keyboard focus.
+    pub fn has_focus(&self) -> bool {
+        self.state.has_focus
+    }
+
     /// Query the "hot" state of the widget.
     ///
     /// See [`EventCtx::is_hot`](struct.EventCtx.html#method.is_hot) for
@@ -245,9 +291,12 @@ impl<T, W: Widget<T>> WidgetPod<T, W> {
 
     /// Set the layout [`Rect`].
     ///
-    /// This is soft-deprecated; you should use [`set_origin`] instead for new code.
+    /// The size is already known from the child's [`layout`], so only the
+    /// origin is used; you should use [`set_origin`] instead.
     ///
+    /// [`layout`]: WidgetPod::layout
     /// [`set_origin`]: WidgetPod::set_origin
+    #[deprecated(since = "0.8.0", note = "use set_origin instead")]
     pub fn set_layout_rect(&mut self, ctx: &mut LayoutCtx, data: &T, env: &Env, layout_rect: Rect) {
         if layout_rect.size() != self.state.size {
             warn!("set_layout_rect passed different size than returned by layout method");
@@ -429,6 +478,10 @@ impl<T: Data, W: Widget<T>> WidgetPod<T, W> {
     /// [`Widget::paint`]: trait.Widget.html#tymethod.paint
     /// [`paint`]: #method.paint
     pub fn paint_raw(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        #[cfg(feature = "log-missing-keys")]
+        let _widget_span = self.widget_span();
+        #[cfg(feature = "log-missing-keys")]
+        let _widget_span = _widget_span.enter();
         // we need to do this before we borrow from self
         if env.get(Env::DEBUG_WIDGET_ID) {
             self.make_widget_id_layout_if_needed(self.state.id, ctx, env);
@@ -467,6 +520,14 @@ impl<T: Data, W: Widget<T>> WidgetPod<T, W> {
 
     /// Paint the widget, even if its layout rect is outside of the currently
     /// visible region.
+    ///
+    /// The whole [`paint_rect`] of the widget, including its paint insets, is
+    /// treated as visible, so its descendants aren't culled either. This does
+    /// not escape any clip set by an ancestor (such as [`Scroll`]); a widget
+    /// that needs to draw outside of that can use [`PaintCtx::paint_with_z_index`].
+    ///
+    /// [`paint_rect`]: WidgetPod::paint_rect
+    /// [`Scroll`]: crate::widget::Scroll
     pub fn paint_always(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
         self.paint_impl(ctx, data, env, true)
     }
@@ -488,8 +549,13 @@ impl<T: Data, W: Widget<T>> WidgetPod<T, W> {
         ctx.with_save(|ctx| {
             let layout_origin = self.layout_rect().origin().to_vec2();
             ctx.transform(Affine::translate(layout_origin));
-            let mut visible = ctx.region().clone();
-            visible.intersect_with(self.state.paint_rect());
+            let mut visible = if paint_if_not_visible {
+                Region::from(self.state.paint_rect())
+            } else {
+                let mut visible = ctx.region().clone();
+                visible.intersect_with(self.state.paint_rect());
+                visible
+            };
             visible -= layout_origin;
             ctx.with_child_ctx(visible, |ctx| self.paint_raw(ctx, data, env));
         });
@@ -551,6 +617,10 @@ impl<T: Data, W: Widget<T>> WidgetPod<T, W> {
         data: &T,
         env: &Env,
     ) -> Size {
+        #[cfg(feature = "log-missing-keys")]
+        let _widget_span = self.widget_span();
+        #[cfg(feature = "log-missing-keys")]
+        let _widget_span = _widget_span.enter();
         if !self.is_initialized() {
             debug_panic!(
                 "{:?}: layout method called before receiving WidgetAdded.",
@@ -595,6 +665,13 @@ impl<T: Data, W: Widget<T>> WidgetPod<T, W> {
         new_size
     }
 
+    /// A span that marks logs from this widget's methods with its id, so that
+    /// a missing [`Env`] key can be traced back to the widget that asked for it.
+    #[cfg(feature = "log-missing-keys")]
+    fn widget_span(&self) -> tracing::Span {
+        info_span!("widget", id = ?self.state.id)
+    }
+
     fn log_layout_issues(&self, size: Size) {
         if size.width.is_infinite() {
             let name = self.widget().type_name();
@@ -632,6 +709,10 @@ impl<T: Data, W: Widget<T>> WidgetPod<T, W> {
     ///
     /// [`event`]: trait.Widget.html#tymethod.event
     pub fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        #[cfg(feature = "log-missing-keys")]
+        let _widget_span = self.widget_span();
+        #[cfg(feature = "log-missing-keys")]
+        let _widget_span = _widget_span.enter();
         if !self.is_initialized() {
             debug_panic!(
                 "{:?}: event method called before receiving WidgetAdded.",
@@ -644,7 +725,7 @@ impl<T: Data, W: Widget<T>> WidgetPod<T, W> {
         if self.state.is_expecting_set_origin_call && !event.should_propagate_to_hidden() {
             warn!(
                 "{:?} received an event ({:?}) without having been laid out. \
-                This likely indicates a missed call to set_layout_rect.",
+                This likely indicates a missed call to set_origin.",
                 ctx.widget_id(),
                 event,
             );
@@ -723,6 +804,10 @@ impl<T: Data, W: Widget<T>> WidgetPod<T, W> {
                 self.state.needs_layout = true;
                 ctx.is_root
             }
+            Event::WindowScale(_) => {
+                self.state.needs_layout = true;
+                true
+            }
             Event::MouseDown(mouse_event) => {
                 WidgetPod::set_hot_state(
                     &mut self.inner,
@@ -811,7 +896,29 @@ impl<T: Data, W: Widget<T>> WidgetPod<T, W> {
             Event::KeyUp(_) => self.state.has_focus,
             Event::Paste(_) => self.state.has_focus,
             Event::Zoom(_) => had_active || self.state.is_hot,
+            Event::DropHover(pos, paths) | Event::Dropped(pos, paths) => {
+                let was_hovered = self.state.is_drop_hovered;
+                let is_hovered = rect.winding(*pos) != 0;
+                self.state.is_drop_hovered = is_hovered && matches!(event, Event::DropHover(..));
+                if is_hovered {
+                    let pos = *pos - rect.origin().to_vec2();
+                    modified_event = Some(match event {
+                        Event::DropHover(..) => Event::DropHover(pos, paths.clone()),
+                        _ => Event::Dropped(pos, paths.clone()),
+                    });
+                    true
+                } else if was_hovered {
+                    // the files were dragged away from this widget
+                    modified_event = Some(Event::DropCancelled);
+                    true
+                } else {
+                    false
+                }
+            }
+            Event::DropCancelled => std::mem::take(&mut self.state.is_drop_hovered),
             Event::Timer(_) => false, // This event was targeted only to our parent
+            // These are sent by a widget's pod only to that widget, see `run_animations`
+            Event::AnimProgress(..) | Event::AnimEnded(_) => false,
             Event::ImeStateChange => true, // once delivered to the focus widget, recurse to the component?
             Event::Command(_) => true,
             Event::Notification(_) => false,
@@ -841,6 +948,9 @@ impl<T: Data, W: Widget<T>> WidgetPod<T, W> {
                 }
                 _ => {
                     self.inner.event(&mut inner_ctx, inner_event, data, env);
+                    if let Event::AnimFrame(interval) = inner_event {
+                        Self::run_animations(&mut self.inner, &mut inner_ctx, *interval, data, env);
+                    }
 
                     inner_ctx.widget_state.has_active |= inner_ctx.widget_state.is_active;
                     ctx.is_handled |= inner_ctx.is_handled;
@@ -858,6 +968,38 @@ impl<T: Data, W: Widget<T>> WidgetPod<T, W> {
         ctx.widget_state.merge_up(&mut self.state);
     }
 
+    /// Advance this widget's animations by `interval` nanoseconds, and send it
+    /// their progress.
+    fn run_animations(inner: &mut W, ctx: &mut EventCtx, interval: u64, data: &mut T, env: &Env) {
+        // The widget may start or cancel animations while handling these events,
+        // so we only look at the ones that were running at the start of the frame.
+        let tokens: Vec<_> = ctx
+            .widget_state
+            .animations
+            .iter()
+            .map(|a| a.token)
+            .collect();
+        for token in tokens {
+            let animations = &mut ctx.widget_state.animations;
+            let idx = match animations.iter().position(|a| a.token == token) {
+                Some(idx) => idx,
+                // cancelled by an earlier event in this frame
+                None => continue,
+            };
+            let (progress, finished) = animations[idx].advance(interval);
+            if finished {
+                animations.remove(idx);
+            }
+            inner.event(ctx, &Event::AnimProgress(token, progress), data, env);
+            if finished {
+                inner.event(ctx, &Event::AnimEnded(token), data, env);
+            }
+        }
+        if !ctx.widget_state.animations.is_empty() {
+            ctx.widget_state.request_anim = true;
+        }
+    }
+
     /// Send notifications originating from this widget's children to this
     /// widget.
     ///
@@ -908,6 +1050,10 @@ impl<T: Data, W: Widget<T>> WidgetPod<T, W> {
     ///
     /// [`LifeCycle`]: enum.LifeCycle.html
     pub fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        #[cfg(feature = "log-missing-keys")]
+        let _widget_span = self.widget_span();
+        #[cfg(feature = "log-missing-keys")]
+        let _widget_span = _widget_span.enter();
         // in the case of an internal routing event, if we are at our target
         // we may send an extra event after the actual event
         let mut extra_event = None;
@@ -1041,6 +1187,7 @@ impl<T: Data, W: Widget<T>> WidgetPod<T, W> {
                     self.state.has_focus = is_focused;
 
                     self.state.focus_chain.clear();
+                    self.state.tab_indices.clear();
                     true
                 } else {
                     false
@@ -1098,8 +1245,17 @@ impl<T: Data, W: Widget<T>> WidgetPod<T, W> {
                 }
                 self.state.has_focus = had_focus;
 
+                if let Some(index) = self.state.tab_index {
+                    for id in &self.state.focus_chain {
+                        self.state.tab_indices.entry(*id).or_insert(index);
+                    }
+                }
+
                 if !self.state.is_disabled() {
                     ctx.widget_state.focus_chain.extend(&self.state.focus_chain);
+                    ctx.widget_state
+                        .tab_indices
+                        .extend(self.state.tab_indices.iter());
                 }
             }
             _ => (),
@@ -1115,6 +1271,10 @@ impl<T: Data, W: Widget<T>> WidgetPod<T, W> {
     ///
     /// [`update`]: trait.Widget.html#tymethod.update
     pub fn update(&mut self, ctx: &mut UpdateCtx, data: &T, env: &Env) {
+        #[cfg(feature = "log-missing-keys")]
+        let _widget_span = self.widget_span();
+        #[cfg(feature = "log-missing-keys")]
+        let _widget_span = _widget_span.enter();
         if !self.state.request_update {
             match (self.old_data.as_ref(), self.env.as_ref()) {
                 (Some(d), Some(e)) if d.same(data) && e.same(env) => {
@@ -1211,18 +1371,24 @@ impl WidgetState {
             is_explicitly_disabled: false,
             baseline_offset: 0.0,
             is_hot: false,
+            is_drop_hovered: false,
             needs_layout: false,
             needs_window_origin: false,
             is_active: false,
             has_active: false,
             has_focus: false,
             request_anim: false,
+            animations: Vec::new(),
             request_update: false,
             request_focus: None,
             focus_chain: Vec::new(),
+            tab_index: None,
+            tab_indices: HashMap::new(),
             children: Bloom::new(),
             children_changed: false,
             timers: HashMap::new(),
+            cancelled_timers: Vec::new(),
+            timer_owner: None,
             cursor_change: CursorChange::Default,
             cursor: None,
             sub_window_hosts: Vec::new(),
@@ -1241,8 +1407,24 @@ impl WidgetState {
             || self.is_explicitly_disabled != self.is_explicitly_disabled_new
     }
 
-    pub(crate) fn add_timer(&mut self, timer_token: TimerToken) {
-        self.timers.insert(timer_token, self.id);
+    /// Associate a platform timer with this widget.
+    ///
+    /// An interval is delivered with the `token` of its first platform timer
+    /// every time it fires, while it's re-armed with new platform timers.
+    pub(crate) fn add_timer(
+        &mut self,
+        platform_token: TimerToken,
+        token: TimerToken,
+        interval: Option<Duration>,
+    ) {
+        let owner = self.timer_owner.get_or_insert_with(|| Rc::new(()));
+        let timer = WidgetTimer {
+            widget_id: self.id,
+            owner: Rc::downgrade(owner),
+            token,
+            interval,
+        };
+        self.timers.insert(platform_token, timer);
     }
 
     /// Update to incorporate state changes from a child.
@@ -1285,6 +1467,8 @@ impl WidgetState {
         self.request_update |= child_state.request_update;
         self.request_focus = child_state.request_focus.take().or(self.request_focus);
         self.timers.extend_drain(&mut child_state.timers);
+        self.cancelled_timers
+            .extend(child_state.cancelled_timers.drain(..));
         self.text_registrations
             .extend(child_state.text_registrations.drain(..));
         self.update_focus_chain |= child_state.update_focus_chain;
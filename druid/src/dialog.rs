@@ -152,7 +152,10 @@ impl FileDialogOptions {
 
     /// Set multiple items to be selectable.
     ///
-    /// This is only relevant for open dialogs.
+    /// This is only relevant for open dialogs. All the chosen paths are available
+    /// from [`FileInfo::paths`].
+    ///
+    /// [`FileInfo::paths`]: crate::FileInfo::paths
     pub fn multi_selection(mut self) -> Self {
         self.opt = self.opt.multi_selection();
         self
@@ -14,17 +14,16 @@
 
 //! An environment which is passed downward into the widget tree.
 
-use std::any;
+use std::any::{self, Any};
 use std::borrow::Borrow;
 use std::collections::{hash_map::Entry, HashMap};
 use std::fmt::{Debug, Formatter};
-use std::marker::PhantomData;
 use std::ops::Deref;
 use std::sync::Arc;
 
 use crate::localization::L10nManager;
 use crate::text::FontDescriptor;
-use crate::{ArcStr, Color, Data, Insets, Point, Rect, Size};
+use crate::{ArcStr, Color, Data, Insets, Point, Rect, RoundedRectRadii, Size};
 
 /// An environment passed down through all widget traversals.
 ///
@@ -43,7 +42,8 @@ use crate::{ArcStr, Color, Data, Insets, Point, Rect, Size};
 /// It is the programmer's responsibility to ensure that the environment
 /// is used correctly. See [`Key`] for an example.
 /// - [`Key`]s should be `const`s with unique names
-/// - [`Key`]s must always be set before they are used.
+/// - [`Key`]s must always be set before they are used, unless they were
+///   created with a default value.
 /// - Values can only be overwritten by values of the same type.
 ///
 /// [`EnvScope`]: widget/struct.EnvScope.html
@@ -87,10 +87,10 @@ struct EnvImpl {
 ///
 /// [`ValueType`]: trait.ValueType.html
 /// [`Env`]: struct.Env.html
-#[derive(Clone, Debug, PartialEq, Data)]
+#[derive(Clone, Debug)]
 pub struct Key<T> {
     key: &'static str,
-    value_type: PhantomData<T>,
+    default: Option<T>,
 }
 
 // we could do some serious deriving here: the set of types that can be stored
@@ -105,15 +105,32 @@ pub enum Value {
     Size(Size),
     Rect(Rect),
     Insets(Insets),
+    RoundedRectRadii(RoundedRectRadii),
     Color(Color),
     Float(f64),
     Bool(bool),
     UnsignedInt(u64),
     String(ArcStr),
     Font(FontDescriptor),
+    /// A value of a type that is not one of the other variants; see
+    /// [`ValueType`] for how to store your own types in an `Env`.
+    ///
+    /// [`ValueType`]: trait.ValueType.html
+    Other(OtherValue),
 }
 // ANCHOR_END: value_type
 
+/// A value of any [`Data`] type, stored in a [`Value::Other`].
+///
+/// [`Data`]: trait.Data.html
+/// [`Value::Other`]: enum.Value.html#variant.Other
+#[derive(Clone)]
+pub struct OtherValue {
+    value: Arc<dyn Any>,
+    type_name: &'static str,
+    same: fn(&dyn Any, &dyn Any) -> bool,
+}
+
 /// Either a concrete `T` or a [`Key<T>`] that can be resolved in the [`Env`].
 ///
 /// This is a way to allow widgets to interchangeably use either a specific
@@ -160,7 +177,7 @@ pub trait KeyLike<T> {
 
 impl<T: ValueType> KeyLike<T> for Key<T> {
     fn changed(&self, old: &Env, new: &Env) -> bool {
-        !old.get_untyped(self).same(new.get_untyped(self))
+        key_changed(self.key, old, new)
     }
 }
 
@@ -168,15 +185,70 @@ impl<T> KeyLike<T> for KeyOrValue<T> {
     fn changed(&self, old: &Env, new: &Env) -> bool {
         match self {
             KeyOrValue::Concrete(_) => false,
-            KeyOrValue::Key(key) => !old.get_untyped(key).same(new.get_untyped(key)),
+            KeyOrValue::Key(key) => key_changed(key.key, old, new),
         }
     }
 }
 
+// A key that is only set in one of the two environments counts as changed,
+// even if it has a default value that is the same as the value that is set.
+fn key_changed(key: &str, old: &Env, new: &Env) -> bool {
+    match (old.0.map.get(key), new.0.map.get(key)) {
+        (Some(old), Some(new)) => !old.same(new),
+        (None, None) => false,
+        _ => true,
+    }
+}
+
 /// Values which can be stored in an environment.
+///
+/// # Custom types
+///
+/// Any type that implements [`Data`] can be stored in an `Env`, in a
+/// [`Value::Other`]. This only takes a conversion in each direction:
+///
+/// ```
+/// use druid::{Data, Env, Key, Value, ValueType, ValueTypeError};
+///
+/// #[derive(Clone, Data)]
+/// struct IconSet {
+///     size: f64,
+/// }
+///
+/// impl From<IconSet> for Value {
+///     fn from(icons: IconSet) -> Value {
+///         Value::other(icons)
+///     }
+/// }
+///
+/// impl ValueType for IconSet {
+///     fn try_from_value(value: &Value) -> Result<Self, ValueTypeError> {
+///         value.downcast_other()
+///     }
+/// }
+///
+/// const ICONS: Key<IconSet> = Key::new("org.linebender.example.icons");
+///
+/// let env = Env::default().adding(ICONS, IconSet { size: 16.0 });
+/// assert_eq!(env.get(ICONS).size, 16.0);
+/// ```
+///
+/// [`Data`]: trait.Data.html
+/// [`Value::Other`]: enum.Value.html#variant.Other
 pub trait ValueType: Sized + Clone + Into<Value> {
     /// Attempt to convert the generic `Value` into this type.
     fn try_from_value(v: &Value) -> Result<Self, ValueTypeError>;
+
+    /// A value that stands in for a missing key that has no default.
+    ///
+    /// This is only used when the `log-missing-keys` feature is enabled, in
+    /// which case [`Env::get`] logs the missing key and returns this value
+    /// instead of panicking. If this returns `None`, a missing key still panics.
+    ///
+    /// [`Env::get`]: struct.Env.html#method.get
+    fn placeholder() -> Option<Self> {
+        None
+    }
 }
 
 /// The error type for environment access.
@@ -186,6 +258,7 @@ pub trait ValueType: Sized + Clone + Into<Value> {
 /// mismatch.
 #[derive(Debug, Clone)]
 pub struct ValueTypeError {
+    key: Option<Arc<str>>,
     expected: &'static str,
     found: Value,
 }
@@ -242,35 +315,59 @@ impl Env {
 
     /// Gets a value from the environment, expecting it to be present.
     ///
+    /// If the key is not set, but was created with [`Key::with_default`], its
+    /// default value is returned.
+    ///
     /// Note that the return value is a reference for "expensive" types such
     /// as strings, but an ordinary value for "cheap" types such as numbers
     /// and colors.
     ///
     /// # Panics
     ///
-    /// Panics if the key is not found, or if it is present with the wrong type.
+    /// Panics if the key is not found and has no default, or if it is present
+    /// with the wrong type.
+    ///
+    /// With the `log-missing-keys` feature enabled, a missing key or a value
+    /// of the wrong type is logged as an error instead, and the
+    /// [`ValueType::placeholder`] is returned if the type has one. The logs of
+    /// widget methods are marked with the id of the widget, so this points to
+    /// the widget that asked for the key.
+    ///
+    /// [`Key::with_default`]: struct.Key.html#method.with_default
+    /// [`ValueType::placeholder`]: trait.ValueType.html#method.placeholder
     pub fn get<V: ValueType>(&self, key: impl Borrow<Key<V>>) -> V {
-        match self.try_get(key) {
-            Ok(value) => value,
-            Err(err) => panic!("{}", err),
+        let key = key.borrow();
+        match self.0.map.get(key.key) {
+            Some(value) => {
+                V::try_from_value(value).unwrap_or_else(|err| key_error(err.with_key(key.key)))
+            }
+            None => key.default.clone().unwrap_or_else(|| {
+                key_error(MissingKeyError {
+                    key: key.key.into(),
+                })
+            }),
         }
     }
 
     /// Trys to get a value from the environment.
     ///
-    /// If the value is not found, the raw key is returned as the error.
+    /// If the key is not set, but has a default value, the default is returned.
+    /// Otherwise, the error contains the raw key that was missing.
     ///
     /// # Panics
     ///
     /// Panics if the value for the key is found, but has the wrong type.
     pub fn try_get<V: ValueType>(&self, key: impl Borrow<Key<V>>) -> Result<V, MissingKeyError> {
-        self.0
-            .map
-            .get(key.borrow().key)
-            .map(|value| value.to_inner_unchecked())
-            .ok_or(MissingKeyError {
-                key: key.borrow().key.into(),
-            })
+        let key = key.borrow();
+        match self.0.map.get(key.key) {
+            Some(value) => match V::try_from_value(value) {
+                Ok(value) => Ok(value),
+                Err(err) => panic!("{}", err.with_key(key.key)),
+            },
+            None => key.default.clone().ok_or(MissingKeyError {
+                key: key.key.into(),
+            }),
+        }
     }
 
     /// Gets a value from the environment, in its encapsulated [`Value`] form,
@@ -281,7 +378,8 @@ impl Env {
     ///
     /// # Panics
     ///
-    /// Panics if the key is not found.
+    /// Panics if the key is not found. Default values of keys are not
+    /// considered, as they are not part of the `Env`.
     ///
     /// [`Value`]: enum.Value.html
     pub fn get_untyped<V>(&self, key: impl Borrow<Key<V>>) -> &Value {
@@ -346,7 +444,8 @@ impl Env {
             Entry::Occupied(mut e) => {
                 let existing = e.get_mut();
                 if !existing.is_same_type(&raw) {
-                    return Err(ValueTypeError::new(any::type_name::<V>(), raw));
+                    let err = ValueTypeError::new(any::type_name::<V>(), raw);
+                    return Err(err.with_key(e.key()));
                 }
                 *existing = raw;
             }
@@ -365,6 +464,19 @@ impl Env {
         &self.0.l10n
     }
 
+    /// Switches the localization resources to `locale`, a BCP47 language tag.
+    ///
+    /// Returns `false`, and leaves the `Env` unchanged, if `locale` is not valid.
+    pub(crate) fn set_locale(&mut self, locale: &str) -> bool {
+        match self.0.l10n.with_locale(locale) {
+            Some(l10n) => {
+                Arc::make_mut(&mut self.0).l10n = Arc::new(l10n);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Given an id, returns one of 18 distinct colors
     #[doc(hidden)]
     pub fn get_debug_color(&self, id: u64) -> Color {
@@ -387,13 +499,46 @@ impl<T> Key<T> {
     /// let color_key: Key<Color> = Key::new("org.linebender.example.a.very.nice.color");
     /// ```
     pub const fn new(key: &'static str) -> Self {
+        Key { key, default: None }
+    }
+
+    /// Create a new strongly typed `Key` with a default value, which is used
+    /// when the key is not set in the [`Env`].
+    ///
+    /// A value that is set in the `Env` always takes precedence over the default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid::{Env, Key};
+    ///
+    /// const SPACING: Key<f64> = Key::with_default("org.linebender.example.spacing", 8.0);
+    ///
+    /// assert_eq!(Env::default().get(SPACING), 8.0);
+    /// assert_eq!(Env::default().adding(SPACING, 4.0).get(SPACING), 4.0);
+    /// ```
+    ///
+    /// [`Env`]: struct.Env.html
+    pub const fn with_default(key: &'static str, default: T) -> Self {
         Key {
             key,
-            value_type: PhantomData,
+            default: Some(default),
         }
     }
 }
 
+impl<T> PartialEq for Key<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<T: Clone + 'static> Data for Key<T> {
+    fn same(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
 impl Key<()> {
     /// Create an untyped `Key` with the given string value.
     ///
@@ -403,10 +548,7 @@ impl Key<()> {
     ///
     /// [`Env`]: struct.Env.html
     pub const fn untyped(key: &'static str) -> Self {
-        Key {
-            key,
-            value_type: PhantomData,
-        }
+        Key { key, default: None }
     }
 
     /// Return this key's raw string value.
@@ -431,14 +573,46 @@ impl Value {
         }
     }
 
+    /// Wraps a value of any [`Data`] type that is not one of the other variants,
+    /// for implementing `From<T> for Value`; see [`ValueType`].
+    ///
+    /// [`Data`]: trait.Data.html
+    /// [`ValueType`]: trait.ValueType.html
+    pub fn other<T: Data>(value: T) -> Value {
+        Value::Other(OtherValue {
+            value: Arc::new(value),
+            type_name: any::type_name::<T>(),
+            same: same_other::<T>,
+        })
+    }
+
+    /// Returns a clone of the value in a [`Value::Other`], for implementing
+    /// [`ValueType::try_from_value`].
+    ///
+    /// Returns an error if this is another variant, or if the value is not a `T`.
+    ///
+    /// [`Value::Other`]: #variant.Other
+    /// [`ValueType::try_from_value`]: trait.ValueType.html#tymethod.try_from_value
+    pub fn downcast_other<T: Data>(&self) -> Result<T, ValueTypeError> {
+        match self {
+            Value::Other(other) => other.value.downcast_ref::<T>().cloned(),
+            _ => None,
+        }
+        .ok_or_else(|| ValueTypeError::new(any::type_name::<T>(), self.clone()))
+    }
+
     fn is_same_type(&self, other: &Value) -> bool {
         use Value::*;
+        if let (Other(a), Other(b)) = (self, other) {
+            return (*a.value).type_id() == (*b.value).type_id();
+        }
         matches!(
             (self, other),
             (Point(_), Point(_))
                 | (Size(_), Size(_))
                 | (Rect(_), Rect(_))
                 | (Insets(_), Insets(_))
+                | (RoundedRectRadii(_), RoundedRectRadii(_))
                 | (Color(_), Color(_))
                 | (Float(_), Float(_))
                 | (Bool(_), Bool(_))
@@ -456,16 +630,50 @@ impl Debug for Value {
             Value::Size(s) => write!(f, "Size {:?}", s),
             Value::Rect(r) => write!(f, "Rect {:?}", r),
             Value::Insets(i) => write!(f, "Insets {:?}", i),
+            Value::RoundedRectRadii(r) => write!(f, "RoundedRectRadii {:?}", r),
             Value::Color(c) => write!(f, "Color {:?}", c),
             Value::Float(x) => write!(f, "Float {}", x),
             Value::Bool(b) => write!(f, "Bool {}", b),
             Value::UnsignedInt(x) => write!(f, "UnsignedInt {}", x),
             Value::String(s) => write!(f, "String {:?}", s),
             Value::Font(font) => write!(f, "Font {:?}", font),
+            Value::Other(other) => write!(f, "Other {:?}", other),
         }
     }
 }
 
+impl OtherValue {
+    /// The name of the type of the value.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+fn same_other<T: Data>(a: &dyn Any, b: &dyn Any) -> bool {
+    match (a.downcast_ref::<T>(), b.downcast_ref::<T>()) {
+        (Some(a), Some(b)) => a.same(b),
+        _ => false,
+    }
+}
+
+impl Data for OtherValue {
+    fn same(&self, other: &OtherValue) -> bool {
+        Arc::ptr_eq(&self.value, &other.value) || (self.same)(&*self.value, &*other.value)
+    }
+}
+
+impl PartialEq for OtherValue {
+    fn eq(&self, other: &OtherValue) -> bool {
+        self.same(other)
+    }
+}
+
+impl Debug for OtherValue {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.type_name)
+    }
+}
+
 impl Data for Env {
     fn same(&self, other: &Env) -> bool {
         Arc::ptr_eq(&self.0, &other.0) || self.0.deref().same(other.0.deref())
@@ -474,7 +682,8 @@ impl Data for Env {
 
 impl Data for EnvImpl {
     fn same(&self, other: &EnvImpl) -> bool {
-        self.map.len() == other.map.len()
+        self.l10n.current_locale() == other.l10n.current_locale()
+            && self.map.len() == other.map.len()
             && self
                 .map
                 .iter()
@@ -528,6 +737,16 @@ impl Env {
 
         crate::theme::add_to_env(env)
     }
+
+    /// An environment without any values, to see what other code adds to it.
+    #[cfg(test)]
+    pub(crate) fn empty() -> Self {
+        Env(Arc::new(EnvImpl {
+            l10n: Arc::new(L10nManager::new(vec![], "./resources/i18n/")),
+            map: HashMap::new(),
+            debug_colors: DEBUG_COLOR.into(),
+        }))
+    }
 }
 
 impl<T> From<Key<T>> for ArcStr {
@@ -538,16 +757,41 @@ impl<T> From<Key<T>> for ArcStr {
 
 impl ValueTypeError {
     fn new(expected: &'static str, found: Value) -> ValueTypeError {
-        ValueTypeError { expected, found }
+        ValueTypeError {
+            key: None,
+            expected,
+            found,
+        }
+    }
+
+    fn with_key(mut self, key: &str) -> ValueTypeError {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// The raw key whose value had the wrong type, if this error came from
+    /// accessing an [`Env`].
+    ///
+    /// [`Env`]: struct.Env.html
+    pub fn raw_key(&self) -> Option<&str> {
+        self.key.as_deref()
     }
 }
+
 impl std::fmt::Display for ValueTypeError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(
-            f,
-            "Incorrect value type: expected {} found {:?}",
-            self.expected, self.found
-        )
+        match &self.key {
+            Some(key) => write!(
+                f,
+                "Incorrect value type for key '{}': expected {} found {:?}",
+                key, self.expected, self.found
+            ),
+            None => write!(
+                f,
+                "Incorrect value type: expected {} found {:?}",
+                self.expected, self.found
+            ),
+        }
     }
 }
 
@@ -567,9 +811,25 @@ impl std::fmt::Display for MissingKeyError {
 impl std::error::Error for ValueTypeError {}
 impl std::error::Error for MissingKeyError {}
 
+#[cfg(not(feature = "log-missing-keys"))]
+fn key_error<V: ValueType>(err: impl std::error::Error) -> V {
+    panic!("{}", err)
+}
+
+#[cfg(feature = "log-missing-keys")]
+fn key_error<V: ValueType>(err: impl std::error::Error) -> V {
+    match V::placeholder() {
+        Some(value) => {
+            tracing::error!("{}", err);
+            value
+        }
+        None => panic!("{}", err),
+    }
+}
+
 /// Use this macro for types which are cheap to clone (ie all `Copy` types).
 macro_rules! impl_value_type {
-    ($ty:ty, $var:ident) => {
+    ($ty:ty, $var:ident, $placeholder:expr) => {
         impl ValueType for $ty {
             fn try_from_value(value: &Value) -> Result<Self, ValueTypeError> {
                 match value {
@@ -577,6 +837,10 @@ macro_rules! impl_value_type {
                     other => Err(ValueTypeError::new(any::type_name::<$ty>(), other.clone())),
                 }
             }
+
+            fn placeholder() -> Option<Self> {
+                Some($placeholder)
+            }
         }
 
         impl From<$ty> for Value {
@@ -587,16 +851,41 @@ macro_rules! impl_value_type {
     };
 }
 
-impl_value_type!(f64, Float);
-impl_value_type!(bool, Bool);
-impl_value_type!(u64, UnsignedInt);
-impl_value_type!(Color, Color);
-impl_value_type!(Rect, Rect);
-impl_value_type!(Point, Point);
-impl_value_type!(Size, Size);
-impl_value_type!(Insets, Insets);
-impl_value_type!(ArcStr, String);
-impl_value_type!(FontDescriptor, Font);
+impl_value_type!(f64, Float, 0.0);
+impl_value_type!(bool, Bool, false);
+impl_value_type!(u64, UnsignedInt, 0);
+// a loud color, so that whatever is painted with a missing color stands out
+impl_value_type!(Color, Color, Color::rgb8(0xff, 0x00, 0xff));
+impl_value_type!(Rect, Rect, Rect::ZERO);
+impl_value_type!(Point, Point, Point::ORIGIN);
+impl_value_type!(Size, Size, Size::ZERO);
+impl_value_type!(Insets, Insets, Insets::ZERO);
+impl_value_type!(ArcStr, String, ArcStr::from(""));
+
+// A single radius is stored as a float, so this accepts floats as well.
+impl ValueType for RoundedRectRadii {
+    fn try_from_value(value: &Value) -> Result<Self, ValueTypeError> {
+        match value {
+            Value::RoundedRectRadii(radii) => Ok(*radii),
+            Value::Float(radius) => Ok(RoundedRectRadii::from(*radius)),
+            other => Err(ValueTypeError::new(
+                any::type_name::<RoundedRectRadii>(),
+                other.clone(),
+            )),
+        }
+    }
+
+    fn placeholder() -> Option<Self> {
+        Some(RoundedRectRadii::from(0.0))
+    }
+}
+
+impl From<RoundedRectRadii> for Value {
+    fn from(val: RoundedRectRadii) -> Value {
+        Value::RoundedRectRadii(val)
+    }
+}
+impl_value_type!(FontDescriptor, Font, FontDescriptor::default());
 
 impl<T: ValueType> KeyOrValue<T> {
     /// Resolve the concrete type `T` from this `KeyOrValue`, using the provided
@@ -629,6 +918,27 @@ impl From<f64> for KeyOrValue<Insets> {
     }
 }
 
+impl From<f64> for KeyOrValue<RoundedRectRadii> {
+    fn from(src: f64) -> KeyOrValue<RoundedRectRadii> {
+        KeyOrValue::Concrete(src.into())
+    }
+}
+
+impl From<(f64, f64, f64, f64)> for KeyOrValue<RoundedRectRadii> {
+    fn from(src: (f64, f64, f64, f64)) -> KeyOrValue<RoundedRectRadii> {
+        KeyOrValue::Concrete(src.into())
+    }
+}
+
+impl From<Key<f64>> for KeyOrValue<RoundedRectRadii> {
+    fn from(key: Key<f64>) -> KeyOrValue<RoundedRectRadii> {
+        KeyOrValue::Key(Key {
+            key: key.key,
+            default: key.default.map(RoundedRectRadii::from),
+        })
+    }
+}
+
 impl From<(f64, f64)> for KeyOrValue<Insets> {
     fn from(src: (f64, f64)) -> KeyOrValue<Insets> {
         KeyOrValue::Concrete(src.into())
@@ -664,4 +974,71 @@ mod tests {
 
         assert_send_sync::<Key<()>>();
     }
+
+    #[test]
+    fn default_is_used_for_missing_keys() {
+        const MY_KEY: Key<f64> = Key::with_default("org.linebender.test.defaulted-key", 2.0);
+        const NO_DEFAULT: Key<f64> = Key::new("org.linebender.test.not-defaulted-key");
+
+        let env = Env::default();
+        assert_eq!(env.get(MY_KEY), 2.0);
+        assert_eq!(
+            env.try_get(NO_DEFAULT).unwrap_err().raw_key(),
+            NO_DEFAULT.key
+        );
+
+        let env = env.adding(MY_KEY, 4.0);
+        assert_eq!(env.get(MY_KEY), 4.0);
+    }
+
+    #[test]
+    fn missing_keys_changed() {
+        const MY_KEY: Key<f64> = Key::with_default("org.linebender.test.changed-key", 2.0);
+
+        let old = Env::default();
+        assert!(!MY_KEY.changed(&old, &old));
+
+        let new = old.clone().adding(MY_KEY, 2.0);
+        assert!(MY_KEY.changed(&old, &new));
+        assert!(!MY_KEY.changed(&new, &new.clone().adding(MY_KEY, 2.0)));
+    }
+
+    #[derive(Clone, Data)]
+    struct Custom(f64);
+
+    impl From<Custom> for Value {
+        fn from(custom: Custom) -> Value {
+            Value::other(custom)
+        }
+    }
+
+    impl ValueType for Custom {
+        fn try_from_value(value: &Value) -> Result<Self, ValueTypeError> {
+            value.downcast_other()
+        }
+    }
+
+    #[test]
+    fn custom_value_type() {
+        const CUSTOM_KEY: Key<Custom> = Key::new("org.linebender.test.custom-key");
+
+        let env = Env::default().adding(CUSTOM_KEY, Custom(1.0));
+        assert_eq!(env.get(CUSTOM_KEY).0, 1.0);
+        assert!(!CUSTOM_KEY.changed(&env, &env.clone().adding(CUSTOM_KEY, Custom(1.0))));
+        assert!(CUSTOM_KEY.changed(&env, &env.clone().adding(CUSTOM_KEY, Custom(2.0))));
+
+        let mut env = env;
+        let err = env.try_set_raw(CUSTOM_KEY, Value::Float(1.0)).unwrap_err();
+        assert_eq!(err.raw_key(), Some(CUSTOM_KEY.key));
+    }
+
+    #[test]
+    #[should_panic(expected = "Incorrect value type for key 'org.linebender.test.mismatched-key'")]
+    fn type_mismatch_names_the_key() {
+        const FLOAT_KEY: Key<f64> = Key::new("org.linebender.test.mismatched-key");
+        const CUSTOM_KEY: Key<Custom> = Key::new("org.linebender.test.mismatched-key");
+
+        let env = Env::default().adding(FLOAT_KEY, 1.0);
+        env.get(CUSTOM_KEY);
+    }
 }
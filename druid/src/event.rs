@@ -14,12 +14,14 @@
 
 //! Events.
 
-use crate::kurbo::{Rect, Shape, Size, Vec2};
+use std::path::PathBuf;
 
-use druid_shell::{Clipboard, KeyEvent, TimerToken};
+use crate::kurbo::{Point, Rect, Shape, Size, Vec2};
+
+use druid_shell::{Clipboard, KeyEvent, Scale, TimerToken};
 
 use crate::mouse::MouseEvent;
-use crate::{Command, Notification, WidgetId};
+use crate::{AnimationToken, Command, Notification, WidgetId};
 
 /// An event, propagated downwards during event flow.
 ///
@@ -67,7 +69,18 @@ pub enum Event {
     /// that the window *will* close just because this event is received; for instance, you should
     /// avoid destructive side effects such as cleaning up resources.
     ///
+    /// This is sent when the user clicks the close button in the titlebar, and when a
+    /// [`CLOSE_WINDOW`] command is submitted. The [`AppDelegate`] sees it first, and can
+    /// cancel the close by not returning it from [`AppDelegate::event`].
+    ///
+    /// To close the window later, for instance once the user has confirmed that unsaved
+    /// changes can be discarded, submit [`FORCE_CLOSE_WINDOW`], which doesn't ask again.
+    ///
     /// [`set_handled`]: crate::EventCtx::set_handled
+    /// [`CLOSE_WINDOW`]: crate::commands::CLOSE_WINDOW
+    /// [`FORCE_CLOSE_WINDOW`]: crate::commands::FORCE_CLOSE_WINDOW
+    /// [`AppDelegate`]: crate::AppDelegate
+    /// [`AppDelegate::event`]: crate::AppDelegate::event
     WindowCloseRequested,
     /// Sent to all widgets in a given window when the system is going to close that window.
     ///
@@ -81,6 +94,13 @@ pub enum Event {
     /// in the WindowPod, but after that it might be considered better
     /// to just handle it in `layout`.
     WindowSize(Size),
+    /// Sent to all widgets in a given window when the window's scale changes,
+    /// for example when it is moved to a monitor with a different DPI.
+    ///
+    /// Layout is redone automatically, but widgets that cache anything
+    /// rendered at the old scale, such as pixel-snapped text layouts or
+    /// rasterized images, should rebuild it.
+    WindowScale(Scale),
     /// Called when a mouse button is pressed.
     MouseDown(MouseEvent),
     /// Called when a mouse button is released.
@@ -115,6 +135,47 @@ pub enum Event {
     ///
     /// The value is a delta.
     Zoom(f64),
+    /// Called while files are dragged over the widget, with the position of the
+    /// cursor and the paths of the files.
+    ///
+    /// This is sent to the widgets under the cursor, every time the cursor moves.
+    /// Widgets that accept the files can use it to paint a highlight, which they
+    /// should remove when they receive [`Event::DropCancelled`] or [`Event::Dropped`].
+    ///
+    /// # Platform support
+    ///
+    /// Only the GTK and macOS backends report files being dragged over a window.
+    /// The Windows backend only reports the drop, so widgets get an
+    /// [`Event::Dropped`] without any `DropHover` or `DropCancelled` before it. The
+    /// X11 and web backends don't support dropping files at all.
+    ///
+    /// [`Event::DropCancelled`]: Event::DropCancelled
+    /// [`Event::Dropped`]: Event::Dropped
+    DropHover(Point, Vec<PathBuf>),
+    /// Called when files that were dragged over the widget leave it without being
+    /// dropped on it, either because the cursor moved away or because the drag was
+    /// cancelled.
+    ///
+    /// This is only sent to widgets that received an [`Event::DropHover`], so it
+    /// is only sent on the platforms listed there.
+    ///
+    /// [`Event::DropHover`]: Event::DropHover
+    DropCancelled,
+    /// Called when files are dropped on the widget, with the position of the cursor
+    /// and the paths of the files.
+    ///
+    /// Like mouse events, this is sent to the widgets under the cursor, from the root
+    /// down. A container that wants to accept the files only if none of its children
+    /// do should pass the event to its children first, and only handle it if they
+    /// didn't call [`set_handled`]; [`WidgetExt::on_file_drop`] does this.
+    ///
+    /// This is sent on GTK, macOS and Windows; see [`Event::DropHover`] for the
+    /// details.
+    ///
+    /// [`Event::DropHover`]: Event::DropHover
+    /// [`set_handled`]: crate::EventCtx::set_handled
+    /// [`WidgetExt::on_file_drop`]: crate::WidgetExt::on_file_drop
+    Dropped(Point, Vec<PathBuf>),
     /// Called on a timer event.
     ///
     /// Request a timer event through [`EventCtx::request_timer()`]. That will
@@ -137,6 +198,25 @@ pub enum Event {
     /// intensive in response to an `AnimFrame` event: it might make Druid miss
     /// the monitor's refresh, causing lag or jerky animation.
     AnimFrame(u64),
+    /// Called on each animation frame while an animation is running, with the
+    /// eased progress of the animation, from `0.0` to `1.0`.
+    ///
+    /// Request an animation through [`EventCtx::request_animation`]. Only the
+    /// widget that requested it receives this event; the progress of the last
+    /// frame is always exactly `1.0`. Depending on its [`AnimationCurve`], the
+    /// progress may briefly go beyond `1.0` before that.
+    ///
+    /// [`EventCtx::request_animation`]: crate::EventCtx::request_animation
+    /// [`AnimationCurve`]: crate::AnimationCurve
+    AnimProgress(AnimationToken, f64),
+    /// Called right after the last [`AnimProgress`] of an animation.
+    ///
+    /// This isn't sent for animations that were cancelled with
+    /// [`EventCtx::cancel_animation`].
+    ///
+    /// [`AnimProgress`]: Event::AnimProgress
+    /// [`EventCtx::cancel_animation`]: crate::EventCtx::cancel_animation
+    AnimEnded(AnimationToken),
     /// An event containing a [`Command`] to be handled by the widget.
     ///
     /// [`Command`]s are messages, optionally with attached data, that can
@@ -280,6 +360,11 @@ pub enum LifeCycle {
     /// when the mouse moves over a widget, that widget will receive
     /// `LifeCycle::HotChanged` before it receives `Event::MouseMove`.
     ///
+    /// It is only sent when the hot status actually changes, so widgets that
+    /// only need hover feedback can request a paint here instead of on every
+    /// mouse move. When the mouse leaves the window, every hot widget receives
+    /// `HotChanged(false)`.
+    ///
     /// See [`is_hot`](struct.EventCtx.html#method.is_hot) for
     /// discussion about the hot status.
     HotChanged(bool),
@@ -393,6 +478,12 @@ impl Event {
                     None
                 }
             }
+            // Outside of the viewport, the contents are no longer under the cursor.
+            Event::DropHover(pos, _) | Event::Dropped(pos, _) if viewport.winding(*pos) == 0 => {
+                Some(Event::DropCancelled)
+            }
+            Event::DropHover(pos, paths) => Some(Event::DropHover(*pos + offset, paths.clone())),
+            Event::Dropped(pos, paths) => Some(Event::Dropped(*pos + offset, paths.clone())),
             _ => Some(self.clone()),
         }
     }
@@ -405,10 +496,14 @@ impl Event {
             | Event::WindowCloseRequested
             | Event::WindowDisconnected
             | Event::WindowSize(_)
+            | Event::WindowScale(_)
             | Event::Timer(_)
             | Event::AnimFrame(_)
+            | Event::AnimProgress(..)
+            | Event::AnimEnded(_)
             | Event::Command(_)
             | Event::Notification(_)
+            | Event::DropCancelled
             | Event::Internal(_) => true,
             Event::MouseDown(_)
             | Event::MouseUp(_)
@@ -418,7 +513,9 @@ impl Event {
             | Event::KeyUp(_)
             | Event::Paste(_)
             | Event::ImeStateChange
-            | Event::Zoom(_) => false,
+            | Event::Zoom(_)
+            | Event::DropHover(..)
+            | Event::Dropped(..) => false,
         }
     }
 }
@@ -16,7 +16,7 @@
 
 use std::any::Any;
 use std::collections::VecDeque;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, Weak};
 
 use crate::shell::IdleHandle;
 use crate::win_handler::EXT_EVENT_IDLE_TOKEN;
@@ -27,10 +27,16 @@ pub(crate) type ExtCommand = (SelectorSymbol, Box<dyn Any + Send>, Target);
 /// A thing that can move into other threads and be used to submit commands back
 /// to the running application.
 ///
+/// A sink can be used before any window is open; the commands are delivered
+/// once the application is running. Once the application has shut down,
+/// submitting returns an [`ExtEventError`].
+///
 /// This API is preliminary, and may be changed or removed without warning.
 #[derive(Clone)]
 pub struct ExtEventSink {
-    queue: Arc<Mutex<VecDeque<ExtCommand>>>,
+    /// Only the application holds on to the queue, so that we notice when it
+    /// has gone away.
+    queue: Weak<Mutex<VecDeque<ExtCommand>>>,
     handle: Arc<Mutex<Option<IdleHandle>>>,
 }
 
@@ -61,7 +67,7 @@ impl ExtEventHost {
 
     pub(crate) fn make_sink(&self) -> ExtEventSink {
         ExtEventSink {
-            queue: self.queue.clone(),
+            queue: Arc::downgrade(&self.queue),
             handle: self.handle.clone(),
         }
     }
@@ -95,6 +101,8 @@ impl ExtEventSink {
     ///
     /// If the [`Target::Auto`] is equivalent to [`Target::Global`].
     ///
+    /// Returns an [`ExtEventError`] if the application has shut down.
+    ///
     /// [`Command`]: struct.Command.html
     /// [`Selector`]: struct.Selector.html
     /// [`Target::Auto`]: enum.Target.html#variant.Auto
@@ -107,22 +115,42 @@ impl ExtEventSink {
     ) -> Result<(), ExtEventError> {
         let target = target.into();
         let payload = payload.into();
+        let queue = self.queue.upgrade().ok_or(ExtEventError)?;
+        queue
+            .lock()
+            .map_err(|_| ExtEventError)?
+            .push_back((selector.symbol(), payload, target));
+        // wake up the event loop only once the command is in the queue
         if let Some(handle) = self.handle.lock().unwrap().as_mut() {
             handle.schedule_idle(EXT_EVENT_IDLE_TOKEN);
         }
-        self.queue.lock().map_err(|_| ExtEventError)?.push_back((
-            selector.symbol(),
-            payload,
-            target,
-        ));
         Ok(())
     }
 }
 
 impl std::fmt::Display for ExtEventError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "Window missing for external event")
+        write!(f, "The application is no longer running")
     }
 }
 
 impl std::error::Error for ExtEventError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PING: Selector<u32> = Selector::new("druid-test.ping");
+
+    #[test]
+    fn submit_after_shutdown_errors() {
+        let mut host = ExtEventHost::new();
+        let sink = host.make_sink();
+        // no window yet, the command just waits in the queue
+        assert!(sink.submit_command(PING, 1, Target::Auto).is_ok());
+        assert!(host.recv().unwrap().is(PING));
+
+        drop(host);
+        assert!(sink.submit_command(PING, 2, Target::Auto).is_err());
+    }
+}
@@ -148,6 +148,7 @@ pub mod lens;
 #[macro_use]
 mod util;
 
+mod animation;
 mod app;
 mod app_delegate;
 mod bloom;
@@ -174,7 +175,7 @@ mod win_handler;
 mod window;
 
 // Types from kurbo & piet that are required by public API.
-pub use kurbo::{Affine, Insets, Point, Rect, Size, Vec2};
+pub use kurbo::{Affine, Insets, Point, Rect, RoundedRectRadii, Size, Vec2};
 pub use piet::{Color, ImageBuf, LinearGradient, RadialGradient, RenderContext, UnitPoint};
 
 // these are the types from shell that we expose; others we only use internally.
@@ -182,13 +183,14 @@ pub use piet::{Color, ImageBuf, LinearGradient, RadialGradient, RenderContext, U
 pub use shell::image;
 pub use shell::keyboard_types;
 pub use shell::{
-    Application, Clipboard, ClipboardFormat, Code, Cursor, CursorDesc, Error as PlatformError,
-    FileInfo, FileSpec, FormatId, HotKey, KbKey, KeyEvent, Location, Modifiers, Monitor,
-    MouseButton, MouseButtons, RawMods, Region, Scalable, Scale, Screen, SysMods, TimerToken,
-    WindowHandle, WindowLevel, WindowState,
+    Appearance, Application, Clipboard, ClipboardFormat, Code, Cursor, CursorDesc,
+    Error as PlatformError, FileInfo, FileSpec, FormatId, HotKey, KbKey, KeyEvent, Location,
+    Modifiers, Monitor, MouseButton, MouseButtons, RawMods, Region, Scalable, Scale, Screen,
+    SysMods, TimerToken, WindowHandle, WindowLevel, WindowState,
 };
 
 pub use crate::core::WidgetPod;
+pub use animation::{AnimationCurve, AnimationToken};
 pub use app::{AppLauncher, WindowConfig, WindowDesc, WindowSizePolicy};
 pub use app_delegate::{AppDelegate, DelegateCtx};
 pub use box_constraints::BoxConstraints;
@@ -196,7 +198,7 @@ pub use command::{sys as commands, Command, Notification, Selector, SingleUse, T
 pub use contexts::{EventCtx, LayoutCtx, LifeCycleCtx, PaintCtx, UpdateCtx};
 pub use data::Data;
 pub use dialog::FileDialogOptions;
-pub use env::{Env, Key, KeyOrValue, Value, ValueType, ValueTypeError};
+pub use env::{Env, Key, KeyOrValue, OtherValue, Value, ValueType, ValueTypeError};
 pub use event::{Event, InternalEvent, InternalLifeCycle, LifeCycle};
 pub use ext_event::{ExtEventError, ExtEventSink};
 pub use lens::{Lens, LensExt};
@@ -27,14 +27,28 @@
 //! At runtime, you resolve your [`LocalizedString`] into an actual string,
 //! passing it the current [`Env`] and [`Data`].
 //!
+//! Numeric arguments select the plural category of the current locale, so a
+//! message can have a different variant for one item and for many:
+//!
+//! ```ftl
+//! items-selected = { $count ->
+//!     [one] One item selected
+//!    *[other] { $count } items selected
+//! }
+//! ```
+//!
+//! The locale can be switched while the application runs, with the
+//! [`SET_LOCALE`] command; every localized string is then resolved again.
 //!
 //! [Fluent]: https://projectfluent.org
 //! [fluent-rs]: https://github.com/projectfluent/fluent-rs
 //! [`LocalizedString`]: struct.LocalizedString.html
 //! [`Env`]: struct.Env.html
 //! [`Data`]: trait.Data.html
+//! [`SET_LOCALE`]: crate::commands::SET_LOCALE
 
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::{fs, io};
 
@@ -56,17 +70,17 @@ use unic_langid::LanguageIdentifier;
 static FALLBACK_STRINGS: &str = include_str!("../resources/i18n/en-US/builtin.ftl");
 
 /// Provides access to the localization strings for the current locale.
-#[allow(dead_code)]
 pub(crate) struct L10nManager {
-    // these two are not currently used; will be used when we let the user
-    // add additional localization files.
     res_mgr: ResourceManager,
     resources: Vec<String>,
     current_bundle: BundleStack,
     current_locale: LanguageIdentifier,
+    /// The keys we have already warned about, so that each is only logged once.
+    missing_keys: RefCell<HashSet<String>>,
 }
 
 /// Manages a collection of localization files.
+#[derive(Clone)]
 struct ResourceManager {
     resources: HashMap<String, Arc<FluentResource>>,
     locales: Vec<LanguageIdentifier>,
@@ -234,9 +248,31 @@ impl L10nManager {
             resources,
             current_bundle,
             current_locale,
+            missing_keys: Default::default(),
         }
     }
 
+    /// Create a manager for the same resources, with the strings for `locale`.
+    ///
+    /// Returns `None` if `locale` is not a valid BCP47 language tag.
+    pub(crate) fn with_locale(&self, locale: &str) -> Option<Self> {
+        let current_locale: LanguageIdentifier = locale.parse().ok()?;
+        let mut res_mgr = self.res_mgr.clone();
+        let current_bundle = res_mgr.get_bundle(&current_locale, &self.resources);
+        Some(L10nManager {
+            res_mgr,
+            resources: self.resources.clone(),
+            current_bundle,
+            current_locale,
+            missing_keys: Default::default(),
+        })
+    }
+
+    /// The locale that strings are currently localized for.
+    pub(crate) fn current_locale(&self) -> &LanguageIdentifier {
+        &self.current_locale
+    }
+
     /// Fetch a localized string from the current bundle by key.
     ///
     /// In general, this should not be used directly; [`LocalizedString`]
@@ -257,7 +293,15 @@ impl L10nManager {
             .and_then(|msg| msg.value())
         {
             Some(v) => v,
-            None => return None,
+            None => {
+                if self.missing_keys.borrow_mut().insert(key.to_string()) {
+                    warn!(
+                        "no localization for key '{}' in locale {}",
+                        key, self.current_locale
+                    );
+                }
+                return None;
+            }
         };
         let mut errs = Vec::new();
         let result = self
@@ -284,7 +328,6 @@ impl L10nManager {
             Some(result.into())
         }
     }
-    //TODO: handle locale change
 }
 
 impl<T> LocalizedString<T> {
@@ -319,6 +362,10 @@ impl<T> LocalizedString<T> {
     /// Add a named argument and a corresponding closure. This closure
     /// is a function that will return a value for the given key from the current
     /// environment and data.
+    ///
+    /// The arguments are computed again whenever the string is resolved. If the
+    /// closure returns a number, for instance with `(data.len() as u64).into()`,
+    /// the message can select a variant by its plural category.
     pub fn with_arg(
         mut self,
         key: &'static str,
@@ -424,4 +471,63 @@ mod tests {
         assert_eq!(resmgr.resolve_locales(cn_hk), vec![en_us.clone()]);
         assert_eq!(resmgr.resolve_locales(pt_pt), vec![en_us]);
     }
+
+    #[test]
+    fn switching_locale_resolves_again() {
+        let mut env = Env::default();
+        assert!(env.set_locale("en-US"));
+        let mut string = LocalizedString::new("hello-counter")
+            .with_arg("count", |data: &u32, _env| (*data).into());
+
+        assert!(string.resolve(&1, &env));
+        assert_eq!(&*string.localized_str(), "Current value is 1");
+        assert!(!string.resolve(&1, &env));
+        assert!(string.resolve(&2, &env));
+
+        assert!(env.set_locale("de-DE"));
+        assert!(string.resolve(&2, &env));
+        assert_eq!(&*string.localized_str(), "Der aktuelle Wert ist 2");
+
+        assert!(!env.set_locale("not a locale"));
+    }
+
+    #[test]
+    fn plural_arguments() {
+        const TEST_FTL: &str = r#"
+items-selected = { $count ->
+    [one] One item selected
+   *[other] { $count } items selected
+}
+"#;
+
+        let dir = tempfile::tempdir().unwrap();
+        let en_us = dir.path().join("en-US");
+        fs::create_dir(&en_us).unwrap();
+        fs::write(en_us.join("test.ftl"), TEST_FTL).unwrap();
+
+        let mut env = Env::with_i10n(vec!["test.ftl".into()], dir.path().to_str().unwrap());
+        assert!(env.set_locale("en-US"));
+        let mut string = LocalizedString::new("items-selected")
+            .with_arg("count", |data: &u32, _env| (*data).into());
+
+        string.resolve(&1, &env);
+        assert_eq!(&*string.localized_str(), "One item selected");
+        string.resolve(&3, &env);
+        assert_eq!(&*string.localized_str(), "3 items selected");
+    }
+
+    #[test]
+    fn missing_message_uses_placeholder() {
+        let env = Env::default();
+        let mut string =
+            LocalizedString::<()>::new("druid-test-missing").with_placeholder("Placeholder");
+
+        string.resolve(&(), &env);
+        assert_eq!(&*string.localized_str(), "Placeholder");
+        assert!(env
+            .localization_manager()
+            .missing_keys
+            .borrow()
+            .contains("druid-test-missing"));
+    }
 }
@@ -45,6 +45,15 @@
 //!   when to do them. You need to use [`Menu::rebuild_on`] to decide when rebuild should
 //!   occur.
 //!
+//! A window's menu can also be replaced outright, with [`EventCtx::set_menu`] or
+//! [`DelegateCtx::set_menu`].
+//!
+//! ## Checkmarks and radio groups
+//!
+//! A menu item shows a checkmark while it is selected; see [`MenuItem::selected_if`]. Items that
+//! choose between several values, only one of which is checked at a time, can be made with
+//! [`MenuItem::radio_item`].
+//!
 //! ## The macOS app menu
 //!
 //! On macOS, the main menu belongs to the application, not to the window.
@@ -106,6 +115,8 @@
 //! ```
 //!
 //! [`LabelText::Dynamic`]: crate::widget::LabelText::Dynamic
+//! [`EventCtx::set_menu`]: crate::EventCtx::set_menu
+//! [`DelegateCtx::set_menu`]: crate::DelegateCtx::set_menu
 //! [`WindowDesc::menu`]: crate::WindowDesc::menu
 //! [`Command`]: crate::Command
 
@@ -153,6 +164,16 @@ impl<T: Data> MenuManager<T> {
         }
     }
 
+    /// Create a new [`MenuManager`] for a title-bar menu that is never rebuilt.
+    pub fn new_static(menu: Menu<T>) -> MenuManager<T> {
+        MenuManager {
+            build: None,
+            popup: false,
+            old_data: None,
+            menu: Some(menu),
+        }
+    }
+
     /// Create a new [`MenuManager`] for a context menu.
     pub fn new_for_popup(menu: Menu<T>) -> MenuManager<T> {
         MenuManager {
@@ -217,7 +238,7 @@ impl<T: Data> MenuManager<T> {
                     if let Some(build) = &mut self.build {
                         self.menu = Some((build)(window, data, env));
                     } else {
-                        tracing::warn!("tried to rebuild a context menu");
+                        tracing::warn!("tried to rebuild a menu that has no build function");
                     }
                     Some(self.refresh(data, env))
                 }
@@ -684,6 +705,27 @@ impl<T: Data> MenuItem<T> {
         self.selected_if(move |_data, _env| selected)
     }
 
+    /// Turns this menu item into one item of a radio group.
+    ///
+    /// The item is selected whenever the data is equal to `variant`, and
+    /// choosing it sets the data to `variant`. A group is made of several of
+    /// these items, one for each possible value, usually with a [`lens`] to
+    /// the field that they choose between.
+    ///
+    /// This replaces any callback set with [`on_activate`] or [`command`].
+    ///
+    /// [`lens`]: MenuItem::lens
+    /// [`on_activate`]: MenuItem::on_activate
+    /// [`command`]: MenuItem::command
+    pub fn radio_item(self, variant: T) -> Self
+    where
+        T: PartialEq,
+    {
+        let selected_variant = variant.clone();
+        self.on_activate(move |_ctx, data, _env| *data = variant.clone())
+            .selected_if(move |data, _env| *data == selected_variant)
+    }
+
     /// Wraps this menu item in a lens, so that it can be added to a `Menu<S>`.
     pub fn lens<S: Data>(self, lens: impl Lens<S, T> + 'static) -> MenuEntry<S> {
         MenuLensWrap {
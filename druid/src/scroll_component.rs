@@ -17,10 +17,17 @@
 
 use std::time::Duration;
 
-use crate::kurbo::{Point, Rect, Vec2};
+use crate::kurbo::{Point, Rect, Size, Vec2};
 use crate::theme;
 use crate::widget::{Axis, Viewport};
-use crate::{Env, Event, EventCtx, LifeCycle, LifeCycleCtx, PaintCtx, RenderContext, TimerToken};
+use crate::{
+    AnimationCurve, AnimationToken, Color, Env, Event, EventCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    RenderContext, Selector, TimerToken,
+};
+
+/// The part of a wheel delta that a scroll area could not apply because it reached the
+/// end of its content, submitted as a notification so that an ancestor can use it.
+const WHEEL_REMAINDER: Selector<Vec2> = Selector::new("druid-builtin.scroll-wheel-remainder");
 
 #[derive(Debug, Copy, Clone)]
 /// Which scroll bars of a scroll area are currently enabled.
@@ -92,9 +99,77 @@ impl Default for ScrollbarsEnabled {
     }
 }
 
+/// The edge of a scroll area along which the vertical scrollbar is placed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum VerticalBarPosition {
+    /// Place the vertical scrollbar along the left edge.
+    Left,
+    /// Place the vertical scrollbar along the right edge.
+    Right,
+}
+
+impl Default for VerticalBarPosition {
+    fn default() -> Self {
+        VerticalBarPosition::Right
+    }
+}
+
+/// The edge of a scroll area along which the horizontal scrollbar is placed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HorizontalBarPosition {
+    /// Place the horizontal scrollbar along the top edge.
+    Top,
+    /// Place the horizontal scrollbar along the bottom edge.
+    Bottom,
+}
+
+impl Default for HorizontalBarPosition {
+    fn default() -> Self {
+        HorizontalBarPosition::Bottom
+    }
+}
+
+/// How the scrollbars of a scroll area are laid out relative to its content.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ScrollbarStyle {
+    /// The scrollbars are drawn on top of the content, and fade out when they
+    /// are not in use.
+    Overlay,
+    /// The scrollbars are placed in tracks next to the content, which reserve
+    /// space for them. They stay visible as long as the content overflows.
+    Inlay,
+}
+
+impl Default for ScrollbarStyle {
+    fn default() -> Self {
+        ScrollbarStyle::Overlay
+    }
+}
+
+/// When the scrollbars of a scroll area are shown.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ScrollbarVisibility {
+    /// Show the scrollbars while scrolling or hovering, and fade out
+    /// [`Overlay`] scrollbars otherwise.
+    ///
+    /// [`Overlay`]: ScrollbarStyle::Overlay
+    Auto,
+    /// Always show the scrollbars while the content overflows.
+    Always,
+    /// Never show the scrollbars. The content can still be scrolled in other ways,
+    /// such as with the mouse wheel.
+    Never,
+}
+
+impl Default for ScrollbarVisibility {
+    fn default() -> Self {
+        ScrollbarVisibility::Auto
+    }
+}
+
 /// Denotes which scrollbar, if any, is currently being hovered over
 /// by the mouse.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum BarHoveredState {
     /// Neither scrollbar is being hovered by the mouse.
     None,
@@ -120,10 +195,10 @@ pub enum BarHeldState {
     /// Neither scrollbar is being dragged.
     None,
     /// Vertical scrollbar is being dragged. Contains an `f64` with
-    /// the initial y-offset of the dragging input.
+    /// the point where the scrollbar was grabbed, as a fraction of its length.
     Vertical(f64),
     /// Horizontal scrollbar is being dragged. Contains an `f64` with
-    /// the initial x-offset of the dragging input.
+    /// the point where the scrollbar was grabbed, as a fraction of its length.
     Horizontal(f64),
 }
 
@@ -145,6 +220,8 @@ pub enum BarHeldState {
 ///   to children.
 /// - Call [`handle_scroll`] with all events after handling / propagating them.
 /// - Call [`draw_bars`] to draw the scrollbars.
+/// - For [`Inlay`] scrollbars, lay out the content in the space left over by
+///   [`calc_track_eat`], and call [`draw_scrollbar_background`] before painting it.
 ///
 /// Taking a look at the [`Scroll`] source code can be helpful. You can also do scrolling
 /// without wrapping a child in a [`ClipBox`], but you will need to do certain event and
@@ -157,18 +234,37 @@ pub enum BarHeldState {
 /// [`handle_scroll`]: struct.ScrollComponent.html#method.handle_scroll
 /// [`draw_bars`]: #method.draw_bars
 /// [`lifecycle`]: struct.ScrollComponent.html#method.lifecycle
+/// [`Inlay`]: ScrollbarStyle::Inlay
+/// [`calc_track_eat`]: ScrollComponent::calc_track_eat
+/// [`draw_scrollbar_background`]: ScrollComponent::draw_scrollbar_background
 #[derive(Debug, Copy, Clone)]
 pub struct ScrollComponent {
     /// Current opacity for both scrollbars
     pub opacity: f64,
     /// ID for the timer which schedules scrollbar fade out
     pub timer_id: TimerToken,
+    /// Token for the animation that fades the scrollbars out
+    pub fade_animation: AnimationToken,
     /// Which if any scrollbar is currently hovered by the mouse
     pub hovered: BarHoveredState,
     /// Which if any scrollbar is currently being dragged by the mouse
     pub held: BarHeldState,
     /// Which scrollbars are enabled
     pub enabled: ScrollbarsEnabled,
+    /// The edge the vertical scrollbar is placed along
+    pub vertical_bar_position: VerticalBarPosition,
+    /// The edge the horizontal scrollbar is placed along
+    pub horizontal_bar_position: HorizontalBarPosition,
+    /// Whether the scrollbars are drawn over the content or next to it
+    pub scrollbar_style: ScrollbarStyle,
+    /// When the scrollbars are shown
+    pub scrollbar_visibility: ScrollbarVisibility,
+    /// Whether the vertical scrollbar is shown when scrolling vertically is enabled
+    pub show_vertical_bar: bool,
+    /// Whether the horizontal scrollbar is shown when scrolling horizontally is enabled
+    pub show_horizontal_bar: bool,
+    /// Whether the viewport was moved since the last call to `take_scrolled`
+    scrolled: bool,
 }
 
 impl Default for ScrollComponent {
@@ -176,9 +272,17 @@ impl Default for ScrollComponent {
         Self {
             opacity: 0.0,
             timer_id: TimerToken::INVALID,
+            fade_animation: AnimationToken::INVALID,
             hovered: BarHoveredState::None,
             held: BarHeldState::None,
             enabled: ScrollbarsEnabled::Both,
+            vertical_bar_position: VerticalBarPosition::Right,
+            horizontal_bar_position: HorizontalBarPosition::Bottom,
+            scrollbar_style: ScrollbarStyle::Overlay,
+            scrollbar_visibility: ScrollbarVisibility::Auto,
+            show_vertical_bar: true,
+            show_horizontal_bar: true,
+            scrolled: false,
         }
     }
 }
@@ -189,22 +293,100 @@ impl ScrollComponent {
         Default::default()
     }
 
+    /// Returns `true` if [`event`] or [`handle_scroll`] moved the viewport since the
+    /// last call to this method, and resets that state.
+    ///
+    /// This can be used to react exactly when the visible part of the content changes,
+    /// for example by dragging a scrollbar or by using the mouse wheel.
+    ///
+    /// [`event`]: ScrollComponent::event
+    /// [`handle_scroll`]: ScrollComponent::handle_scroll
+    pub fn take_scrolled(&mut self) -> bool {
+        std::mem::replace(&mut self.scrolled, false)
+    }
+
     /// true if either scrollbar is currently held down/being dragged
     pub fn are_bars_held(&self) -> bool {
         !matches!(self.held, BarHeldState::None)
     }
 
+    /// Returns `true` if the scrollbar for `axis` may be shown. Hidden scrollbars are
+    /// neither drawn nor hit tested, but scrolling along their axis still works.
+    fn shows_bar(&self, axis: Axis) -> bool {
+        let shown = match axis {
+            Axis::Vertical => self.show_vertical_bar,
+            Axis::Horizontal => self.show_horizontal_bar,
+        };
+        shown
+            && self.enabled.is_enabled(axis)
+            && self.scrollbar_visibility != ScrollbarVisibility::Never
+    }
+
+    /// Returns `true` if the scrollbars fade out when they are not in use.
+    fn fades(&self) -> bool {
+        self.scrollbar_style == ScrollbarStyle::Overlay
+            && self.scrollbar_visibility == ScrollbarVisibility::Auto
+    }
+
     /// Makes the scrollbars visible, and resets the fade timer.
+    ///
+    /// This does nothing if all scrollbars are disabled, or if their visibility is
+    /// not [`Auto`]. [`Inlay`] scrollbars don't fade, so no timer is requested for them.
+    ///
+    /// [`Auto`]: ScrollbarVisibility::Auto
+    /// [`Inlay`]: ScrollbarStyle::Inlay
     pub fn reset_scrollbar_fade<F>(&mut self, request_timer: F, env: &Env)
     where
         F: FnOnce(Duration) -> TimerToken,
     {
+        if self.enabled.is_none() || self.scrollbar_visibility != ScrollbarVisibility::Auto {
+            return;
+        }
         self.opacity = env.get(theme::SCROLLBAR_MAX_OPACITY);
+        // progress of a fade that is still running is ignored from now on
+        self.fade_animation = AnimationToken::INVALID;
+        if !self.fades() {
+            return;
+        }
         let fade_delay = env.get(theme::SCROLLBAR_FADE_DELAY);
         let deadline = Duration::from_millis(fade_delay);
         self.timer_id = request_timer(deadline);
     }
 
+    /// Returns the space that the scrollbar tracks take away from the content of a
+    /// scroll area of `size`, whose content has size `content_size`.
+    ///
+    /// This is always zero for [`Overlay`] scrollbars, and for scrollbars that are
+    /// never shown. For [`Inlay`] scrollbars, a widget should lay out its content in
+    /// the remaining space, and place the tracks around it.
+    ///
+    /// [`Overlay`]: ScrollbarStyle::Overlay
+    /// [`Inlay`]: ScrollbarStyle::Inlay
+    pub fn calc_track_eat(&self, size: Size, content_size: Size, env: &Env) -> Size {
+        if self.scrollbar_style != ScrollbarStyle::Inlay {
+            return Size::ZERO;
+        }
+        let track = env.get(theme::SCROLLBAR_WIDTH) + 2.0 * env.get(theme::SCROLLBAR_PAD);
+        let needs_track = |axis: Axis, cross_track: f64| {
+            self.shows_bar(axis) && axis.major(content_size) > axis.major(size) - cross_track
+        };
+
+        let mut vertical = needs_track(Axis::Vertical, 0.0);
+        let mut horizontal = needs_track(Axis::Horizontal, 0.0);
+        // Each track makes the content narrower along the other axis, which can
+        // make the other scrollbar necessary as well.
+        if vertical && !horizontal {
+            horizontal = needs_track(Axis::Horizontal, track);
+        } else if horizontal && !vertical {
+            vertical = needs_track(Axis::Vertical, track);
+        }
+
+        Size::new(
+            if vertical { track } else { 0.0 },
+            if horizontal { track } else { 0.0 },
+        )
+    }
+
     /// Calculates the paint rect of the vertical scrollbar, or `None` if the vertical scrollbar is
     /// not visible.
     pub fn calc_vertical_bar_bounds(&self, port: &Viewport, env: &Env) -> Option<Rect> {
@@ -217,13 +399,21 @@ impl ScrollComponent {
         self.calc_bar_bounds(Axis::Horizontal, port, env)
     }
 
-    fn calc_bar_bounds(&self, axis: Axis, port: &Viewport, env: &Env) -> Option<Rect> {
-        let viewport_size = port.view_size;
-        let content_size = port.content_size;
-        let scroll_offset = port.view_origin.to_vec2();
+    /// Returns `true` if the scrollbar for `axis` is placed along the start of the
+    /// cross axis (the left or top edge) rather than along its end.
+    fn bar_at_start(&self, axis: Axis) -> bool {
+        match axis {
+            Axis::Vertical => self.vertical_bar_position == VerticalBarPosition::Left,
+            Axis::Horizontal => self.horizontal_bar_position == HorizontalBarPosition::Top,
+        }
+    }
 
-        let viewport_major = axis.major(viewport_size);
-        let content_major = axis.major(content_size);
+    /// Returns the length of the scrollbar thumb on `axis`, and the start and length
+    /// of the track it moves along, or `None` if the content fits in the viewport on
+    /// that axis.
+    fn thumb_and_track(&self, axis: Axis, port: &Viewport, env: &Env) -> Option<(f64, f64, f64)> {
+        let viewport_major = axis.major(port.view_size);
+        let content_major = axis.major(port.content_size);
 
         if viewport_major >= content_major {
             return None;
@@ -234,27 +424,57 @@ impl ScrollComponent {
         let bar_min_size = env.get(theme::SCROLLBAR_MIN_SIZE);
 
         let percent_visible = viewport_major / content_major;
-        let percent_scrolled = axis.major_vec(scroll_offset) / (content_major - viewport_major);
 
-        let major_padding = if self.enabled.is_enabled(axis.cross()) {
-            bar_pad + bar_pad + bar_width
+        // Leave room for the other scrollbar, but only if it is actually shown. Inlay
+        // scrollbars meet in a corner outside of the viewport instead.
+        let cross_bar_visible = self.scrollbar_style == ScrollbarStyle::Overlay
+            && self.shows_bar(axis.cross())
+            && axis.minor(port.content_size) > axis.minor(port.view_size);
+        let (track_start, major_padding) = if cross_bar_visible {
+            if self.bar_at_start(axis.cross()) {
+                (bar_pad + bar_width, bar_pad + bar_pad + bar_width)
+            } else {
+                (bar_pad, bar_pad + bar_pad + bar_width)
+            }
         } else {
-            bar_pad + bar_pad
+            (bar_pad, bar_pad + bar_pad)
         };
         let usable_space = viewport_major - major_padding;
 
         let length = (percent_visible * viewport_major).ceil();
         let length = length.max(bar_min_size).min(usable_space);
 
-        let left_x_offset = bar_pad + ((usable_space - length) * percent_scrolled).ceil();
+        Some((length, track_start, usable_space))
+    }
+
+    fn calc_bar_bounds(&self, axis: Axis, port: &Viewport, env: &Env) -> Option<Rect> {
+        if !self.shows_bar(axis) {
+            return None;
+        }
+        let viewport_size = port.view_size;
+        let content_size = port.content_size;
+        let scroll_offset = port.view_origin.to_vec2();
+
+        let (length, track_start, usable_space) = self.thumb_and_track(axis, port, env)?;
+
+        let bar_width = env.get(theme::SCROLLBAR_WIDTH);
+        let bar_pad = env.get(theme::SCROLLBAR_PAD);
+
+        let percent_scrolled =
+            axis.major_vec(scroll_offset) / (axis.major(content_size) - axis.major(viewport_size));
+
+        let left_x_offset = track_start + ((usable_space - length) * percent_scrolled).ceil();
         let right_x_offset = left_x_offset + length;
 
-        let (x0, y0) = axis.pack(
-            left_x_offset,
-            axis.minor(viewport_size) - bar_width - bar_pad,
-        );
+        let minor_start = match (self.scrollbar_style, self.bar_at_start(axis)) {
+            (ScrollbarStyle::Overlay, true) => bar_pad,
+            (ScrollbarStyle::Overlay, false) => axis.minor(viewport_size) - bar_width - bar_pad,
+            (ScrollbarStyle::Inlay, true) => -(bar_width + bar_pad),
+            (ScrollbarStyle::Inlay, false) => axis.minor(viewport_size) + bar_pad,
+        };
 
-        let (x1, y1) = axis.pack(right_x_offset, axis.minor(viewport_size) - bar_pad);
+        let (x0, y0) = axis.pack(left_x_offset, minor_start);
+        let (x1, y1) = axis.pack(right_x_offset, minor_start + bar_width);
 
         if x0 >= x1 || y0 >= y1 {
             return None;
@@ -263,42 +483,177 @@ impl ScrollComponent {
         Some(Rect::new(x0, y0, x1, y1) + scroll_offset)
     }
 
+    /// Moves the viewport so that the scrollbar thumb on `axis` follows a dragging pointer.
+    ///
+    /// `mouse_pos` is in the coordinate space of the viewport, and `grab_fraction` is the
+    /// point where the thumb was grabbed, as a fraction of its length. The pointer
+    /// is mapped linearly onto the scrollable range and only the resulting offset is
+    /// clamped, so this keeps working when the pointer leaves the widget.
+    ///
+    /// The thumb length is recomputed on every call, so the thumb stays under the pointer
+    /// even if the content size changes during the drag.
+    fn drag_thumb(
+        &mut self,
+        axis: Axis,
+        port: &mut Viewport,
+        mouse_pos: Point,
+        grab_fraction: f64,
+        env: &Env,
+    ) {
+        if let Some((length, track_start, usable_space)) = self.thumb_and_track(axis, port, env) {
+            let travel = usable_space - length;
+            let thumb_start = axis.major_pos(mouse_pos) - grab_fraction * length - track_start;
+            let percent_scrolled = if travel > 0.0 {
+                thumb_start / travel
+            } else {
+                0.0
+            };
+            let max_offset = axis.major_pos(port.max_view_origin());
+            let new_origin = axis.pack(
+                percent_scrolled * max_offset,
+                axis.minor_pos(port.view_origin),
+            );
+            self.scrolled |= port.pan_to(new_origin.into());
+        }
+    }
+
+    /// The color of the scrollbar on `axis`, depending on whether it is being
+    /// dragged or hovered.
+    fn bar_color(&self, axis: Axis, env: &Env) -> Color {
+        match (self.held, self.hovered, axis) {
+            (BarHeldState::Vertical(_), _, Axis::Vertical)
+            | (BarHeldState::Horizontal(_), _, Axis::Horizontal) => {
+                env.get(theme::SCROLLBAR_ACTIVE_COLOR)
+            }
+            (BarHeldState::None, BarHoveredState::Vertical, Axis::Vertical)
+            | (BarHeldState::None, BarHoveredState::Horizontal, Axis::Horizontal) => {
+                env.get(theme::SCROLLBAR_HOVER_COLOR)
+            }
+            _ => env.get(theme::SCROLLBAR_COLOR),
+        }
+    }
+
+    /// Returns the start and end, along the cross axis and relative to the viewport, of
+    /// the band taken up by the scrollbar on `axis`, or `None` if that scrollbar is not
+    /// shown.
+    ///
+    /// For [`Overlay`] scrollbars this is the bar and the padding between it and the
+    /// edge. For [`Inlay`] scrollbars it is the whole track.
+    ///
+    /// [`Overlay`]: ScrollbarStyle::Overlay
+    /// [`Inlay`]: ScrollbarStyle::Inlay
+    fn calc_bar_band(&self, axis: Axis, port: &Viewport, env: &Env) -> Option<(f64, f64)> {
+        if !self.shows_bar(axis) {
+            return None;
+        }
+        self.thumb_and_track(axis, port, env)?;
+
+        let bar_width = env.get(theme::SCROLLBAR_WIDTH);
+        let bar_pad = env.get(theme::SCROLLBAR_PAD);
+        let view_minor = axis.minor(port.view_size);
+        let band = match (self.scrollbar_style, self.bar_at_start(axis)) {
+            (ScrollbarStyle::Overlay, true) => (0.0, bar_width + bar_pad),
+            (ScrollbarStyle::Overlay, false) => (view_minor - bar_width - bar_pad, view_minor),
+            (ScrollbarStyle::Inlay, true) => (-(bar_width + 2.0 * bar_pad), 0.0),
+            (ScrollbarStyle::Inlay, false) => (view_minor, view_minor + bar_width + 2.0 * bar_pad),
+        };
+        Some(band)
+    }
+
+    /// Returns the track of the [`Inlay`] scrollbar on `axis`, relative to the viewport,
+    /// or `None` if that scrollbar is not shown.
+    ///
+    /// [`Inlay`]: ScrollbarStyle::Inlay
+    fn calc_track_bounds(&self, axis: Axis, port: &Viewport, env: &Env) -> Option<Rect> {
+        if self.scrollbar_style != ScrollbarStyle::Inlay {
+            return None;
+        }
+        let (minor_start, minor_end) = self.calc_bar_band(axis, port, env)?;
+        let (x0, y0) = axis.pack(0.0, minor_start);
+        let (x1, y1) = axis.pack(axis.major(port.view_size), minor_end);
+        Some(Rect::new(x0, y0, x1, y1))
+    }
+
+    /// Returns the corner where the bands of both scrollbars cross, relative to the
+    /// viewport, or `None` unless both scrollbars are shown.
+    ///
+    /// Neither thumb ever enters this corner, and clicks in it don't grab either bar.
+    fn calc_corner_bounds(&self, port: &Viewport, env: &Env) -> Option<Rect> {
+        let (x0, x1) = self.calc_bar_band(Axis::Vertical, port, env)?;
+        let (y0, y1) = self.calc_bar_band(Axis::Horizontal, port, env)?;
+        Some(Rect::new(x0, y0, x1, y1))
+    }
+
+    /// Returns `true` if `pos`, in the coordinate space of the content, is in the
+    /// corner between both scrollbars.
+    fn point_hits_corner(&self, port: &Viewport, pos: Point, env: &Env) -> bool {
+        self.calc_corner_bounds(port, env).map_or(false, |corner| {
+            (corner + port.view_origin.to_vec2()).contains(pos)
+        })
+    }
+
+    /// Draw the tracks of [`Inlay`] scrollbars, and the corner where they meet.
+    ///
+    /// This does nothing for [`Overlay`] scrollbars. Call it before painting the
+    /// content, in the same coordinate space as [`draw_bars`].
+    ///
+    /// [`Inlay`]: ScrollbarStyle::Inlay
+    /// [`Overlay`]: ScrollbarStyle::Overlay
+    /// [`draw_bars`]: ScrollComponent::draw_bars
+    pub fn draw_scrollbar_background(&self, ctx: &mut PaintCtx, port: &Viewport, env: &Env) {
+        let vertical = self.calc_track_bounds(Axis::Vertical, port, env);
+        let horizontal = self.calc_track_bounds(Axis::Horizontal, port, env);
+        let corner = vertical.and(self.calc_corner_bounds(port, env));
+
+        let color = env.get(theme::SCROLLBAR_TRACK_COLOR);
+        for rect in [vertical, horizontal, corner].iter().flatten() {
+            ctx.fill(*rect, &color);
+        }
+    }
+
     /// Draw scroll bars.
     pub fn draw_bars(&self, ctx: &mut PaintCtx, port: &Viewport, env: &Env) {
         let scroll_offset = port.view_origin.to_vec2();
+        let opacity = if self.fades() {
+            self.opacity
+        } else {
+            env.get(theme::SCROLLBAR_MAX_OPACITY)
+        };
 
-        if self.enabled.is_none() || self.opacity <= 0.0 {
+        if self.enabled.is_none() || opacity <= 0.0 {
             return;
         }
 
-        let brush = ctx
+        let border_brush = ctx
             .render_ctx
-            .solid_brush(env.get(theme::SCROLLBAR_COLOR).with_alpha(self.opacity));
-        let border_brush = ctx.render_ctx.solid_brush(
-            env.get(theme::SCROLLBAR_BORDER_COLOR)
-                .with_alpha(self.opacity),
-        );
+            .solid_brush(env.get(theme::SCROLLBAR_BORDER_COLOR).with_alpha(opacity));
 
         let radius = env.get(theme::SCROLLBAR_RADIUS);
         let edge_width = env.get(theme::SCROLLBAR_EDGE_WIDTH);
 
         // Vertical bar
-        if self.enabled.is_enabled(Axis::Vertical) {
+        if self.shows_bar(Axis::Vertical) {
             if let Some(bounds) = self.calc_vertical_bar_bounds(port, env) {
                 let rect = (bounds - scroll_offset)
                     .inset(-edge_width / 2.0)
                     .to_rounded_rect(radius);
+                let brush = ctx
+                    .render_ctx
+                    .solid_brush(self.bar_color(Axis::Vertical, env).with_alpha(opacity));
                 ctx.render_ctx.fill(rect, &brush);
                 ctx.render_ctx.stroke(rect, &border_brush, edge_width);
             }
         }
 
         // Horizontal bar
-        if self.enabled.is_enabled(Axis::Horizontal) {
+        if self.shows_bar(Axis::Horizontal) {
             if let Some(bounds) = self.calc_horizontal_bar_bounds(port, env) {
                 let rect = (bounds - scroll_offset)
                     .inset(-edge_width / 2.0)
                     .to_rounded_rect(radius);
+                let brush = ctx
+                    .render_ctx
+                    .solid_brush(self.bar_color(Axis::Horizontal, env).with_alpha(opacity));
                 ctx.render_ctx.fill(rect, &brush);
                 ctx.render_ctx.stroke(rect, &border_brush, edge_width);
             }
@@ -309,15 +664,17 @@ impl ScrollComponent {
     ///
     /// Returns false if the vertical scrollbar is not visible
     pub fn point_hits_vertical_bar(&self, port: &Viewport, pos: Point, env: &Env) -> bool {
-        if !self.enabled.is_enabled(Axis::Vertical) {
+        if !self.shows_bar(Axis::Vertical) || self.point_hits_corner(port, pos, env) {
             return false;
         }
-        let viewport_size = port.view_size;
-        let scroll_offset = port.view_origin.to_vec2();
+        let bar_pad = env.get(theme::SCROLLBAR_PAD);
 
         if let Some(mut bounds) = self.calc_vertical_bar_bounds(port, env) {
-            // Stretch hitbox to edge of widget
-            bounds.x1 = scroll_offset.x + viewport_size.width;
+            // Stretch hitbox to the outer edge of the widget or track
+            match self.vertical_bar_position {
+                VerticalBarPosition::Left => bounds.x0 -= bar_pad,
+                VerticalBarPosition::Right => bounds.x1 += bar_pad,
+            }
             bounds.contains(pos)
         } else {
             false
@@ -328,15 +685,17 @@ impl ScrollComponent {
     ///
     /// Returns false if the horizontal scrollbar is not visible
     pub fn point_hits_horizontal_bar(&self, port: &Viewport, pos: Point, env: &Env) -> bool {
-        if !self.enabled.is_enabled(Axis::Horizontal) {
+        if !self.shows_bar(Axis::Horizontal) || self.point_hits_corner(port, pos, env) {
             return false;
         }
-        let viewport_size = port.view_size;
-        let scroll_offset = port.view_origin.to_vec2();
+        let bar_pad = env.get(theme::SCROLLBAR_PAD);
 
         if let Some(mut bounds) = self.calc_horizontal_bar_bounds(port, env) {
-            // Stretch hitbox to edge of widget
-            bounds.y1 = scroll_offset.y + viewport_size.height;
+            // Stretch hitbox to the outer edge of the widget or track
+            match self.horizontal_bar_position {
+                HorizontalBarPosition::Top => bounds.y0 -= bar_pad,
+                HorizontalBarPosition::Bottom => bounds.y1 += bar_pad,
+            }
             bounds.contains(pos)
         } else {
             false
@@ -347,8 +706,6 @@ impl ScrollComponent {
     ///
     /// Make sure to call on every event
     pub fn event(&mut self, port: &mut Viewport, ctx: &mut EventCtx, event: &Event, env: &Env) {
-        let viewport_size = port.view_size;
-        let content_size = port.content_size;
         let scroll_offset = port.view_origin.to_vec2();
 
         let scrollbar_is_hovered = match event {
@@ -365,24 +722,12 @@ impl ScrollComponent {
             match event {
                 Event::MouseMove(event) => {
                     match self.held {
-                        BarHeldState::Vertical(offset) => {
-                            let scale_y = viewport_size.height / content_size.height;
-                            let bounds = self
-                                .calc_vertical_bar_bounds(port, env)
-                                .unwrap_or(Rect::ZERO);
-                            let mouse_y = event.pos.y + scroll_offset.y;
-                            let delta = mouse_y - bounds.y0 - offset;
-                            port.pan_by(Vec2::new(0f64, (delta / scale_y).ceil()));
+                        BarHeldState::Vertical(fraction) => {
+                            self.drag_thumb(Axis::Vertical, port, event.pos, fraction, env);
                             ctx.set_handled();
                         }
-                        BarHeldState::Horizontal(offset) => {
-                            let scale_x = viewport_size.width / content_size.width;
-                            let bounds = self
-                                .calc_horizontal_bar_bounds(port, env)
-                                .unwrap_or(Rect::ZERO);
-                            let mouse_x = event.pos.x + scroll_offset.x;
-                            let delta = mouse_x - bounds.x0 - offset;
-                            port.pan_by(Vec2::new((delta / scale_x).ceil(), 0f64));
+                        BarHeldState::Horizontal(fraction) => {
+                            self.drag_thumb(Axis::Horizontal, port, event.pos, fraction, env);
                             ctx.set_handled();
                         }
                         _ => (),
@@ -392,6 +737,7 @@ impl ScrollComponent {
                 Event::MouseUp(_) => {
                     self.held = BarHeldState::None;
                     ctx.set_active(false);
+                    ctx.request_paint();
 
                     if !scrollbar_is_hovered {
                         self.hovered = BarHoveredState::None;
@@ -407,17 +753,25 @@ impl ScrollComponent {
             match event {
                 Event::MouseMove(event) => {
                     let offset_pos = event.pos + scroll_offset;
-                    if self.point_hits_vertical_bar(port, offset_pos, env) {
-                        self.hovered = BarHoveredState::Vertical;
+                    let hovered = if self.point_hits_vertical_bar(port, offset_pos, env) {
+                        BarHoveredState::Vertical
                     } else if self.point_hits_horizontal_bar(port, offset_pos, env) {
-                        self.hovered = BarHoveredState::Horizontal;
+                        BarHoveredState::Horizontal
                     } else {
                         unreachable!();
+                    };
+
+                    let max_opacity = env.get(theme::SCROLLBAR_MAX_OPACITY);
+                    // Only repaint when the hovered bar or its opacity actually changes.
+                    if hovered != self.hovered || self.opacity != max_opacity {
+                        self.hovered = hovered;
+                        self.opacity = max_opacity;
+                        ctx.request_paint();
                     }
-
-                    self.opacity = env.get(theme::SCROLLBAR_MAX_OPACITY);
-                    self.timer_id = TimerToken::INVALID; // Cancel any fade out in progress
-                    ctx.request_paint();
+                    // Cancel any fade out in progress
+                    self.timer_id = TimerToken::INVALID;
+                    ctx.cancel_animation(self.fade_animation);
+                    self.fade_animation = AnimationToken::INVALID;
                     ctx.set_handled();
                 }
                 Event::MouseDown(event) => {
@@ -425,20 +779,19 @@ impl ScrollComponent {
 
                     if self.point_hits_vertical_bar(port, pos, env) {
                         ctx.set_active(true);
-                        self.held = BarHeldState::Vertical(
-                            // The bounds must be non-empty, because the point hits the scrollbar.
-                            pos.y - self.calc_vertical_bar_bounds(port, env).unwrap().y0,
-                        );
+                        // The bounds must be non-empty, because the point hits the scrollbar.
+                        let bounds = self.calc_vertical_bar_bounds(port, env).unwrap();
+                        self.held = BarHeldState::Vertical((pos.y - bounds.y0) / bounds.height());
                     } else if self.point_hits_horizontal_bar(port, pos, env) {
                         ctx.set_active(true);
-                        self.held = BarHeldState::Horizontal(
-                            // The bounds must be non-empty, because the point hits the scrollbar.
-                            pos.x - self.calc_horizontal_bar_bounds(port, env).unwrap().x0,
-                        );
+                        // The bounds must be non-empty, because the point hits the scrollbar.
+                        let bounds = self.calc_horizontal_bar_bounds(port, env).unwrap();
+                        self.held = BarHeldState::Horizontal((pos.x - bounds.x0) / bounds.width());
                     } else {
                         unreachable!();
                     }
 
+                    ctx.request_paint();
                     ctx.set_handled();
                 }
                 // if the mouse was downed elsewhere, moved over a scroll bar and released: noop.
@@ -452,33 +805,32 @@ impl ScrollComponent {
                     if self.hovered.is_hovered() && !scrollbar_is_hovered {
                         self.hovered = BarHoveredState::None;
                         self.reset_scrollbar_fade(|d| ctx.request_timer(d), env);
+                        ctx.request_paint();
                     }
                 }
                 Event::Timer(id) if *id == self.timer_id => {
-                    // Schedule scroll bars animation
-                    ctx.request_anim_frame();
+                    // Fade the scroll bars out over `SCROLLBAR_FADE_DURATION`
+                    if self.fades() {
+                        let duration =
+                            Duration::from_millis(env.get(theme::SCROLLBAR_FADE_DURATION));
+                        self.fade_animation =
+                            ctx.request_animation(duration, AnimationCurve::Linear);
+                    }
                     self.timer_id = TimerToken::INVALID;
                     ctx.set_handled();
                 }
-                Event::AnimFrame(interval) => {
-                    // Guard by the timer id being invalid, otherwise the scroll bars would fade
-                    // immediately if some other widget started animating.
-                    if self.timer_id == TimerToken::INVALID {
-                        // Animate scroll bars opacity
-                        let diff = 2.0 * (*interval as f64) * 1e-9;
-                        self.opacity -= diff;
-                        if self.opacity > 0.0 {
-                            ctx.request_anim_frame();
-                        }
-
-                        if let Some(bounds) = self.calc_horizontal_bar_bounds(port, env) {
-                            ctx.request_paint_rect(bounds - scroll_offset);
-                        }
-                        if let Some(bounds) = self.calc_vertical_bar_bounds(port, env) {
-                            ctx.request_paint_rect(bounds - scroll_offset);
-                        }
+                Event::AnimProgress(token, t) if *token == self.fade_animation => {
+                    self.opacity = env.get(theme::SCROLLBAR_MAX_OPACITY) * (1.0 - t);
+                    if let Some(bounds) = self.calc_horizontal_bar_bounds(port, env) {
+                        ctx.request_paint_rect(bounds - scroll_offset);
+                    }
+                    if let Some(bounds) = self.calc_vertical_bar_bounds(port, env) {
+                        ctx.request_paint_rect(bounds - scroll_offset);
                     }
                 }
+                Event::AnimEnded(token) if *token == self.fade_animation => {
+                    self.fade_animation = AnimationToken::INVALID;
+                }
 
                 _ => (),
             }
@@ -486,6 +838,10 @@ impl ScrollComponent {
     }
 
     /// Applies mousewheel scrolling if the event has not already been handled
+    ///
+    /// Only the part of the wheel delta that can actually be applied is used. If
+    /// the viewport reaches the end of the content, the remainder is passed on to
+    /// any scrolling ancestor, so that nested scroll areas hand off to each other.
     pub fn handle_scroll(
         &mut self,
         port: &mut Viewport,
@@ -493,13 +849,28 @@ impl ScrollComponent {
         event: &Event,
         env: &Env,
     ) {
-        if !ctx.is_handled() {
-            if let Event::Wheel(mouse) = event {
-                if port.pan_by(mouse.wheel_delta) {
-                    ctx.request_paint();
-                    ctx.set_handled();
-                    self.reset_scrollbar_fade(|d| ctx.request_timer(d), env);
-                }
+        if ctx.is_handled() {
+            return;
+        }
+        let delta = match event {
+            Event::Wheel(mouse) => mouse.wheel_delta,
+            Event::Notification(note) => match note.get(WHEEL_REMAINDER) {
+                Some(remainder) => *remainder,
+                None => return,
+            },
+            _ => return,
+        };
+
+        let old_origin = port.view_origin;
+        if port.pan_by(delta) {
+            self.scrolled = true;
+            ctx.request_paint();
+            ctx.set_handled();
+            self.reset_scrollbar_fade(|d| ctx.request_timer(d), env);
+
+            let remainder = delta - (port.view_origin - old_origin);
+            if remainder.hypot2() > 1e-12 {
+                ctx.submit_notification(WHEEL_REMAINDER.with(remainder));
             }
         }
     }
@@ -508,9 +879,18 @@ impl ScrollComponent {
     ///
     /// Make sure to call on every lifecycle event
     pub fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, env: &Env) {
-        if let LifeCycle::Size(_) = event {
-            // Show the scrollbars any time our size changes
-            self.reset_scrollbar_fade(|d| ctx.request_timer(d), env);
+        match event {
+            LifeCycle::Size(_) => {
+                // Show the scrollbars any time our size changes
+                self.reset_scrollbar_fade(|d| ctx.request_timer(d), env);
+            }
+            // The mouse left the widget (or the window) while over a scrollbar.
+            LifeCycle::HotChanged(false) if self.hovered.is_hovered() && !self.are_bars_held() => {
+                self.hovered = BarHoveredState::None;
+                self.reset_scrollbar_fade(|d| ctx.request_timer(d), env);
+                ctx.request_paint();
+            }
+            _ => {}
         }
     }
 }
@@ -642,7 +1022,7 @@ mod tests {
         let mut scroll_component = ScrollComponent::new();
         scroll_component.enabled = ScrollbarsEnabled::Both;
         let viewport = Viewport {
-            content_size: Size::new(100.0, 100.0),
+            content_size: Size::new(200.0, 100.0),
             view_origin: (0.0, 50.0).into(),
             view_size: (100.0, 50.0).into(),
         };
@@ -665,6 +1045,171 @@ mod tests {
         assert_eq!(scrollbar_rect, Rect::new(86.0, 61.0, 97.0, 86.0));
     }
 
+    #[test]
+    fn scrollbar_layout_no_padding_for_hidden_other_bar() {
+        let mut scroll_component = ScrollComponent::new();
+        scroll_component.enabled = ScrollbarsEnabled::Both;
+        let mut viewport = Viewport {
+            content_size: Size::new(100.0, 100.0),
+            view_origin: (0.0, 50.0).into(),
+            view_size: (100.0, 50.0).into(),
+        };
+
+        // the content is exactly as wide as the viewport, so there is no horizontal bar
+        assert_eq!(
+            scroll_component.calc_horizontal_bar_bounds(&viewport, &test_env()),
+            None
+        );
+        let scrollbar_rect = scroll_component
+            .calc_vertical_bar_bounds(&viewport, &test_env())
+            .unwrap();
+        assert_eq!(scrollbar_rect, Rect::new(86.0, 72.0, 97.0, 97.0));
+
+        // one pixel wider, and the horizontal bar needs room
+        viewport.content_size.width = 101.0;
+        assert!(scroll_component
+            .calc_horizontal_bar_bounds(&viewport, &test_env())
+            .is_some());
+        let scrollbar_rect = scroll_component
+            .calc_vertical_bar_bounds(&viewport, &test_env())
+            .unwrap();
+        assert_eq!(scrollbar_rect, Rect::new(86.0, 61.0, 97.0, 86.0));
+    }
+
+    #[test]
+    fn scrollbar_layout_left_and_top() {
+        let mut scroll_component = ScrollComponent::new();
+        scroll_component.enabled = ScrollbarsEnabled::Both;
+        scroll_component.vertical_bar_position = VerticalBarPosition::Left;
+        scroll_component.horizontal_bar_position = HorizontalBarPosition::Top;
+        let viewport = Viewport {
+            content_size: Size::new(200.0, 100.0),
+            view_origin: Point::ZERO,
+            view_size: (100.0, 50.0).into(),
+        };
+
+        let vertical_rect = scroll_component
+            .calc_vertical_bar_bounds(&viewport, &test_env())
+            .unwrap();
+        let horizontal_rect = scroll_component
+            .calc_horizontal_bar_bounds(&viewport, &test_env())
+            .unwrap();
+
+        // both bars leave space for the other in the top left corner
+        assert_eq!(vertical_rect, Rect::new(3.0, 14.0, 14.0, 39.0));
+        assert_eq!(horizontal_rect, Rect::new(14.0, 3.0, 64.0, 14.0));
+
+        // the hitboxes are stretched to the left and top edges
+        let env = test_env();
+        assert!(scroll_component.point_hits_vertical_bar(&viewport, Point::new(1.0, 20.0), &env));
+        assert!(scroll_component.point_hits_horizontal_bar(&viewport, Point::new(20.0, 1.0), &env));
+        assert!(!scroll_component.point_hits_vertical_bar(&viewport, Point::new(99.0, 20.0), &env));
+    }
+
+    #[test]
+    fn inlay_scrollbar_layout() {
+        let mut scroll_component = ScrollComponent::new();
+        let env = test_env();
+        let size = Size::new(100.0, 50.0);
+        assert_eq!(
+            scroll_component.calc_track_eat(size, Size::new(90.0, 100.0), &env),
+            Size::ZERO
+        );
+
+        scroll_component.scrollbar_style = ScrollbarStyle::Inlay;
+        let track = TEST_SCROLLBAR_WIDTH + 2.0 * TEST_SCROLLBAR_PAD;
+        assert_eq!(
+            scroll_component.calc_track_eat(size, Size::new(80.0, 100.0), &env),
+            Size::new(track, 0.0)
+        );
+        // The vertical track leaves too little width for the content.
+        assert_eq!(
+            scroll_component.calc_track_eat(size, Size::new(90.0, 100.0), &env),
+            Size::new(track, track)
+        );
+
+        let viewport = Viewport {
+            content_size: Size::new(80.0, 100.0),
+            view_origin: Point::ZERO,
+            view_size: (100.0 - track, 50.0).into(),
+        };
+        // The bar sits in the track next to the viewport, and has the full height
+        // of the viewport to move in.
+        let scrollbar_rect = scroll_component
+            .calc_vertical_bar_bounds(&viewport, &env)
+            .unwrap();
+        assert_eq!(scrollbar_rect, Rect::new(86.0, 3.0, 97.0, 28.0));
+        assert!(scroll_component.point_hits_vertical_bar(&viewport, Point::new(99.0, 10.0), &env));
+        assert!(!scroll_component.point_hits_vertical_bar(&viewport, Point::new(80.0, 10.0), &env));
+    }
+
+    #[test]
+    fn scrollbar_visibility() {
+        let mut scroll_component = ScrollComponent::new();
+        let env = test_env();
+        let viewport = Viewport {
+            content_size: Size::new(100.0, 100.0),
+            view_origin: Point::ZERO,
+            view_size: (100.0, 50.0).into(),
+        };
+
+        scroll_component.scrollbar_visibility = ScrollbarVisibility::Always;
+        scroll_component.reset_scrollbar_fade(|_| panic!("no fade timer expected"), &env);
+        assert!(scroll_component
+            .calc_vertical_bar_bounds(&viewport, &env)
+            .is_some());
+
+        scroll_component.scrollbar_visibility = ScrollbarVisibility::Never;
+        scroll_component.scrollbar_style = ScrollbarStyle::Inlay;
+        assert!(scroll_component
+            .calc_vertical_bar_bounds(&viewport, &env)
+            .is_none());
+        assert_eq!(
+            scroll_component.calc_track_eat(Size::new(100.0, 50.0), Size::new(80.0, 100.0), &env),
+            Size::ZERO
+        );
+    }
+
+    #[test]
+    fn scrollbar_corner_is_dead_zone() {
+        let scroll_component = ScrollComponent::new();
+        let env = test_env();
+        // scrolled all the way to the end, so that both thumbs touch the corner
+        let viewport = Viewport {
+            content_size: Size::new(200.0, 200.0),
+            view_origin: Point::new(100.0, 100.0),
+            view_size: (100.0, 100.0).into(),
+        };
+        let offset = viewport.view_origin.to_vec2();
+        let hits = |x: f64, y: f64| {
+            let pos = Point::new(x, y) + offset;
+            (
+                scroll_component.point_hits_vertical_bar(&viewport, pos, &env),
+                scroll_component.point_hits_horizontal_bar(&viewport, pos, &env),
+            )
+        };
+
+        assert_eq!(
+            scroll_component.calc_vertical_bar_bounds(&viewport, &env),
+            Some(Rect::new(86.0, 36.0, 97.0, 86.0) + offset)
+        );
+        assert_eq!(
+            scroll_component.calc_horizontal_bar_bounds(&viewport, &env),
+            Some(Rect::new(36.0, 86.0, 86.0, 97.0) + offset)
+        );
+
+        assert_eq!(hits(92.0, 80.0), (true, false));
+        assert_eq!(hits(99.0, 85.0), (true, false));
+        assert_eq!(hits(80.0, 92.0), (false, true));
+        assert_eq!(hits(85.0, 99.0), (false, true));
+        // the corner itself
+        assert_eq!(hits(86.0, 86.0), (false, false));
+        assert_eq!(hits(92.0, 92.0), (false, false));
+        assert_eq!(hits(99.0, 99.0), (false, false));
+        // away from both bars
+        assert_eq!(hits(80.0, 80.0), (false, false));
+    }
+
     #[test]
     fn scrollbar_layout_min_bar_size() {
         let mut scroll_component = ScrollComponent::new();
@@ -14,6 +14,7 @@
 
 //! Tools and infrastructure for testing widgets.
 
+use std::any::Any;
 use std::path::Path;
 use std::sync::Arc;
 
@@ -21,6 +22,8 @@ use crate::app::PendingWindow;
 use crate::core::{CommandQueue, WidgetState};
 use crate::ext_event::ExtEventHost;
 use crate::piet::{BitmapTarget, Device, Error, ImageFormat, Piet};
+use crate::shell::text::{simulate_input, InputHandler};
+use crate::shell::{TextFieldToken, WinHandler};
 use crate::*;
 
 pub(crate) const DEFAULT_SIZE: Size = Size::new(400., 400.);
@@ -44,7 +47,10 @@ pub(crate) const DEFAULT_SIZE: Size = Size::new(400., 400.);
 ///
 /// if you want those functions run you will need to call them yourself.
 ///
-/// Also, timers don't work.  ¯\_(ツ)_/¯
+/// Timers never fire by themselves either; use [`fire_timers`] to fire all
+/// of the pending ones.
+///
+/// [`fire_timers`]: Harness::fire_timers
 pub struct Harness<'a, T> {
     piet: Piet<'a>,
     inner: Inner<T>,
@@ -225,6 +231,30 @@ impl<T: Data> Harness<'_, T> {
         self.event(event);
     }
 
+    /// Fire every pending timer of the window, as if their deadlines had
+    /// passed.
+    pub fn fire_timers(&mut self) {
+        let tokens: Vec<_> = self.inner.window.timers.keys().copied().collect();
+        for token in tokens {
+            self.event(Event::Timer(token));
+        }
+    }
+
+    /// Press a key, the way a platform window does: the key is sent as a
+    /// `KeyDown` event, and if no widget handles it, it edits the focused
+    /// text field.
+    pub fn key_down(&mut self, key: KeyEvent) {
+        let window = &self.inner.window;
+        let token = window.focus.and_then(|focus| {
+            window
+                .ime_handlers
+                .iter()
+                .find(|(_, reg)| reg.widget_id == focus)
+                .map(|(token, _)| *token)
+        });
+        simulate_input(&mut KeyInput(self), token, key);
+    }
+
     /// Send the events that would normally be sent when the app starts.
     // should we do this automatically? Also these will change regularly?
     pub fn send_initial_events(&mut self) {
@@ -239,9 +269,14 @@ impl<T: Data> Harness<'_, T> {
     ///
     /// Commands dispatched during `update` will not be sent?
     pub fn event(&mut self, event: Event) {
-        self.inner.event(event);
+        self.handle_event(event);
+    }
+
+    fn handle_event(&mut self, event: Event) -> Handled {
+        let handled = self.inner.event(event);
         self.process_commands();
         self.update();
+        handled
     }
 
     fn process_commands(&mut self) {
@@ -258,6 +293,18 @@ impl<T: Data> Harness<'_, T> {
         self.inner.lifecycle(event)
     }
 
+    /// Replace the root `Env`, and send `update` to the widgets, like an
+    /// application does when its theme is switched.
+    pub fn set_env(&mut self, env: Env) {
+        self.inner.env = env;
+        self.update();
+    }
+
+    /// The root `Env`.
+    pub fn env(&self) -> &Env {
+        &self.inner.env
+    }
+
     //TODO: should we expose this? I don't think so?
     fn update(&mut self) {
         self.inner.update()
@@ -288,9 +335,9 @@ impl<T: Data> Harness<'_, T> {
 }
 
 impl<T: Data> Inner<T> {
-    fn event(&mut self, event: Event) {
+    fn event(&mut self, event: Event) -> Handled {
         self.window
-            .event(&mut self.cmds, event, &mut self.data, &self.env);
+            .event(&mut self.cmds, event, &mut self.data, &self.env)
     }
 
     fn lifecycle(&mut self, event: LifeCycle) {
@@ -314,6 +361,40 @@ impl<T: Data> Inner<T> {
     }
 }
 
+/// Lets `simulate_input` drive a `Harness` like it drives a platform window.
+struct KeyInput<'h, 'a, T>(&'h mut Harness<'a, T>);
+
+impl<T: Data> WinHandler for KeyInput<'_, '_, T> {
+    fn connect(&mut self, _handle: &WindowHandle) {}
+
+    fn prepare_paint(&mut self) {}
+
+    fn paint(&mut self, _piet: &mut Piet, _invalid: &Region) {}
+
+    fn key_down(&mut self, event: KeyEvent) -> bool {
+        self.0.handle_event(Event::KeyDown(event)).is_handled()
+    }
+
+    fn acquire_input_lock(
+        &mut self,
+        token: TextFieldToken,
+        mutable: bool,
+    ) -> Box<dyn InputHandler> {
+        self.0.inner.window.get_ime_handler(token, mutable)
+    }
+
+    fn release_input_lock(&mut self, token: TextFieldToken) {
+        if let Some(widget) = self.0.inner.window.release_ime_lock(token) {
+            self.0
+                .event(Event::Internal(InternalEvent::RouteImeStateChange(widget)));
+        }
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        unimplemented!("the harness has no handler to downcast to")
+    }
+}
+
 impl<T> Drop for Harness<'_, T> {
     fn drop(&mut self) {
         // We need to call finish even if a test assert failed
@@ -273,3 +273,323 @@ fn aspect_ratio() {
         assert_eq!(state.layout_rect().size(), Size::new(1000., 500.));
     });
 }
+
+#[test]
+fn aspect_ratio_ext() {
+    let id = WidgetId::next();
+    let thumbnail = SizedBox::<()>::empty()
+        .aspect_ratio(16. / 9.)
+        .with_id(id)
+        .center();
+
+    Harness::create_simple((), thumbnail, |harness| {
+        harness.set_initial_size(Size::new(320., 400.));
+        harness.send_initial_events();
+        harness.just_layout();
+        let state = harness.get_state(id);
+        assert_eq!(state.layout_rect().size(), Size::new(320., 180.));
+    });
+}
+
+#[test]
+fn flex_gap() {
+    let [id1, id2, id3, flex_id] = widget_ids();
+    let widget = Flex::row()
+        .gap(10.)
+        .must_fill_main_axis(true)
+        .with_child(SizedBox::empty().width(20.).with_id(id1))
+        .with_child(SizedBox::empty().width(20.).with_id(id2))
+        .with_flex_child(SizedBox::empty().expand_width().with_id(flex_id), 1.0)
+        .with_child(SizedBox::empty().width(20.).with_id(id3))
+        .fix_width(200.)
+        .center();
+
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        assert_eq!(harness.get_state(id2).layout_rect().x0, 30.);
+        // the gaps are set aside before the flex child gets the rest
+        let flex_rect = harness.get_state(flex_id).layout_rect();
+        assert_eq!((flex_rect.x0, flex_rect.width()), (60., 110.));
+        assert_eq!(harness.get_state(id3).layout_rect().x0, 180.);
+    });
+
+    let [id1, id2] = widget_ids();
+    let widget = Flex::row()
+        .gap(10.)
+        .collapse_empty_gaps(true)
+        .with_child(SizedBox::empty().width(20.))
+        .with_child(SizedBox::empty().width(0.).with_id(id1))
+        .with_child(SizedBox::empty().width(20.).with_id(id2))
+        .center();
+
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        // the empty child doesn't get a gap of its own
+        assert_eq!(harness.get_state(id1).layout_rect().x0, 20.);
+        assert_eq!(harness.get_state(id2).layout_rect().x0, 30.);
+    });
+}
+
+#[test]
+fn flex_child_alignment_override() {
+    let [id1, id2, id3] = widget_ids();
+    let widget = Flex::row()
+        .cross_axis_alignment(CrossAxisAlignment::Center)
+        .with_child_aligned(
+            SizedBox::empty().height(20.).with_id(id1),
+            CrossAxisAlignment::Start,
+        )
+        .with_child(SizedBox::empty().height(40.))
+        .with_child(SizedBox::empty().height(10.).with_id(id2))
+        .with_flex_child(
+            SizedBox::empty().height(10.).with_id(id3),
+            FlexParams::new(1.0, CrossAxisAlignment::End),
+        )
+        .center();
+
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        assert_eq!(harness.get_state(id1).layout_rect().y0, 0.);
+        assert_eq!(harness.get_state(id2).layout_rect().y0, 15.);
+        assert_eq!(harness.get_state(id3).layout_rect().y0, 30.);
+    });
+}
+
+#[test]
+fn flex_reversed() {
+    let [id1, id2] = widget_ids();
+    let widget = Flex::column()
+        .reversed(true)
+        .reversed_cross_axis(true)
+        .must_fill_main_axis(true)
+        .main_axis_alignment(MainAxisAlignment::End)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child(SizedBox::empty().width(10.).height(10.).with_id(id1))
+        .with_child(SizedBox::empty().width(30.).height(20.).with_id(id2))
+        .fix_height(100.)
+        .center();
+
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        // the first child ends up at the bottom, and at the trailing edge
+        assert_eq!(
+            harness.get_state(id1).layout_rect(),
+            Rect::new(20., 90., 30., 100.)
+        );
+        assert_eq!(
+            harness.get_state(id2).layout_rect(),
+            Rect::new(0., 70., 30., 90.)
+        );
+    });
+}
+
+#[test]
+fn wrap_lines() {
+    let [id1, id2, id3, wrap_id] = widget_ids();
+    let widget = Wrap::row()
+        .spacing(10.)
+        .run_spacing(5.)
+        .cross_axis_alignment(CrossAxisAlignment::End)
+        .with_child(SizedBox::empty().width(100.).height(20.))
+        .with_child(SizedBox::empty().width(100.).height(30.).with_id(id1))
+        .with_child(SizedBox::empty().width(100.).height(10.).with_id(id2))
+        .with_child(SizedBox::empty().width(300.).height(10.).with_id(id3))
+        .with_id(wrap_id)
+        .fix_width(250.)
+        .center();
+
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        assert_eq!(
+            harness.get_state(id1).layout_rect().origin(),
+            Point::new(110., 0.)
+        );
+        // the second line starts below the first, and is as tall as its tallest child
+        assert_eq!(
+            harness.get_state(id2).layout_rect().origin(),
+            Point::new(0., 35.)
+        );
+        // too wide for any line, so it gets one of its own
+        assert_eq!(
+            harness.get_state(id3).layout_rect().origin(),
+            Point::new(0., 50.)
+        );
+        assert_eq!(harness.get_state(wrap_id).layout_rect().height(), 60.);
+    });
+}
+
+#[test]
+fn baseline_through_wrappers() {
+    fn text_like(height: f64, baseline: f64) -> impl Widget<()> {
+        ModularWidget::new(()).layout_fn(move |_, ctx, bc, _, _| {
+            ctx.set_baseline_offset(baseline);
+            bc.constrain((10., height))
+        })
+    }
+
+    // the padded child's baseline is 15 up from its bottom, so 15 down from its top
+    let [id1, id2] = widget_ids();
+    let padded = text_like(20., 5.).padding((0., 0., 0., 10.));
+    let column = Flex::column()
+        .with_child(text_like(20., 5.).padding((0., 0., 0., 10.)))
+        .with_child(SizedBox::empty().height(10.));
+
+    let row = Flex::row()
+        .cross_axis_alignment(CrossAxisAlignment::Baseline)
+        .with_child(padded)
+        .with_child(text_like(10., 0.).with_id(id1))
+        .center();
+    Harness::create_simple((), row, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        assert_eq!(harness.get_state(id1).layout_rect().y0, 5.);
+    });
+
+    // a column has the baseline of its first child
+    let row = Flex::row()
+        .cross_axis_alignment(CrossAxisAlignment::Baseline)
+        .with_child(column)
+        .with_child(text_like(10., 0.).with_id(id2))
+        .center();
+    Harness::create_simple((), row, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        assert_eq!(harness.get_state(id2).layout_rect().y0, 5.);
+    });
+}
+
+#[test]
+fn grid_columns_line_up() {
+    let [header, label1, label2, field1, field2, grid_id] = widget_ids();
+    let widget = Grid::new()
+        .with_column(GridTrack::Fit)
+        .with_column(GridTrack::Flex(1.0))
+        .column_spacing(10.)
+        .row_spacing(5.)
+        .with_child(
+            SizedBox::empty().width(150.).height(30.).with_id(header),
+            GridParams::new(0, 0).span(1, 2),
+        )
+        .with_child(
+            SizedBox::empty().width(50.).height(20.).with_id(label1),
+            (1, 0),
+        )
+        .with_child(
+            SizedBox::empty().expand_width().height(20.).with_id(field1),
+            (1, 1),
+        )
+        .with_child(
+            SizedBox::empty().width(80.).height(20.).with_id(label2),
+            (2, 0),
+        )
+        .with_child(
+            SizedBox::empty().expand_width().height(40.).with_id(field2),
+            (2, 1),
+        )
+        .with_id(grid_id)
+        .fix_width(300.)
+        .center();
+
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        assert_eq!(
+            harness.get_state(label1).layout_rect().origin(),
+            Point::new(0., 35.)
+        );
+        // the fields start after the widest label, and share what is left
+        let field1 = harness.get_state(field1).layout_rect();
+        assert_eq!(field1, Rect::new(90., 35., 300., 55.));
+        let field2 = harness.get_state(field2).layout_rect();
+        assert_eq!(field2, Rect::new(90., 60., 300., 100.));
+        assert_eq!(
+            harness.get_state(label2).layout_rect().origin(),
+            Point::new(0., 60.)
+        );
+        assert_eq!(harness.get_state(grid_id).layout_rect().height(), 100.);
+    });
+}
+
+#[test]
+fn split_collapses_first_pane() {
+    let id = WidgetId::next();
+    let split = Split::columns(SizedBox::<()>::empty().with_id(id), SizedBox::empty())
+        .min_size(50., 50.)
+        .draggable(true)
+        .collapsible(true);
+
+    let double_click = |x: f64| {
+        let mut mouse = left_button((x, 10.));
+        mouse.count = 2;
+        Event::MouseDown(mouse)
+    };
+
+    Harness::create_simple((), split, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        assert_eq!(harness.get_state(id).layout_rect().width(), 197.);
+
+        // the first pane can collapse past its minimum size
+        harness.event(double_click(200.));
+        for _ in 0..10 {
+            harness.event(Event::AnimFrame(16_000_000));
+        }
+        harness.just_layout();
+        assert_eq!(harness.get_state(id).layout_rect().width(), 0.);
+
+        harness.event(double_click(3.));
+        for _ in 0..10 {
+            harness.event(Event::AnimFrame(16_000_000));
+        }
+        harness.just_layout();
+        assert_eq!(harness.get_state(id).layout_rect().width(), 197.);
+    });
+}
+
+#[test]
+fn set_origin_translates_events() {
+    let id = WidgetId::next();
+    let last_pos = Rc::new(Cell::new(None));
+    let pos = last_pos.clone();
+
+    let child = ModularWidget::new(())
+        .event_fn(move |_, _, event, _, _| {
+            if let Event::MouseMove(mouse) = event {
+                pos.set(Some(mouse.pos));
+            }
+        })
+        .with_id(id);
+
+    let parent = ModularWidget::new(WidgetPod::new(child))
+        .event_fn(|child, ctx, event, data, env| child.event(ctx, event, data, env))
+        .lifecycle_fn(|child, ctx, event, data, env| child.lifecycle(ctx, event, data, env))
+        .layout_fn(|child, ctx, bc, data, env| {
+            child.layout(ctx, bc, data, env);
+            child.set_origin(ctx, data, env, Point::new(20., 30.));
+            bc.max()
+        });
+
+    Harness::create_simple((), parent, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        assert_eq!(
+            harness.get_state(id).layout_rect(),
+            Rect::new(20., 30., 120., 130.)
+        );
+
+        // outside the child: not hot, no position reported
+        harness.event(Event::MouseMove(move_mouse((10., 10.))));
+        assert!(!harness.get_state(id).is_hot);
+        assert_eq!(last_pos.get(), None);
+
+        // inside the child: positions are in the child's coordinate space
+        harness.event(Event::MouseMove(move_mouse((25., 35.))));
+        assert!(harness.get_state(id).is_hot);
+        assert_eq!(last_pos.get(), Some(Point::new(5., 5.)));
+    });
+}
@@ -0,0 +1,732 @@
+// Copyright 2021 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests related to the `List` widget.
+
+use std::cell::RefCell;
+use std::sync::Arc;
+use std::time::Duration;
+
+use test_env_log::test;
+
+use super::*;
+
+/// A 20px tall row, which counts how many rows have been built.
+fn counted_row(built: &Rc<Cell<usize>>) -> impl Widget<u32> {
+    let built = built.clone();
+    ModularWidget::new(())
+        .lifecycle_fn(move |_, _, event, _, _| {
+            if let LifeCycle::WidgetAdded = event {
+                built.set(built.get() + 1);
+            }
+        })
+        .layout_fn(|_, _, bc, _, _| bc.constrain((100., 20.)))
+}
+
+#[test]
+fn virtual_list_only_builds_visible_rows() {
+    let built: Rc<Cell<usize>> = Default::default();
+    let built_clone = built.clone();
+    let data: Arc<Vec<u32>> = Arc::new((0..50_000).collect());
+    let list = List::new(move || counted_row(&built_clone)).virtual_fixed_extent(20.);
+    let scroll = Scroll::new(list).vertical();
+
+    Harness::create_simple(data, scroll, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        harness.event(Event::MouseMove(move_mouse((10., 10.))));
+        harness.just_layout();
+
+        // the 20 visible rows, and 4 more below them
+        assert_eq!(built.get(), 24);
+
+        harness.event(Event::Wheel(scroll_mouse((10., 10.), (0., 2000.))));
+        harness.just_layout();
+
+        // rows 96 to 123
+        assert_eq!(built.get(), 52);
+    });
+}
+
+#[test]
+fn virtual_rows_keep_their_state_when_scrolled_back() {
+    const CHANGE_FIRST: Selector = Selector::new("druid-tests.change-first");
+
+    struct ChangeFirst;
+    impl<W: Widget<Arc<Vec<u32>>>> Controller<Arc<Vec<u32>>, W> for ChangeFirst {
+        fn event(
+            &mut self,
+            child: &mut W,
+            ctx: &mut EventCtx,
+            event: &Event,
+            data: &mut Arc<Vec<u32>>,
+            env: &Env,
+        ) {
+            if matches!(event, Event::Command(cmd) if cmd.is(CHANGE_FIRST)) {
+                Arc::make_mut(data)[0] = 500;
+            }
+            child.event(ctx, event, data, env)
+        }
+    }
+
+    let built: Rc<Cell<usize>> = Default::default();
+    let built_clone = built.clone();
+    // the item each row was built for, and the item it has been updated to
+    let updates: Rc<RefCell<Vec<(u32, u32)>>> = Default::default();
+    let updates_clone = updates.clone();
+    let list = List::new(move || {
+        let built = built_clone.clone();
+        let updates = updates_clone.clone();
+        ModularWidget::new(None)
+            .lifecycle_fn(move |first, _, event, data: &u32, _| {
+                if let LifeCycle::WidgetAdded = event {
+                    *first = Some(*data);
+                    built.set(built.get() + 1);
+                }
+            })
+            .update_fn(move |first, _, _, data, _| {
+                updates.borrow_mut().push((first.unwrap(), *data));
+            })
+            .layout_fn(|_, _, bc, _, _| bc.constrain((100., 20.)))
+    })
+    .virtual_fixed_extent(20.);
+    let data: Arc<Vec<u32>> = Arc::new((0..1000).collect());
+    let scroll = Scroll::new(list).vertical().controller(ChangeFirst);
+
+    Harness::create_simple(data, scroll, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        harness.event(Event::MouseMove(move_mouse((10., 10.))));
+        harness.event(Event::Wheel(scroll_mouse((10., 10.), (0., 2000.))));
+        harness.just_layout();
+        assert_eq!(built.get(), 52);
+
+        // the first row is out of view, so it doesn't hear about this yet
+        harness.submit_command(CHANGE_FIRST);
+        assert!(updates.borrow().is_empty());
+
+        harness.event(Event::Wheel(scroll_mouse((10., 10.), (0., -2000.))));
+        harness.just_layout();
+
+        // the first rows come back instead of being built again, and catch up
+        assert_eq!(built.get(), 52);
+        assert_eq!(*updates.borrow(), vec![(0, 500)]);
+    });
+}
+
+#[test]
+fn virtual_list_keeps_a_bounded_number_of_rows() {
+    // every row holds a clone, so the count tells how many rows are alive
+    let alive = Rc::new(());
+    let alive_clone = alive.clone();
+    let list = List::new(move || {
+        ModularWidget::new(alive_clone.clone())
+            .layout_fn(|_, _, bc, _, _| bc.constrain((100., 20.)))
+    })
+    .virtual_fixed_extent(20.);
+    let data: Arc<Vec<u32>> = Arc::new((0..50_000).collect());
+    let scroll = Scroll::new(list).vertical();
+
+    Harness::create_simple(data, scroll, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        harness.event(Event::MouseMove(move_mouse((10., 10.))));
+        for _ in 0..200 {
+            harness.event(Event::Wheel(scroll_mouse((10., 10.), (0., 300.))));
+            harness.just_layout();
+        }
+
+        // the rows in view, plus the ones set aside nearest to them
+        assert!(Rc::strong_count(&alive) - 1 <= 28 + 64);
+    });
+}
+
+#[test]
+fn separators_between_items() {
+    let built: Rc<Cell<usize>> = Default::default();
+    let list_id = WidgetId::next();
+
+    for (len, height) in &[(0, 0.), (1, 20.), (3, 78.)] {
+        let built = built.clone();
+        let data: Arc<Vec<u32>> = Arc::new((0..*len).collect());
+        // three 20px rows, with 4px of spacing on either side of the 1px separators
+        let list = List::new(move || counted_row(&built))
+            .with_spacing(4.)
+            .with_separator(|| SizedBox::empty().height(1.))
+            .with_id(list_id)
+            .center();
+
+        Harness::create_simple(data, list, |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            let state = harness.get_state(list_id);
+            assert_eq!(state.layout_rect().height(), *height);
+        });
+    }
+}
+
+#[test]
+fn indexed_children_follow_inserts() {
+    const INSERT: Selector = Selector::new("druid-tests.insert");
+    const SET_TO_INDEX: Selector = Selector::new("druid-tests.set-to-index");
+
+    struct Insert;
+    impl<W: Widget<Arc<Vec<u32>>>> Controller<Arc<Vec<u32>>, W> for Insert {
+        fn event(
+            &mut self,
+            child: &mut W,
+            ctx: &mut EventCtx,
+            event: &Event,
+            data: &mut Arc<Vec<u32>>,
+            env: &Env,
+        ) {
+            if matches!(event, Event::Command(cmd) if cmd.is(INSERT)) {
+                Arc::make_mut(data).insert(1, 99);
+            }
+            child.event(ctx, event, data, env)
+        }
+    }
+
+    // the last (index, item) each row has seen, by index
+    let seen: Rc<RefCell<HashMap<usize, u32>>> = Default::default();
+    let seen_clone = seen.clone();
+    let list = List::new_indexed(move || {
+        let seen = seen_clone.clone();
+        let seen_clone = seen_clone.clone();
+        ModularWidget::new(())
+            .event_fn(|_, _, event, data: &mut (usize, u32), _| {
+                if matches!(event, Event::Command(cmd) if cmd.is(SET_TO_INDEX)) {
+                    data.1 = data.0 as u32 * 10;
+                }
+            })
+            .lifecycle_fn(move |_, _, event, data, _| {
+                if let LifeCycle::WidgetAdded = event {
+                    seen.borrow_mut().insert(data.0, data.1);
+                }
+            })
+            .update_fn(move |_, _, _, data, _| {
+                seen_clone.borrow_mut().insert(data.0, data.1);
+            })
+    })
+    .controller(Insert);
+
+    let data: Arc<Vec<u32>> = Arc::new(vec![0, 1, 2]);
+    Harness::create_simple(data, list, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        let expected: HashMap<_, _> = vec![(0, 0), (1, 1), (2, 2)].into_iter().collect();
+        assert_eq!(*seen.borrow(), expected);
+
+        harness.submit_command(INSERT);
+        let expected: HashMap<_, _> = vec![(0, 0), (1, 99), (2, 1), (3, 2)].into_iter().collect();
+        assert_eq!(*seen.borrow(), expected);
+
+        // only the item half is written back
+        harness.submit_command(SET_TO_INDEX);
+        assert_eq!(**harness.data(), vec![0, 10, 20, 30]);
+    });
+}
+
+#[test]
+fn drag_to_reorder() {
+    fn reorderable_list() -> impl Widget<Arc<Vec<u32>>> {
+        let built: Rc<Cell<usize>> = Default::default();
+        List::new(move || counted_row(&built)).reorderable(true)
+    }
+
+    let data: Arc<Vec<u32>> = Arc::new(vec![0, 1, 2, 3]);
+    Harness::create_simple(data, reorderable_list(), |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+
+        // pick up the first row, and drag it past the next two
+        harness.event(Event::MouseDown(left_button((10., 5.))));
+        harness.event(Event::MouseMove(left_button((10., 50.))));
+        harness.just_layout();
+        harness.event(Event::MouseUp(left_button((10., 50.))));
+        assert_eq!(**harness.data(), vec![1, 2, 0, 3]);
+    });
+
+    let data: Arc<Vec<u32>> = Arc::new(vec![0, 1, 2, 3]);
+    Harness::create_simple(data, reorderable_list(), |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+
+        // escape puts the row back
+        harness.event(Event::MouseDown(left_button((10., 5.))));
+        harness.event(Event::MouseMove(left_button((10., 50.))));
+        harness.just_layout();
+        harness.event(Event::KeyDown(KeyEvent::for_test(
+            RawMods::None,
+            KbKey::Escape,
+        )));
+        harness.event(Event::MouseUp(left_button((10., 50.))));
+        assert_eq!(**harness.data(), vec![0, 1, 2, 3]);
+    });
+}
+
+#[test]
+fn keyed_rows_keep_their_widgets() {
+    const PREPEND: Selector = Selector::new("druid-tests.prepend");
+
+    struct Prepend;
+    impl<W: Widget<Arc<Vec<(u32, String)>>>> Controller<Arc<Vec<(u32, String)>>, W> for Prepend {
+        fn event(
+            &mut self,
+            child: &mut W,
+            ctx: &mut EventCtx,
+            event: &Event,
+            data: &mut Arc<Vec<(u32, String)>>,
+            env: &Env,
+        ) {
+            if matches!(event, Event::Command(cmd) if cmd.is(PREPEND)) {
+                Arc::make_mut(data).insert(0, (0, "new".into()));
+            }
+            child.event(ctx, event, data, env)
+        }
+    }
+
+    // the ids of the text boxes, in the order they were built
+    let ids: Rc<RefCell<Vec<WidgetId>>> = Default::default();
+    let ids_clone = ids.clone();
+    let list = List::new(move || {
+        let id = WidgetId::next();
+        ids_clone.borrow_mut().push(id);
+        TextBox::new()
+            .lens(crate::lens!((u32, String), 1))
+            .with_id(id)
+    })
+    .with_key(|item: &(u32, String)| item.0)
+    .controller(Prepend);
+
+    let data = Arc::new(vec![(1, "one".to_string()), (2, "two".to_string())]);
+    Harness::create_simple(data, list, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+
+        // start editing the first row
+        let first = ids.borrow()[0];
+        harness.event(Event::MouseDown(left_button((10., 5.))));
+        harness.event(Event::MouseUp(left_button((10., 5.))));
+        assert!(harness.get_state(first).has_focus);
+        assert_eq!(harness.get_state(first).layout_rect().y0, 0.);
+        harness.key_down(KeyEvent::for_test(RawMods::None, KbKey::End));
+        harness.key_down(KeyEvent::for_test(RawMods::None, "!"));
+        assert_eq!(harness.data()[0].1, "one!");
+
+        harness.submit_command(PREPEND);
+        harness.just_layout();
+
+        // the text box moved down with its item, and a new one was built for the new item
+        assert_eq!(ids.borrow().len(), 3);
+        assert!(harness.get_state(first).has_focus);
+        assert!(harness.get_state(first).layout_rect().y0 > 0.);
+
+        // the text box still has the edit, and still edits its own item
+        harness.key_down(KeyEvent::for_test(RawMods::None, "?"));
+        let expected = vec![
+            (0, "new".to_string()),
+            (1, "one!?".to_string()),
+            (2, "two".to_string()),
+        ];
+        assert_eq!(**harness.data(), expected);
+    });
+}
+
+#[cfg(feature = "im")]
+#[test]
+fn map_entries_write_back() {
+    use crate::im::{HashMap as ImHashMap, OrdMap};
+
+    const DOUBLE: Selector = Selector::new("druid-tests.double");
+
+    fn doubling_list() -> List<(u32, u32)> {
+        List::new(|| {
+            ModularWidget::new(()).event_fn(|_, _, event, data: &mut (u32, u32), _| {
+                if matches!(event, Event::Command(cmd) if cmd.is(DOUBLE)) {
+                    data.1 *= 2;
+                }
+            })
+        })
+    }
+
+    let data: OrdMap<u32, u32> = vec![(1, 10), (2, 20)].into_iter().collect();
+    Harness::create_simple(data, doubling_list(), |harness| {
+        harness.send_initial_events();
+        harness.submit_command(DOUBLE);
+        let expected: OrdMap<u32, u32> = vec![(1, 20), (2, 40)].into_iter().collect();
+        assert_eq!(*harness.data(), expected);
+    });
+
+    let data: ImHashMap<u32, u32> = vec![(1, 10), (2, 20)].into_iter().collect();
+    Harness::create_simple(data, doubling_list(), |harness| {
+        harness.send_initial_events();
+        harness.submit_command(DOUBLE);
+        let expected: ImHashMap<u32, u32> = vec![(1, 20), (2, 40)].into_iter().collect();
+        assert_eq!(*harness.data(), expected);
+    });
+}
+
+#[test]
+fn animated_insert_and_remove() {
+    const PUSH: Selector = Selector::new("druid-tests.push");
+    const POP: Selector = Selector::new("druid-tests.pop");
+
+    struct PushPop;
+    impl<W: Widget<Arc<Vec<u32>>>> Controller<Arc<Vec<u32>>, W> for PushPop {
+        fn event(
+            &mut self,
+            child: &mut W,
+            ctx: &mut EventCtx,
+            event: &Event,
+            data: &mut Arc<Vec<u32>>,
+            env: &Env,
+        ) {
+            if let Event::Command(cmd) = event {
+                if cmd.is(PUSH) {
+                    Arc::make_mut(data).push(9);
+                } else if cmd.is(POP) {
+                    Arc::make_mut(data).pop();
+                }
+            }
+            child.event(ctx, event, data, env)
+        }
+    }
+
+    let built: Rc<Cell<usize>> = Default::default();
+    let built_clone = built.clone();
+    let list_id = WidgetId::next();
+    let list = List::new(move || counted_row(&built_clone))
+        .with_animation(Duration::from_millis(100))
+        .controller(PushPop)
+        .with_id(list_id)
+        .center();
+
+    let data: Arc<Vec<u32>> = Arc::new(vec![0, 1]);
+    Harness::create_simple(data, list, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        assert_eq!(harness.get_state(list_id).layout_rect().height(), 40.);
+
+        // the new row starts out without any height, and grows to its full height
+        harness.submit_command(PUSH);
+        harness.just_layout();
+        assert_eq!(harness.get_state(list_id).layout_rect().height(), 40.);
+        for _ in 0..10 {
+            harness.event(Event::AnimFrame(16_000_000));
+        }
+        harness.just_layout();
+        assert_eq!(harness.get_state(list_id).layout_rect().height(), 60.);
+
+        // a removed row keeps its height at first, and shrinks away
+        harness.submit_command(POP);
+        harness.just_layout();
+        assert_eq!(harness.get_state(list_id).layout_rect().height(), 60.);
+        for _ in 0..10 {
+            harness.event(Event::AnimFrame(16_000_000));
+        }
+        harness.just_layout();
+        assert_eq!(harness.get_state(list_id).layout_rect().height(), 40.);
+        assert_eq!(built.get(), 3);
+    });
+}
+
+#[test]
+fn placeholder_when_empty() {
+    let list_id = WidgetId::next();
+
+    for (len, height) in &[(0, 50.), (2, 40.)] {
+        let built: Rc<Cell<usize>> = Default::default();
+        let data: Arc<Vec<u32>> = Arc::new((0..*len).collect());
+        let list = List::new(move || counted_row(&built))
+            .with_placeholder(|| SizedBox::empty().height(50.))
+            .with_id(list_id)
+            .center();
+
+        Harness::create_simple(data, list, |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            let state = harness.get_state(list_id);
+            assert_eq!(state.layout_rect().height(), *height);
+        });
+    }
+}
+
+#[test]
+fn only_changed_items_are_updated() {
+    const CHANGE_ONE: Selector = Selector::new("druid-tests.change-one");
+    const REQUEST_UPDATE: Selector = Selector::new("druid-tests.request-update");
+
+    struct ChangeOne;
+    impl<W: Widget<Arc<Vec<u32>>>> Controller<Arc<Vec<u32>>, W> for ChangeOne {
+        fn event(
+            &mut self,
+            child: &mut W,
+            ctx: &mut EventCtx,
+            event: &Event,
+            data: &mut Arc<Vec<u32>>,
+            env: &Env,
+        ) {
+            if matches!(event, Event::Command(cmd) if cmd.is(CHANGE_ONE)) {
+                Arc::make_mut(data)[1000] = 42;
+            }
+            child.event(ctx, event, data, env)
+        }
+    }
+
+    let updates: Rc<Cell<usize>> = Default::default();
+    let updates_clone = updates.clone();
+    let list_id = WidgetId::next();
+    let list = List::new(move || {
+        let updates = updates_clone.clone();
+        ModularWidget::new(())
+            .event_fn(|_, ctx, event, data: &mut u32, _| {
+                if matches!(event, Event::Command(cmd) if cmd.is(REQUEST_UPDATE)) && *data == 7 {
+                    ctx.request_update();
+                }
+            })
+            .update_fn(move |_, _, _, _, _| updates.set(updates.get() + 1))
+            .layout_fn(|_, _, bc, _, _| bc.constrain((100., 20.)))
+    })
+    .with_id(list_id)
+    .controller(ChangeOne);
+
+    let data: Arc<Vec<u32>> = Arc::new((0..2000).collect());
+    Harness::create_simple(data, list, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        assert_eq!(updates.get(), 0);
+
+        harness.submit_command(CHANGE_ONE);
+        assert_eq!(updates.get(), 1);
+        assert!(!harness.get_state(list_id).needs_layout);
+
+        // the data is the same, but a row that asks for an update still gets it
+        harness.submit_command(REQUEST_UPDATE);
+        assert_eq!(updates.get(), 2);
+    });
+}
+
+#[test]
+fn scroll_to_item() {
+    let offset: Rc<Cell<f64>> = Default::default();
+    let offset_clone = offset.clone();
+    let list_id = WidgetId::next();
+    let built: Rc<Cell<usize>> = Default::default();
+    let list = List::new(move || counted_row(&built)).with_id(list_id);
+    let scroll = Scroll::new(list)
+        .vertical()
+        .on_scroll(move |_, port, _, _| offset_clone.set(port.view_origin.y));
+
+    let data: Arc<Vec<u32>> = Arc::new((0..50).collect());
+    Harness::create_simple(data, scroll, |harness| {
+        // before the first layout there is nothing to scroll to
+        harness.send_initial_events();
+        harness.submit_command(List::SCROLL_TO_ITEM.with(30).to(list_id));
+        assert_eq!(offset.get(), 0.);
+
+        harness.just_layout();
+        harness.event(Event::MouseMove(move_mouse((10., 10.))));
+
+        // row 30 is brought into view at the bottom edge
+        harness.submit_command(List::SCROLL_TO_ITEM.with(30).to(list_id));
+        assert_eq!(offset.get(), 220.);
+
+        // a visible row doesn't move the viewport
+        harness.submit_command(List::SCROLL_TO_ITEM.with(25).to(list_id));
+        assert_eq!(offset.get(), 220.);
+
+        // out of range indices go to the last row
+        harness.submit_command(List::SCROLL_TO_ITEM.with(100).to(list_id));
+        assert_eq!(offset.get(), 600.);
+    });
+}
+
+#[test]
+fn sticky_section_headers() {
+    // the headers and items that were clicked, with headers as `section` and items
+    // as `100 + item`
+    let clicks: Rc<RefCell<Vec<u32>>> = Default::default();
+    let header_clicks = clicks.clone();
+    let item_clicks = clicks.clone();
+
+    let list = List::new(move || {
+        let clicks = item_clicks.clone();
+        ModularWidget::new(())
+            .event_fn(move |_, _, event, data: &mut u32, _| {
+                if let Event::MouseDown(_) = event {
+                    clicks.borrow_mut().push(100 + *data);
+                }
+            })
+            .layout_fn(|_, _, bc, _, _| bc.constrain((100., 20.)))
+    })
+    .with_sections(
+        |item| item / 10,
+        move || {
+            let clicks = header_clicks.clone();
+            ModularWidget::new(())
+                .event_fn(move |_, ctx, event, section: &mut u32, _| {
+                    if let Event::MouseDown(_) = event {
+                        clicks.borrow_mut().push(*section);
+                        ctx.set_handled();
+                    }
+                })
+                .layout_fn(|_, _, bc, _, _| bc.constrain((100., 30.)))
+        },
+    );
+    let scroll = Scroll::new(list).vertical();
+
+    // five sections of ten 20px rows, each after a 30px header
+    let data: Arc<Vec<u32>> = Arc::new((0..50).collect());
+    Harness::create_simple(data, scroll, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        harness.event(Event::MouseMove(move_mouse((10., 10.))));
+
+        // the first header sticks to the top of the viewport
+        harness.event(Event::Wheel(scroll_mouse((10., 10.), (0., 100.))));
+        harness.just_layout();
+        harness.event(Event::MouseDown(left_button((10., 10.))));
+        harness.event(Event::MouseUp(left_button((10., 10.))));
+        // below it is item 8, at 190 to 210
+        harness.event(Event::MouseDown(left_button((10., 90.))));
+        harness.event(Event::MouseUp(left_button((10., 90.))));
+        assert_eq!(*clicks.borrow(), vec![0, 108]);
+        clicks.borrow_mut().clear();
+
+        // the second header, at 230, pushes the first one out
+        harness.event(Event::Wheel(scroll_mouse((10., 10.), (0., 120.))));
+        harness.just_layout();
+        harness.event(Event::MouseDown(left_button((10., 5.))));
+        harness.event(Event::MouseUp(left_button((10., 5.))));
+        harness.event(Event::MouseDown(left_button((10., 15.))));
+        harness.event(Event::MouseUp(left_button((10., 15.))));
+        assert_eq!(*clicks.borrow(), vec![0, 1]);
+    });
+}
+
+#[test]
+fn row_backgrounds_follow_the_pointer() {
+    // the rows that were painted as hovered
+    let hovered: Rc<RefCell<Vec<usize>>> = Default::default();
+    let hovered_clone = hovered.clone();
+    let built: Rc<Cell<usize>> = Default::default();
+    let list = List::new(move || counted_row(&built)).with_row_background(move |i, hot, _| {
+        if hot {
+            hovered_clone.borrow_mut().push(i);
+            Some(Color::BLACK)
+        } else if i % 2 == 1 {
+            Some(Color::grey(0.2))
+        } else {
+            None
+        }
+    });
+
+    let data: Arc<Vec<u32>> = Arc::new((0..5).collect());
+    Harness::create_simple(data, list, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        harness.paint();
+        assert!(hovered.borrow().is_empty());
+
+        harness.event(Event::MouseMove(move_mouse((10., 25.))));
+        harness.paint();
+        assert_eq!(*hovered.borrow(), vec![1]);
+
+        harness.event(Event::MouseMove(move_mouse((10., 85.))));
+        harness.paint();
+        assert_eq!(*hovered.borrow(), vec![1, 4]);
+
+        // below the last row
+        harness.event(Event::MouseMove(move_mouse((10., 300.))));
+        harness.paint();
+        assert_eq!(*hovered.borrow(), vec![1, 4]);
+    });
+}
+
+#[test]
+fn lazy_list_only_produces_visible_items() {
+    let lines: Rc<RefCell<Vec<u32>>> = Rc::new(RefCell::new((0..1_000_000).collect()));
+    let produced: Rc<Cell<usize>> = Default::default();
+    // the data the rows were last updated with
+    let shown: Rc<RefCell<Vec<u32>>> = Default::default();
+
+    let (len_lines, item_lines, produced_clone, shown_clone) = (
+        lines.clone(),
+        lines.clone(),
+        produced.clone(),
+        shown.clone(),
+    );
+    let list_id = WidgetId::next();
+    let list = LazyList::new(
+        move || len_lines.borrow().len(),
+        move |i| {
+            produced_clone.set(produced_clone.get() + 1);
+            item_lines.borrow()[i]
+        },
+        20.,
+        move || {
+            let shown = shown_clone.clone();
+            ModularWidget::new(())
+                .update_fn(move |_, _, _, data: &u32, _| shown.borrow_mut().push(*data))
+                .layout_fn(|_, _, bc, _, _| bc.constrain((100., 20.)))
+        },
+    )
+    .with_id(list_id);
+    let scroll = Scroll::new(list).vertical();
+
+    Harness::create_simple((), scroll, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        harness.event(Event::MouseMove(move_mouse((10., 10.))));
+        harness.just_layout();
+
+        // the 20 visible rows, and 4 more below them
+        assert_eq!(produced.get(), 24);
+        let state = harness.get_state(list_id);
+        assert_eq!(state.layout_rect().height(), 20_000_000.);
+
+        lines.borrow_mut()[3] = 42;
+        lines.borrow_mut().truncate(10);
+        harness.submit_command(LazyList::INVALIDATE.to(list_id));
+        harness.just_layout();
+        assert_eq!(produced.get(), 34);
+        assert!(shown.borrow().contains(&42));
+        let state = harness.get_state(list_id);
+        assert_eq!(state.layout_rect().height(), 200.);
+    });
+}
+
+#[test]
+fn wrapping_list_reflows() {
+    let list_id = WidgetId::next();
+    for (width, height) in &[(350., 110.), (250., 140.), (150., 290.)] {
+        let built: Rc<Cell<usize>> = Default::default();
+        let data: Arc<Vec<u32>> = Arc::new((0..10).collect());
+        // ten 100px wide rows, with 10px between them and between the lines
+        let list = List::new(move || counted_row(&built))
+            .wrap()
+            .with_spacing(10.)
+            .with_id(list_id)
+            .fix_width(*width)
+            .center();
+
+        Harness::create_simple(data, list, |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            let state = harness.get_state(list_id);
+            assert_eq!(state.layout_rect().height(), *height);
+        });
+    }
+}
@@ -23,12 +23,17 @@ pub mod helpers;
 mod invalidation_tests;
 #[cfg(test)]
 mod layout_tests;
+#[cfg(test)]
+mod list_tests;
+#[cfg(test)]
+mod scroll_tests;
 
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::rc::Rc;
+use std::time::Duration;
 
 use crate::widget::*;
 use crate::*;
@@ -51,6 +56,21 @@ pub fn move_mouse(p: impl Into<Point>) -> MouseEvent {
     }
 }
 
+/// Helper function to construct a left mouse button event.
+pub fn left_button(p: impl Into<Point>) -> MouseEvent {
+    let pos = p.into();
+    MouseEvent {
+        pos,
+        window_pos: pos,
+        buttons: MouseButtons::new().with(MouseButton::Left),
+        mods: Modifiers::default(),
+        count: 1,
+        focus: false,
+        button: MouseButton::Left,
+        wheel_delta: Vec2::ZERO,
+    }
+}
+
 /// Helper function to construct a "scroll by n ticks" mouse event.
 pub fn scroll_mouse(p: impl Into<Point>, delta: impl Into<Vec2>) -> MouseEvent {
     let pos = p.into();
@@ -188,6 +208,397 @@ fn propagate_hot() {
         assert!(root_rec.is_empty() && padding_rec.is_empty() && button_rec.is_empty());
     });
 }
+
+/// test that hot widgets are told when the mouse leaves the window.
+#[test]
+fn mouse_leave_clears_hot() {
+    let [button, pad] = widget_ids();
+
+    let button_rec = Recording::default();
+
+    let widget = Button::new("hot")
+        .record(&button_rec)
+        .with_id(button)
+        .padding(50.)
+        .with_id(pad);
+
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+
+        harness.event(Event::MouseMove(move_mouse((60., 60.))));
+        assert!(harness.get_state(button).is_hot);
+        assert!(harness.get_state(pad).is_hot);
+        button_rec.clear();
+
+        harness.event(Event::Internal(InternalEvent::MouseLeave));
+        assert!(!harness.get_state(button).is_hot);
+        assert!(!harness.get_state(pad).is_hot);
+        assert!(matches!(
+            button_rec.next(),
+            Record::L(LifeCycle::HotChanged(false))
+        ));
+
+        // leaving again is not a transition, so nothing more is sent.
+        button_rec.clear();
+        harness.event(Event::Internal(InternalEvent::MouseLeave));
+        assert!(!button_rec
+            .drain()
+            .any(|rec| matches!(rec, Record::L(LifeCycle::HotChanged(_)))));
+    });
+}
+
+#[test]
+fn switching_the_theme_updates_widgets() {
+    let changes: Rc<RefCell<Vec<(bool, bool)>>> = Default::default();
+    let changes_clone = changes.clone();
+    let widget = ModularWidget::new(()).update_fn(move |_, ctx, _, _, _| {
+        changes_clone.borrow_mut().push((
+            ctx.env_changed(),
+            ctx.env_key_changed(&theme::WINDOW_BACKGROUND_COLOR),
+        ));
+    });
+
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        changes.borrow_mut().clear();
+
+        let mut env = harness.env().clone();
+        theme::set_appearance(&mut env, Appearance::Light);
+        harness.set_env(env.clone());
+        assert_eq!(changes.borrow().as_slice(), &[(true, true)]);
+
+        // the same theme again is not a change
+        changes.borrow_mut().clear();
+        theme::set_appearance(&mut env, Appearance::Light);
+        harness.set_env(env.clone());
+        assert!(changes.borrow().is_empty());
+
+        theme::set_appearance(&mut env, Appearance::Dark);
+        harness.set_env(env);
+        assert_eq!(changes.borrow().as_slice(), &[(true, true)]);
+    });
+}
+
+#[test]
+fn switching_the_locale_updates_labels() {
+    let label_rec = Recording::default();
+    let text =
+        LocalizedString::new("hello-counter").with_arg("count", |data: &u32, _| (*data).into());
+    let label = Label::new(text).record(&label_rec);
+
+    let was_laid_out = |rec: &Recording| rec.drain().any(|ev| matches!(ev, Record::Layout(_)));
+    Harness::create_simple(1, label, |harness| {
+        harness.send_initial_events();
+        let mut env = harness.env().clone();
+        assert!(env.set_locale("en-US"));
+        harness.set_env(env.clone());
+        harness.just_layout();
+        label_rec.clear();
+
+        assert!(env.set_locale("de-DE"));
+        harness.set_env(env);
+        harness.just_layout();
+        assert!(was_laid_out(&label_rec));
+    });
+}
+
+#[test]
+fn env_scope_follows_data() {
+    const TOGGLE_ERROR: Selector = Selector::new("druid-tests.toggle-error");
+    const ERROR_COLOR: Color = Color::rgb8(0xff, 0x00, 0x00);
+
+    struct ToggleError;
+    impl<W: Widget<bool>> Controller<bool, W> for ToggleError {
+        fn event(
+            &mut self,
+            child: &mut W,
+            ctx: &mut EventCtx,
+            event: &Event,
+            data: &mut bool,
+            env: &Env,
+        ) {
+            if let Event::Command(cmd) = event {
+                if cmd.is(TOGGLE_ERROR) {
+                    *data = !*data;
+                }
+            }
+            child.event(ctx, event, data, env)
+        }
+    }
+
+    let colors: Rc<RefCell<Vec<Color>>> = Default::default();
+    let colors_clone = colors.clone();
+    let observer = ModularWidget::new(()).update_fn(move |_, ctx, _, _, env| {
+        if ctx.env_key_changed(&theme::TEXT_COLOR) {
+            colors_clone.borrow_mut().push(env.get(theme::TEXT_COLOR));
+        }
+    });
+    let label_rec = Recording::default();
+    let widget = Flex::column()
+        .with_child(Label::new("Status").record(&label_rec))
+        .with_child(observer)
+        .env_scope(|env, is_error: &bool| {
+            if *is_error {
+                env.set(theme::TEXT_COLOR, ERROR_COLOR);
+            }
+        })
+        .controller(ToggleError);
+
+    let was_laid_out = |rec: &Recording| rec.drain().any(|ev| matches!(ev, Record::Layout(_)));
+    Harness::create_simple(false, widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        let default_color = harness.env().get(theme::TEXT_COLOR);
+        colors.borrow_mut().clear();
+        label_rec.clear();
+
+        harness.submit_command(TOGGLE_ERROR);
+        harness.just_layout();
+        assert_eq!(colors.borrow().as_slice(), &[ERROR_COLOR]);
+        assert!(was_laid_out(&label_rec));
+
+        harness.submit_command(TOGGLE_ERROR);
+        harness.just_layout();
+        assert_eq!(colors.borrow().as_slice(), &[ERROR_COLOR, default_color]);
+        assert!(was_laid_out(&label_rec));
+    });
+}
+
+#[test]
+fn animations_run_concurrently() {
+    const START: Selector = Selector::new("druid-tests.start-animations");
+
+    let animated = Recording::default();
+    let other = Recording::default();
+    let animated_widget = ModularWidget::new(()).event_fn(|_, ctx, event, _, _| {
+        if matches!(event, Event::Command(cmd) if cmd.is(START)) {
+            ctx.request_animation(Duration::from_millis(32), AnimationCurve::Linear);
+            ctx.request_animation(Duration::from_millis(64), AnimationCurve::Linear);
+        }
+    });
+    let widget = Flex::row()
+        .with_child(animated_widget.record(&animated))
+        .with_child(SizedBox::empty().record(&other));
+
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        harness.submit_command(START);
+        animated.clear();
+        other.clear();
+
+        for _ in 0..5 {
+            harness.event(Event::AnimFrame(16_000_000));
+        }
+
+        let mut progress = Vec::new();
+        let mut ended = 0;
+        for record in animated.drain() {
+            match record {
+                Record::E(Event::AnimProgress(_, t)) => progress.push(t),
+                Record::E(Event::AnimEnded(_)) => ended += 1,
+                _ => (),
+            }
+        }
+        assert_eq!(progress, vec![0.5, 0.25, 1.0, 0.5, 0.75, 1.0]);
+        assert_eq!(ended, 2);
+
+        // the events only go to the widget that requested the animations
+        assert!(!other.drain().any(|r| matches!(
+            r,
+            Record::E(Event::AnimProgress(..)) | Record::E(Event::AnimEnded(_))
+        )));
+    });
+}
+
+#[test]
+fn intervals_repeat_until_cancelled() {
+    const START: Selector = Selector::new("druid-tests.start-interval");
+    const STOP: Selector = Selector::new("druid-tests.stop-interval");
+
+    let ticks = Rc::new(RefCell::new(Vec::new()));
+    let ticks_clone = ticks.clone();
+    let widget =
+        ModularWidget::new(None::<TimerToken>).event_fn(
+            move |token, ctx, event, _, _| match event {
+                Event::Command(cmd) if cmd.is(START) => {
+                    *token = Some(ctx.request_interval(Duration::from_millis(10)));
+                }
+                Event::Command(cmd) if cmd.is(STOP) => ctx.cancel_timer(token.unwrap()),
+                Event::Timer(tick) => ticks_clone.borrow_mut().push(*tick),
+                _ => (),
+            },
+        );
+
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.submit_command(START);
+        assert_eq!(harness.window().timers.len(), 1);
+
+        for _ in 0..3 {
+            harness.fire_timers();
+        }
+        let token = harness
+            .window()
+            .timers
+            .values()
+            .next()
+            .map(|timer| timer.token);
+        assert_eq!(ticks.borrow().len(), 3);
+        assert!(ticks.borrow().iter().all(|tick| Some(*tick) == token));
+
+        harness.submit_command(STOP);
+        assert!(harness.window().timers.is_empty());
+        harness.fire_timers();
+        assert_eq!(ticks.borrow().len(), 3);
+    });
+}
+
+#[test]
+fn timers_of_removed_widgets_are_dropped() {
+    const START: Selector = Selector::new("druid-tests.start-interval");
+
+    let ticking = ModularWidget::new(()).event_fn(|_, ctx, event, _, _| {
+        if matches!(event, Event::Command(cmd) if cmd.is(START)) {
+            ctx.request_interval(Duration::from_millis(10));
+        }
+    });
+    let widget = ReplaceChild::new(ticking, SizedBox::empty);
+
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.submit_command(START);
+        assert_eq!(harness.window().timers.len(), 1);
+
+        harness.submit_command(REPLACE_CHILD);
+        assert!(harness.window().timers.is_empty());
+    });
+}
+
+#[test]
+fn window_scale_reaches_every_widget() {
+    let [first_id, second_id] = widget_ids();
+    let first = Recording::default();
+    let second = Recording::default();
+    let widget = Flex::row()
+        .with_child(SizedBox::empty().record(&first).with_id(first_id))
+        .with_child(SizedBox::empty().record(&second).with_id(second_id));
+
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        first.clear();
+        second.clear();
+
+        let scale = Scale::new(2.0, 2.0);
+        harness.event(Event::WindowScale(scale));
+        for recording in &[&first, &second] {
+            assert!(recording
+                .drain()
+                .any(|r| matches!(r, Record::E(Event::WindowScale(s)) if s == scale)));
+        }
+        assert!(harness.get_state(first_id).needs_layout);
+        assert!(harness.get_state(second_id).needs_layout);
+    });
+}
+
+#[test]
+fn hotkeys_are_handled_before_the_focused_widget() {
+    const TAKE_FOCUS: Selector = Selector::new("druid-tests.take-focus");
+    const SAVE: Selector = Selector::new("druid-tests.save");
+
+    let recording = Recording::default();
+    let widget = ModularWidget::new(())
+        .event_fn(|_, ctx, event, _, _| {
+            if matches!(event, Event::Command(cmd) if cmd.is(TAKE_FOCUS)) {
+                ctx.request_focus();
+            }
+        })
+        .record(&recording);
+
+    Harness::create_simple((), widget, |harness| {
+        harness
+            .window_mut()
+            .hotkeys
+            .push((HotKey::new(RawMods::Ctrl, "s"), SAVE.into()));
+        harness.send_initial_events();
+        harness.submit_command(TAKE_FOCUS);
+        recording.clear();
+
+        let is_key = |r: &Record| matches!(r, Record::E(Event::KeyDown(_)));
+        let is_save = |r: &Record| matches!(r, Record::E(Event::Command(cmd)) if cmd.is(SAVE));
+
+        harness.event(Event::KeyDown(KeyEvent::for_test(RawMods::Ctrl, "s")));
+        let records: Vec<_> = recording.drain().collect();
+        assert!(records.iter().any(is_save));
+        assert!(!records.iter().any(is_key));
+
+        // other keys still go to the focused widget
+        harness.event(Event::KeyDown(KeyEvent::for_test(RawMods::None, "s")));
+        let records: Vec<_> = recording.drain().collect();
+        assert!(!records.iter().any(is_save));
+        assert!(records.iter().any(is_key));
+    });
+}
+
+#[test]
+fn file_drop_goes_to_widget_under_cursor() {
+    let [left, right] = widget_ids();
+
+    let left_rec = Recording::default();
+    let dropped = Rc::new(RefCell::new(Vec::new()));
+    let dropped_clone = dropped.clone();
+
+    let row = Flex::row()
+        .with_child(
+            SizedBox::empty()
+                .width(100.)
+                .height(100.)
+                .record(&left_rec)
+                .with_id(left),
+        )
+        .with_child(
+            SizedBox::empty()
+                .width(100.)
+                .height(100.)
+                .with_id(right)
+                .on_file_drop(move |_, paths, _, _| {
+                    dropped_clone.borrow_mut().extend_from_slice(paths)
+                }),
+        );
+    let widget = Align::new(UnitPoint::TOP_LEFT, row);
+
+    let paths = vec![std::path::PathBuf::from("file.txt")];
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        left_rec.clear();
+
+        harness.event(Event::DropHover(Point::new(50., 50.), paths.clone()));
+        assert!(harness.get_state(left).is_drop_hovered);
+        assert!(left_rec
+            .drain()
+            .any(|rec| matches!(rec, Record::E(Event::DropHover(..)))));
+
+        // moving to the other widget cancels the drop for the first one
+        harness.event(Event::DropHover(Point::new(150., 50.), paths.clone()));
+        assert!(!harness.get_state(left).is_drop_hovered);
+        assert!(harness.get_state(right).is_drop_hovered);
+        assert!(left_rec
+            .drain()
+            .any(|rec| matches!(rec, Record::E(Event::DropCancelled))));
+
+        harness.event(Event::Dropped(Point::new(150., 50.), paths.clone()));
+        assert!(!harness.get_state(right).is_drop_hovered);
+        assert_eq!(*dropped.borrow(), paths);
+        assert!(!left_rec
+            .drain()
+            .any(|rec| matches!(rec, Record::E(Event::Dropped(..)))));
+    });
+}
+
 #[test]
 fn take_focus() {
     const TAKE_FOCUS: Selector = Selector::new("druid-tests.take-focus");
@@ -961,3 +1372,1024 @@ fn notifications() {
         assert!(saw_notification(&grandparent_rec));
     });
 }
+
+#[test]
+fn context_menu_handles_right_click() {
+    let menus_built = Rc::new(Cell::new(0));
+    let child_rec = Recording::default();
+
+    let counter = menus_built.clone();
+    let widget = SizedBox::<()>::empty()
+        .expand()
+        .record(&child_rec)
+        .context_menu(move |_, _| {
+            counter.set(counter.get() + 1);
+            Menu::<()>::empty()
+        });
+
+    let saw_mouse_down = |rec: &Recording| {
+        rec.drain()
+            .any(|ev| matches!(ev, Record::E(Event::MouseDown(_))))
+    };
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        child_rec.clear();
+
+        let mut right_click = left_button((10., 10.));
+        right_click.button = MouseButton::Right;
+        right_click.buttons = MouseButtons::new().with(MouseButton::Right);
+        harness.event(Event::MouseDown(right_click));
+        assert_eq!(menus_built.get(), 1);
+        assert!(!saw_mouse_down(&child_rec));
+
+        harness.event(Event::MouseDown(left_button((10., 10.))));
+        assert_eq!(menus_built.get(), 1);
+        assert!(saw_mouse_down(&child_rec));
+    });
+}
+
+#[test]
+fn modal_takes_the_mouse() {
+    let content_rec = Recording::default();
+    let modal_rec = Recording::default();
+
+    let widget = ModalHost::new(SizedBox::<()>::empty().expand().record(&content_rec));
+    let modal = SizedBox::empty()
+        .width(100.)
+        .height(100.)
+        .record(&modal_rec);
+
+    let saw_mouse_down = |rec: &Recording| {
+        rec.drain()
+            .any(|ev| matches!(ev, Record::E(Event::MouseDown(_))))
+    };
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+
+        harness.submit_command(ModalHost::show_modal(modal));
+        harness.just_layout();
+        content_rec.clear();
+        harness.event(Event::MouseDown(left_button((10., 10.))));
+        harness.event(Event::MouseDown(left_button((200., 200.))));
+        assert!(!saw_mouse_down(&content_rec));
+        assert!(saw_mouse_down(&modal_rec));
+
+        harness.submit_command(ModalHost::DISMISS_MODAL);
+        harness.just_layout();
+        harness.event(Event::MouseDown(left_button((10., 10.))));
+        assert!(saw_mouse_down(&content_rec));
+    });
+}
+
+#[test]
+fn either_transition_keeps_outgoing_child() {
+    const TOGGLE: Selector = Selector::new("druid-tests.toggle");
+
+    struct Toggle;
+    impl<W: Widget<bool>> Controller<bool, W> for Toggle {
+        fn event(
+            &mut self,
+            child: &mut W,
+            ctx: &mut EventCtx,
+            event: &Event,
+            data: &mut bool,
+            env: &Env,
+        ) {
+            if let Event::Command(cmd) = event {
+                if cmd.is(TOGGLE) {
+                    *data = !*data;
+                }
+            }
+            child.event(ctx, event, data, env)
+        }
+    }
+
+    let true_rec = Recording::default();
+    let false_rec = Recording::default();
+    let either = Either::new(
+        |data: &bool, _| *data,
+        SizedBox::empty().expand().record(&true_rec),
+        SizedBox::empty().expand().record(&false_rec),
+    )
+    .with_transition(Transition::SlideLeft(Duration::from_millis(100)))
+    .controller(Toggle);
+
+    let was_laid_out = |rec: &Recording| rec.drain().any(|ev| matches!(ev, Record::Layout(_)));
+    let saw_mouse_down = |rec: &Recording| {
+        rec.drain()
+            .any(|ev| matches!(ev, Record::E(Event::MouseDown(_))))
+    };
+    Harness::create_simple(false, either, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+
+        // both children are alive during the transition, but only the
+        // incoming one gets the mouse
+        harness.submit_command(TOGGLE);
+        true_rec.clear();
+        false_rec.clear();
+        harness.just_layout();
+        assert!(was_laid_out(&true_rec));
+        assert!(was_laid_out(&false_rec));
+        harness.event(Event::MouseDown(left_button((10., 10.))));
+        assert!(saw_mouse_down(&true_rec));
+        assert!(!saw_mouse_down(&false_rec));
+
+        for _ in 0..10 {
+            harness.event(Event::AnimFrame(16_000_000));
+        }
+        true_rec.clear();
+        false_rec.clear();
+        harness.just_layout();
+        assert!(was_laid_out(&true_rec));
+        assert!(!was_laid_out(&false_rec));
+    });
+}
+
+#[test]
+fn stepper_wheel_clamps_to_range() {
+    let stepper = Stepper::new().with_range(0., 2.).with_step(1.5);
+    let wheel = |y: f64| {
+        let mut mouse = move_mouse((5., 5.));
+        mouse.wheel_delta = Vec2::new(0., y);
+        Event::Wheel(mouse)
+    };
+
+    Harness::create_simple(1.0, stepper, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        harness.event(Event::MouseMove(move_mouse((5., 5.))));
+
+        harness.event(wheel(-10.));
+        assert_eq!(*harness.data(), 2.);
+        harness.event(wheel(-10.));
+        assert_eq!(*harness.data(), 2.);
+        harness.event(wheel(10.));
+        assert_eq!(*harness.data(), 0.5);
+        harness.event(wheel(10.));
+        assert_eq!(*harness.data(), 0.);
+    });
+}
+
+#[test]
+fn selectable_label_takes_focus_on_click() {
+    let plain_id = WidgetId::next();
+    let selectable_id = WidgetId::next();
+    let widget = Flex::column()
+        .with_child(Label::new("plain").with_id(plain_id))
+        .with_child(Label::new("selectable").selectable().with_id(selectable_id));
+
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        let plain = harness.get_state(plain_id).layout_rect().center();
+        let selectable = harness.get_state(selectable_id).layout_rect().center();
+
+        harness.event(Event::MouseDown(left_button(plain)));
+        harness.event(Event::MouseUp(left_button(plain)));
+        assert_eq!(harness.window().focus, None);
+
+        harness.event(Event::MouseDown(left_button(selectable)));
+        harness.event(Event::MouseUp(left_button(selectable)));
+        assert_eq!(harness.window().focus, Some(selectable_id));
+    });
+}
+
+#[test]
+fn rich_text_clamps_invalid_ranges() {
+    use crate::text::{Attribute, RichText};
+
+    let text = RichText::new("h\u{e9}llo".into())
+        .with_attribute(2..20, Attribute::weight(FontWeight::BOLD))
+        .with_attribute(4..1, Attribute::underline(true));
+    let label_id = WidgetId::next();
+    let label = Label::raw().with_id(label_id);
+
+    // the clamped ranges are checked in `text::rich_text`; here they have to
+    // make it through a real text layout
+    Harness::create_simple(text, label, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        harness.paint();
+        let size = harness.get_state(label_id).layout_rect().size();
+        assert!(size.width > 0. && size.height > 0.);
+    });
+}
+
+#[test]
+fn label_links_cycle_with_keyboard() {
+    use crate::text::RichTextBuilder;
+
+    const TAKE_FOCUS: Selector = Selector::new("druid-tests.take-focus");
+    const LINK: Selector<u32> = Selector::new("druid-tests.link");
+
+    struct LinkWatcher(Rc<Cell<Option<u32>>>);
+
+    impl<W: Widget<text::RichText>> Controller<text::RichText, W> for LinkWatcher {
+        fn event(
+            &mut self,
+            child: &mut W,
+            ctx: &mut EventCtx,
+            event: &Event,
+            data: &mut text::RichText,
+            env: &Env,
+        ) {
+            if let Event::Command(cmd) = event {
+                if cmd.is(TAKE_FOCUS) {
+                    ctx.request_focus();
+                } else if let Some(link) = cmd.get(LINK) {
+                    self.0.set(Some(*link));
+                }
+            }
+            child.event(ctx, event, data, env)
+        }
+    }
+
+    let mut builder = RichTextBuilder::new();
+    builder.push("first").link(LINK.with(1));
+    builder.push(" and ");
+    builder.push("second").link(LINK.with(2));
+    let activated = Rc::new(Cell::new(None));
+    let label = Label::raw().controller(LinkWatcher(activated.clone()));
+
+    Harness::create_simple(builder.build(), label, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        harness.submit_command(TAKE_FOCUS);
+
+        let key = |key: KbKey| Event::KeyDown(KeyEvent::for_test(RawMods::None, key));
+        harness.event(key(KbKey::Enter));
+        assert_eq!(activated.get(), Some(1));
+        harness.event(key(KbKey::Tab));
+        harness.event(key(KbKey::Enter));
+        assert_eq!(activated.get(), Some(2));
+    });
+}
+
+#[test]
+fn label_max_lines_limits_height() {
+    let one_id = WidgetId::next();
+    let truncated_id = WidgetId::next();
+    let widget = Flex::column()
+        .with_child(Label::new("one").with_id(one_id))
+        .with_child(
+            Label::new("one\ntwo\nthree")
+                .with_max_lines(1)
+                .with_id(truncated_id),
+        );
+
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        let one = harness.get_state(one_id).layout_rect();
+        let truncated = harness.get_state(truncated_id).layout_rect();
+        assert_eq!(one.height(), truncated.height());
+        harness.paint();
+    });
+}
+
+#[test]
+fn button_content_does_not_get_mouse_events() {
+    let record = Recording::default();
+    let clicks = Rc::new(Cell::new(0));
+    let clicks_clone = clicks.clone();
+    let content = SizedBox::empty().width(20.).height(20.).record(&record);
+    let button = Button::new_with_child(content)
+        .on_click(move |_, _, _| clicks_clone.set(clicks_clone.get() + 1));
+
+    Harness::create_simple((), button, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        record.clear();
+
+        harness.event(Event::MouseMove(move_mouse((10., 10.))));
+        harness.event(Event::MouseDown(left_button((10., 10.))));
+        harness.event(Event::MouseUp(left_button((10., 10.))));
+        assert_eq!(clicks.get(), 1);
+        for rec in record.drain() {
+            assert!(!matches!(
+                rec,
+                Record::E(Event::MouseMove(_))
+                    | Record::E(Event::MouseDown(_))
+                    | Record::E(Event::MouseUp(_))
+            ));
+        }
+    });
+}
+
+#[test]
+fn repeating_button_fires_on_press() {
+    let button = Button::new("+")
+        .with_repeat(Duration::from_millis(500), Duration::from_millis(100))
+        .on_click(|_, data: &mut u32, _| *data += 1);
+
+    Harness::create_simple(0u32, button, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        harness.event(Event::MouseMove(move_mouse((5., 5.))));
+        harness.event(Event::MouseDown(left_button((5., 5.))));
+        assert_eq!(*harness.data(), 1);
+        harness.event(Event::MouseUp(left_button((5., 5.))));
+        assert_eq!(*harness.data(), 1);
+    });
+}
+
+#[test]
+fn slider_steps_with_keyboard() {
+    let slider = Slider::new().with_range(0., 10.).with_step(2.);
+    let key = |key: KbKey| Event::KeyDown(KeyEvent::for_test(RawMods::None, key));
+
+    Harness::create_simple(3.0, slider, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        harness.event(Event::MouseDown(left_button((50., 5.))));
+        harness.event(Event::MouseUp(left_button((50., 5.))));
+        assert_eq!(*harness.data() % 2.0, 0.0);
+
+        harness.event(key(KbKey::Home));
+        assert_eq!(*harness.data(), 0.0);
+        harness.event(key(KbKey::ArrowRight));
+        assert_eq!(*harness.data(), 2.0);
+        harness.event(key(KbKey::PageUp));
+        assert_eq!(*harness.data(), 10.0);
+        harness.event(key(KbKey::ArrowDown));
+        assert_eq!(*harness.data(), 8.0);
+    });
+}
+
+#[test]
+fn multiline_textbox_submits_on_hotkey() {
+    let record = Recording::default();
+    let widget = Flex::row().with_child(TextBox::multiline()).record(&record);
+    let submit_mods = if cfg!(target_os = "macos") {
+        RawMods::Meta
+    } else {
+        RawMods::Ctrl
+    };
+    let submitted = |rec: &Recording| {
+        rec.drain().any(|r| {
+            matches!(r, Record::E(Event::Notification(cmd)) if cmd.is(text::TextComponent::RETURN))
+        })
+    };
+
+    Harness::create_simple(String::new(), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        harness.event(Event::MouseDown(left_button((10., 5.))));
+        harness.event(Event::MouseUp(left_button((10., 5.))));
+        record.clear();
+
+        harness.key_down(KeyEvent::for_test(RawMods::None, "a"));
+        harness.key_down(KeyEvent::for_test(RawMods::None, KbKey::Enter));
+        harness.key_down(KeyEvent::for_test(RawMods::None, "b"));
+        assert_eq!(harness.data(), "a\nb");
+        assert!(!submitted(&record));
+
+        harness.key_down(KeyEvent::for_test(submit_mods, KbKey::Enter));
+        assert_eq!(harness.data(), "a\nb");
+        assert!(submitted(&record));
+    });
+}
+
+#[test]
+fn range_slider_picks_movable_knob_when_overlapping() {
+    Harness::create_simple((1.0, 1.0), RangeSlider::new(), |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        harness.paint();
+
+        // both knobs are at the far right; only the lower one can move left.
+        harness.event(Event::MouseDown(left_button((91., 9.))));
+        harness.event(Event::MouseMove(left_button((50., 9.))));
+        harness.event(Event::MouseUp(left_button((50., 9.))));
+        let (low, high) = *harness.data();
+        assert!(low < 1.0);
+        assert_eq!(high, 1.0);
+    });
+}
+
+#[test]
+fn progress_bar_completes_once() {
+    const SET_PROGRESS: Selector<f64> = Selector::new("druid-tests.set-progress");
+
+    struct SetProgress;
+
+    impl<W: Widget<f64>> Controller<f64, W> for SetProgress {
+        fn event(
+            &mut self,
+            child: &mut W,
+            ctx: &mut EventCtx,
+            event: &Event,
+            data: &mut f64,
+            env: &Env,
+        ) {
+            if let Event::Command(cmd) = event {
+                if let Some(progress) = cmd.get(SET_PROGRESS) {
+                    *data = *progress;
+                }
+            }
+            child.event(ctx, event, data, env)
+        }
+    }
+
+    let completions = Rc::new(Cell::new(0));
+    let completions_clone = completions.clone();
+    let bar = ProgressBar::new()
+        .on_complete(move |_, _| completions_clone.set(completions_clone.get() + 1))
+        .controller(SetProgress);
+
+    Harness::create_simple(0.0, bar, |harness| {
+        harness.send_initial_events();
+        harness.submit_command(SET_PROGRESS.with(0.5));
+        assert_eq!(completions.get(), 0);
+        harness.submit_command(SET_PROGRESS.with(1.0));
+        assert_eq!(completions.get(), 1);
+        harness.submit_command(SET_PROGRESS.with(1.5));
+        assert_eq!(completions.get(), 1);
+        harness.submit_command(SET_PROGRESS.with(0.2));
+        harness.submit_command(SET_PROGRESS.with(1.0));
+        assert_eq!(completions.get(), 2);
+    });
+}
+
+#[test]
+fn drag_reports_cumulative_delta_after_slop() {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let events2 = events.clone();
+    let widget = SizedBox::empty()
+        .width(100.)
+        .height(100.)
+        .on_drag(move |_, drag, _: &mut (), _| events2.borrow_mut().push(drag));
+
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        harness.event(Event::MouseDown(left_button((10., 10.))));
+        harness.event(Event::MouseMove(move_mouse((12., 10.))));
+        assert!(events.borrow().is_empty());
+
+        harness.event(Event::MouseMove(move_mouse((30., 10.))));
+        harness.event(Event::MouseMove(move_mouse((150., 20.))));
+        harness.event(Event::MouseUp(left_button((150., 20.))));
+        assert_eq!(
+            *events.borrow(),
+            vec![
+                DragEvent::Started,
+                DragEvent::Moved(Vec2::new(20., 0.)),
+                DragEvent::Moved(Vec2::new(140., 10.)),
+                DragEvent::Ended(Vec2::new(140., 10.)),
+            ]
+        );
+    });
+}
+
+#[test]
+fn tab_index_orders_focus_chain() {
+    const FOCUS_NEXT: Selector = Selector::new("druid-tests.focus-next");
+
+    let focusable = |id: WidgetId| {
+        ModularWidget::new(())
+            .lifecycle_fn(|_, ctx, event, _, _| {
+                if let LifeCycle::BuildFocusChain = event {
+                    ctx.register_for_focus();
+                }
+            })
+            .event_fn(|_, ctx, event, _, _| {
+                if let Event::Command(cmd) = event {
+                    if cmd.is(FOCUS_NEXT) {
+                        ctx.focus_next();
+                    }
+                }
+            })
+            .with_id(id)
+    };
+
+    let (id_0, id_1, id_2) = (WidgetId::next(), WidgetId::next(), WidgetId::next());
+    let widget = Flex::row()
+        .with_child(focusable(id_0))
+        .with_child(focusable(id_1))
+        .with_child(focusable(id_2).tab_index(-1));
+
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        assert_eq!(harness.window().focus, None);
+
+        harness.submit_command(commands::FOCUS_WIDGET.to(id_0));
+        assert_eq!(harness.window().focus, Some(id_0));
+
+        // the unfocused widget can still move focus along the chain.
+        harness.submit_command(FOCUS_NEXT.to(id_2));
+        assert_eq!(harness.window().focus, Some(id_1));
+        harness.submit_command(FOCUS_NEXT.to(id_2));
+        assert_eq!(harness.window().focus, Some(id_2));
+        harness.submit_command(FOCUS_NEXT.to(id_2));
+        assert_eq!(harness.window().focus, Some(id_0));
+    });
+}
+
+#[test]
+fn scope_syncs_state_both_ways() {
+    #[derive(Clone, Data, Lens)]
+    struct ScopeState {
+        outer: u32,
+        local: u32,
+    }
+
+    const BUMP_LOCAL: Selector = Selector::new("druid-tests.bump-local");
+    const BUMP_OUTER: Selector = Selector::new("druid-tests.bump-outer");
+    const SET_OUTER: Selector<u32> = Selector::new("druid-tests.set-outer");
+
+    struct SetOuter;
+
+    impl<W: Widget<u32>> Controller<u32, W> for SetOuter {
+        fn event(
+            &mut self,
+            child: &mut W,
+            ctx: &mut EventCtx,
+            event: &Event,
+            data: &mut u32,
+            env: &Env,
+        ) {
+            if let Event::Command(cmd) = event {
+                if let Some(value) = cmd.get(SET_OUTER) {
+                    *data = *value;
+                }
+            }
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    let updates: Rc<RefCell<Vec<(u32, u32)>>> = Default::default();
+    let id = WidgetId::next();
+    let inner = ModularWidget::new(updates.clone())
+        .event_fn(|_, _, event, data: &mut ScopeState, _| {
+            if let Event::Command(cmd) = event {
+                if cmd.is(BUMP_LOCAL) {
+                    data.local += 1;
+                } else if cmd.is(BUMP_OUTER) {
+                    data.outer += 10;
+                }
+            }
+        })
+        .update_fn(|updates, _, _, data, _| updates.borrow_mut().push((data.outer, data.local)))
+        .with_id(id);
+    let scope = Scope::from_lens(
+        |outer| ScopeState { outer, local: 0 },
+        ScopeState::outer,
+        inner,
+    )
+    .controller(SetOuter);
+
+    Harness::create_simple(1u32, scope, |harness| {
+        harness.send_initial_events();
+        updates.borrow_mut().clear();
+
+        // a change to the private part of the state reaches the inner widget,
+        // but not the outer data.
+        harness.submit_command(BUMP_LOCAL.to(id));
+        assert_eq!(*harness.data(), 1);
+        assert_eq!(updates.borrow().last(), Some(&(1, 1)));
+
+        // a change to the shared part is written back.
+        harness.submit_command(BUMP_OUTER.to(id));
+        assert_eq!(*harness.data(), 11);
+        assert_eq!(updates.borrow().last(), Some(&(11, 1)));
+
+        // and a change from outside is read in, keeping the private part.
+        harness.submit_command(SET_OUTER.with(5));
+        assert_eq!(*harness.data(), 5);
+        assert_eq!(updates.borrow().last(), Some(&(5, 1)));
+
+        // an event that changes nothing doesn't cause any updates.
+        updates.borrow_mut().clear();
+        harness.submit_command(Selector::NOOP.to(id));
+        assert!(updates.borrow().is_empty());
+    });
+}
+
+#[test]
+fn maybe_rebuilds_only_when_variant_changes() {
+    const SET: Selector<Vec<Option<u32>>> = Selector::new("druid-tests.set-option");
+
+    struct SetOption;
+
+    impl<W: Widget<Option<u32>>> Controller<Option<u32>, W> for SetOption {
+        fn event(
+            &mut self,
+            child: &mut W,
+            ctx: &mut EventCtx,
+            event: &Event,
+            data: &mut Option<u32>,
+            env: &Env,
+        ) {
+            if let Event::Command(cmd) = event {
+                if let Some(values) = cmd.get(SET) {
+                    for value in values {
+                        *data = *value;
+                    }
+                }
+            }
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    let added = Rc::new(Cell::new(0));
+    let added2 = added.clone();
+    let maybe = Maybe::or_empty(move || {
+        ModularWidget::new(added2.clone()).lifecycle_fn(|added, _, event, _: &u32, _| {
+            if let LifeCycle::WidgetAdded = event {
+                added.set(added.get() + 1);
+            }
+        })
+    })
+    .controller(SetOption);
+
+    Harness::create_simple(Some(1), maybe, |harness| {
+        harness.send_initial_events();
+        assert_eq!(added.get(), 1);
+
+        harness.submit_command(SET.with(vec![None]));
+        harness.submit_command(SET.with(vec![Some(2)]));
+        assert_eq!(added.get(), 2);
+
+        // flapping between variants in one event keeps the live widget.
+        harness.submit_command(SET.with(vec![None, Some(3)]));
+        assert_eq!(*harness.data(), Some(3));
+        assert_eq!(added.get(), 2);
+    });
+}
+
+#[test]
+fn either_skips_hidden_branch() {
+    const SET: Selector<(bool, u32)> = Selector::new("druid-tests.set-either");
+
+    struct Set;
+
+    impl<W: Widget<(bool, u32)>> Controller<(bool, u32), W> for Set {
+        fn event(
+            &mut self,
+            child: &mut W,
+            ctx: &mut EventCtx,
+            event: &Event,
+            data: &mut (bool, u32),
+            env: &Env,
+        ) {
+            if let Event::Command(cmd) = event {
+                if let Some(value) = cmd.get(SET) {
+                    *data = *value;
+                }
+            }
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    // (updates, layouts, paints, last value laid out)
+    let counts: Rc<Cell<(u32, u32, u32, u32)>> = Default::default();
+    let hidden = ModularWidget::new(counts.clone())
+        .update_fn(|counts, _, _, _, _| {
+            let (u, l, p, v) = counts.get();
+            counts.set((u + 1, l, p, v));
+        })
+        .layout_fn(|counts, _, bc, data: &(bool, u32), _| {
+            let (u, l, p, _) = counts.get();
+            counts.set((u, l + 1, p, data.1));
+            bc.max()
+        })
+        .paint_fn(|counts, _, _, _| {
+            let (u, l, p, v) = counts.get();
+            counts.set((u, l, p + 1, v));
+        });
+    let focusable_id = WidgetId::next();
+    let shown = ModularWidget::new(())
+        .lifecycle_fn(|_, ctx, event, _, _| {
+            if let LifeCycle::BuildFocusChain = event {
+                ctx.register_for_focus();
+            }
+        })
+        .with_id(focusable_id);
+    let either = Either::new(|data: &(bool, u32), _| data.0, shown, hidden).controller(Set);
+
+    Harness::create_simple((true, 0), either, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        harness.paint();
+        harness.submit_command(commands::FOCUS_WIDGET.to(focusable_id));
+        assert_eq!(harness.window().focus, Some(focusable_id));
+
+        harness.submit_command(SET.with((true, 5)));
+        harness.just_layout();
+        harness.paint();
+        assert_eq!(counts.get(), (0, 0, 0, 0));
+
+        harness.submit_command(SET.with((false, 5)));
+        harness.just_layout();
+        harness.paint();
+        let (updates, layouts, paints, value) = counts.get();
+        assert!(updates > 0 && layouts > 0 && paints > 0);
+        assert_eq!(value, 5);
+        assert_eq!(harness.window().focus, None);
+    });
+}
+
+#[test]
+fn rounded_hit_test_ignores_corners() {
+    let presses = Rc::new(Cell::new(0));
+    let child = ModularWidget::new(presses.clone()).event_fn(|presses, _, event, _: &mut (), _| {
+        if let Event::MouseDown(_) = event {
+            presses.set(presses.get() + 1);
+        }
+    });
+    let container = Container::new(child)
+        .rounded((20., 0., 0., 0.))
+        .clip_content(true)
+        .rounded_hit_test(true);
+
+    Harness::create_simple((), container, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        harness.event(Event::MouseDown(left_button((2., 2.))));
+        harness.event(Event::MouseUp(left_button((2., 2.))));
+        assert_eq!(presses.get(), 0);
+
+        // only the top left corner is rounded.
+        harness.event(Event::MouseDown(left_button((98., 2.))));
+        harness.event(Event::MouseUp(left_button((98., 2.))));
+        assert_eq!(presses.get(), 1);
+    });
+}
+
+#[test]
+fn per_side_border_layout() {
+    let child_id = WidgetId::next();
+    let container_id = WidgetId::next();
+    let child = ModularWidget::<(), ()>::new(()).with_id(child_id);
+    let border = BorderStyle::default()
+        .left(Color::WHITE, 2.0)
+        .bottom(Color::BLACK, 4.0);
+    let widget = Container::new(child)
+        .border_style(border)
+        .with_id(container_id)
+        .center();
+
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        let container = harness.get_state(container_id).layout_rect();
+        let child = harness.get_state(child_id).layout_rect();
+        assert_eq!(container.size(), Size::new(102., 104.));
+        assert_eq!(child.origin(), Point::new(2., 0.));
+        // painting four different sides shouldn't panic.
+        harness.paint();
+    });
+}
+
+#[test]
+fn shadow_extends_paint_insets() {
+    let id = WidgetId::next();
+    let widget = SizedBox::empty()
+        .width(50.)
+        .height(50.)
+        .shadow(4.0, (0., 6.), Color::BLACK)
+        .with_id(id)
+        .center();
+
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        let state = harness.get_state(id);
+        let (layout, paint) = (state.layout_rect(), state.paint_rect());
+        // the shadow is moved down, so it reaches further below than above.
+        assert!(paint.y1 - layout.y1 > layout.y0 - paint.y0);
+        assert!(layout.y0 - paint.y0 > 0.);
+        harness.paint();
+    });
+}
+
+#[test]
+fn flex_paints_children_by_z_index() {
+    let painted = Rc::new(RefCell::new(Vec::new()));
+    let child = |n: u32| {
+        let painted = painted.clone();
+        ModularWidget::new(()).paint_fn(move |_, _, _, _| painted.borrow_mut().push(n))
+    };
+
+    let widget = Flex::row()
+        .with_child(child(0))
+        .with_child(child(1))
+        .with_z_index(1)
+        .with_child(child(2))
+        .with_child(child(3))
+        .with_z_index(-1);
+
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        harness.paint();
+    });
+    assert_eq!(*painted.borrow(), vec![3, 0, 2, 1]);
+}
+
+#[test]
+fn z_stack_hit_tests_top_layer() {
+    let [base, middle, bottom] = widget_ids();
+
+    let widget = ZStack::new(SizedBox::empty().width(200.).height(200.).with_id(base))
+        .with_centered_child(SizedBox::empty().width(100.).height(100.).with_id(middle))
+        // added last, but below the middle layer
+        .with_z_indexed_child(
+            SizedBox::empty().width(50.).height(50.).with_id(bottom),
+            UnitPoint::CENTER,
+            -1,
+        );
+
+    let widget = Align::new(UnitPoint::TOP_LEFT, widget);
+
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        assert_eq!(
+            harness.get_state(middle).layout_rect(),
+            Rect::new(50., 50., 150., 150.)
+        );
+
+        harness.event(Event::MouseMove(move_mouse((100., 100.))));
+        assert!(harness.get_state(middle).is_hot);
+        assert!(!harness.get_state(bottom).is_hot);
+        assert!(!harness.get_state(base).is_hot);
+
+        harness.event(Event::MouseMove(move_mouse((10., 10.))));
+        assert!(!harness.get_state(middle).is_hot);
+        assert!(harness.get_state(base).is_hot);
+    });
+}
+
+#[test]
+fn command_to_unknown_widget_is_dropped() {
+    const POKE: Selector = Selector::new("druid-tests.poke");
+
+    let [id, unknown] = widget_ids();
+    let rec = Recording::default();
+    let widget = Flex::column()
+        .with_child(SizedBox::empty().record(&rec).with_id(id))
+        .with_child(SizedBox::empty());
+
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        rec.clear();
+
+        harness.submit_command(POKE.to(unknown));
+        assert!(!rec
+            .drain()
+            .any(|rec| matches!(rec, Record::E(Event::Command(_)))));
+
+        harness.submit_command(POKE.to(id));
+        assert!(rec
+            .drain()
+            .any(|rec| matches!(rec, Record::E(Event::Command(cmd)) if cmd.is(POKE))));
+    });
+}
+
+/// The body of a tab, which records when it is built and when it gets mouse
+/// events, and counts the clicks it gets. A click also focuses it.
+fn recording_tab_body(
+    n: usize,
+    built: &Rc<RefCell<Vec<usize>>>,
+    events: &Rc<RefCell<Vec<(usize, u32)>>>,
+) -> impl Widget<()> {
+    let built = built.clone();
+    let events = events.clone();
+    ModularWidget::new(0u32)
+        .event_fn(move |clicks, ctx, event, _, _| match event {
+            Event::MouseDown(_) => {
+                *clicks += 1;
+                ctx.request_focus();
+                events.borrow_mut().push((n, *clicks));
+            }
+            Event::MouseMove(_) => events.borrow_mut().push((n, *clicks)),
+            _ => (),
+        })
+        .lifecycle_fn(move |_, ctx, event, _, _| {
+            if let LifeCycle::WidgetAdded = event {
+                ctx.register_for_focus();
+                built.borrow_mut().push(n);
+            }
+        })
+        .layout_fn(|_, _, bc, _, _| bc.max())
+}
+
+#[test]
+fn tabs_cycle_lazy_bodies_with_ctrl_tab() {
+    /// The tab that gets mouse events, and how many clicks it has had.
+    fn shown(
+        harness: &mut Harness<()>,
+        events: &RefCell<Vec<(usize, u32)>>,
+    ) -> Option<(usize, u32)> {
+        harness.just_layout();
+        events.borrow_mut().clear();
+        harness.event(Event::MouseMove(move_mouse((200., 200.))));
+        events.borrow().last().copied()
+    }
+
+    let built: Rc<RefCell<Vec<usize>>> = Default::default();
+    let events: Rc<RefCell<Vec<(usize, u32)>>> = Default::default();
+    let tabs = Tabs::new()
+        .with_lazy_bodies(true)
+        .with_tab("Zero", recording_tab_body(0, &built, &events))
+        .with_tab("One", recording_tab_body(1, &built, &events))
+        .with_tab("Two", recording_tab_body(2, &built, &events));
+    let body = (200., 200.);
+
+    Harness::create_simple((), tabs, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        // only the selected body is built
+        assert_eq!(*built.borrow(), vec![0]);
+
+        harness.event(Event::MouseDown(left_button(body)));
+        harness.event(Event::MouseUp(left_button(body)));
+        harness.event(Event::MouseDown(left_button(body)));
+        harness.event(Event::MouseUp(left_button(body)));
+        assert_eq!(shown(harness, &events), Some((0, 2)));
+
+        harness.key_down(KeyEvent::for_test(RawMods::Ctrl, KbKey::Tab));
+        assert_eq!(shown(harness, &events), Some((1, 0)));
+        assert_eq!(*built.borrow(), vec![0, 1]);
+        harness.key_down(KeyEvent::for_test(RawMods::Ctrl, KbKey::Tab));
+        assert_eq!(shown(harness, &events), Some((2, 0)));
+        // the last tab wraps around to the first, which kept its state
+        harness.key_down(KeyEvent::for_test(RawMods::Ctrl, KbKey::Tab));
+        assert_eq!(shown(harness, &events), Some((0, 2)));
+
+        // and backwards, the first wraps around to the last
+        harness.key_down(KeyEvent::for_test(RawMods::CtrlShift, KbKey::Tab));
+        assert_eq!(shown(harness, &events), Some((2, 0)));
+        harness.key_down(KeyEvent::for_test(RawMods::CtrlShift, KbKey::Tab));
+        assert_eq!(shown(harness, &events), Some((1, 0)));
+
+        // no body was built twice
+        assert_eq!(*built.borrow(), vec![0, 1, 2]);
+    });
+}
+
+#[test]
+fn tooltip_waits_shows_and_hides() {
+    let record = Recording::default();
+    let widget = SizedBox::empty()
+        .width(100.)
+        .height(100.)
+        .tooltip("Hint")
+        .center()
+        .record(&record);
+    let opened = |rec: &Recording| {
+        rec.drain()
+            .filter(
+                |r| matches!(r, Record::E(Event::Command(cmd)) if cmd.is(commands::NEW_SUB_WINDOW)),
+            )
+            .count()
+    };
+    let closed = |rec: &Recording| {
+        rec.drain()
+            .filter(
+                |r| matches!(r, Record::E(Event::Command(cmd)) if cmd.is(commands::CLOSE_WINDOW)),
+            )
+            .count()
+    };
+
+    Harness::create_simple((), widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+
+        // resting on the widget starts the delay
+        harness.event(Event::MouseMove(move_mouse((200., 200.))));
+        assert_eq!(harness.window().timers.len(), 1);
+        record.clear();
+        harness.fire_timers();
+        assert_eq!(opened(&record), 1);
+
+        // moving hides it, and starts the delay again
+        harness.event(Event::MouseMove(move_mouse((210., 200.))));
+        assert_eq!(closed(&record), 1);
+        assert_eq!(harness.window().timers.len(), 1);
+        harness.fire_timers();
+        assert_eq!(opened(&record), 1);
+
+        // a click hides it, and it isn't shown again until the mouse moves
+        harness.event(Event::MouseDown(left_button((210., 200.))));
+        assert_eq!(closed(&record), 1);
+        harness.fire_timers();
+        assert_eq!(opened(&record), 0);
+        harness.event(Event::MouseUp(left_button((210., 200.))));
+
+        // leaving the widget hides it
+        harness.event(Event::MouseMove(move_mouse((200., 200.))));
+        harness.fire_timers();
+        assert_eq!(opened(&record), 1);
+        harness.event(Event::MouseMove(move_mouse((10., 10.))));
+        assert_eq!(closed(&record), 1);
+        harness.fire_timers();
+        assert_eq!(opened(&record), 0);
+    });
+}
@@ -0,0 +1,421 @@
+// Copyright 2021 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests related to scrolling.
+
+use test_env_log::test;
+
+use super::*;
+use crate::scroll_component::{ScrollComponent, ScrollbarsEnabled};
+
+/// A vertical `Scroll` around a 2000px tall child, which records its offset.
+fn tall_scroll(offset: &Rc<Cell<Vec2>>) -> impl Widget<()> {
+    let offset = offset.clone();
+    Scroll::new(SizedBox::empty().width(400.).height(2000.))
+        .vertical()
+        .on_scroll(move |_, port, _, _| offset.set(port.view_origin.to_vec2()))
+}
+
+#[test]
+fn drag_thumb_outside_widget() {
+    let offset: Rc<Cell<Vec2>> = Default::default();
+
+    Harness::create_simple((), tall_scroll(&offset), |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+
+        // With the default theme the thumb is 80px long, and it can travel 316px
+        // (the 400px viewport, minus the thumb, minus 2px of padding on each end)
+        // to cover the 1600px of scrollable range.
+        harness.event(Event::MouseDown(left_button((394., 12.))));
+        harness.event(Event::MouseMove(left_button((1000., 170.))));
+        assert_eq!(offset.get(), Vec2::new(0., 800.));
+
+        harness.event(Event::MouseMove(left_button((-500., 91.))));
+        assert_eq!(offset.get(), Vec2::new(0., 400.));
+
+        // moving past the end of the track clamps
+        harness.event(Event::MouseMove(left_button((394., 5000.))));
+        assert_eq!(offset.get(), Vec2::new(0., 1600.));
+
+        // and the mapping is still linear on the way back
+        harness.event(Event::MouseMove(left_button((-2000., 249.))));
+        assert_eq!(offset.get(), Vec2::new(0., 1200.));
+
+        harness.event(Event::MouseUp(left_button((-2000., 249.))));
+        harness.event(Event::MouseMove(left_button((-2000., 12.))));
+        assert_eq!(offset.get(), Vec2::new(0., 1200.));
+    });
+}
+
+#[test]
+fn drag_thumb_while_content_grows() {
+    const GROW: Selector = Selector::new("druid-tests.scroll-grow-content");
+    let port: Rc<Cell<Viewport>> = Default::default();
+    let port_clone = port.clone();
+
+    let content = ModularWidget::new(2000.)
+        .event_fn(|height, ctx, event, _, _| {
+            if matches!(event, Event::Command(cmd) if cmd.is(GROW)) {
+                *height = 4000.;
+                ctx.request_layout();
+            }
+        })
+        .layout_fn(|height, _, _, _, _| Size::new(400., *height));
+    let scroll = Scroll::new(content)
+        .vertical()
+        .on_scroll(move |_, new_port, _, _| port_clone.set(*new_port));
+
+    // where the pointer sits along the thumb, in pixels
+    let grab_point = |port: Viewport| {
+        let mut scroll_component = ScrollComponent::new();
+        scroll_component.enabled = ScrollbarsEnabled::Vertical;
+        let bounds = scroll_component
+            .calc_vertical_bar_bounds(&port, &Env::default())
+            .unwrap()
+            - port.view_origin.to_vec2();
+        (bounds.y0, bounds.height())
+    };
+
+    Harness::create_simple((), scroll, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+
+        // grab the thumb an eighth of the way along its length
+        harness.event(Event::MouseDown(left_button((394., 12.))));
+        harness.event(Event::MouseMove(left_button((394., 170.))));
+        let (thumb_start, thumb_length) = grab_point(port.get());
+        assert!((thumb_start + thumb_length / 8. - 170.).abs() <= 1.);
+
+        harness.submit_command(GROW);
+        harness.just_layout();
+        harness.event(Event::MouseMove(left_button((394., 170.))));
+        assert_eq!(port.get().content_size.height, 4000.);
+        let (thumb_start, thumb_length) = grab_point(port.get());
+        assert!((thumb_start + thumb_length / 8. - 170.).abs() <= 1.);
+    });
+}
+
+#[test]
+fn initial_offset_is_clamped() {
+    let offset: Rc<Cell<Vec2>> = Default::default();
+    let offset_clone = offset.clone();
+    let scroll = Scroll::new(SizedBox::empty().width(400.).height(2000.))
+        .vertical()
+        .with_initial_offset(Vec2::new(50., 5000.))
+        .on_scroll(move |_, port, _, _| offset_clone.set(port.view_origin.to_vec2()));
+
+    Harness::create_simple((), scroll, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        // offset changes during layout are reported in the following event pass
+        harness.event(Event::MouseMove(move_mouse((10., 10.))));
+        assert_eq!(offset.get(), Vec2::new(0., 1600.));
+    });
+}
+
+#[test]
+fn set_offset_is_not_reapplied_after_scrolling() {
+    const SET_OFFSET: Selector = Selector::new("druid-tests.scroll-set-offset");
+
+    struct SetOffset;
+    impl<W: Widget<()>> Controller<(), Scroll<(), W>> for SetOffset {
+        fn event(
+            &mut self,
+            child: &mut Scroll<(), W>,
+            ctx: &mut EventCtx,
+            event: &Event,
+            data: &mut (),
+            env: &Env,
+        ) {
+            if matches!(event, Event::Command(cmd) if cmd.is(SET_OFFSET)) {
+                child.set_offset(Vec2::new(0., 500.));
+                ctx.request_layout();
+            }
+            child.event(ctx, event, data, env)
+        }
+    }
+
+    let offset: Rc<Cell<Vec2>> = Default::default();
+    let offset_clone = offset.clone();
+    let scroll = Scroll::new(SizedBox::empty().width(400.).height(2000.))
+        .vertical()
+        .on_scroll(move |_, port, _, _| offset_clone.set(port.view_origin.to_vec2()))
+        .controller(SetOffset);
+
+    Harness::create_simple((), scroll, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        harness.submit_command(SET_OFFSET);
+        harness.event(Event::MouseMove(move_mouse((10., 10.))));
+        harness.event(Event::Wheel(scroll_mouse((10., 10.), (0., 300.))));
+        harness.just_layout();
+        harness.event(Event::MouseMove(move_mouse((10., 10.))));
+        assert_eq!(offset.get(), Vec2::new(0., 800.));
+    });
+}
+
+#[test]
+fn momentum_fling_and_cancel() {
+    fn flick(harness: &mut Harness<()>) {
+        for _ in 0..3 {
+            harness.event(Event::Wheel(scroll_mouse((10., 10.), (0., 20.))));
+        }
+        // the wheel input pauses, and the fling starts
+        harness.fire_timers();
+    }
+
+    let offset: Rc<Cell<Vec2>> = Default::default();
+    let offset_clone = offset.clone();
+    let scroll = Scroll::new(SizedBox::empty().width(400.).height(2000.))
+        .vertical()
+        .with_momentum(true)
+        .on_scroll(move |_, port, _, _| offset_clone.set(port.view_origin.to_vec2()));
+
+    Harness::create_simple((), scroll, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        harness.event(Event::MouseMove(move_mouse((10., 10.))));
+        flick(harness);
+        assert_eq!(offset.get(), Vec2::new(0., 60.));
+        harness.event(Event::AnimFrame(16_000_000));
+        let flung = offset.get().y;
+        assert!(flung > 60.);
+        harness.event(Event::AnimFrame(16_000_000));
+        assert!(offset.get().y > flung);
+
+        // a click stops the fling
+        harness.event(Event::MouseDown(left_button((10., 10.))));
+        let stopped = offset.get();
+        harness.event(Event::AnimFrame(16_000_000));
+        assert_eq!(offset.get(), stopped);
+        harness.event(Event::MouseUp(left_button((10., 10.))));
+
+        // and so does more wheel input
+        flick(harness);
+        harness.event(Event::AnimFrame(16_000_000));
+        harness.event(Event::Wheel(scroll_mouse((10., 10.), (0., 1.))));
+        let stopped = offset.get();
+        harness.event(Event::AnimFrame(16_000_000));
+        assert_eq!(offset.get(), stopped);
+    });
+}
+
+#[test]
+fn stick_to_bottom_and_anchoring() {
+    const GROW: Selector = Selector::new("druid-tests.scroll-grow-content");
+    let offset: Rc<Cell<Vec2>> = Default::default();
+    let offset_clone = offset.clone();
+
+    let content = ModularWidget::new(2000.)
+        .event_fn(|height, ctx, event, _, _| {
+            if matches!(event, Event::Command(cmd) if cmd.is(GROW)) {
+                *height += 2000.;
+                ctx.request_layout();
+            }
+        })
+        .layout_fn(|height, _, _, _, _| Size::new(400., *height));
+    let scroll = Scroll::new(content)
+        .vertical()
+        .with_stick_to_bottom(true)
+        .with_content_anchoring(true)
+        .on_scroll(move |_, port, _, _| offset_clone.set(port.view_origin.to_vec2()));
+
+    fn grow(harness: &mut Harness<()>) {
+        harness.submit_command(GROW);
+        harness.just_layout();
+        harness.event(Event::MouseMove(move_mouse((10., 10.))));
+    }
+
+    Harness::create_simple((), scroll, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        harness.event(Event::MouseMove(move_mouse((10., 10.))));
+        assert_eq!(offset.get(), Vec2::new(0., 1600.));
+
+        // at the bottom, we stay at the bottom
+        grow(harness);
+        assert_eq!(offset.get(), Vec2::new(0., 3600.));
+
+        // otherwise, we keep our distance from the bottom
+        harness.event(Event::Wheel(scroll_mouse((10., 10.), (0., -600.))));
+        assert_eq!(offset.get(), Vec2::new(0., 3000.));
+        grow(harness);
+        assert_eq!(offset.get(), Vec2::new(0., 5000.));
+    });
+}
+
+#[test]
+fn nested_scroll_hands_off_wheel() {
+    let inner_offset: Rc<Cell<Vec2>> = Default::default();
+    let outer_offset: Rc<Cell<Vec2>> = Default::default();
+    let inner_clone = inner_offset.clone();
+    let outer_clone = outer_offset.clone();
+
+    let inner = Scroll::new(SizedBox::empty().width(400.).height(500.))
+        .vertical()
+        .on_scroll(move |_, port, _, _| inner_clone.set(port.view_origin.to_vec2()))
+        .fix_height(200.);
+    let outer = Scroll::new(
+        Flex::column()
+            .with_child(inner)
+            .with_child(SizedBox::empty().width(400.).height(1000.)),
+    )
+    .vertical()
+    .on_scroll(move |_, port, _, _| outer_clone.set(port.view_origin.to_vec2()));
+
+    Harness::create_simple((), outer, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+
+        // the inner scroll can only move by 300, the outer one takes the rest
+        harness.event(Event::Wheel(scroll_mouse((10., 10.), (0., 500.))));
+        assert_eq!(inner_offset.get(), Vec2::new(0., 300.));
+        assert_eq!(outer_offset.get(), Vec2::new(0., 200.));
+    });
+}
+
+#[test]
+fn wheel_and_drag_both_axes() {
+    let offset: Rc<Cell<Vec2>> = Default::default();
+    let offset_clone = offset.clone();
+    let scroll = Scroll::new(SizedBox::empty().width(800.).height(800.))
+        .on_scroll(move |_, port, _, _| offset_clone.set(port.view_origin.to_vec2()));
+
+    Harness::create_simple((), scroll, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+
+        harness.event(Event::Wheel(scroll_mouse((10., 10.), (30., 50.))));
+        assert_eq!(offset.get(), Vec2::new(30., 50.));
+
+        // Both thumbs are 200px long and travel 188px, as each leaves room for the other.
+        // Grab the horizontal thumb in its middle, and move it to the middle of the track.
+        harness.event(Event::MouseDown(left_button((117., 394.))));
+        harness.event(Event::MouseMove(left_button((196., 394.))));
+        assert_eq!(offset.get(), Vec2::new(200., 50.));
+        harness.event(Event::MouseUp(left_button((196., 394.))));
+
+        harness.event(Event::Wheel(scroll_mouse((10., 10.), (-500., 500.))));
+        assert_eq!(offset.get(), Vec2::new(0., 400.));
+    });
+}
+
+#[test]
+fn hidden_scrollbar_passes_events_to_child() {
+    let clicks: Rc<Cell<usize>> = Default::default();
+    let offset: Rc<Cell<Vec2>> = Default::default();
+    let clicks_clone = clicks.clone();
+    let offset_clone = offset.clone();
+
+    let content = ModularWidget::new(())
+        .event_fn(move |_, _, event, _, _| {
+            if let Event::MouseDown(_) = event {
+                clicks_clone.set(clicks_clone.get() + 1);
+            }
+        })
+        .layout_fn(|_, _, _, _, _| Size::new(400., 2000.));
+    let scroll = Scroll::new(content)
+        .vertical()
+        .show_vertical_scrollbar(false)
+        .on_scroll(move |_, port, _, _| offset_clone.set(port.view_origin.to_vec2()));
+
+    Harness::create_simple((), scroll, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+
+        // this is where the thumb would be
+        harness.event(Event::MouseMove(move_mouse((394., 12.))));
+        harness.event(Event::MouseDown(left_button((394., 12.))));
+        assert_eq!(clicks.get(), 1);
+        harness.event(Event::MouseUp(left_button((394., 12.))));
+
+        // the wheel still scrolls
+        harness.event(Event::Wheel(scroll_mouse((394., 12.), (0., 300.))));
+        assert_eq!(offset.get(), Vec2::new(0., 300.));
+    });
+}
+
+#[test]
+fn ctrl_wheel_zooms() {
+    let offset: Rc<Cell<Vec2>> = Default::default();
+    let zoom: Rc<Cell<Option<(Point, Vec2)>>> = Default::default();
+    let zoom_clone = zoom.clone();
+
+    let scroll = tall_scroll(&offset);
+    let zooming = Scroll::new(SizedBox::empty().width(400.).height(2000.))
+        .vertical()
+        .on_scroll({
+            let offset = offset.clone();
+            move |_, port, _, _| offset.set(port.view_origin.to_vec2())
+        })
+        .on_zoom(move |_, _, pos, delta, _| zoom_clone.set(Some((pos, delta))));
+
+    // without a callback, ctrl+wheel scrolls
+    Harness::create_simple((), scroll, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+
+        let mut wheel = scroll_mouse((10., 10.), (0., 300.));
+        wheel.mods = Modifiers::CONTROL;
+        harness.event(Event::Wheel(wheel));
+        assert_eq!(offset.get(), Vec2::new(0., 300.));
+    });
+
+    offset.set(Vec2::ZERO);
+    Harness::create_simple((), zooming, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+
+        harness.event(Event::Wheel(scroll_mouse((10., 10.), (0., 300.))));
+        assert_eq!(offset.get(), Vec2::new(0., 300.));
+        assert_eq!(zoom.get(), None);
+
+        let mut wheel = scroll_mouse((10., 10.), (0., 50.));
+        wheel.mods = Modifiers::CONTROL;
+        harness.event(Event::Wheel(wheel));
+        assert_eq!(
+            zoom.get(),
+            Some((Point::new(10., 310.), Vec2::new(0., 50.)))
+        );
+        assert_eq!(offset.get(), Vec2::new(0., 300.));
+    });
+}
+
+#[test]
+fn snap_to_page_after_drag() {
+    let offset: Rc<Cell<Vec2>> = Default::default();
+    let offset_clone = offset.clone();
+    let scroll = Scroll::new(SizedBox::empty().width(1600.).height(400.))
+        .horizontal()
+        .with_snap_points(SnapBehavior::Interval(400.))
+        .on_scroll(move |_, port, _, _| offset_clone.set(port.view_origin.to_vec2()));
+
+    Harness::create_simple((), scroll, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+
+        // The thumb is 100px long and can travel 296px, for 1200px of scrollable
+        // range; grab it 8px from its start, and drag it 111px.
+        harness.event(Event::MouseDown(left_button((10., 394.))));
+        harness.event(Event::MouseMove(left_button((121., 394.))));
+        assert_eq!(offset.get(), Vec2::new(450., 0.));
+
+        harness.event(Event::MouseUp(left_button((121., 394.))));
+        for _ in 0..100 {
+            harness.event(Event::AnimFrame(16_000_000));
+        }
+        assert_eq!(offset.get(), Vec2::new(400., 0.));
+    });
+}
@@ -225,3 +225,207 @@ impl<T: std::fmt::Display> Default for ParseFormatter<T> {
         ParseFormatter::new()
     }
 }
+
+/// A [`Formatter`] for `f64` values.
+///
+/// Unlike a [`ParseFormatter`], this accepts the partial input that comes up
+/// while a number is typed, such as `-` or `1e`. Values can be displayed with a
+/// fixed number of decimal places.
+///
+/// [`Formatter`]: Formatter
+#[derive(Debug, Clone, Default)]
+pub struct NumberFormatter {
+    precision: Option<usize>,
+}
+
+/// A [`Formatter`] for integer values, optionally limited to a range.
+///
+/// [`Formatter`]: Formatter
+#[derive(Debug, Clone)]
+pub struct IntegerFormatter<T> {
+    min: Option<T>,
+    max: Option<T>,
+}
+
+/// A [`Formatter`] for `String`s, which accepts any text as it is.
+///
+/// [`Formatter`]: Formatter
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IdentityFormatter;
+
+/// The error returned by an [`IntegerFormatter`] for a value outside of its range.
+#[derive(Debug, Clone)]
+pub struct OutOfRangeError {
+    min: Option<String>,
+    max: Option<String>,
+}
+
+impl NumberFormatter {
+    /// Create a new `NumberFormatter`, which displays values as short as
+    /// possible.
+    pub fn new() -> Self {
+        NumberFormatter { precision: None }
+    }
+
+    /// Builder-style method to display values with the given number of decimal
+    /// places.
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+}
+
+impl Formatter<f64> for NumberFormatter {
+    fn format(&self, value: &f64) -> String {
+        match self.precision {
+            Some(precision) => format!("{:.*}", precision, value),
+            None => value.to_string(),
+        }
+    }
+
+    fn validate_partial_input(&self, input: &str, _sel: &Selection) -> Validation {
+        // input such as "-", "1." or "1e-" is valid if it can become a number
+        let completed = format!("{}0", input);
+        match input.parse::<f64>().or_else(|_| completed.parse()) {
+            Ok(_) => Validation::success(),
+            Err(e) => Validation::failure(e),
+        }
+    }
+
+    fn value(&self, input: &str) -> Result<f64, ValidationError> {
+        input.trim().parse().map_err(ValidationError::new)
+    }
+}
+
+impl<T> IntegerFormatter<T> {
+    /// Create a new `IntegerFormatter`, for any value of the type.
+    pub fn new() -> Self {
+        IntegerFormatter {
+            min: None,
+            max: None,
+        }
+    }
+
+    /// Builder-style method to only accept values from `min` to `max`, inclusive.
+    ///
+    /// Input outside of the range can still be typed, since it may be the start
+    /// of a valid value; it is rejected when editing completes.
+    pub fn with_range(mut self, min: T, max: T) -> Self {
+        self.min = Some(min);
+        self.max = Some(max);
+        self
+    }
+}
+
+impl<T> Default for IntegerFormatter<T> {
+    fn default() -> Self {
+        IntegerFormatter::new()
+    }
+}
+
+impl<T> Formatter<T> for IntegerFormatter<T>
+where
+    T: FromStr<Err = std::num::ParseIntError> + std::fmt::Display + PartialOrd,
+{
+    fn format(&self, value: &T) -> String {
+        value.to_string()
+    }
+
+    fn validate_partial_input(&self, input: &str, _sel: &Selection) -> Validation {
+        // a lone minus sign is the start of a negative number, if the type has them
+        let completed = format!("{}1", input);
+        if input.is_empty() || (input == "-" && completed.parse::<T>().is_ok()) {
+            return Validation::success();
+        }
+        match input.parse::<T>() {
+            Ok(_) => Validation::success(),
+            Err(e) => Validation::failure(e),
+        }
+    }
+
+    fn value(&self, input: &str) -> Result<T, ValidationError> {
+        let value: T = input.trim().parse().map_err(ValidationError::new)?;
+        let too_small = self.min.as_ref().map_or(false, |min| value < *min);
+        let too_large = self.max.as_ref().map_or(false, |max| value > *max);
+        if too_small || too_large {
+            return Err(ValidationError::new(OutOfRangeError {
+                min: self.min.as_ref().map(ToString::to_string),
+                max: self.max.as_ref().map(ToString::to_string),
+            }));
+        }
+        Ok(value)
+    }
+}
+
+impl Formatter<String> for IdentityFormatter {
+    fn format(&self, value: &String) -> String {
+        value.clone()
+    }
+
+    fn validate_partial_input(&self, _input: &str, _sel: &Selection) -> Validation {
+        Validation::success()
+    }
+
+    fn value(&self, input: &str) -> Result<String, ValidationError> {
+        Ok(input.to_owned())
+    }
+}
+
+impl std::fmt::Display for OutOfRangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match (&self.min, &self.max) {
+            (Some(min), Some(max)) => write!(f, "value must be from {} to {}", min, max),
+            (Some(min), None) => write!(f, "value must be at least {}", min),
+            (None, Some(max)) => write!(f, "value must be at most {}", max),
+            (None, None) => write!(f, "value is out of range"),
+        }
+    }
+}
+
+impl std::error::Error for OutOfRangeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn number_partial_input() {
+        let fmt = NumberFormatter::new();
+        let sel = Selection::caret(0);
+        for input in &["", "-", "12.", ".5", "1e", "1e-", "-3.25"] {
+            assert!(
+                !fmt.validate_partial_input(input, &sel).is_err(),
+                "{}",
+                input
+            );
+        }
+        for input in &["--", "1.2.", "abc"] {
+            assert!(
+                fmt.validate_partial_input(input, &sel).is_err(),
+                "{}",
+                input
+            );
+        }
+        assert!(fmt.value("-").is_err());
+        assert_eq!(fmt.value("12.").unwrap(), 12.0);
+        assert_eq!(
+            NumberFormatter::new().with_precision(2).format(&1.5),
+            "1.50"
+        );
+    }
+
+    #[test]
+    fn integer_range() {
+        let fmt = IntegerFormatter::<i32>::new().with_range(-5, 50);
+        let sel = Selection::caret(0);
+        assert!(!fmt.validate_partial_input("-", &sel).is_err());
+        assert!(!fmt.validate_partial_input("500", &sel).is_err());
+        assert!(fmt.validate_partial_input("5.", &sel).is_err());
+        assert_eq!(fmt.value("42").unwrap(), 42);
+        assert!(fmt.value("51").is_err());
+        assert!(fmt.value("-6").is_err());
+
+        let unsigned = IntegerFormatter::<u8>::new();
+        assert!(unsigned.validate_partial_input("-", &sel).is_err());
+    }
+}
@@ -19,13 +19,14 @@ use std::ops::Range;
 use std::sync::{Arc, Weak};
 
 use tracing::instrument;
+use unicode_segmentation::UnicodeSegmentation;
 
 use super::{
     EditableText, ImeHandlerRef, ImeInvalidation, InputHandler, Movement, Selection, TextAction,
     TextLayout, TextStorage,
 };
 use crate::kurbo::{Line, Point, Rect, Vec2};
-use crate::piet::TextLayout as _;
+use crate::piet::{HitTestPoint, PietText, TextLayout as _};
 use crate::widget::prelude::*;
 use crate::{text, theme, Cursor, Env, Modifiers, Selector, TextAlignment, UpdateCtx};
 
@@ -80,6 +81,10 @@ pub struct EditSession<T> {
     /// you should avoid doing things like rebuilding this layout manually, or
     /// setting the text directly.
     pub layout: TextLayout<T>,
+    /// The character shown in place of each grapheme, if the text is obscured.
+    obscuring: Option<char>,
+    /// The layout of the masked text, when the text is obscured.
+    mask_layout: Option<TextLayout<T>>,
     /// If the platform modifies the text, this contains the new text;
     /// we update the app `Data` with this text on the next update pass.
     external_text_change: Option<T>,
@@ -283,7 +288,7 @@ impl<T: TextStorage + EditableText> Widget<T> for TextComponent<T> {
                     .unwrap_or(true);
                 if needs_rebuild {
                     self.borrow_mut().layout.set_text(data.clone());
-                    self.borrow_mut().layout.rebuild_if_needed(ctx.text(), env);
+                    self.borrow_mut().rebuild_layouts(ctx.text(), env);
                     self.borrow_mut()
                         .update_pending_invalidation(ImeInvalidation::Reset);
                 }
@@ -368,7 +373,7 @@ impl<T: TextStorage + EditableText> Widget<T> for TextComponent<T> {
                     "ime should never be locked at WidgetAdded"
                 );
                 self.borrow_mut().layout.set_text(data.to_owned());
-                self.borrow_mut().layout.rebuild_if_needed(ctx.text(), env);
+                self.borrow_mut().rebuild_layouts(ctx.text(), env);
             }
             //FIXME: this should happen in the parent too?
             LifeCycle::Internal(crate::InternalLifeCycle::ParentWindowOrigin)
@@ -422,8 +427,8 @@ impl<T: TextStorage + EditableText> Widget<T> for TextComponent<T> {
         }
 
         self.borrow_mut().layout.set_wrap_width(bc.max().width);
-        self.borrow_mut().layout.rebuild_if_needed(ctx.text(), env);
-        let metrics = self.borrow().layout.layout_metrics();
+        self.borrow_mut().rebuild_layouts(ctx.text(), env);
+        let metrics = self.borrow().display_layout().layout_metrics();
         let width = if bc.max().width.is_infinite() || bc.max().width < f64::MAX {
             metrics.trailing_whitespace_width
         } else {
@@ -456,13 +461,15 @@ impl<T: TextStorage + EditableText> Widget<T> for TextComponent<T> {
         let cursor_color = env.get(theme::CURSOR_COLOR);
         let text_offset = Vec2::new(self.borrow().alignment_offset, 0.0);
 
-        let selection = self.borrow().selection();
-        let composition = self.borrow().composition_range();
-        let sel_rects = self.borrow().layout.rects_for_range(selection.range());
+        let session = self.borrow();
+        let layout = session.display_layout();
+        let selection = session.selection();
+        let composition = session.composition_range();
+        let sel_rects = layout.rects_for_range(session.range_to_display(selection.range()));
         if let Some(composition) = composition {
             // I believe selection should always be contained in composition range while composing?
             assert!(composition.start <= selection.anchor && composition.end >= selection.active);
-            let comp_rects = self.borrow().layout.rects_for_range(composition);
+            let comp_rects = layout.rects_for_range(session.range_to_display(composition));
             for region in comp_rects {
                 let y = region.max_y().floor();
                 let line = Line::new((region.min_x(), y), (region.max_x(), y)) + text_offset;
@@ -479,7 +486,7 @@ impl<T: TextStorage + EditableText> Widget<T> for TextComponent<T> {
                 ctx.fill(rounded, &selection_color);
             }
         }
-        self.borrow().layout.draw(ctx, text_offset.to_point());
+        layout.draw(ctx, text_offset.to_point());
     }
 }
 
@@ -524,6 +531,19 @@ impl<T> EditSession<T> {
         self.alignment = alignment;
     }
 
+    /// Set the character that is shown in place of each grapheme, or `None`
+    /// to show the text itself.
+    ///
+    /// The text is unchanged; it is only obscured in the layout, and cannot be
+    /// copied. Selecting a word selects all of the text, since the boundaries
+    /// of words would give away what the text is.
+    pub fn set_text_obscuring(&mut self, mask: Option<char>) {
+        if self.obscuring != mask {
+            self.obscuring = mask;
+            self.mask_layout = None;
+        }
+    }
+
     /// Returns any invalidation action that should be passed to the platform.
     ///
     /// The user of this component *must* check this after calling `update`.
@@ -591,7 +611,12 @@ impl<T: TextStorage + EditableText> EditSession<T> {
     ///
     /// Returns `true` if the clipboard was set, and `false` if not (indicating)
     /// that the selection was empty.)
+    ///
+    /// Obscured text is never put on the clipboard.
     pub fn set_clipboard(&self) -> bool {
+        if self.obscuring.is_some() {
+            return false;
+        }
         if let Some(text) = self
             .layout
             .text()
@@ -612,12 +637,12 @@ impl<T: TextStorage + EditableText> EditSession<T> {
     fn do_action(&mut self, buffer: &mut T, action: TextAction) {
         match action {
             TextAction::Move(movement) => {
-                let sel = text::movement(movement, self.selection, &self.layout, false);
+                let sel = self.movement(movement, false);
                 self.external_selection_change = Some(sel);
                 self.scroll_to_selection_end(false);
             }
             TextAction::MoveSelecting(movement) => {
-                let sel = text::movement(movement, self.selection, &self.layout, true);
+                let sel = self.movement(movement, true);
                 self.external_selection_change = Some(sel);
                 self.scroll_to_selection_end(false);
             }
@@ -627,8 +652,11 @@ impl<T: TextStorage + EditableText> EditSession<T> {
             }
             TextAction::SelectWord => {
                 if self.selection.is_caret() {
-                    let range =
-                        text::movement::word_range_for_pos(buffer.as_str(), self.selection.active);
+                    let range = if self.obscuring.is_some() {
+                        0..buffer.len()
+                    } else {
+                        text::movement::word_range_for_pos(buffer.as_str(), self.selection.active)
+                    };
                     self.external_selection_change = Some(Selection::new(range.start, range.end));
                 }
 
@@ -650,7 +678,7 @@ impl<T: TextStorage + EditableText> EditSession<T> {
                 if movement == Movement::Grapheme(druid_shell::text::Direction::Upstream) {
                     self.backspace(buffer);
                 } else {
-                    let to_delete = text::movement(movement, self.selection, &self.layout, true);
+                    let to_delete = self.movement(movement, true);
                     self.selection = to_delete;
                     self.ime_insert_text(buffer, "")
                 }
@@ -720,7 +748,7 @@ impl<T: TextStorage + EditableText> EditSession<T> {
 
     fn do_mouse_down(&mut self, point: Point, mods: Modifiers, count: u8) {
         let point = point - Vec2::new(self.alignment_offset, 0.0);
-        let pos = self.layout.text_position_for_point(point);
+        let pos = self.from_display(self.display_layout().text_position_for_point(point));
         if mods.shift() {
             self.selection.active = pos;
         } else {
@@ -737,7 +765,7 @@ impl<T: TextStorage + EditableText> EditSession<T> {
     fn do_drag(&mut self, point: Point) {
         let point = point - Vec2::new(self.alignment_offset, 0.0);
         //FIXME: this should behave differently if we were double or triple clicked
-        let pos = self.layout.text_position_for_point(point);
+        let pos = self.from_display(self.display_layout().text_position_for_point(point));
         let text = match self.layout.text() {
             Some(text) => text,
             None => return,
@@ -770,10 +798,97 @@ impl<T: TextStorage + EditableText> EditSession<T> {
 
     /// Returns a line suitable for drawing a standard cursor.
     pub fn cursor_line_for_text_position(&self, pos: usize) -> Line {
-        let line = self.layout.cursor_line_for_text_position(pos);
+        let line = self
+            .display_layout()
+            .cursor_line_for_text_position(self.to_display(pos));
         line + Vec2::new(self.alignment_offset, 0.0)
     }
 
+    /// The layout that is shown, which is the masked text if the text is
+    /// obscured, and [`layout`] otherwise.
+    ///
+    /// Offsets in this layout are not offsets in the text when the text is
+    /// obscured; the methods of the session take care of this.
+    ///
+    /// [`layout`]: EditSession::layout
+    pub fn display_layout(&self) -> &TextLayout<T> {
+        self.mask_layout.as_ref().unwrap_or(&self.layout)
+    }
+
+    /// Convert an offset in the text into an offset in the [`display_layout`].
+    ///
+    /// [`display_layout`]: EditSession::display_layout
+    pub(crate) fn to_display(&self, offset: usize) -> usize {
+        match (self.obscuring, self.layout.text()) {
+            (Some(mask), Some(text)) => {
+                let graphemes = text
+                    .as_str()
+                    .grapheme_indices(true)
+                    .take_while(|(i, _)| *i < offset)
+                    .count();
+                graphemes * mask.len_utf8()
+            }
+            _ => offset,
+        }
+    }
+
+    /// Convert an offset in the [`display_layout`] into an offset in the text.
+    ///
+    /// [`display_layout`]: EditSession::display_layout
+    fn from_display(&self, offset: usize) -> usize {
+        match (self.obscuring, self.layout.text()) {
+            (Some(mask), Some(text)) => {
+                let text = text.as_str();
+                text.grapheme_indices(true)
+                    .nth(offset / mask.len_utf8())
+                    .map(|(i, _)| i)
+                    .unwrap_or_else(|| text.len())
+            }
+            _ => offset,
+        }
+    }
+
+    fn range_to_display(&self, range: Range<usize>) -> Range<usize> {
+        self.to_display(range.start)..self.to_display(range.end)
+    }
+
+    /// Rebuild the layout, and the masked layout if the text is obscured.
+    fn rebuild_layouts(&mut self, factory: &mut PietText, env: &Env) {
+        let changed = self.layout.needs_rebuild() || self.mask_layout.is_none();
+        self.layout.rebuild_if_needed(factory, env);
+        match (self.obscuring, self.layout.text()) {
+            (Some(mask), Some(text)) if changed => {
+                let masked: String = text.as_str().graphemes(true).map(|_| mask).collect();
+                // the masked layout has the same font, size and wrap width
+                let mut mask_layout = self.layout.clone();
+                mask_layout.set_text(T::from_str(&masked));
+                mask_layout.rebuild_if_needed(factory, env);
+                self.mask_layout = Some(mask_layout);
+            }
+            (Some(_), _) => (),
+            (None, _) => self.mask_layout = None,
+        }
+    }
+
+    /// The selection after a movement, worked out on the masked text if the text
+    /// is obscured.
+    fn movement(&self, movement: Movement, modify: bool) -> Selection {
+        if self.obscuring.is_none() {
+            return text::movement(movement, self.selection, &self.layout, modify);
+        }
+        let selection = Selection::new(
+            self.to_display(self.selection.anchor),
+            self.to_display(self.selection.active),
+        )
+        .with_h_pos(self.selection.h_pos);
+        let moved = text::movement(movement, selection, self.display_layout(), modify);
+        Selection::new(
+            self.from_display(moved.anchor),
+            self.from_display(moved.active),
+        )
+        .with_h_pos(moved.h_pos)
+    }
+
     fn sel_region_for_pos(&mut self, pos: usize, click_count: u8) -> Range<usize> {
         match click_count {
             1 => pos..pos,
@@ -791,6 +906,9 @@ impl<T: TextStorage + EditableText> EditSession<T> {
     }
 
     fn word_for_pos(&self, pos: usize) -> Range<usize> {
+        if self.obscuring.is_some() {
+            return 0..self.layout.text().map(|text| text.len()).unwrap_or(0);
+        }
         let layout = match self.layout.layout() {
             Some(layout) => layout,
             None => return pos..pos,
@@ -825,7 +943,7 @@ impl<T: TextStorage + EditableText> EditSession<T> {
             self.selection = new_sel;
             self.update_pending_invalidation(ImeInvalidation::SelectionChanged);
         }
-        self.layout.rebuild_if_needed(ctx.text(), env);
+        self.rebuild_layouts(ctx.text(), env);
     }
 }
 
@@ -871,31 +989,35 @@ impl<T: TextStorage + EditableText> InputHandler for EditSessionHandle<T> {
         self.inner.borrow_mut().external_text_change = Some(self.text.clone());
     }
 
-    fn hit_test_point(&self, point: Point) -> crate::piet::HitTestPoint {
-        self.inner
-            .borrow()
-            .layout
+    fn hit_test_point(&self, point: Point) -> HitTestPoint {
+        let inner = self.inner.borrow();
+        let mut hit = inner
+            .display_layout()
             .layout()
             .map(|layout| layout.hit_test_point(point))
-            .unwrap_or_default()
+            .unwrap_or_default();
+        hit.idx = inner.from_display(hit.idx);
+        hit
     }
 
     fn line_range(&self, index: usize, _affinity: druid_shell::text::Affinity) -> Range<usize> {
         let inner = self.inner.borrow();
-        let layout = inner.layout.layout().unwrap();
-        let hit = layout.hit_test_text_position(index);
+        let layout = inner.display_layout().layout().unwrap();
+        let hit = layout.hit_test_text_position(inner.to_display(index));
         let metric = layout.line_metric(hit.line).unwrap();
-        metric.range()
+        inner.from_display(metric.start_offset)..inner.from_display(metric.end_offset)
     }
 
     fn bounding_box(&self) -> Option<Rect> {
-        let size = self.inner.borrow().layout.size();
+        let size = self.inner.borrow().display_layout().size();
         Some(Rect::from_origin_size(self.inner.borrow().origin, size))
     }
 
     fn slice_bounding_box(&self, range: Range<usize>) -> Option<Rect> {
-        let origin = self.inner.borrow().origin;
-        let layout = &self.inner.borrow().layout;
+        let inner = self.inner.borrow();
+        let origin = inner.origin;
+        let range = inner.range_to_display(range);
+        let layout = inner.display_layout();
         if range.is_empty() {
             let hit = layout
                 .layout()
@@ -928,6 +1050,8 @@ impl<T> Default for TextComponent<T> {
     fn default() -> Self {
         let inner = EditSession {
             layout: TextLayout::new(),
+            obscuring: None,
+            mask_layout: None,
             external_scroll_to: None,
             external_text_change: None,
             external_selection_change: None,
@@ -952,3 +1076,27 @@ impl<T> Default for TextComponent<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn obscured_offsets() {
+        let component = TextComponent::<String>::default();
+        let mut session = component.borrow_mut();
+        // 'é' as 'e' and a combining accent is a single grapheme
+        session.layout.set_text("ae\u{301}b".to_string());
+        assert_eq!(session.to_display(4), 4);
+
+        session.set_text_obscuring(Some('\u{2022}'));
+        let mask_len = '\u{2022}'.len_utf8();
+        assert_eq!(session.to_display(0), 0);
+        assert_eq!(session.to_display(1), mask_len);
+        assert_eq!(session.to_display(4), mask_len * 2);
+        assert_eq!(session.to_display(5), mask_len * 3);
+        assert_eq!(session.from_display(mask_len * 2), 4);
+        assert_eq!(session.from_display(mask_len * 3), 5);
+        assert_eq!(session.word_for_pos(1), 0..5);
+    }
+}
@@ -325,13 +325,33 @@ impl<T: TextStorage> TextLayout<T> {
     ///
     /// [`Link`]: super::attribute::Link
     pub fn link_for_pos(&self, pos: Point) -> Option<&Link> {
-        let (_, i) = self
-            .links
+        let i = self.link_index_for_pos(pos)?;
+        let text = self.text()?;
+        text.links().get(i)
+    }
+
+    /// Returns the index of the [`Link`] at the provided point (relative to the
+    /// layout's origin), if one exists.
+    ///
+    /// This is useful for tracking which link is hovered.
+    ///
+    /// [`Link`]: super::attribute::Link
+    pub fn link_index_for_pos(&self, pos: Point) -> Option<usize> {
+        self.links
             .iter()
-            .rfind(|(hit_box, _)| hit_box.contains(pos))?;
+            .rfind(|(hit_box, _)| hit_box.contains(pos))
+            .map(|(_, i)| *i)
+    }
 
-        let text = self.text()?;
-        text.links().get(*i)
+    /// Returns the rects (relative to the layout's origin) covered by the
+    /// [`Link`] at `index`.
+    ///
+    /// [`Link`]: super::attribute::Link
+    pub fn link_rects(&self, index: usize) -> impl Iterator<Item = Rect> + '_ {
+        self.links
+            .iter()
+            .filter(move |(_, i)| *i == index)
+            .map(|(rect, _)| *rect)
     }
 
     /// Called during the containing widgets `update` method; this text object
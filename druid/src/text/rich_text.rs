@@ -40,6 +40,9 @@ impl RichText {
     }
 
     /// Create a new `RichText`, providing explicit attributes.
+    ///
+    /// Attributes whose ranges fall outside of the text are clamped to it
+    /// when the text is laid out.
     pub fn new_with_attributes(buffer: ArcStr, attributes: AttributeSpans) -> Self {
         RichText {
             buffer,
@@ -68,10 +71,15 @@ impl RichText {
 
     /// Add an [`Attribute`] to the provided range of text.
     ///
+    /// The range is clamped to the text; if nothing of it remains, the
+    /// attribute is ignored.
+    ///
     /// [`Attribute`]: enum.Attribute.html
     pub fn add_attribute(&mut self, range: impl RangeBounds<usize>, attr: Attribute) {
         let range = util::resolve_range(range, self.buffer.len());
-        Arc::make_mut(&mut self.attrs).add(range, attr);
+        if let Some(range) = clamp_range(&self.buffer, range) {
+            Arc::make_mut(&mut self.attrs).add(range, attr);
+        }
     }
 }
 
@@ -88,7 +96,9 @@ impl TextStorage for RichText {
         env: &Env,
     ) -> PietTextLayoutBuilder {
         for (range, attr) in self.attrs.to_piet_attrs(env) {
-            builder = builder.range_attribute(range, attr);
+            if let Some(range) = clamp_range(&self.buffer, range) {
+                builder = builder.range_attribute(range, attr);
+            }
         }
         builder
     }
@@ -98,6 +108,26 @@ impl TextStorage for RichText {
     }
 }
 
+/// Clamp `range` to the bounds of `text`, moving each end back to the nearest
+/// char boundary.
+///
+/// Returns `None` if the resulting range is empty.
+fn clamp_range(text: &str, range: Range<usize>) -> Option<Range<usize>> {
+    let clamp = |idx: usize| {
+        let mut idx = idx.min(text.len());
+        while !text.is_char_boundary(idx) {
+            idx -= 1;
+        }
+        idx
+    };
+    let (start, end) = (clamp(range.start), clamp(range.end));
+    if start < end {
+        Some(start..end)
+    } else {
+        None
+    }
+}
+
 /// A builder for creating [`RichText`] objects.
 ///
 /// This builder allows you to construct a [`RichText`] object by building up a sequence
@@ -244,3 +274,43 @@ impl AttributesAdder<'_> {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::piet::TextAttribute;
+    use test_env_log::test;
+
+    /// 'é' takes up bytes 1..3.
+    const TEXT: &str = "h\u{e9}llo";
+
+    #[test]
+    fn clamp_range_to_text() {
+        assert_eq!(clamp_range(TEXT, 0..6), Some(0..6));
+        assert_eq!(clamp_range(TEXT, 3..20), Some(3..6));
+        assert_eq!(clamp_range(TEXT, 20..30), None);
+        assert_eq!(clamp_range(TEXT, 4..1), None);
+        // ends inside 'é' move back to its start
+        assert_eq!(clamp_range(TEXT, 2..6), Some(1..6));
+        assert_eq!(clamp_range(TEXT, 0..2), Some(0..1));
+        assert_eq!(clamp_range(TEXT, 1..2), None);
+    }
+
+    #[test]
+    fn invalid_attribute_ranges_are_clamped() {
+        let text = RichText::new(TEXT.into())
+            .with_attribute(2..20, Attribute::weight(FontWeight::BOLD))
+            .with_attribute(4..1, Attribute::underline(true));
+
+        let attrs = text.attrs.to_piet_attrs(&Env::default());
+        let bold: Vec<_> = attrs
+            .iter()
+            .filter(|(_, attr)| matches!(attr, TextAttribute::Weight(w) if *w == FontWeight::BOLD))
+            .map(|(range, _)| range.clone())
+            .collect();
+        assert_eq!(bold, vec![1..6]);
+        assert!(!attrs
+            .iter()
+            .any(|(_, attr)| matches!(attr, TextAttribute::Underline(_))));
+    }
+}
@@ -18,7 +18,7 @@
 
 use crate::piet::Color;
 
-use crate::{Env, FontDescriptor, FontFamily, FontStyle, FontWeight, Insets, Key};
+use crate::{Appearance, Env, FontDescriptor, FontFamily, FontStyle, FontWeight, Insets, Key};
 
 pub const WINDOW_BACKGROUND_COLOR: Key<Color> =
     Key::new("org.linebender.druid.theme.window_background_color");
@@ -36,6 +36,10 @@ pub const PROGRESS_BAR_RADIUS: Key<f64> =
     Key::new("org.linebender.druid.theme.progress_bar_radius");
 pub const BACKGROUND_LIGHT: Key<Color> = Key::new("org.linebender.druid.theme.background_light");
 pub const BACKGROUND_DARK: Key<Color> = Key::new("org.linebender.druid.theme.background_dark");
+pub const DISABLED_BACKGROUND_LIGHT: Key<Color> =
+    Key::new("org.linebender.druid.theme.disabled_background_light");
+pub const DISABLED_BACKGROUND_DARK: Key<Color> =
+    Key::new("org.linebender.druid.theme.disabled_background_dark");
 pub const FOREGROUND_LIGHT: Key<Color> = Key::new("org.linebender.druid.theme.foreground_light");
 pub const FOREGROUND_DARK: Key<Color> = Key::new("org.linebender.druid.theme.foreground_dark");
 pub const DISABLED_FOREGROUND_LIGHT: Key<Color> =
@@ -100,52 +104,107 @@ pub const WIDGET_PADDING_VERTICAL: Key<f64> =
 pub const WIDGET_CONTROL_COMPONENT_PADDING: Key<f64> =
     Key::new("org.linebender.druid.theme.widget-padding-control-label");
 
-pub const SCROLLBAR_COLOR: Key<Color> = Key::new("org.linebender.druid.theme.scrollbar_color");
-pub const SCROLLBAR_BORDER_COLOR: Key<Color> =
-    Key::new("org.linebender.druid.theme.scrollbar_border_color");
+// The scrollbar keys have defaults, so that scrolling widgets also work in an
+// `Env` that was not built from the default theme. The default theme still
+// sets all of them, and the colors are set by both palettes, so that they are
+// seen by code that reads the `Env` without the defaults, like theme loaders.
+pub const SCROLLBAR_COLOR: Key<Color> = Key::with_default(
+    "org.linebender.druid.theme.scrollbar_color",
+    Color::rgb8(0xff, 0xff, 0xff),
+);
+/// The color of a scrollbar while the mouse is over it.
+pub const SCROLLBAR_HOVER_COLOR: Key<Color> = Key::with_default(
+    "org.linebender.druid.theme.scrollbar_hover_color",
+    Color::rgb8(0xc8, 0xe6, 0xff),
+);
+/// The color of a scrollbar while it is being dragged.
+pub const SCROLLBAR_ACTIVE_COLOR: Key<Color> = Key::with_default(
+    "org.linebender.druid.theme.scrollbar_active_color",
+    Color::rgb8(0x5c, 0xc4, 0xff),
+);
+pub const SCROLLBAR_BORDER_COLOR: Key<Color> = Key::with_default(
+    "org.linebender.druid.theme.scrollbar_border_color",
+    Color::rgb8(0x77, 0x77, 0x77),
+);
+/// The background of the tracks that inlay scrollbars are placed in.
+pub const SCROLLBAR_TRACK_COLOR: Key<Color> = Key::with_default(
+    "org.linebender.druid.theme.scrollbar_track_color",
+    Color::rgb8(0x31, 0x31, 0x31),
+);
 pub const SCROLLBAR_MAX_OPACITY: Key<f64> =
-    Key::new("org.linebender.druid.theme.scrollbar_max_opacity");
+    Key::with_default("org.linebender.druid.theme.scrollbar_max_opacity", 0.7);
 pub const SCROLLBAR_FADE_DELAY: Key<u64> =
-    Key::new("org.linebender.druid.theme.scrollbar_fade_time");
-pub const SCROLLBAR_WIDTH: Key<f64> = Key::new("org.linebender.druid.theme.scrollbar_width");
-pub const SCROLLBAR_PAD: Key<f64> = Key::new("org.linebender.druid.theme.scrollbar_pad");
-pub const SCROLLBAR_RADIUS: Key<f64> = Key::new("org.linebender.druid.theme.scrollbar_radius");
+    Key::with_default("org.linebender.druid.theme.scrollbar_fade_time", 1500);
+/// The time, in milliseconds, that it takes a scrollbar to fade out completely
+/// once its fade delay has passed.
+pub const SCROLLBAR_FADE_DURATION: Key<u64> =
+    Key::with_default("org.linebender.druid.theme.scrollbar_fade_duration", 350);
+pub const SCROLLBAR_WIDTH: Key<f64> =
+    Key::with_default("org.linebender.druid.theme.scrollbar_width", 8.);
+pub const SCROLLBAR_PAD: Key<f64> =
+    Key::with_default("org.linebender.druid.theme.scrollbar_pad", 2.);
+pub const SCROLLBAR_RADIUS: Key<f64> =
+    Key::with_default("org.linebender.druid.theme.scrollbar_radius", 5.);
 pub const SCROLLBAR_EDGE_WIDTH: Key<f64> =
-    Key::new("org.linebender.druid.theme.scrollbar_edge_width");
+    Key::with_default("org.linebender.druid.theme.scrollbar_edge_width", 1.);
 /// Minimum length for any scrollbar to be when measured on that
 /// scrollbar's primary axis.
-pub const SCROLLBAR_MIN_SIZE: Key<f64> = Key::new("org.linebender.theme.scrollbar_min_size");
+pub const SCROLLBAR_MIN_SIZE: Key<f64> =
+    Key::with_default("org.linebender.theme.scrollbar_min_size", 45.);
+
+/// The background of the tabs in a [`Tabs`] bar that aren't selected.
+///
+/// [`Tabs`]: crate::widget::Tabs
+pub const TAB_BACKGROUND: Key<Color> = Key::new("org.linebender.druid.theme.tab_background");
+/// The background of the selected tab in a [`Tabs`] bar.
+///
+/// [`Tabs`]: crate::widget::Tabs
+pub const TAB_SELECTED_BACKGROUND: Key<Color> =
+    Key::new("org.linebender.druid.theme.tab_selected_background");
+/// The background of the tab under the mouse in a [`Tabs`] bar.
+///
+/// [`Tabs`]: crate::widget::Tabs
+pub const TAB_HOT_BACKGROUND: Key<Color> =
+    Key::new("org.linebender.druid.theme.tab_hot_background");
+/// The color of the line that marks the selected tab in a [`Tabs`] bar.
+///
+/// [`Tabs`]: crate::widget::Tabs
+pub const TAB_INDICATOR_COLOR: Key<Color> =
+    Key::new("org.linebender.druid.theme.tab_indicator_color");
+
+/// The time, in milliseconds, that the mouse has to rest on a widget before its
+/// tooltip is shown.
+pub const TOOLTIP_DELAY: Key<u64> = Key::new("org.linebender.druid.theme.tooltip_delay");
+pub const TOOLTIP_BACKGROUND_COLOR: Key<Color> =
+    Key::new("org.linebender.druid.theme.tooltip_background_color");
+pub const TOOLTIP_TEXT_COLOR: Key<Color> =
+    Key::new("org.linebender.druid.theme.tooltip_text_color");
+pub const TOOLTIP_BORDER_COLOR: Key<Color> =
+    Key::new("org.linebender.druid.theme.tooltip_border_color");
+
+/// The color that dims the content of a window behind a modal widget.
+pub const MODAL_SCRIM_COLOR: Key<Color> = Key::new("org.linebender.druid.theme.modal_scrim_color");
+
+/// The border color of a [`ValueTextBox`] whose text is not valid.
+///
+/// [`ValueTextBox`]: crate::widget::ValueTextBox
+pub const TEXTBOX_ERROR_BORDER_COLOR: Key<Color> =
+    Key::new("org.linebender.druid.theme.textbox_error_border_color");
+
+/// The longest time, in milliseconds, between two clicks for them to count as
+/// a double click.
+pub const DOUBLE_CLICK_INTERVAL: Key<u64> =
+    Key::new("org.linebender.druid.theme.double_click_interval");
+/// How far, in pixels, the mouse may move while pressed before a click or
+/// long press turns into a drag.
+pub const GESTURE_SLOP: Key<f64> = Key::new("org.linebender.druid.theme.gesture_slop");
 
 /// An initial theme.
 pub(crate) fn add_to_env(env: Env) -> Env {
-    env.adding(WINDOW_BACKGROUND_COLOR, Color::rgb8(0x29, 0x29, 0x29))
-        .adding(TEXT_COLOR, Color::rgb8(0xf0, 0xf0, 0xea))
-        .adding(DISABLED_TEXT_COLOR, Color::rgb8(0xa0, 0xa0, 0x9a))
-        .adding(PLACEHOLDER_COLOR, Color::rgb8(0x80, 0x80, 0x80))
-        .adding(PRIMARY_LIGHT, Color::rgb8(0x5c, 0xc4, 0xff))
-        .adding(PRIMARY_DARK, Color::rgb8(0x00, 0x8d, 0xdd))
+    add_dark_colors(env)
         .adding(PROGRESS_BAR_RADIUS, 4.)
-        .adding(BACKGROUND_LIGHT, Color::rgb8(0x3a, 0x3a, 0x3a))
-        .adding(BACKGROUND_DARK, Color::rgb8(0x31, 0x31, 0x31))
-        .adding(FOREGROUND_LIGHT, Color::rgb8(0xf9, 0xf9, 0xf9))
-        .adding(FOREGROUND_DARK, Color::rgb8(0xbf, 0xbf, 0xbf))
-        .adding(DISABLED_FOREGROUND_LIGHT, Color::rgb8(0x89, 0x89, 0x89))
-        .adding(DISABLED_FOREGROUND_DARK, Color::rgb8(0x6f, 0x6f, 0x6f))
-        .adding(BUTTON_DARK, Color::BLACK)
-        .adding(BUTTON_LIGHT, Color::rgb8(0x21, 0x21, 0x21))
-        .adding(DISABLED_BUTTON_DARK, Color::grey8(0x28))
-        .adding(DISABLED_BUTTON_LIGHT, Color::grey8(0x38))
         .adding(BUTTON_BORDER_RADIUS, 4.)
         .adding(BUTTON_BORDER_WIDTH, 2.)
-        .adding(BORDER_DARK, Color::rgb8(0x3a, 0x3a, 0x3a))
-        .adding(BORDER_LIGHT, Color::rgb8(0xa1, 0xa1, 0xa1))
-        .adding(
-            SELECTED_TEXT_BACKGROUND_COLOR,
-            Color::rgb8(0x43, 0x70, 0xA8),
-        )
-        .adding(SELECTED_TEXT_INACTIVE_BACKGROUND_COLOR, Color::grey8(0x74))
-        .adding(SELECTION_TEXT_COLOR, Color::rgb8(0x00, 0x00, 0x00))
-        .adding(CURSOR_COLOR, Color::WHITE)
         .adding(TEXT_SIZE_NORMAL, 15.0)
         .adding(TEXT_SIZE_LARGE, 24.0)
         .adding(BASIC_WIDGET_HEIGHT, 18.0)
@@ -154,15 +213,17 @@ pub(crate) fn add_to_env(env: Env) -> Env {
         .adding(TEXTBOX_BORDER_RADIUS, 2.)
         .adding(TEXTBOX_BORDER_WIDTH, 1.)
         .adding(TEXTBOX_INSETS, Insets::new(4.0, 4.0, 4.0, 4.0))
-        .adding(SCROLLBAR_COLOR, Color::rgb8(0xff, 0xff, 0xff))
-        .adding(SCROLLBAR_BORDER_COLOR, Color::rgb8(0x77, 0x77, 0x77))
         .adding(SCROLLBAR_MAX_OPACITY, 0.7)
         .adding(SCROLLBAR_FADE_DELAY, 1500u64)
+        .adding(SCROLLBAR_FADE_DURATION, 350u64)
         .adding(SCROLLBAR_WIDTH, 8.)
         .adding(SCROLLBAR_PAD, 2.)
         .adding(SCROLLBAR_MIN_SIZE, 45.)
         .adding(SCROLLBAR_RADIUS, 5.)
         .adding(SCROLLBAR_EDGE_WIDTH, 1.)
+        .adding(TOOLTIP_DELAY, 500u64)
+        .adding(DOUBLE_CLICK_INTERVAL, 500u64)
+        .adding(GESTURE_SLOP, 4.0)
         .adding(WIDGET_PADDING_VERTICAL, 10.0)
         .adding(WIDGET_PADDING_HORIZONTAL, 8.0)
         .adding(WIDGET_CONTROL_COMPONENT_PADDING, 4.0)
@@ -184,7 +245,148 @@ pub(crate) fn add_to_env(env: Env) -> Env {
         )
 }
 
+/// Sets all the colors of the theme to the bundled palette for `appearance`.
+///
+/// The initial theme uses the [`Appearance::Dark`] palette. Other values, like
+/// sizes and fonts, are left unchanged. To switch the palette of a running
+/// application, submit [`SET_APPEARANCE`] instead, which also keeps the changes
+/// made by [`AppLauncher::configure_env`].
+///
+/// [`SET_APPEARANCE`]: crate::commands::SET_APPEARANCE
+/// [`AppLauncher::configure_env`]: crate::AppLauncher::configure_env
+pub fn set_appearance(env: &mut Env, appearance: Appearance) {
+    *env = match appearance {
+        Appearance::Light => add_light_colors(env.clone()),
+        Appearance::Dark => add_dark_colors(env.clone()),
+    };
+}
+
+fn add_dark_colors(env: Env) -> Env {
+    env.adding(WINDOW_BACKGROUND_COLOR, Color::rgb8(0x29, 0x29, 0x29))
+        .adding(TEXT_COLOR, Color::rgb8(0xf0, 0xf0, 0xea))
+        .adding(DISABLED_TEXT_COLOR, Color::rgb8(0xa0, 0xa0, 0x9a))
+        .adding(PLACEHOLDER_COLOR, Color::rgb8(0x80, 0x80, 0x80))
+        .adding(PRIMARY_LIGHT, Color::rgb8(0x5c, 0xc4, 0xff))
+        .adding(PRIMARY_DARK, Color::rgb8(0x00, 0x8d, 0xdd))
+        .adding(BACKGROUND_LIGHT, Color::rgb8(0x3a, 0x3a, 0x3a))
+        .adding(BACKGROUND_DARK, Color::rgb8(0x31, 0x31, 0x31))
+        .adding(DISABLED_BACKGROUND_LIGHT, Color::grey8(0x2e))
+        .adding(DISABLED_BACKGROUND_DARK, Color::grey8(0x2b))
+        .adding(FOREGROUND_LIGHT, Color::rgb8(0xf9, 0xf9, 0xf9))
+        .adding(FOREGROUND_DARK, Color::rgb8(0xbf, 0xbf, 0xbf))
+        .adding(DISABLED_FOREGROUND_LIGHT, Color::rgb8(0x89, 0x89, 0x89))
+        .adding(DISABLED_FOREGROUND_DARK, Color::rgb8(0x6f, 0x6f, 0x6f))
+        .adding(BUTTON_DARK, Color::BLACK)
+        .adding(BUTTON_LIGHT, Color::rgb8(0x21, 0x21, 0x21))
+        .adding(DISABLED_BUTTON_DARK, Color::grey8(0x28))
+        .adding(DISABLED_BUTTON_LIGHT, Color::grey8(0x38))
+        .adding(BORDER_DARK, Color::rgb8(0x3a, 0x3a, 0x3a))
+        .adding(BORDER_LIGHT, Color::rgb8(0xa1, 0xa1, 0xa1))
+        .adding(
+            SELECTED_TEXT_BACKGROUND_COLOR,
+            Color::rgb8(0x43, 0x70, 0xA8),
+        )
+        .adding(SELECTED_TEXT_INACTIVE_BACKGROUND_COLOR, Color::grey8(0x74))
+        .adding(SELECTION_TEXT_COLOR, Color::rgb8(0x00, 0x00, 0x00))
+        .adding(CURSOR_COLOR, Color::WHITE)
+        .adding(SCROLLBAR_COLOR, Color::rgb8(0xff, 0xff, 0xff))
+        .adding(SCROLLBAR_HOVER_COLOR, Color::rgb8(0xc8, 0xe6, 0xff))
+        .adding(SCROLLBAR_ACTIVE_COLOR, Color::rgb8(0x5c, 0xc4, 0xff))
+        .adding(SCROLLBAR_BORDER_COLOR, Color::rgb8(0x77, 0x77, 0x77))
+        .adding(SCROLLBAR_TRACK_COLOR, Color::rgb8(0x31, 0x31, 0x31))
+        .adding(TAB_BACKGROUND, Color::rgb8(0x31, 0x31, 0x31))
+        .adding(TAB_SELECTED_BACKGROUND, Color::rgb8(0x3a, 0x3a, 0x3a))
+        .adding(TAB_HOT_BACKGROUND, Color::BLACK)
+        .adding(TAB_INDICATOR_COLOR, Color::rgb8(0x5c, 0xc4, 0xff))
+        .adding(TOOLTIP_BACKGROUND_COLOR, Color::rgb8(0x21, 0x21, 0x21))
+        .adding(TOOLTIP_TEXT_COLOR, Color::rgb8(0xf0, 0xf0, 0xea))
+        .adding(TOOLTIP_BORDER_COLOR, Color::rgb8(0xa1, 0xa1, 0xa1))
+        .adding(MODAL_SCRIM_COLOR, Color::BLACK.with_alpha(0.5))
+        .adding(TEXTBOX_ERROR_BORDER_COLOR, Color::rgb8(0xe0, 0x4b, 0x4b))
+}
+
+fn add_light_colors(env: Env) -> Env {
+    env.adding(WINDOW_BACKGROUND_COLOR, Color::rgb8(0xf2, 0xf2, 0xf0))
+        .adding(TEXT_COLOR, Color::rgb8(0x1e, 0x1e, 0x1c))
+        .adding(DISABLED_TEXT_COLOR, Color::rgb8(0x8a, 0x8a, 0x86))
+        .adding(PLACEHOLDER_COLOR, Color::rgb8(0x90, 0x90, 0x90))
+        .adding(PRIMARY_LIGHT, Color::rgb8(0x5c, 0xc4, 0xff))
+        .adding(PRIMARY_DARK, Color::rgb8(0x00, 0x8d, 0xdd))
+        .adding(BACKGROUND_LIGHT, Color::rgb8(0xff, 0xff, 0xff))
+        .adding(BACKGROUND_DARK, Color::rgb8(0xf4, 0xf4, 0xf4))
+        .adding(DISABLED_BACKGROUND_LIGHT, Color::grey8(0xea))
+        .adding(DISABLED_BACKGROUND_DARK, Color::grey8(0xe2))
+        .adding(FOREGROUND_LIGHT, Color::rgb8(0x1e, 0x1e, 0x1e))
+        .adding(FOREGROUND_DARK, Color::rgb8(0x50, 0x50, 0x50))
+        .adding(DISABLED_FOREGROUND_LIGHT, Color::rgb8(0x9a, 0x9a, 0x9a))
+        .adding(DISABLED_FOREGROUND_DARK, Color::rgb8(0xb4, 0xb4, 0xb4))
+        .adding(BUTTON_DARK, Color::grey8(0xd8))
+        .adding(BUTTON_LIGHT, Color::grey8(0xfa))
+        .adding(DISABLED_BUTTON_DARK, Color::grey8(0xe2))
+        .adding(DISABLED_BUTTON_LIGHT, Color::grey8(0xee))
+        .adding(BORDER_DARK, Color::rgb8(0xc4, 0xc4, 0xc4))
+        .adding(BORDER_LIGHT, Color::rgb8(0x6e, 0x6e, 0x6e))
+        .adding(
+            SELECTED_TEXT_BACKGROUND_COLOR,
+            Color::rgb8(0xa8, 0xcc, 0xf0),
+        )
+        .adding(SELECTED_TEXT_INACTIVE_BACKGROUND_COLOR, Color::grey8(0xd0))
+        .adding(SELECTION_TEXT_COLOR, Color::rgb8(0x00, 0x00, 0x00))
+        .adding(CURSOR_COLOR, Color::BLACK)
+        .adding(SCROLLBAR_COLOR, Color::rgb8(0x50, 0x50, 0x50))
+        .adding(SCROLLBAR_HOVER_COLOR, Color::rgb8(0x30, 0x30, 0x30))
+        .adding(SCROLLBAR_ACTIVE_COLOR, Color::rgb8(0x00, 0x8d, 0xdd))
+        .adding(SCROLLBAR_BORDER_COLOR, Color::rgb8(0xb0, 0xb0, 0xb0))
+        .adding(SCROLLBAR_TRACK_COLOR, Color::rgb8(0xe6, 0xe6, 0xe6))
+        .adding(TAB_BACKGROUND, Color::rgb8(0xe6, 0xe6, 0xe6))
+        .adding(TAB_SELECTED_BACKGROUND, Color::rgb8(0xff, 0xff, 0xff))
+        .adding(TAB_HOT_BACKGROUND, Color::grey8(0xd4))
+        .adding(TAB_INDICATOR_COLOR, Color::rgb8(0x00, 0x8d, 0xdd))
+        .adding(TOOLTIP_BACKGROUND_COLOR, Color::rgb8(0xfa, 0xfa, 0xfa))
+        .adding(TOOLTIP_TEXT_COLOR, Color::rgb8(0x1e, 0x1e, 0x1c))
+        .adding(TOOLTIP_BORDER_COLOR, Color::rgb8(0x8a, 0x8a, 0x8a))
+        .adding(MODAL_SCRIM_COLOR, Color::BLACK.with_alpha(0.3))
+        .adding(TEXTBOX_ERROR_BORDER_COLOR, Color::rgb8(0xd0, 0x30, 0x30))
+}
+
 #[deprecated(since = "0.7.0", note = "use Env::default() instead")]
 pub fn init() -> Env {
     Env::default()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use test_env_log::test;
+
+    /// The keys of the values that a palette sets.
+    fn palette_keys(add_colors: fn(Env) -> Env) -> HashSet<String> {
+        add_colors(Env::empty())
+            .get_all()
+            .map(|(key, _)| key.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn palettes_set_the_same_keys() {
+        let dark = palette_keys(add_dark_colors);
+        let light = palette_keys(add_light_colors);
+        assert!(!dark.is_empty());
+        assert_eq!(dark, light);
+    }
+
+    #[test]
+    fn theme_sets_keys_with_defaults() {
+        let env = add_to_env(Env::empty());
+        assert!(env.try_get_untyped(SCROLLBAR_MAX_OPACITY).is_ok());
+        assert!(env.try_get_untyped(SCROLLBAR_FADE_DELAY).is_ok());
+        assert!(env.try_get_untyped(SCROLLBAR_FADE_DURATION).is_ok());
+        assert!(env.try_get_untyped(SCROLLBAR_WIDTH).is_ok());
+        assert!(env.try_get_untyped(SCROLLBAR_PAD).is_ok());
+        assert!(env.try_get_untyped(SCROLLBAR_MIN_SIZE).is_ok());
+        assert!(env.try_get_untyped(SCROLLBAR_RADIUS).is_ok());
+        assert!(env.try_get_untyped(SCROLLBAR_EDGE_WIDTH).is_ok());
+        assert!(env.try_get_untyped(SCROLLBAR_COLOR).is_ok());
+    }
+}
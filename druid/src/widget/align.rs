@@ -128,6 +128,8 @@ impl<T: Data> Widget<T> for Align<T> {
 
         let my_insets = self.child.compute_parent_paint_insets(my_size);
         ctx.set_paint_insets(my_insets);
+        let extra_bottom = my_size.height - self.child.layout_rect().max_y();
+        ctx.set_baseline_offset(self.child.baseline_offset() + extra_bottom);
         trace!(
             "Computed layout: origin={}, size={}, insets={:?}",
             origin,
@@ -0,0 +1,396 @@
+// Copyright 2021 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A widget that loads an image in the background.
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::sync::Arc;
+use std::thread;
+
+use crate::piet::ImageBuf;
+use crate::widget::prelude::*;
+use crate::widget::{Image, Label, Spinner};
+use crate::{ArcStr, ExtEventSink, Point, Selector, Target, WidgetPod};
+use tracing::{instrument, trace, warn};
+
+type Loader = dyn Fn(&str) -> Result<ImageBuf, Box<dyn Error + Send + Sync>> + Send + Sync;
+
+/// The number of images an `AsyncImage` keeps by default.
+const DEFAULT_CACHE_SIZE: usize = 8;
+
+// Sent back from the loading thread.
+const IMAGE_LOADED: Selector<LoadResult> = Selector::new("druid-builtin.async-image-loaded");
+
+struct LoadResult {
+    generation: u64,
+    source: ArcStr,
+    result: Result<ImageBuf, String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LoadState {
+    Loading,
+    Loaded,
+    Failed,
+}
+
+/// A widget that loads and displays the image named by its data.
+///
+/// The data is the source of the image; by default this is a path to an
+/// image file. The image is decoded on a background thread, and a
+/// placeholder is shown until it is ready. If the image can't be loaded, an
+/// error widget is shown instead.
+///
+/// To load images from somewhere else, such as the network, provide a
+/// loader with [`with_loader`].
+///
+/// The most recently shown images are cached by source, so going back to a
+/// source that was shown recently is instant; see [`with_cache_size`]. If the
+/// source changes while an image is still loading, the stale result is ignored.
+///
+/// [`with_loader`]: #method.with_loader
+/// [`with_cache_size`]: #method.with_cache_size
+pub struct AsyncImage {
+    image: WidgetPod<ArcStr, Image>,
+    placeholder: WidgetPod<ArcStr, Box<dyn Widget<ArcStr>>>,
+    error: WidgetPod<ArcStr, Box<dyn Widget<ArcStr>>>,
+    loader: Arc<Loader>,
+    state: LoadState,
+    /// Incremented for each load, to recognise stale results.
+    generation: u64,
+    /// The most recently shown images, the latest last.
+    cache: VecDeque<(ArcStr, ImageBuf)>,
+    cache_size: usize,
+}
+
+impl AsyncImage {
+    /// Create a new `AsyncImage` that loads images from files.
+    ///
+    /// It shows a [`Spinner`] while loading, and a short message if the
+    /// image fails to load.
+    ///
+    /// [`Spinner`]: struct.Spinner.html
+    pub fn new() -> Self {
+        AsyncImage {
+            image: WidgetPod::new(Image::new(ImageBuf::empty())),
+            placeholder: WidgetPod::new(Box::new(Spinner::new())),
+            error: WidgetPod::new(Box::new(Label::new("Failed to load image"))),
+            loader: Arc::new(|path: &str| ImageBuf::from_file(path)),
+            state: LoadState::Loading,
+            generation: 0,
+            cache: VecDeque::new(),
+            cache_size: DEFAULT_CACHE_SIZE,
+        }
+    }
+
+    /// Builder-style method to set the function that loads an image from
+    /// its source.
+    ///
+    /// This is called on a background thread.
+    pub fn with_loader(
+        mut self,
+        loader: impl Fn(&str) -> Result<ImageBuf, Box<dyn Error + Send + Sync>> + Send + Sync + 'static,
+    ) -> Self {
+        self.loader = Arc::new(loader);
+        self
+    }
+
+    /// Builder-style method to set how many of the most recently shown images
+    /// are kept, so that they don't have to be loaded again.
+    ///
+    /// The default is 8. With a size of 0, every image is loaded each time it
+    /// is shown.
+    pub fn with_cache_size(mut self, size: usize) -> Self {
+        self.cache_size = size;
+        self.cache.truncate(size);
+        self
+    }
+
+    /// Builder-style method to set the widget shown while the image loads.
+    pub fn with_placeholder(mut self, placeholder: impl Widget<ArcStr> + 'static) -> Self {
+        self.placeholder = WidgetPod::new(Box::new(placeholder));
+        self
+    }
+
+    /// Builder-style method to set the widget shown if the image fails to load.
+    pub fn with_error_widget(mut self, error: impl Widget<ArcStr> + 'static) -> Self {
+        self.error = WidgetPod::new(Box::new(error));
+        self
+    }
+
+    /// Builder-style method to configure the [`Image`] used to display
+    /// loaded images, for instance to set its fill strategy.
+    ///
+    /// [`Image`]: struct.Image.html
+    pub fn with_image(mut self, configure: impl FnOnce(Image) -> Image) -> Self {
+        let image = configure(Image::new(ImageBuf::empty()));
+        self.image = WidgetPod::new(image);
+        self
+    }
+
+    /// Show the image for `source`, from the cache or by starting a load.
+    fn load(&mut self, source: &ArcStr, sink: ExtEventSink, widget: WidgetId) {
+        self.generation += 1;
+        if let Some(image) = self.recall(source) {
+            self.image.widget_mut().set_image_data(image);
+            self.state = LoadState::Loaded;
+            return;
+        }
+
+        self.state = LoadState::Loading;
+        let generation = self.generation;
+        let source = source.clone();
+        let loader = self.loader.clone();
+        thread::spawn(move || {
+            let result = loader(&source).map_err(|e| e.to_string());
+            let result = LoadResult {
+                generation,
+                source,
+                result,
+            };
+            if sink
+                .submit_command(IMAGE_LOADED, Box::new(result), Target::Widget(widget))
+                .is_err()
+            {
+                trace!(
+                    "AsyncImage {:?} loaded an image after its window closed",
+                    widget
+                );
+            }
+        });
+    }
+
+    /// Take an image from the cache, marking it as the most recently shown.
+    fn recall(&mut self, source: &ArcStr) -> Option<ImageBuf> {
+        let index = self.cache.iter().position(|(cached, _)| cached == source)?;
+        let entry = self.cache.remove(index)?;
+        let image = entry.1.clone();
+        self.cache.push_back(entry);
+        Some(image)
+    }
+
+    /// Add an image to the cache, dropping the least recently shown ones if it
+    /// is full.
+    fn remember(&mut self, source: ArcStr, image: ImageBuf) {
+        self.cache.retain(|(cached, _)| *cached != source);
+        self.cache.push_back((source, image));
+        while self.cache.len() > self.cache_size {
+            self.cache.pop_front();
+        }
+    }
+}
+
+impl Default for AsyncImage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget<ArcStr> for AsyncImage {
+    #[instrument(
+        name = "AsyncImage",
+        level = "trace",
+        skip(self, ctx, event, data, env)
+    )]
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut ArcStr, env: &Env) {
+        if let Event::Command(cmd) = event {
+            if let Some(loaded) = cmd.get(IMAGE_LOADED) {
+                ctx.set_handled();
+                if loaded.generation != self.generation {
+                    trace!("AsyncImage ignoring stale image for {}", loaded.source);
+                    return;
+                }
+                match &loaded.result {
+                    Ok(image) => {
+                        self.remember(loaded.source.clone(), image.clone());
+                        self.image.widget_mut().set_image_data(image.clone());
+                        self.state = LoadState::Loaded;
+                    }
+                    Err(e) => {
+                        warn!("failed to load image {}: {}", loaded.source, e);
+                        self.state = LoadState::Failed;
+                    }
+                }
+                ctx.request_layout();
+                return;
+            }
+        }
+
+        let hidden = event.should_propagate_to_hidden();
+        if self.state == LoadState::Loading || hidden {
+            self.placeholder.event(ctx, event, data, env);
+        }
+        if self.state == LoadState::Loaded || hidden {
+            self.image.event(ctx, event, data, env);
+        }
+        if self.state == LoadState::Failed || hidden {
+            self.error.event(ctx, event, data, env);
+        }
+    }
+
+    #[instrument(
+        name = "AsyncImage",
+        level = "trace",
+        skip(self, ctx, event, data, env)
+    )]
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &ArcStr, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            self.load(data, ctx.get_external_handle(), ctx.widget_id());
+        }
+
+        let hidden = event.should_propagate_to_hidden();
+        if self.state == LoadState::Loading || hidden {
+            self.placeholder.lifecycle(ctx, event, data, env);
+        }
+        if self.state == LoadState::Loaded || hidden {
+            self.image.lifecycle(ctx, event, data, env);
+        }
+        if self.state == LoadState::Failed || hidden {
+            self.error.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    #[instrument(
+        name = "AsyncImage",
+        level = "trace",
+        skip(self, ctx, old_data, data, env)
+    )]
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &ArcStr, data: &ArcStr, env: &Env) {
+        if old_data != data {
+            self.load(data, ctx.get_external_handle(), ctx.widget_id());
+            ctx.request_layout();
+        }
+        if self.image.is_initialized() {
+            self.image.update(ctx, data, env);
+        }
+        if self.placeholder.is_initialized() {
+            self.placeholder.update(ctx, data, env);
+        }
+        if self.error.is_initialized() {
+            self.error.update(ctx, data, env);
+        }
+    }
+
+    #[instrument(name = "AsyncImage", level = "trace", skip(self, ctx, bc, data, env))]
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &ArcStr,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("AsyncImage");
+        let size = match self.state {
+            LoadState::Loading => {
+                let size = self.placeholder.layout(ctx, bc, data, env);
+                self.placeholder.set_origin(ctx, data, env, Point::ORIGIN);
+                size
+            }
+            LoadState::Loaded => {
+                let size = self.image.layout(ctx, bc, data, env);
+                self.image.set_origin(ctx, data, env, Point::ORIGIN);
+                size
+            }
+            LoadState::Failed => {
+                let size = self.error.layout(ctx, bc, data, env);
+                self.error.set_origin(ctx, data, env, Point::ORIGIN);
+                size
+            }
+        };
+        trace!("Computed size: {}", size);
+        size
+    }
+
+    #[instrument(name = "AsyncImage", level = "trace", skip(self, ctx, data, env))]
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &ArcStr, env: &Env) {
+        match self.state {
+            LoadState::Loading => self.placeholder.paint(ctx, data, env),
+            LoadState::Loaded => self.image.paint(ctx, data, env),
+            LoadState::Failed => self.error.paint(ctx, data, env),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::piet::ImageFormat;
+    use crate::tests::harness::Harness;
+    use crate::widget::Align;
+    use crate::WidgetExt;
+
+    #[test]
+    fn cache_keeps_recent_images() {
+        let image = || ImageBuf::from_raw(vec![0; 4], ImageFormat::Grayscale, 2, 2);
+        let mut widget = AsyncImage::new().with_cache_size(2);
+        widget.remember("a".into(), image());
+        widget.remember("b".into(), image());
+        widget.remember("c".into(), image());
+        assert!(widget.recall(&"a".into()).is_none());
+
+        // showing "b" again makes "c" the least recently shown
+        assert!(widget.recall(&"b".into()).is_some());
+        widget.remember("d".into(), image());
+        assert!(widget.recall(&"c".into()).is_none());
+        assert!(widget.recall(&"b".into()).is_some());
+        assert!(widget.recall(&"d".into()).is_some());
+
+        let mut widget = AsyncImage::new().with_cache_size(0);
+        widget.remember("a".into(), image());
+        assert!(widget.recall(&"a".into()).is_none());
+    }
+
+    #[test]
+    fn ignores_stale_results() {
+        let id = WidgetId::next();
+        let widget = AsyncImage::new()
+            .with_loader(|_| Err("never loads".into()))
+            .with_id(id);
+        let image = |width| {
+            let pixels = vec![0; width * 10];
+            Ok(ImageBuf::from_raw(
+                pixels,
+                ImageFormat::Grayscale,
+                width,
+                10,
+            ))
+        };
+        let loaded = |generation, width| {
+            let result = LoadResult {
+                generation,
+                source: "a".into(),
+                result: image(width),
+            };
+            IMAGE_LOADED.with(result).to(id)
+        };
+
+        Harness::create_simple(ArcStr::from("a"), Align::centered(widget), |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            let loading = harness.get_state(id).layout_rect().size();
+
+            harness.submit_command(loaded(0, 30));
+            harness.just_layout();
+            assert_eq!(harness.get_state(id).layout_rect().size(), loading);
+
+            harness.submit_command(loaded(1, 20));
+            harness.just_layout();
+            assert_eq!(
+                harness.get_state(id).layout_rect().size(),
+                Size::new(20., 10.)
+            );
+        });
+    }
+}
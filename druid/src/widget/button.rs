@@ -14,9 +14,11 @@
 
 //! A button widget.
 
+use std::time::Duration;
+
 use crate::widget::prelude::*;
 use crate::widget::{Click, ControllerHost, Label, LabelText};
-use crate::{theme, Affine, Data, Insets, LinearGradient, UnitPoint};
+use crate::{theme, Data, Insets, LinearGradient, UnitPoint, WidgetPod};
 use tracing::{instrument, trace};
 
 // the minimum padding added to a button.
@@ -24,10 +26,19 @@ use tracing::{instrument, trace};
 // should be reevaluated at some point.
 const LABEL_INSETS: Insets = Insets::uniform_xy(8., 2.);
 
-/// A button with a text label.
+/// A button with a text label, or any other widget as its content.
+///
+/// The button handles all mouse input itself; its content never receives
+/// mouse events.
+///
+/// A button can be disabled with [`WidgetExt::disabled_if`]; a disabled
+/// button is painted with the `DISABLED_BUTTON_*` theme colors, shows no
+/// hover or pressed state, and does not deliver clicks.
+///
+/// [`WidgetExt::disabled_if`]: crate::WidgetExt::disabled_if
 pub struct Button<T> {
-    label: Label<T>,
-    label_size: Size,
+    child: WidgetPod<T, Box<dyn Widget<T>>>,
+    repeat: Option<(Duration, Duration)>,
 }
 
 impl<T: Data> Button<T> {
@@ -70,9 +81,29 @@ impl<T: Data> Button<T> {
     /// [`Label`]: struct.Label.html
     /// [`.on_click`]: #method.on_click
     pub fn from_label(label: Label<T>) -> Button<T> {
+        Button::new_with_child(label)
+    }
+
+    /// Create a new button with an arbitrary widget as its content.
+    ///
+    /// The content is centered in the button, surrounded by some padding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid::widget::{Button, Flex, Label};
+    ///
+    /// let content = Flex::row()
+    ///     .with_child(Label::new("+"))
+    ///     .with_child(Label::new("Increment"));
+    /// let button = Button::new_with_child(content).on_click(|_ctx, data: &mut u32, _env| {
+    ///     *data += 1;
+    /// });
+    /// ```
+    pub fn new_with_child(child: impl Widget<T> + 'static) -> Button<T> {
         Button {
-            label,
-            label_size: Size::ZERO,
+            child: WidgetPod::new(Box::new(child)),
+            repeat: None,
         }
     }
 
@@ -101,18 +132,48 @@ impl<T: Data> Button<T> {
         Button::new(text)
     }
 
+    /// Builder-style method to make the button fire repeatedly while held.
+    ///
+    /// The [`.on_click`] closure is called as soon as the button is pressed,
+    /// then again after `initial_delay`, and then every `interval`, speeding
+    /// up the longer the button is held. This is useful for things like
+    /// increment and decrement buttons.
+    ///
+    /// This must be called before [`.on_click`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use druid::widget::Button;
+    ///
+    /// let button = Button::new("+")
+    ///     .with_repeat(Duration::from_millis(500), Duration::from_millis(100))
+    ///     .on_click(|_ctx, data: &mut u32, _env| *data += 1);
+    /// ```
+    ///
+    /// [`.on_click`]: #method.on_click
+    pub fn with_repeat(mut self, initial_delay: Duration, interval: Duration) -> Self {
+        self.repeat = Some((initial_delay, interval));
+        self
+    }
+
     /// Provide a closure to be called when this button is clicked.
     pub fn on_click(
         self,
         f: impl Fn(&mut EventCtx, &mut T, &Env) + 'static,
     ) -> ControllerHost<Self, Click<T>> {
-        ControllerHost::new(self, Click::new(f))
+        let click = match self.repeat {
+            Some((initial_delay, interval)) => Click::new(f).with_repeat(initial_delay, interval),
+            None => Click::new(f),
+        };
+        ControllerHost::new(self, click)
     }
 }
 
 impl<T: Data> Widget<T> for Button<T> {
-    #[instrument(name = "Button", level = "trace", skip(self, ctx, event, _data, _env))]
-    fn event(&mut self, ctx: &mut EventCtx, event: &Event, _data: &mut T, _env: &Env) {
+    #[instrument(name = "Button", level = "trace", skip(self, ctx, event, data, env))]
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
         match event {
             Event::MouseDown(_) => {
                 if !ctx.is_disabled() {
@@ -128,7 +189,9 @@ impl<T: Data> Widget<T> for Button<T> {
                 }
                 ctx.set_active(false);
             }
-            _ => (),
+            // the button owns the mouse; its content doesn't see it.
+            Event::MouseMove(_) | Event::Wheel(_) => (),
+            _ => self.child.event(ctx, event, data, env),
         }
     }
 
@@ -137,30 +200,37 @@ impl<T: Data> Widget<T> for Button<T> {
         if let LifeCycle::HotChanged(_) | LifeCycle::DisabledChanged(_) = event {
             ctx.request_paint();
         }
-        self.label.lifecycle(ctx, event, data, env)
+        self.child.lifecycle(ctx, event, data, env)
     }
 
-    #[instrument(name = "Button", level = "trace", skip(self, ctx, old_data, data, env))]
-    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
-        self.label.update(ctx, old_data, data, env)
+    #[instrument(
+        name = "Button",
+        level = "trace",
+        skip(self, ctx, _old_data, data, env)
+    )]
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        self.child.update(ctx, data, env)
     }
 
     #[instrument(name = "Button", level = "trace", skip(self, ctx, bc, data, env))]
     fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
         bc.debug_check("Button");
         let padding = Size::new(LABEL_INSETS.x_value(), LABEL_INSETS.y_value());
-        let label_bc = bc.shrink(padding).loosen();
-        self.label_size = self.label.layout(ctx, &label_bc, data, env);
+        let child_bc = bc.shrink(padding).loosen();
+        let child_size = self.child.layout(ctx, &child_bc, data, env);
         // HACK: to make sure we look okay at default sizes when beside a textbox,
         // we make sure we will have at least the same height as the default textbox.
         let min_height = env.get(theme::BORDERED_WIDGET_HEIGHT);
-        let baseline = self.label.baseline_offset();
-        ctx.set_baseline_offset(baseline + LABEL_INSETS.y1);
 
         let button_size = bc.constrain(Size::new(
-            self.label_size.width + padding.width,
-            (self.label_size.height + padding.height).max(min_height),
+            child_size.width + padding.width,
+            (child_size.height + padding.height).max(min_height),
         ));
+        let child_origin = ((button_size - child_size).to_vec2() / 2.0).to_point();
+        self.child.set_origin(ctx, data, env, child_origin);
+
+        let child_bottom = child_origin.y + child_size.height;
+        ctx.set_baseline_offset(self.child.baseline_offset() + button_size.height - child_bottom);
         trace!("Computed button size: {}", button_size);
         button_size
     }
@@ -210,11 +280,6 @@ impl<T: Data> Widget<T> for Button<T> {
 
         ctx.fill(rounded_rect, &bg_gradient);
 
-        let label_offset = (size.to_vec2() - self.label_size.to_vec2()) / 2.0;
-
-        ctx.with_save(|ctx| {
-            ctx.transform(Affine::translate(label_offset));
-            self.label.paint(ctx, data, env);
-        });
+        self.child.paint(ctx, data, env);
     }
 }
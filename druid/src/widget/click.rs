@@ -16,10 +16,15 @@
 //!
 //! [`Controller`]: struct.Controller.html
 
+use std::time::Duration;
+
 use crate::widget::Controller;
-use crate::{Data, Env, Event, EventCtx, LifeCycle, LifeCycleCtx, MouseButton, Widget};
+use crate::{Data, Env, Event, EventCtx, LifeCycle, LifeCycleCtx, MouseButton, TimerToken, Widget};
 use tracing::{instrument, trace};
 
+// The number of repeats after which the repeat interval halves, down to a quarter.
+const REPEAT_ACCELERATION: u32 = 5;
+
 /// A clickable [`Controller`] widget. Pass this and a child widget to a
 /// [`ControllerHost`] to make the child interactive. More conveniently, this is
 /// available as an `on_click` method via [`WidgetExt`]'.
@@ -39,6 +44,10 @@ use tracing::{instrument, trace};
 pub struct Click<T> {
     /// A closure that will be invoked when the child widget is clicked.
     action: Box<dyn Fn(&mut EventCtx, &mut T, &Env)>,
+    /// The initial delay and the interval, if the action repeats while held.
+    repeat: Option<(Duration, Duration)>,
+    timer_id: TimerToken,
+    repeat_count: u32,
 }
 
 impl<T: Data> Click<T> {
@@ -46,8 +55,27 @@ impl<T: Data> Click<T> {
     pub fn new(action: impl Fn(&mut EventCtx, &mut T, &Env) + 'static) -> Self {
         Click {
             action: Box::new(action),
+            repeat: None,
+            timer_id: TimerToken::INVALID,
+            repeat_count: 0,
         }
     }
+
+    /// Builder-style method to make the action repeat while the mouse is held.
+    ///
+    /// The action is invoked as soon as the mouse is pressed rather than when
+    /// it is released. After `initial_delay` it is invoked again every
+    /// `interval`, getting faster the longer the mouse is held. Releasing the
+    /// mouse, or the widget becoming disabled, stops the repeat.
+    pub fn with_repeat(mut self, initial_delay: Duration, interval: Duration) -> Self {
+        self.repeat = Some((initial_delay, interval));
+        self
+    }
+
+    fn stop_repeat(&mut self) {
+        self.timer_id = TimerToken::INVALID;
+        self.repeat_count = 0;
+    }
 }
 
 impl<T: Data, W: Widget<T>> Controller<T, W> for Click<T> {
@@ -63,18 +91,39 @@ impl<T: Data, W: Widget<T>> Controller<T, W> for Click<T> {
                     ctx.set_active(true);
                     ctx.request_paint();
                     trace!("Widget {:?} pressed", ctx.widget_id());
+                    if let Some((initial_delay, _)) = self.repeat {
+                        self.repeat_count = 0;
+                        self.timer_id = ctx.request_timer(initial_delay);
+                        (self.action)(ctx, data, env);
+                    }
                 }
             }
             Event::MouseUp(mouse_event) => {
                 if ctx.is_active() && mouse_event.button == MouseButton::Left {
                     ctx.set_active(false);
-                    if ctx.is_hot() && !ctx.is_disabled() {
+                    if ctx.is_hot() && !ctx.is_disabled() && self.repeat.is_none() {
                         (self.action)(ctx, data, env);
                     }
+                    self.stop_repeat();
                     ctx.request_paint();
                     trace!("Widget {:?} released", ctx.widget_id());
                 }
             }
+            Event::Timer(id) if *id == self.timer_id => {
+                if let (Some((_, interval)), true) = (self.repeat, ctx.is_active()) {
+                    // only fire while the pointer is over the widget, but keep
+                    // the timer running so that coming back resumes the repeat.
+                    if ctx.is_hot() {
+                        (self.action)(ctx, data, env);
+                    }
+                    let speedup = 2u32.pow((self.repeat_count / REPEAT_ACCELERATION).min(2));
+                    self.repeat_count += 1;
+                    self.timer_id = ctx.request_timer(interval / speedup);
+                } else {
+                    self.stop_repeat();
+                }
+                ctx.set_handled();
+            }
             _ => {}
         }
 
@@ -94,8 +143,10 @@ impl<T: Data, W: Widget<T>> Controller<T, W> for Click<T> {
         data: &T,
         env: &Env,
     ) {
-        if let LifeCycle::HotChanged(_) | LifeCycle::FocusChanged(_) = event {
-            ctx.request_paint();
+        match event {
+            LifeCycle::HotChanged(_) | LifeCycle::FocusChanged(_) => ctx.request_paint(),
+            LifeCycle::DisabledChanged(true) => self.stop_repeat(),
+            _ => (),
         }
 
         child.lifecycle(ctx, event, data, env);
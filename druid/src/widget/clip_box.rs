@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+//! A widget that clips and offsets its child, with no scrolling UI of its own.
+
 use crate::kurbo::{Affine, Point, Rect, Size, Vec2};
 use crate::widget::prelude::*;
 use crate::widget::Axis;
@@ -42,17 +44,23 @@ impl Viewport {
     /// fail if the content is smaller than the view, and we return `0.0` in each dimension where
     /// the content is smaller.)
     pub fn clamp_view_origin(&self, origin: Point) -> Point {
-        let x = origin
-            .x
-            .min(self.content_size.width - self.view_size.width)
-            .max(0.0);
-        let y = origin
-            .y
-            .min(self.content_size.height - self.view_size.height)
-            .max(0.0);
+        let max = self.max_view_origin();
+        let x = origin.x.min(max.x).max(0.0);
+        let y = origin.y.min(max.y).max(0.0);
         Point::new(x, y)
     }
 
+    /// The largest origin the view rectangle can have while staying inside the content
+    /// rectangle.
+    ///
+    /// In each dimension where the content is smaller than the view, this is `0.0`.
+    pub fn max_view_origin(&self) -> Point {
+        Point::new(
+            (self.content_size.width - self.view_size.width).max(0.0),
+            (self.content_size.height - self.view_size.height).max(0.0),
+        )
+    }
+
     /// Changes the viewport offset by `delta`, while trying to keep the view rectangle inside the
     /// content rectangle.
     ///
@@ -125,6 +133,17 @@ impl Viewport {
 
 /// A widget exposing a rectangular view into its child, which can be used as a building block for
 /// widgets that scroll their child.
+///
+/// A `ClipBox` clips the painting of its child to its own bounds and translates
+/// events into the child's coordinate space, but it draws nothing else; in particular
+/// it has no scrollbars. Move the view with [`pan_by`] and [`pan_to`], and inspect it
+/// with [`viewport`]. [`Scroll`] is a `ClipBox` combined with a [`ScrollComponent`].
+///
+/// [`pan_by`]: ClipBox::pan_by
+/// [`pan_to`]: ClipBox::pan_to
+/// [`viewport`]: ClipBox::viewport
+/// [`Scroll`]: crate::widget::Scroll
+/// [`ScrollComponent`]: crate::scroll_component::ScrollComponent
 pub struct ClipBox<T, W> {
     child: WidgetPod<T, W>,
     port: Viewport,
@@ -312,7 +331,7 @@ impl<T, W: Widget<T>> ClipBox<T, W> {
 impl<T: Data, W: Widget<T>> Widget<T> for ClipBox<T, W> {
     #[instrument(name = "ClipBox", level = "trace", skip(self, ctx, event, data, env))]
     fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
-        let viewport = ctx.size().to_rect();
+        let viewport = self.port.view_size.to_rect();
         let force_event = self.child.is_hot() || self.child.has_active();
         if let Some(child_event) =
             event.transform_scroll(self.viewport_origin().to_vec2(), viewport, force_event)
@@ -366,7 +385,7 @@ impl<T: Data, W: Widget<T>> Widget<T> for ClipBox<T, W> {
 
     #[instrument(name = "ClipBox", level = "trace", skip(self, ctx, data, env))]
     fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
-        let viewport = ctx.size().to_rect();
+        let viewport = self.port.view_size.to_rect();
         let offset = self.viewport_origin().to_vec2();
         ctx.with_save(|ctx| {
             ctx.clip(viewport);
@@ -384,6 +403,27 @@ mod tests {
     use super::*;
     use test_env_log::test;
 
+    #[test]
+    fn clamp_view_origin() {
+        let mut viewport = Viewport {
+            content_size: Size::new(100., 400.),
+            view_size: (50., 50.).into(),
+            view_origin: Point::ORIGIN,
+        };
+
+        assert!(viewport.pan_to((200., 200.).into()));
+        assert_eq!(viewport.view_origin, Point::new(50., 200.));
+        assert!(viewport.pan_by(Vec2::new(-100., 1000.)));
+        assert_eq!(viewport.view_origin, Point::new(0., 350.));
+        assert!(!viewport.pan_by(Vec2::new(0., 10.)));
+
+        // content smaller than the view can't be panned at all
+        assert_eq!(viewport.max_view_origin(), Point::new(50., 350.));
+        viewport.content_size = Size::new(20., 20.);
+        assert_eq!(viewport.max_view_origin(), Point::ORIGIN);
+        assert_eq!(viewport.clamp_view_origin((10., 10.).into()), Point::ORIGIN);
+    }
+
     #[test]
     fn pan_to_visible() {
         let mut viewport = Viewport {
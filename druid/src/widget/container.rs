@@ -15,20 +15,184 @@
 //! A widget that provides simple visual styling options to a child.
 
 use super::BackgroundBrush;
+use crate::kurbo::{BezPath, Shape};
 use crate::widget::prelude::*;
-use crate::{Color, Data, KeyOrValue, Point, WidgetPod};
+use crate::{
+    Color, Data, Insets, InternalEvent, KeyOrValue, Point, Rect, RoundedRectRadii, Vec2, WidgetPod,
+};
 use tracing::{instrument, trace, trace_span};
 
-struct BorderStyle {
+/// The border of a [`Container`], with a width and color for each side.
+///
+/// Sides that aren't given a width have no border. When the sides differ, each
+/// is painted separately, and adjacent sides meet along the diagonal of their
+/// corner.
+///
+/// # Examples
+///
+/// A separator line under a widget:
+///
+/// ```
+/// use druid::widget::{BorderStyle, Container, Label};
+/// use druid::Color;
+///
+/// let _: Container<()> = Container::new(Label::new("Title"))
+///     .border_style(BorderStyle::default().bottom(Color::grey(0.5), 1.0));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct BorderStyle {
+    left: Option<BorderSide>,
+    top: Option<BorderSide>,
+    right: Option<BorderSide>,
+    bottom: Option<BorderSide>,
+}
+
+#[derive(Clone, Debug)]
+struct BorderSide {
     width: KeyOrValue<f64>,
     color: KeyOrValue<Color>,
 }
 
+/// The resolved width and color of each side, in the order left, top, right, bottom.
+type ResolvedBorder = [(f64, Color); 4];
+
+impl BorderStyle {
+    /// A border with the same color and width on all sides.
+    pub fn new(color: impl Into<KeyOrValue<Color>>, width: impl Into<KeyOrValue<f64>>) -> Self {
+        let side = Some(BorderSide {
+            width: width.into(),
+            color: color.into(),
+        });
+        BorderStyle {
+            left: side.clone(),
+            top: side.clone(),
+            right: side.clone(),
+            bottom: side,
+        }
+    }
+
+    /// Builder-style method to set the color and width of the left side.
+    pub fn left(
+        mut self,
+        color: impl Into<KeyOrValue<Color>>,
+        width: impl Into<KeyOrValue<f64>>,
+    ) -> Self {
+        self.left = Some(BorderSide::new(color, width));
+        self
+    }
+
+    /// Builder-style method to set the color and width of the top side.
+    pub fn top(
+        mut self,
+        color: impl Into<KeyOrValue<Color>>,
+        width: impl Into<KeyOrValue<f64>>,
+    ) -> Self {
+        self.top = Some(BorderSide::new(color, width));
+        self
+    }
+
+    /// Builder-style method to set the color and width of the right side.
+    pub fn right(
+        mut self,
+        color: impl Into<KeyOrValue<Color>>,
+        width: impl Into<KeyOrValue<f64>>,
+    ) -> Self {
+        self.right = Some(BorderSide::new(color, width));
+        self
+    }
+
+    /// Builder-style method to set the color and width of the bottom side.
+    pub fn bottom(
+        mut self,
+        color: impl Into<KeyOrValue<Color>>,
+        width: impl Into<KeyOrValue<f64>>,
+    ) -> Self {
+        self.bottom = Some(BorderSide::new(color, width));
+        self
+    }
+
+    fn sides(&self) -> [Option<&BorderSide>; 4] {
+        [
+            self.left.as_ref(),
+            self.top.as_ref(),
+            self.right.as_ref(),
+            self.bottom.as_ref(),
+        ]
+    }
+
+    /// The width of each side, as insets.
+    fn widths(&self, env: &Env) -> Insets {
+        let width = |side: &Option<BorderSide>| {
+            side.as_ref()
+                .map(|side| side.width.resolve(env))
+                .unwrap_or(0.0)
+        };
+        Insets::new(
+            width(&self.left),
+            width(&self.top),
+            width(&self.right),
+            width(&self.bottom),
+        )
+    }
+
+    fn resolve(&self, env: &Env) -> ResolvedBorder {
+        let resolve = |side: &Option<BorderSide>| match side {
+            Some(side) => (side.width.resolve(env), side.color.resolve(env)),
+            None => (0.0, Color::TRANSPARENT),
+        };
+        [
+            resolve(&self.left),
+            resolve(&self.top),
+            resolve(&self.right),
+            resolve(&self.bottom),
+        ]
+    }
+}
+
+impl BorderSide {
+    fn new(color: impl Into<KeyOrValue<Color>>, width: impl Into<KeyOrValue<f64>>) -> Self {
+        BorderSide {
+            width: width.into(),
+            color: color.into(),
+        }
+    }
+}
+
+/// How far a blurred shadow reaches beyond its rect, in multiples of the blur radius.
+const SHADOW_EXTENT: f64 = 2.5;
+
+struct Shadow {
+    radius: f64,
+    offset: Vec2,
+    color: KeyOrValue<Color>,
+}
+
+impl Shadow {
+    /// The rect to blur, and how much to blur it by, for a widget of `size`.
+    ///
+    /// The render context only blurs plain rects, so rounded corners are
+    /// approximated by shrinking the rect so it doesn't poke out past them, and
+    /// blurring it that much more.
+    fn rect_and_blur(&self, size: Size, radii: RoundedRectRadii) -> (Rect, f64) {
+        let corner = radii
+            .top_left
+            .max(radii.top_right)
+            .max(radii.bottom_left)
+            .max(radii.bottom_right);
+        let shrink = corner * (1.0 - std::f64::consts::FRAC_1_SQRT_2);
+        let rect = size.to_rect().inset(-shrink) + self.offset;
+        (rect, self.radius + shrink)
+    }
+}
+
 /// A widget that provides simple visual styling options to a child.
 pub struct Container<T> {
     background: Option<BackgroundBrush<T>>,
     border: Option<BorderStyle>,
-    corner_radius: KeyOrValue<f64>,
+    corner_radius: KeyOrValue<RoundedRectRadii>,
+    shadow: Option<Shadow>,
+    clip_content: bool,
+    rounded_hit_test: bool,
 
     inner: WidgetPod<T, Box<dyn Widget<T>>>,
 }
@@ -40,6 +204,9 @@ impl<T: Data> Container<T> {
             background: None,
             border: None,
             corner_radius: 0.0.into(),
+            shadow: None,
+            clip_content: false,
+            rounded_hit_test: false,
             inner: WidgetPod::new(inner).boxed(),
         }
     }
@@ -106,10 +273,18 @@ impl<T: Data> Container<T> {
         color: impl Into<KeyOrValue<Color>>,
         width: impl Into<KeyOrValue<f64>>,
     ) {
-        self.border = Some(BorderStyle {
-            color: color.into(),
-            width: width.into(),
-        });
+        self.border = Some(BorderStyle::new(color, width));
+    }
+
+    /// Builder-style method for painting a border whose sides can differ.
+    pub fn border_style(mut self, border: BorderStyle) -> Self {
+        self.set_border_style(border);
+        self
+    }
+
+    /// Paint a border whose sides can differ.
+    pub fn set_border_style(&mut self, border: BorderStyle) {
+        self.border = Some(border);
     }
 
     /// Clears border.
@@ -118,16 +293,91 @@ impl<T: Data> Container<T> {
     }
 
     /// Builder style method for rounding off corners of this container by setting a corner radius
-    pub fn rounded(mut self, radius: impl Into<KeyOrValue<f64>>) -> Self {
+    ///
+    /// This can be a single radius for all corners, a [`RoundedRectRadii`] or a
+    /// `(top_left, top_right, bottom_right, bottom_left)` tuple, or a key for
+    /// either a single radius or a [`RoundedRectRadii`]. The background and
+    /// the border follow the rounded corners.
+    pub fn rounded(mut self, radius: impl Into<KeyOrValue<RoundedRectRadii>>) -> Self {
         self.set_rounded(radius);
         self
     }
 
     /// Round off corners of this container by setting a corner radius
-    pub fn set_rounded(&mut self, radius: impl Into<KeyOrValue<f64>>) {
+    ///
+    /// See [`rounded`](Container::rounded) for what can be passed.
+    pub fn set_rounded(&mut self, radius: impl Into<KeyOrValue<RoundedRectRadii>>) {
         self.corner_radius = radius.into();
     }
 
+    /// Builder-style method for painting a drop shadow behind this container.
+    ///
+    /// The shadow is the container's shape, moved by `offset` and blurred by
+    /// `radius`. It is painted outside the container's bounds, which are
+    /// extended with paint insets to make room for it.
+    pub fn shadow(
+        mut self,
+        radius: f64,
+        offset: impl Into<Vec2>,
+        color: impl Into<KeyOrValue<Color>>,
+    ) -> Self {
+        self.set_shadow(radius, offset, color);
+        self
+    }
+
+    /// Paint a drop shadow behind this container.
+    ///
+    /// See [`shadow`](Container::shadow) for details.
+    pub fn set_shadow(
+        &mut self,
+        radius: f64,
+        offset: impl Into<Vec2>,
+        color: impl Into<KeyOrValue<Color>>,
+    ) {
+        self.shadow = Some(Shadow {
+            radius: radius.max(0.0),
+            offset: offset.into(),
+            color: color.into(),
+        });
+    }
+
+    /// Clears the shadow.
+    pub fn clear_shadow(&mut self) {
+        self.shadow = None;
+    }
+
+    /// Builder-style method to clip the child's painting to the rounded shape
+    /// of this container.
+    ///
+    /// By default the child paints over the rounded corners.
+    pub fn clip_content(mut self, clip: bool) -> Self {
+        self.set_clip_content(clip);
+        self
+    }
+
+    /// Set whether the child's painting is clipped to the rounded shape of
+    /// this container.
+    pub fn set_clip_content(&mut self, clip: bool) {
+        self.clip_content = clip;
+    }
+
+    /// Builder-style method to make the child ignore the mouse in the corners
+    /// that are cut off by the rounding.
+    ///
+    /// The child doesn't get mouse presses or wheel events there, and stops
+    /// being hot when the mouse moves into a corner. A child that is active
+    /// still gets all mouse events.
+    pub fn rounded_hit_test(mut self, rounded: bool) -> Self {
+        self.set_rounded_hit_test(rounded);
+        self
+    }
+
+    /// Set whether the child ignores the mouse in the corners that are cut off
+    /// by the rounding.
+    pub fn set_rounded_hit_test(&mut self, rounded: bool) {
+        self.rounded_hit_test = rounded;
+    }
+
     #[cfg(test)]
     pub(crate) fn background_is_some(&self) -> bool {
         self.background.is_some()
@@ -142,6 +392,22 @@ impl<T: Data> Container<T> {
 impl<T: Data> Widget<T> for Container<T> {
     #[instrument(name = "Container", level = "trace", skip(self, ctx, event, data, env))]
     fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if self.rounded_hit_test && !self.inner.has_active() {
+            if let Event::MouseDown(mouse)
+            | Event::MouseUp(mouse)
+            | Event::MouseMove(mouse)
+            | Event::Wheel(mouse) = event
+            {
+                let shape = ctx.size().to_rounded_rect(self.corner_radius.resolve(env));
+                if !shape.contains(mouse.pos) {
+                    if let Event::MouseMove(_) = event {
+                        let leave = Event::Internal(InternalEvent::MouseLeave);
+                        self.inner.event(ctx, &leave, data, env);
+                    }
+                    return;
+                }
+            }
+        }
         self.inner.event(ctx, event, data, env);
     }
 
@@ -161,6 +427,29 @@ impl<T: Data> Widget<T> for Container<T> {
                 p.update(ctx, old_data, data, env);
             });
         }
+        if let Some(BackgroundBrush::ColorKey(key)) = &self.background {
+            if ctx.env_key_changed(key) {
+                ctx.request_paint();
+            }
+        }
+        if let Some(border) = &self.border {
+            for side in border.sides().iter().flatten() {
+                if ctx.env_key_changed(&side.width) {
+                    ctx.request_layout();
+                }
+                if ctx.env_key_changed(&side.color) {
+                    ctx.request_paint();
+                }
+            }
+        }
+        if ctx.env_key_changed(&self.corner_radius) {
+            ctx.request_paint();
+        }
+        if let Some(shadow) = &self.shadow {
+            if ctx.env_key_changed(&shadow.color) {
+                ctx.request_paint();
+            }
+        }
         self.inner.update(ctx, data, env);
     }
 
@@ -169,22 +458,34 @@ impl<T: Data> Widget<T> for Container<T> {
         bc.debug_check("Container");
 
         // Shrink constraints by border offset
-        let border_width = match &self.border {
-            Some(border) => border.width.resolve(env),
-            None => 0.0,
+        let border = match &self.border {
+            Some(border) => border.widths(env),
+            None => Insets::ZERO,
         };
-        let child_bc = bc.shrink((2.0 * border_width, 2.0 * border_width));
+        let child_bc = bc.shrink((border.x_value(), border.y_value()));
         let size = self.inner.layout(ctx, &child_bc, data, env);
-        let origin = Point::new(border_width, border_width);
+        let origin = Point::new(border.x0, border.y0);
         self.inner.set_origin(ctx, data, env, origin);
 
         let my_size = Size::new(
-            size.width + 2.0 * border_width,
-            size.height + 2.0 * border_width,
+            size.width + border.x_value(),
+            size.height + border.y_value(),
         );
 
-        let my_insets = self.inner.compute_parent_paint_insets(my_size);
+        let mut my_insets = self.inner.compute_parent_paint_insets(my_size);
+        if let Some(shadow) = &self.shadow {
+            let (rect, blur) = shadow.rect_and_blur(my_size, self.corner_radius.resolve(env));
+            let shadow_insets =
+                rect.inflate(blur * SHADOW_EXTENT, blur * SHADOW_EXTENT) - my_size.to_rect();
+            my_insets = Insets::new(
+                my_insets.x0.max(shadow_insets.x0),
+                my_insets.y0.max(shadow_insets.y0),
+                my_insets.x1.max(shadow_insets.x1),
+                my_insets.y1.max(shadow_insets.y1),
+            );
+        }
         ctx.set_paint_insets(my_insets);
+        ctx.set_baseline_offset(self.inner.baseline_offset() + border.y1);
         trace!("Computed layout: size={}, insets={:?}", my_size, my_insets);
         my_size
     }
@@ -193,6 +494,14 @@ impl<T: Data> Widget<T> for Container<T> {
     fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
         let corner_radius = self.corner_radius.resolve(env);
 
+        if let Some(shadow) = &self.shadow {
+            let color = shadow.color.resolve(env);
+            if color.as_rgba8().3 > 0 {
+                let (rect, blur) = shadow.rect_and_blur(ctx.size(), corner_radius);
+                trace_span!("paint shadow").in_scope(|| ctx.blurred_rect(rect, blur, &color));
+            }
+        }
+
         if let Some(background) = self.background.as_mut() {
             let panel = ctx.size().to_rounded_rect(corner_radius);
 
@@ -205,15 +514,57 @@ impl<T: Data> Widget<T> for Container<T> {
         }
 
         if let Some(border) = &self.border {
-            let border_width = border.width.resolve(env);
-            let border_rect = ctx
-                .size()
-                .to_rect()
-                .inset(border_width / -2.0)
-                .to_rounded_rect(corner_radius);
-            ctx.stroke(border_rect, &border.color.resolve(env), border_width);
+            let sides = border.resolve(env);
+            let (width, color) = &sides[0];
+            if sides.iter().all(|(w, c)| w == width && c == color) {
+                let border_rect = ctx
+                    .size()
+                    .to_rect()
+                    .inset(width / -2.0)
+                    .to_rounded_rect(corner_radius);
+                ctx.stroke(border_rect, color, *width);
+            } else {
+                paint_border_sides(ctx, &sides, corner_radius);
+            }
         };
 
-        self.inner.paint(ctx, data, env);
+        if self.clip_content {
+            let inner = &mut self.inner;
+            let shape = ctx.size().to_rounded_rect(corner_radius);
+            ctx.with_save(|ctx| {
+                ctx.clip(shape);
+                inner.paint(ctx, data, env);
+            });
+        } else {
+            self.inner.paint(ctx, data, env);
+        }
     }
 }
+
+/// Paint each side of a border as its own trapezoid, mitered at the corners,
+/// clipped to the rounded outline of the widget.
+fn paint_border_sides(ctx: &mut PaintCtx, sides: &ResolvedBorder, corner_radius: RoundedRectRadii) {
+    let size = ctx.size();
+    let [(l, _), (t, _), (r, _), (b, _)] = *sides;
+    let (w, h) = (size.width, size.height);
+    let outer = [(0.0, 0.0), (w, 0.0), (w, h), (0.0, h)];
+    let inner = [(l, t), (w - r, t), (w - r, h - b), (l, h - b)];
+    // each side runs between two corners, clockwise from the top left.
+    let corners = [(3, 0), (0, 1), (1, 2), (2, 3)];
+
+    ctx.with_save(|ctx| {
+        ctx.clip(size.to_rounded_rect(corner_radius));
+        for ((width, color), (start, end)) in sides.iter().zip(corners.iter()) {
+            if *width <= 0.0 {
+                continue;
+            }
+            let mut path = BezPath::new();
+            path.move_to(outer[*start]);
+            path.line_to(outer[*end]);
+            path.line_to(inner[*end]);
+            path.line_to(inner[*start]);
+            path.close_path();
+            ctx.fill(path, color);
+        }
+    });
+}
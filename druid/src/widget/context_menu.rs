@@ -0,0 +1,84 @@
+// Copyright 2021 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A [`Controller`] that shows a context menu for its child.
+//!
+//! [`Controller`]: struct.Controller.html
+
+use tracing::{instrument, trace};
+
+use crate::widget::Controller;
+use crate::{Data, Env, Event, EventCtx, KbKey, Menu, Point, Widget};
+
+/// A [`Controller`] that shows a context menu when its child is right-clicked.
+///
+/// The menu is also shown when the menu key, or Shift+F10, is pressed while the
+/// child has focus; it then appears at the bottom left corner of the child.
+///
+/// The menu is built by a closure when it is opened, so that it can reflect the
+/// current data, for instance in its checkmarks or in which items are enabled.
+/// The commands of the menu's items are submitted like those of any other menu.
+/// The event that opens the menu is handled, so the child doesn't see it.
+///
+/// Menus work on the application's data, so `U` must be the type of the data
+/// the application was launched with. `T` is the type of the child's data.
+///
+/// This is more conveniently available as the `context_menu` method on
+/// [`WidgetExt`].
+///
+/// [`Controller`]: struct.Controller.html
+/// [`WidgetExt`]: ../trait.WidgetExt.html
+pub struct ContextMenuController<T, U> {
+    make_menu: Box<dyn Fn(&T, &Env) -> Menu<U>>,
+}
+
+impl<T: Data, U: Data> ContextMenuController<T, U> {
+    /// Create a new context menu [`Controller`], with a closure that builds the
+    /// menu from the child's data.
+    ///
+    /// [`Controller`]: struct.Controller.html
+    pub fn new(make_menu: impl Fn(&T, &Env) -> Menu<U> + 'static) -> Self {
+        ContextMenuController {
+            make_menu: Box::new(make_menu),
+        }
+    }
+}
+
+impl<T: Data, U: Data, W: Widget<T>> Controller<T, W> for ContextMenuController<T, U> {
+    #[instrument(
+        name = "ContextMenuController",
+        level = "trace",
+        skip(self, child, ctx, event, data, env)
+    )]
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        let location = match event {
+            Event::MouseDown(mouse) if mouse.button.is_right() => Some(mouse.window_pos),
+            Event::KeyDown(key)
+                if key.key == KbKey::ContextMenu || (key.key == KbKey::F10 && key.mods.shift()) =>
+            {
+                Some(ctx.to_window(Point::new(0.0, ctx.size().height)))
+            }
+            _ => None,
+        };
+
+        if let Some(location) = location {
+            trace!("Showing context menu at {}", location);
+            let menu = (self.make_menu)(data, env);
+            ctx.show_context_menu(menu, location);
+            ctx.set_handled();
+        } else {
+            child.event(ctx, event, data, env);
+        }
+    }
+}
@@ -0,0 +1,271 @@
+// Copyright 2021 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Gesture [`Controller`]s: double click, long press and drag.
+//!
+//! All of these forward events to their child first, and ignore a mouse press
+//! that the child has already handled.
+//!
+//! [`Controller`]: struct.Controller.html
+
+use std::time::{Duration, Instant};
+
+use crate::widget::Controller;
+use crate::{
+    theme, Data, Env, Event, EventCtx, LifeCycle, LifeCycleCtx, MouseButton, Point, TimerToken,
+    Vec2, Widget,
+};
+use tracing::{instrument, trace};
+
+/// A [`Controller`] that invokes an action when its child is double clicked.
+///
+/// Two presses of the left mouse button count as a double click if they are
+/// no more than [`theme::DOUBLE_CLICK_INTERVAL`] apart, and no further apart
+/// than [`theme::GESTURE_SLOP`]. This is available as `on_double_click` via
+/// [`WidgetExt`].
+///
+/// [`Controller`]: struct.Controller.html
+/// [`WidgetExt`]: ../trait.WidgetExt.html
+pub struct DoubleClick<T> {
+    action: Box<dyn Fn(&mut EventCtx, &mut T, &Env)>,
+    /// The time and window position of the previous press.
+    last_press: Option<(Instant, Point)>,
+}
+
+/// A [`Controller`] that invokes an action when the left mouse button is held
+/// down on its child for a given duration.
+///
+/// Releasing the button, or moving further than [`theme::GESTURE_SLOP`],
+/// cancels the long press. This is available as `on_long_press` via
+/// [`WidgetExt`].
+///
+/// [`Controller`]: struct.Controller.html
+/// [`WidgetExt`]: ../trait.WidgetExt.html
+pub struct LongPress<T> {
+    action: Box<dyn Fn(&mut EventCtx, &mut T, &Env)>,
+    duration: Duration,
+    timer_id: TimerToken,
+    origin: Option<Point>,
+}
+
+/// The stage of a drag gesture, as reported by a [`Drag`] controller.
+///
+/// The moved and ended stages carry the total distance moved since the drag
+/// began.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DragEvent {
+    /// The mouse moved far enough with the button held to start a drag.
+    Started,
+    /// The mouse moved during a drag.
+    Moved(Vec2),
+    /// The mouse button was released, ending the drag.
+    Ended(Vec2),
+}
+
+/// A [`Controller`] that reports drags of the left mouse button on its child.
+///
+/// A drag starts once the mouse has moved further than [`theme::GESTURE_SLOP`]
+/// with the button held, so a plain click is not reported. While dragging, the
+/// widget is active and keeps getting mouse events outside its bounds. This is
+/// available as `on_drag` via [`WidgetExt`].
+///
+/// [`Controller`]: struct.Controller.html
+/// [`WidgetExt`]: ../trait.WidgetExt.html
+pub struct Drag<T> {
+    action: Box<dyn Fn(&mut EventCtx, DragEvent, &mut T, &Env)>,
+    origin: Option<Point>,
+    dragging: bool,
+}
+
+impl<T: Data> DoubleClick<T> {
+    /// Create a new double click [`Controller`].
+    ///
+    /// [`Controller`]: struct.Controller.html
+    pub fn new(action: impl Fn(&mut EventCtx, &mut T, &Env) + 'static) -> Self {
+        DoubleClick {
+            action: Box::new(action),
+            last_press: None,
+        }
+    }
+}
+
+impl<T: Data> LongPress<T> {
+    /// Create a new long press [`Controller`] that fires after the button has
+    /// been held for `duration`.
+    ///
+    /// [`Controller`]: struct.Controller.html
+    pub fn new(duration: Duration, action: impl Fn(&mut EventCtx, &mut T, &Env) + 'static) -> Self {
+        LongPress {
+            action: Box::new(action),
+            duration,
+            timer_id: TimerToken::INVALID,
+            origin: None,
+        }
+    }
+
+    fn cancel(&mut self) {
+        self.timer_id = TimerToken::INVALID;
+        self.origin = None;
+    }
+}
+
+impl<T: Data> Drag<T> {
+    /// Create a new drag [`Controller`].
+    ///
+    /// [`Controller`]: struct.Controller.html
+    pub fn new(action: impl Fn(&mut EventCtx, DragEvent, &mut T, &Env) + 'static) -> Self {
+        Drag {
+            action: Box::new(action),
+            origin: None,
+            dragging: false,
+        }
+    }
+}
+
+impl<T: Data, W: Widget<T>> Controller<T, W> for DoubleClick<T> {
+    #[instrument(
+        name = "DoubleClick",
+        level = "trace",
+        skip(self, child, ctx, event, data, env)
+    )]
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        child.event(ctx, event, data, env);
+
+        if let Event::MouseDown(mouse) = event {
+            if mouse.button != MouseButton::Left || ctx.is_handled() || ctx.is_disabled() {
+                self.last_press = None;
+                return;
+            }
+            let now = Instant::now();
+            let interval = Duration::from_millis(env.get(theme::DOUBLE_CLICK_INTERVAL));
+            let slop = env.get(theme::GESTURE_SLOP);
+            match self.last_press.take() {
+                Some((time, pos))
+                    if now.duration_since(time) <= interval
+                        && (mouse.window_pos - pos).hypot() <= slop =>
+                {
+                    trace!("Widget {:?} double clicked", ctx.widget_id());
+                    (self.action)(ctx, data, env);
+                }
+                _ => self.last_press = Some((now, mouse.window_pos)),
+            }
+        }
+    }
+}
+
+impl<T: Data, W: Widget<T>> Controller<T, W> for LongPress<T> {
+    #[instrument(
+        name = "LongPress",
+        level = "trace",
+        skip(self, child, ctx, event, data, env)
+    )]
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        child.event(ctx, event, data, env);
+
+        match event {
+            Event::MouseDown(mouse) => {
+                if mouse.button == MouseButton::Left && !ctx.is_handled() && !ctx.is_disabled() {
+                    ctx.set_active(true);
+                    self.origin = Some(mouse.window_pos);
+                    self.timer_id = ctx.request_timer(self.duration);
+                }
+            }
+            Event::MouseMove(mouse) => {
+                if let Some(origin) = self.origin {
+                    if (mouse.window_pos - origin).hypot() > env.get(theme::GESTURE_SLOP) {
+                        self.timer_id = TimerToken::INVALID;
+                    }
+                }
+            }
+            Event::MouseUp(mouse) => {
+                if mouse.button == MouseButton::Left && self.origin.is_some() {
+                    ctx.set_active(false);
+                    self.cancel();
+                }
+            }
+            Event::Timer(id) if *id == self.timer_id => {
+                self.timer_id = TimerToken::INVALID;
+                trace!("Widget {:?} long pressed", ctx.widget_id());
+                (self.action)(ctx, data, env);
+                ctx.set_handled();
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        child: &mut W,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &T,
+        env: &Env,
+    ) {
+        if let LifeCycle::DisabledChanged(true) = event {
+            self.cancel();
+        }
+        child.lifecycle(ctx, event, data, env);
+    }
+}
+
+impl<T: Data, W: Widget<T>> Controller<T, W> for Drag<T> {
+    #[instrument(
+        name = "Drag",
+        level = "trace",
+        skip(self, child, ctx, event, data, env)
+    )]
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        child.event(ctx, event, data, env);
+
+        match event {
+            Event::MouseDown(mouse) => {
+                if mouse.button == MouseButton::Left && !ctx.is_handled() && !ctx.is_disabled() {
+                    ctx.set_active(true);
+                    self.origin = Some(mouse.window_pos);
+                }
+            }
+            Event::MouseMove(mouse) => {
+                if let Some(origin) = self.origin {
+                    let delta = mouse.window_pos - origin;
+                    if !self.dragging && delta.hypot() > env.get(theme::GESTURE_SLOP) {
+                        self.dragging = true;
+                        trace!("Widget {:?} drag started", ctx.widget_id());
+                        (self.action)(ctx, DragEvent::Started, data, env);
+                    }
+                    if self.dragging {
+                        (self.action)(ctx, DragEvent::Moved(delta), data, env);
+                    }
+                }
+            }
+            Event::MouseUp(mouse) => {
+                if mouse.button == MouseButton::Left {
+                    if let Some(origin) = self.origin.take() {
+                        ctx.set_active(false);
+                        if self.dragging {
+                            self.dragging = false;
+                            trace!("Widget {:?} drag ended", ctx.widget_id());
+                            (self.action)(
+                                ctx,
+                                DragEvent::Ended(mouse.window_pos - origin),
+                                data,
+                                env,
+                            );
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
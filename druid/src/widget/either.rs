@@ -14,16 +14,153 @@
 
 //! A widget that switches dynamically between two child views.
 
+use instant::Duration;
+use tracing::{instrument, trace};
+
 use crate::widget::prelude::*;
-use crate::{Data, Point, WidgetPod};
-use tracing::instrument;
+use crate::{Affine, Data, Point, Vec2, WidgetPod};
 
 /// A widget that switches between two possible child views.
+///
+/// Only the visible view is updated, laid out and painted; the hidden one gets
+/// just the lifecycle events that have to reach every widget, and is brought up
+/// to date when it is shown again.
 pub struct Either<T> {
     closure: Box<dyn Fn(&T, &Env) -> bool>,
     true_branch: WidgetPod<T, Box<dyn Widget<T>>>,
     false_branch: WidgetPod<T, Box<dyn Widget<T>>>,
     current: bool,
+    transition: Transition,
+    progress: TransitionProgress,
+}
+
+/// How a widget that switches between views animates the change.
+///
+/// This is used by [`Either`] and [`ViewSwitcher`].
+///
+/// There is no crossfade: the render contexts have no way to paint a view with
+/// reduced opacity, so the views can only be moved.
+///
+/// [`ViewSwitcher`]: crate::widget::ViewSwitcher
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Transition {
+    /// Switch instantly, with no animation.
+    Instant,
+    /// Slide the old view out to the left, while the new view comes in from
+    /// the right.
+    SlideLeft(Duration),
+    /// Slide the old view out to the right, while the new view comes in from
+    /// the left.
+    SlideRight(Duration),
+}
+
+/// How far a [`Transition`] has got.
+///
+/// This is shared by the widgets that switch between views.
+pub(crate) struct TransitionProgress {
+    /// From `0.0`, when only the outgoing view is visible, to `1.0` when only the
+    /// incoming one is.
+    progress: f64,
+    /// Whether a slide goes the other way, because the transition was reversed.
+    backwards: bool,
+}
+
+impl Transition {
+    fn duration(self) -> Option<Duration> {
+        match self {
+            Transition::Instant => None,
+            Transition::SlideLeft(duration) | Transition::SlideRight(duration) => {
+                Some(duration).filter(|d| d.as_nanos() > 0)
+            }
+        }
+    }
+}
+
+impl Default for Transition {
+    fn default() -> Self {
+        Transition::Instant
+    }
+}
+
+impl TransitionProgress {
+    pub(crate) fn new() -> Self {
+        TransitionProgress {
+            progress: 1.0,
+            backwards: false,
+        }
+    }
+
+    /// Whether both views are visible.
+    pub(crate) fn is_animating(&self) -> bool {
+        self.progress < 1.0
+    }
+
+    /// Start a transition to a new view. If a transition was already running,
+    /// the new view is the one that was going out, and the transition goes back
+    /// from where it was.
+    ///
+    /// Returns `true` if the transition needs animation frames.
+    pub(crate) fn start(&mut self, transition: Transition) -> bool {
+        if transition.duration().is_none() {
+            self.progress = 1.0;
+            return false;
+        }
+        if self.is_animating() {
+            self.progress = 1.0 - self.progress;
+            self.backwards = !self.backwards;
+        } else {
+            self.progress = 0.0;
+            self.backwards = false;
+        }
+        true
+    }
+
+    /// Move the transition forward by `interval` nanoseconds.
+    ///
+    /// Returns `true` if the transition is still running.
+    pub(crate) fn advance(&mut self, transition: Transition, interval: u64) -> bool {
+        match transition.duration() {
+            Some(duration) => {
+                let step = interval as f64 / duration.as_nanos() as f64;
+                self.progress = (self.progress + step).min(1.0);
+            }
+            None => self.progress = 1.0,
+        }
+        self.is_animating()
+    }
+
+    /// Paint the outgoing and the incoming views, according to the progress.
+    pub(crate) fn paint<T: Data>(
+        &self,
+        transition: Transition,
+        ctx: &mut PaintCtx,
+        outgoing: &mut WidgetPod<T, Box<dyn Widget<T>>>,
+        incoming: &mut WidgetPod<T, Box<dyn Widget<T>>>,
+        data: &T,
+        env: &Env,
+    ) {
+        let t = self.progress;
+        let t = t * t * (3.0 - 2.0 * t);
+        let size = ctx.size();
+        match transition {
+            Transition::SlideLeft(_) | Transition::SlideRight(_) => {
+                let to_left = matches!(transition, Transition::SlideLeft(_)) != self.backwards;
+                let offset = if to_left { -size.width } else { size.width };
+                ctx.with_save(|ctx| {
+                    ctx.clip(size.to_rect());
+                    ctx.with_save(|ctx| {
+                        ctx.transform(Affine::translate(Vec2::new(offset * t, 0.0)));
+                        outgoing.paint(ctx, data, env);
+                    });
+                    ctx.with_save(|ctx| {
+                        ctx.transform(Affine::translate(Vec2::new(-offset * (1.0 - t), 0.0)));
+                        incoming.paint(ctx, data, env);
+                    });
+                });
+            }
+            Transition::Instant => incoming.paint(ctx, data, env),
+        }
+    }
 }
 
 impl<T> Either<T> {
@@ -41,8 +178,23 @@ impl<T> Either<T> {
             true_branch: WidgetPod::new(true_branch).boxed(),
             false_branch: WidgetPod::new(false_branch).boxed(),
             current: false,
+            transition: Transition::Instant,
+            progress: TransitionProgress::new(),
         }
     }
+
+    /// Builder-style method for setting how the change between the two views
+    /// is animated.
+    ///
+    /// While the transition runs, both views are laid out and painted, but only
+    /// the incoming view gets mouse and keyboard events. If the closure changes
+    /// its value back during the transition, it is reversed from where it was.
+    ///
+    /// The default is [`Transition::Instant`].
+    pub fn with_transition(mut self, transition: Transition) -> Self {
+        self.transition = transition;
+        self
+    }
 }
 
 impl<T: Data> Widget<T> for Either<T> {
@@ -54,6 +206,18 @@ impl<T: Data> Widget<T> for Either<T> {
         } else {
             self.current_widget().event(ctx, event, data, env)
         }
+
+        if let Event::AnimFrame(interval) = event {
+            if self.progress.is_animating() {
+                if self.progress.advance(self.transition, *interval) {
+                    ctx.request_anim_frame();
+                } else {
+                    trace!("Transition done");
+                    ctx.request_layout();
+                }
+                ctx.request_paint();
+            }
+        }
     }
 
     #[instrument(name = "Either", level = "trace", skip(self, ctx, event, data, env), fields(branch = self.current))]
@@ -75,13 +239,27 @@ impl<T: Data> Widget<T> for Either<T> {
         let current = (self.closure)(data, env);
         if current != self.current {
             self.current = current;
+            if self.progress.start(self.transition) {
+                ctx.request_anim_frame();
+            }
+            // rebuild the focus chain, so the hidden branch gives up focus.
+            ctx.children_changed();
             ctx.request_layout();
         }
-        self.current_widget().update(ctx, data, env)
+        self.current_widget().update(ctx, data, env);
+        if self.progress.is_animating() {
+            // the outgoing view is still painted
+            self.other_widget().update(ctx, data, env);
+        }
     }
 
     #[instrument(name = "Either", level = "trace", skip(self, ctx, bc, data, env), fields(branch = self.current))]
     fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        if self.progress.is_animating() {
+            let other_widget = self.other_widget();
+            other_widget.layout(ctx, bc, data, env);
+            other_widget.set_origin(ctx, data, env, Point::ORIGIN);
+        }
         let current_widget = self.current_widget();
         let size = current_widget.layout(ctx, bc, data, env);
         current_widget.set_origin(ctx, data, env, Point::ORIGIN);
@@ -91,7 +269,17 @@ impl<T: Data> Widget<T> for Either<T> {
 
     #[instrument(name = "Either", level = "trace", skip(self, ctx, data, env), fields(branch = self.current))]
     fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
-        self.current_widget().paint(ctx, data, env)
+        if self.progress.is_animating() {
+            let (incoming, outgoing) = if self.current {
+                (&mut self.true_branch, &mut self.false_branch)
+            } else {
+                (&mut self.false_branch, &mut self.true_branch)
+            };
+            self.progress
+                .paint(self.transition, ctx, outgoing, incoming, data, env);
+        } else {
+            self.current_widget().paint(ctx, data, env)
+        }
     }
 }
 
@@ -103,4 +291,12 @@ impl<T> Either<T> {
             &mut self.false_branch
         }
     }
+
+    fn other_widget(&mut self) -> &mut WidgetPod<T, Box<dyn Widget<T>>> {
+        if self.current {
+            &mut self.false_branch
+        } else {
+            &mut self.true_branch
+        }
+    }
 }
@@ -20,6 +20,14 @@ use crate::{Data, Point, WidgetPod};
 use tracing::instrument;
 
 /// A widget that accepts a closure to update the environment for its child.
+///
+/// The closure also gets the widget's data, so the environment can depend on
+/// it. The closure runs again on every pass, including `update`; when that
+/// changes a key, the descendants see it through [`UpdateCtx::env_key_changed`],
+/// which is how widgets like [`Label`] know to repaint with the new value.
+///
+/// [`UpdateCtx::env_key_changed`]: crate::UpdateCtx::env_key_changed
+/// [`Label`]: crate::widget::Label
 pub struct EnvScope<T, W> {
     pub(crate) f: Box<dyn Fn(&mut Env, &T)>,
     pub(crate) child: WidgetPod<T, W>,
@@ -48,6 +56,24 @@ impl<T, W: Widget<T>> EnvScope<T, W> {
     /// # }
     /// ```
     ///
+    /// The environment can also follow the data:
+    ///
+    /// ```
+    /// # use druid::{theme, Widget};
+    /// # use druid::piet::{Color};
+    /// # use druid::widget::{Label, EnvScope};
+    /// # fn build_widget() -> impl Widget<bool> {
+    /// EnvScope::new(
+    ///     |env, is_error: &bool| {
+    ///         if *is_error {
+    ///             env.set(theme::TEXT_COLOR, Color::rgb8(0xff, 0x00, 0x00));
+    ///         }
+    ///     },
+    ///     Label::new("Red when there is an error")
+    /// )
+    /// # }
+    /// ```
+    ///
     /// [`WidgetExt::env_scope`]: ../trait.WidgetExt.html#method.env_scope
     pub fn new(f: impl Fn(&mut Env, &T) + 'static, child: W) -> EnvScope<T, W> {
         EnvScope {
@@ -0,0 +1,68 @@
+// Copyright 2021 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A [`Controller`] widget that responds to files being dropped on its child.
+//!
+//! [`Controller`]: crate::widget::Controller
+
+use std::path::PathBuf;
+
+use crate::widget::Controller;
+use crate::{Data, Env, Event, EventCtx, Widget};
+use tracing::{instrument, trace};
+
+/// This [`Controller`] widget calls the provided closure with the paths of the
+/// files that are dropped on its child.
+///
+/// The child gets the [`Event::Dropped`] first; the closure is only called if
+/// the child doesn't handle it, so a widget nested inside another one that
+/// accepts files can accept them instead. This is also available, for
+/// convenience, as an `on_file_drop` method via [`WidgetExt`].
+///
+/// [`Controller`]: crate::widget::Controller
+/// [`WidgetExt`]: crate::widget::WidgetExt
+/// [`Event::Dropped`]: crate::Event::Dropped
+pub struct FileDrop<T> {
+    /// A closure that will be invoked with the dropped paths.
+    action: Box<dyn Fn(&mut EventCtx, &[PathBuf], &mut T, &Env)>,
+}
+
+impl<T: Data> FileDrop<T> {
+    /// Create a new [`Controller`] widget that calls `action` when files are dropped.
+    ///
+    /// [`Controller`]: crate::widget::Controller
+    pub fn new(action: impl Fn(&mut EventCtx, &[PathBuf], &mut T, &Env) + 'static) -> Self {
+        FileDrop {
+            action: Box::new(action),
+        }
+    }
+}
+
+impl<T: Data, W: Widget<T>> Controller<T, W> for FileDrop<T> {
+    #[instrument(
+        name = "FileDrop",
+        level = "trace",
+        skip(self, child, ctx, event, data, env)
+    )]
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        child.event(ctx, event, data, env);
+        if let Event::Dropped(_, paths) = event {
+            if !ctx.is_handled() {
+                trace!("Files dropped: {:?}", paths);
+                (self.action)(ctx, paths, data, env);
+                ctx.set_handled();
+            }
+        }
+    }
+}
@@ -140,6 +140,10 @@ pub struct Flex<T> {
     cross_alignment: CrossAxisAlignment,
     main_alignment: MainAxisAlignment,
     fill_major_axis: bool,
+    gap: KeyOrValue<f64>,
+    collapse_empty_gaps: bool,
+    reversed: bool,
+    reversed_cross_axis: bool,
     children: Vec<Child<T>>,
 }
 
@@ -319,6 +323,12 @@ pub enum CrossAxisAlignment {
 ///
 /// If there is surplus space on the main axis after laying out children, this
 /// enum represents how children are laid out in this space.
+///
+/// There is only surplus space if the container has no flex children (or flex
+/// spacers), since those take up all of the space that is left. It comes from
+/// the container's minimum size, or from [`must_fill_main_axis`].
+///
+/// [`must_fill_main_axis`]: Flex::must_fill_main_axis
 #[derive(Debug, Clone, Copy, PartialEq, Data)]
 pub enum MainAxisAlignment {
     /// Top or leading.
@@ -374,6 +384,10 @@ impl<T: Data> Flex<T> {
             cross_alignment: CrossAxisAlignment::Center,
             main_alignment: MainAxisAlignment::Start,
             fill_major_axis: false,
+            gap: KeyOrValue::Concrete(0.0),
+            collapse_empty_gaps: false,
+            reversed: false,
+            reversed_cross_axis: false,
         }
     }
 
@@ -402,6 +416,9 @@ impl<T: Data> Flex<T> {
 
     /// Builder-style method for specifying the childrens' [`MainAxisAlignment`].
     ///
+    /// This has no effect if any children are flex, because they take up all of
+    /// the surplus space.
+    ///
     /// [`MainAxisAlignment`]: enum.MainAxisAlignment.html
     pub fn main_axis_alignment(mut self, alignment: MainAxisAlignment) -> Self {
         self.main_alignment = alignment;
@@ -427,6 +444,69 @@ impl<T: Data> Flex<T> {
         self
     }
 
+    /// Builder-style method for setting the gap between adjacent children.
+    ///
+    /// The gap is fixed space along the main axis, between every pair of
+    /// adjacent children (including spacers) but not before the first or after
+    /// the last. It is set aside before the remaining space is divided between
+    /// the flex children.
+    ///
+    /// The default value is `0.0`.
+    pub fn gap(mut self, gap: impl Into<KeyOrValue<f64>>) -> Self {
+        self.set_gap(gap);
+        self
+    }
+
+    /// Builder-style method for setting whether children with no size on the
+    /// main axis get a [`gap`] next to them.
+    ///
+    /// If this is `true`, non-flex children and spacers whose size on the main
+    /// axis is exactly zero are skipped when placing gaps, so that hiding a
+    /// child doesn't leave a double gap. Flex children always get gaps, because
+    /// their size isn't known until after the gaps are set aside.
+    ///
+    /// The default value is `false`.
+    ///
+    /// [`gap`]: Flex::gap
+    pub fn collapse_empty_gaps(mut self, collapse: bool) -> Self {
+        self.collapse_empty_gaps = collapse;
+        self
+    }
+
+    /// Builder-style method for setting whether the children are placed in
+    /// reverse order along the main axis.
+    ///
+    /// If this is `true`, the last child is placed first: at the left of a row,
+    /// or at the top of a column. Only the placement changes; events, focus
+    /// order and painting still follow the order in which the children were
+    /// added. The [`MainAxisAlignment`] isn't reversed, so a reversed column
+    /// with [`MainAxisAlignment::End`] keeps its first child at the bottom.
+    ///
+    /// The default value is `false`.
+    ///
+    /// [`MainAxisAlignment`]: enum.MainAxisAlignment.html
+    pub fn reversed(mut self, reversed: bool) -> Self {
+        self.reversed = reversed;
+        self
+    }
+
+    /// Builder-style method for setting whether the [`CrossAxisAlignment`]s of
+    /// the children are mirrored.
+    ///
+    /// If this is `true`, [`Start`] aligns children with the trailing edge of
+    /// the cross axis and [`End`] with the leading edge, as for right-to-left
+    /// text in a column. See [`CrossAxisAlignment::mirrored`].
+    ///
+    /// The default value is `false`.
+    ///
+    /// [`CrossAxisAlignment`]: enum.CrossAxisAlignment.html
+    /// [`Start`]: CrossAxisAlignment::Start
+    /// [`End`]: CrossAxisAlignment::End
+    pub fn reversed_cross_axis(mut self, reversed: bool) -> Self {
+        self.reversed_cross_axis = reversed;
+        self
+    }
+
     /// Builder-style variant of `add_child`.
     ///
     /// Convenient for assembling a group of widgets in a single expression.
@@ -435,6 +515,28 @@ impl<T: Data> Flex<T> {
         self
     }
 
+    /// Builder-style variant of `add_child_aligned`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use druid::widget::{CrossAxisAlignment, Flex, Label};
+    ///
+    /// let my_row = Flex::row()
+    ///     .cross_axis_alignment(CrossAxisAlignment::Center)
+    ///     .with_child(Label::new("icon"))
+    ///     .with_child_aligned(Label::new("A long\ndescription"), CrossAxisAlignment::Start);
+    /// # let _: Flex<()> = my_row;
+    /// ```
+    pub fn with_child_aligned(
+        mut self,
+        child: impl Widget<T> + 'static,
+        alignment: CrossAxisAlignment,
+    ) -> Self {
+        self.add_child_aligned(child, alignment);
+        self
+    }
+
     /// Builder-style method to add a flexible child to the container.
     ///
     /// This method is used when you need more control over the behaviour
@@ -470,6 +572,17 @@ impl<T: Data> Flex<T> {
         self
     }
 
+    /// Builder-style method for setting the z-index of the most recently
+    /// added child.
+    ///
+    /// See [`set_z_index`] for details.
+    ///
+    /// [`set_z_index`]: Flex::set_z_index
+    pub fn with_z_index(mut self, z_index: i32) -> Self {
+        self.set_z_index(z_index);
+        self
+    }
+
     /// Builder-style method to add a spacer widget with a standard size.
     ///
     /// The actual value of this spacer depends on whether this container is
@@ -516,6 +629,43 @@ impl<T: Data> Flex<T> {
         self.fill_major_axis = fill;
     }
 
+    /// Set whether the children are placed in reverse order along the main axis.
+    pub fn set_reversed(&mut self, reversed: bool) {
+        self.reversed = reversed;
+    }
+
+    /// Set whether the [`CrossAxisAlignment`]s of the children are mirrored.
+    ///
+    /// [`CrossAxisAlignment`]: enum.CrossAxisAlignment.html
+    pub fn set_reversed_cross_axis(&mut self, reversed: bool) {
+        self.reversed_cross_axis = reversed;
+    }
+
+    /// Set the gap between adjacent children.
+    ///
+    /// See [`gap`] for details.
+    ///
+    /// [`gap`]: Flex::gap
+    pub fn set_gap(&mut self, gap: impl Into<KeyOrValue<f64>>) {
+        let mut value = gap.into();
+        if let KeyOrValue::Concrete(ref mut gap) = value {
+            if *gap < 0.0 {
+                tracing::warn!("Provided gap was less than 0. Value was: {}", gap);
+            }
+            *gap = gap.clamp(0.0, f64::MAX);
+        }
+        self.gap = value;
+    }
+
+    /// Set whether children with no size on the main axis get a gap next to them.
+    ///
+    /// See [`collapse_empty_gaps`] for details.
+    ///
+    /// [`collapse_empty_gaps`]: Flex::collapse_empty_gaps
+    pub fn set_collapse_empty_gaps(&mut self, collapse: bool) {
+        self.collapse_empty_gaps = collapse;
+    }
+
     /// Add a non-flex child widget.
     ///
     /// See also [`with_child`].
@@ -525,6 +675,32 @@ impl<T: Data> Flex<T> {
         let child = Child::Fixed {
             widget: WidgetPod::new(Box::new(child)),
             alignment: None,
+            z_index: 0,
+        };
+        self.children.push(child);
+    }
+
+    /// Add a non-flex child widget with its own [`CrossAxisAlignment`].
+    ///
+    /// The child is aligned with `alignment` instead of the container's
+    /// [`cross_axis_alignment`]. Children that are aligned on their
+    /// [`Baseline`] line up with each other, even if the container uses a
+    /// different alignment.
+    ///
+    /// See also [`with_child_aligned`].
+    ///
+    /// [`cross_axis_alignment`]: Flex::cross_axis_alignment
+    /// [`Baseline`]: CrossAxisAlignment::Baseline
+    /// [`with_child_aligned`]: Flex::with_child_aligned
+    pub fn add_child_aligned(
+        &mut self,
+        child: impl Widget<T> + 'static,
+        alignment: CrossAxisAlignment,
+    ) {
+        let child = Child::Fixed {
+            widget: WidgetPod::new(Box::new(child)),
+            alignment: Some(alignment),
+            z_index: 0,
         };
         self.children.push(child);
     }
@@ -564,17 +740,37 @@ impl<T: Data> Flex<T> {
                 widget: WidgetPod::new(Box::new(child)),
                 alignment: params.alignment,
                 flex: params.flex,
+                z_index: 0,
             }
         } else {
             tracing::warn!("Flex value should be > 0.0. To add a non-flex child use the add_child or with_child methods.\nSee the docs for more information: https://docs.rs/druid/0.7.0/druid/widget/struct.Flex.html");
             Child::Fixed {
                 widget: WidgetPod::new(Box::new(child)),
-                alignment: None,
+                alignment: params.alignment,
+                z_index: 0,
             }
         };
         self.children.push(child);
     }
 
+    /// Set the z-index of the most recently added child.
+    ///
+    /// Children are painted in order of increasing z-index, so a child with a
+    /// higher z-index is painted above its siblings where they overlap (for
+    /// instance a child with paint insets, like a shadow or a drag preview).
+    /// Children with the same z-index are painted in the order they were
+    /// added. The default z-index is `0`. This does not change the layout.
+    ///
+    /// See also [`with_z_index`].
+    ///
+    /// [`with_z_index`]: Flex::with_z_index
+    pub fn set_z_index(&mut self, z_index: i32) {
+        match self.children.last_mut().and_then(Child::z_index_mut) {
+            Some(z) => *z = z_index,
+            None => debug_panic!("set_z_index must be called after adding a child widget"),
+        }
+    }
+
     /// Add a spacer widget with a standard size.
     ///
     /// The actual value of this spacer depends on whether this container is
@@ -657,15 +853,18 @@ impl<T: Data> Widget<T> for Flex<T> {
         // these two are calculated but only used if we're baseline aligned
         let mut max_above_baseline = 0f64;
         let mut max_below_baseline = 0f64;
-        let mut any_use_baseline = self.cross_alignment == CrossAxisAlignment::Baseline;
+        let mut any_use_baseline = false;
 
         // Measure non-flex children.
         let mut major_non_flex = 0.0;
         let mut flex_sum = 0.0;
         for child in &mut self.children {
             match child {
-                Child::Fixed { widget, alignment } => {
-                    any_use_baseline &= *alignment == Some(CrossAxisAlignment::Baseline);
+                Child::Fixed {
+                    widget, alignment, ..
+                } => {
+                    any_use_baseline |=
+                        alignment.unwrap_or(self.cross_alignment) == CrossAxisAlignment::Baseline;
 
                     let child_bc =
                         self.direction
@@ -695,10 +894,29 @@ impl<T: Data> Widget<T> for Flex<T> {
                     *calculated_siz = calculated_siz.max(0.0);
                     major_non_flex += *calculated_siz;
                 }
-                Child::Flex { flex, .. } | Child::FlexedSpacer(flex, _) => flex_sum += *flex,
+                Child::Flex {
+                    flex, alignment, ..
+                } => {
+                    any_use_baseline |=
+                        alignment.unwrap_or(self.cross_alignment) == CrossAxisAlignment::Baseline;
+                    flex_sum += *flex;
+                }
+                Child::FlexedSpacer(flex, _) => flex_sum += *flex,
             }
         }
 
+        // set aside the gaps between the children that get them
+        let gap = self.gap.resolve(env).max(0.0);
+        let collapse_empty_gaps = self.collapse_empty_gaps;
+        let direction = self.direction;
+        let gets_gap: Vec<bool> = self
+            .children
+            .iter()
+            .map(|child| !collapse_empty_gaps || !child.is_empty(direction))
+            .collect();
+        let gap_count = gets_gap.iter().filter(|gets_gap| **gets_gap).count();
+        major_non_flex += gap * gap_count.saturating_sub(1) as f64;
+
         let total_major = self.direction.major(bc.max());
         let remaining = (total_major - major_non_flex).max(0.0);
         let mut remainder: f64 = 0.0;
@@ -755,18 +973,31 @@ impl<T: Data> Widget<T> for Flex<T> {
 
         let mut major = spacing.next().unwrap_or(0.);
         let mut child_paint_rect = Rect::ZERO;
+        let mut any_placed = false;
 
-        for child in &mut self.children {
+        let order: Vec<usize> = if self.reversed {
+            (0..self.children.len()).rev().collect()
+        } else {
+            (0..self.children.len()).collect()
+        };
+        for i in order {
+            let child = &mut self.children[i];
+            if gets_gap[i] {
+                if any_placed {
+                    major += gap;
+                }
+                any_placed = true;
+            }
             match child {
-                Child::Fixed { widget, alignment }
+                Child::Fixed {
+                    widget, alignment, ..
+                }
                 | Child::Flex {
                     widget, alignment, ..
                 } => {
                     let child_size = widget.layout_rect().size();
                     let alignment = alignment.unwrap_or(self.cross_alignment);
                     let child_minor_offset = match alignment {
-                        // This will ignore baseline alignment if it is overridden on children,
-                        // but is not the default for the container. Is this okay?
                         CrossAxisAlignment::Baseline
                             if matches!(self.direction, Axis::Horizontal) =>
                         {
@@ -785,7 +1016,11 @@ impl<T: Data> Widget<T> for Flex<T> {
                         }
                         _ => {
                             let extra_minor = minor_dim - self.direction.minor(child_size);
-                            alignment.align(extra_minor)
+                            if self.reversed_cross_axis {
+                                alignment.mirrored().align(extra_minor)
+                            } else {
+                                alignment.align(extra_minor)
+                            }
                         }
                     };
 
@@ -828,20 +1063,23 @@ impl<T: Data> Widget<T> for Flex<T> {
 
         let baseline_offset = match self.direction {
             Axis::Horizontal => max_below_baseline,
-            Axis::Vertical => (&self.children)
-                .last()
-                .map(|last| {
-                    let child = last.widget();
-                    if let Some(widget) = child {
+            // a column's baseline is that of its first line, like a paragraph's
+            Axis::Vertical => {
+                let mut widgets = self.children.iter().filter_map(|child| child.widget());
+                let first = if self.reversed {
+                    widgets.next_back()
+                } else {
+                    widgets.next()
+                };
+                first
+                    .map(|widget| {
                         let child_bl = widget.baseline_offset();
                         let child_max_y = widget.layout_rect().max_y();
                         let extra_bottom_padding = my_size.height - child_max_y;
                         child_bl + extra_bottom_padding
-                    } else {
-                        0.0
-                    }
-                })
-                .unwrap_or(0.0),
+                    })
+                    .unwrap_or(0.0)
+            }
         };
 
         ctx.set_baseline_offset(baseline_offset);
@@ -855,7 +1093,22 @@ impl<T: Data> Widget<T> for Flex<T> {
 
     #[instrument(name = "Flex", level = "trace", skip(self, ctx, data, env))]
     fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
-        for child in self.children.iter_mut().filter_map(|x| x.widget_mut()) {
+        let mut children: Vec<_> = self
+            .children
+            .iter_mut()
+            .filter_map(|child| match child {
+                Child::Fixed {
+                    widget, z_index, ..
+                }
+                | Child::Flex {
+                    widget, z_index, ..
+                } => Some((*z_index, widget)),
+                _ => None,
+            })
+            .collect();
+        // a stable sort, so children with equal z-index keep their order
+        children.sort_by_key(|(z_index, _)| *z_index);
+        for (_, child) in children {
             child.paint(ctx, data, env);
         }
 
@@ -874,7 +1127,7 @@ impl CrossAxisAlignment {
     /// Given the difference between the size of the container and the size
     /// of the child (on their minor axis) return the necessary offset for
     /// this alignment.
-    fn align(self, val: f64) -> f64 {
+    pub(crate) fn align(self, val: f64) -> f64 {
         match self {
             CrossAxisAlignment::Start => 0.0,
             // in vertical layout, baseline is equivalent to center
@@ -883,6 +1136,19 @@ impl CrossAxisAlignment {
             CrossAxisAlignment::Fill => 0.0,
         }
     }
+
+    /// The alignment that is the mirror image of this one: `Start` and `End`
+    /// are swapped, and the other alignments stay the same.
+    ///
+    /// This is useful for right-to-left layouts, where the leading edge of a
+    /// column is its right edge.
+    pub fn mirrored(self) -> Self {
+        match self {
+            CrossAxisAlignment::Start => CrossAxisAlignment::End,
+            CrossAxisAlignment::End => CrossAxisAlignment::Start,
+            other => other,
+        }
+    }
 }
 
 struct Spacing {
@@ -990,11 +1256,13 @@ enum Child<T> {
     Fixed {
         widget: WidgetPod<T, Box<dyn Widget<T>>>,
         alignment: Option<CrossAxisAlignment>,
+        z_index: i32,
     },
     Flex {
         widget: WidgetPod<T, Box<dyn Widget<T>>>,
         alignment: Option<CrossAxisAlignment>,
         flex: f64,
+        z_index: i32,
     },
     FixedSpacer(KeyOrValue<f64>, f64),
     FlexedSpacer(f64, f64),
@@ -1013,6 +1281,23 @@ impl<T> Child<T> {
             _ => None,
         }
     }
+
+    fn z_index_mut(&mut self) -> Option<&mut i32> {
+        match self {
+            Child::Fixed { z_index, .. } | Child::Flex { z_index, .. } => Some(z_index),
+            _ => None,
+        }
+    }
+
+    /// Whether this is a non-flex child or spacer that was measured to have
+    /// no size on the main axis.
+    fn is_empty(&self, axis: Axis) -> bool {
+        match self {
+            Child::Fixed { widget, .. } => axis.major(widget.layout_rect().size()) == 0.0,
+            Child::FixedSpacer(_, calculated_size) => *calculated_size == 0.0,
+            Child::Flex { .. } | Child::FlexedSpacer(..) => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,428 @@
+// Copyright 2021 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A widget that arranges its children in rows and columns.
+
+use tracing::{instrument, trace, warn};
+
+use crate::widget::prelude::*;
+use crate::{KeyOrValue, Rect, UnitPoint, WidgetPod};
+
+/// A container that arranges its children in rows and columns.
+///
+/// Unlike nested [`Flex`] containers, the columns of a `Grid` line up across all
+/// of its rows, and the rows across all of its columns. Each child goes in the
+/// cell at a given row and column, and can span several rows or columns; see
+/// [`GridParams`].
+///
+/// The size of each row and column is set with a [`GridTrack`]: it can be fixed,
+/// fit its content, or take a share of the space that is left. Rows and columns
+/// that aren't set explicitly fit their content.
+///
+/// Children are laid out with the width of their cell, and are aligned within
+/// their cell according to the grid's [`cell_alignment`].
+///
+/// # Examples
+///
+/// ```
+/// use druid::widget::{Grid, GridParams, GridTrack, Label, TextBox};
+/// use druid::UnitPoint;
+///
+/// let form = Grid::new()
+///     .with_column(GridTrack::Fit)
+///     .with_column(GridTrack::Flex(1.0))
+///     .column_spacing(8.0)
+///     .row_spacing(4.0)
+///     .cell_alignment(UnitPoint::LEFT)
+///     .with_child(Label::new("Account"), GridParams::new(0, 0).span(1, 2))
+///     .with_child(Label::new("Name"), (1, 0))
+///     .with_child(TextBox::new(), (1, 1));
+/// ```
+///
+/// [`Flex`]: crate::widget::Flex
+/// [`cell_alignment`]: Grid::cell_alignment
+pub struct Grid<T> {
+    columns: Vec<GridTrack>,
+    rows: Vec<GridTrack>,
+    column_spacing: KeyOrValue<f64>,
+    row_spacing: KeyOrValue<f64>,
+    cell_alignment: UnitPoint,
+    children: Vec<GridChild<T>>,
+}
+
+struct GridChild<T> {
+    widget: WidgetPod<T, Box<dyn Widget<T>>>,
+    params: GridParams,
+}
+
+/// How large a row or column of a [`Grid`] is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GridTrack {
+    /// A fixed size.
+    Fixed(f64),
+    /// As large as the largest child in it.
+    ///
+    /// Children that span several tracks make the tracks that fit their content
+    /// grow evenly if they don't fit already, unless they also span a flex track.
+    Fit,
+    /// A share of the space that is left after the other tracks, in proportion
+    /// to the given factor.
+    ///
+    /// If the grid is unbounded along this axis there is no space left, and the
+    /// track fits its content instead.
+    Flex(f64),
+}
+
+/// Where a child goes in a [`Grid`].
+///
+/// A `(row, column)` pair converts into `GridParams` for a child that takes up
+/// a single cell.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridParams {
+    row: usize,
+    col: usize,
+    row_span: usize,
+    col_span: usize,
+}
+
+impl GridParams {
+    /// Place a child in the cell at the given row and column.
+    pub fn new(row: usize, col: usize) -> Self {
+        GridParams {
+            row,
+            col,
+            row_span: 1,
+            col_span: 1,
+        }
+    }
+
+    /// Make the child span the given number of rows and columns, from its cell
+    /// towards the bottom right.
+    pub fn span(mut self, rows: usize, cols: usize) -> Self {
+        if rows == 0 || cols == 0 {
+            warn!("A grid child must span at least one row and column");
+        }
+        self.row_span = rows.max(1);
+        self.col_span = cols.max(1);
+        self
+    }
+}
+
+impl From<(usize, usize)> for GridParams {
+    fn from((row, col): (usize, usize)) -> Self {
+        GridParams::new(row, col)
+    }
+}
+
+impl GridTrack {
+    /// Whether the track is sized by its content, given the space available
+    /// along its axis.
+    fn fits_content(self, available: f64) -> bool {
+        match self {
+            GridTrack::Fixed(_) => false,
+            GridTrack::Fit => true,
+            GridTrack::Flex(_) => available.is_infinite(),
+        }
+    }
+}
+
+impl<T: Data> Grid<T> {
+    /// Create a new, empty grid.
+    pub fn new() -> Self {
+        Grid {
+            columns: Vec::new(),
+            rows: Vec::new(),
+            column_spacing: KeyOrValue::Concrete(0.0),
+            row_spacing: KeyOrValue::Concrete(0.0),
+            cell_alignment: UnitPoint::TOP_LEFT,
+            children: Vec::new(),
+        }
+    }
+
+    /// Builder-style method to add a column with the given size, after the
+    /// columns that were added before.
+    pub fn with_column(mut self, column: GridTrack) -> Self {
+        self.columns.push(column);
+        self
+    }
+
+    /// Builder-style method to add a row with the given size, after the rows
+    /// that were added before.
+    pub fn with_row(mut self, row: GridTrack) -> Self {
+        self.rows.push(row);
+        self
+    }
+
+    /// Builder-style method for setting the space between columns.
+    pub fn column_spacing(mut self, spacing: impl Into<KeyOrValue<f64>>) -> Self {
+        self.column_spacing = spacing.into();
+        self
+    }
+
+    /// Builder-style method for setting the space between rows.
+    pub fn row_spacing(mut self, spacing: impl Into<KeyOrValue<f64>>) -> Self {
+        self.row_spacing = spacing.into();
+        self
+    }
+
+    /// Builder-style method for setting how children are aligned within their
+    /// cell, if they are smaller than it.
+    ///
+    /// The default is [`UnitPoint::TOP_LEFT`].
+    pub fn cell_alignment(mut self, alignment: UnitPoint) -> Self {
+        self.cell_alignment = alignment;
+        self
+    }
+
+    /// Builder-style variant of `add_child`.
+    pub fn with_child(
+        mut self,
+        child: impl Widget<T> + 'static,
+        params: impl Into<GridParams>,
+    ) -> Self {
+        self.add_child(child, params);
+        self
+    }
+
+    /// Add a child, in the cell given by `params`.
+    ///
+    /// This takes a `(row, column)` pair, or [`GridParams`] for a child that spans
+    /// several cells.
+    pub fn add_child(&mut self, child: impl Widget<T> + 'static, params: impl Into<GridParams>) {
+        self.children.push(GridChild {
+            widget: WidgetPod::new(Box::new(child)),
+            params: params.into(),
+        });
+    }
+}
+
+impl<T: Data> Default for Grid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Data> Widget<T> for Grid<T> {
+    #[instrument(name = "Grid", level = "trace", skip(self, ctx, event, data, env))]
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        for child in &mut self.children {
+            child.widget.event(ctx, event, data, env);
+        }
+    }
+
+    #[instrument(name = "Grid", level = "trace", skip(self, ctx, event, data, env))]
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        for child in &mut self.children {
+            child.widget.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    #[instrument(name = "Grid", level = "trace", skip(self, ctx, _old_data, data, env))]
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        for child in &mut self.children {
+            child.widget.update(ctx, data, env);
+        }
+    }
+
+    #[instrument(name = "Grid", level = "trace", skip(self, ctx, bc, data, env))]
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        bc.debug_check("Grid");
+        let column_spacing = self.column_spacing.resolve(env);
+        let row_spacing = self.row_spacing.resolve(env);
+        let n_cols = self
+            .children
+            .iter()
+            .map(|child| child.params.col + child.params.col_span)
+            .fold(self.columns.len(), usize::max);
+        let n_rows = self
+            .children
+            .iter()
+            .map(|child| child.params.row + child.params.row_span)
+            .fold(self.rows.len(), usize::max);
+        let columns = &self.columns;
+        let column = |i: usize| columns.get(i).copied().unwrap_or(GridTrack::Fit);
+        let available = bc.max();
+
+        // measure the children in columns that fit their content, at their natural width
+        let natural_bc =
+            BoxConstraints::new(Size::ZERO, Size::new(f64::INFINITY, available.height));
+        let mut column_items = Vec::new();
+        for child in &mut self.children {
+            let GridParams { col, col_span, .. } = child.params;
+            if (col..col + col_span).any(|i| column(i).fits_content(available.width)) {
+                let size = child.widget.layout(ctx, &natural_bc, data, env);
+                column_items.push((col, col_span, size.width));
+            }
+        }
+        let widths = solve_tracks(
+            &self.columns,
+            n_cols,
+            &column_items,
+            column_spacing,
+            available.width,
+        );
+
+        // lay the children out in their columns, to find out how tall they are
+        let mut row_items = Vec::new();
+        for child in &mut self.children {
+            let GridParams {
+                row,
+                col,
+                row_span,
+                col_span,
+            } = child.params;
+            let width = span_size(&widths, col, col_span, column_spacing);
+            let child_bc = BoxConstraints::new(Size::ZERO, Size::new(width, f64::INFINITY));
+            let size = child.widget.layout(ctx, &child_bc, data, env);
+            row_items.push((row, row_span, size.height));
+        }
+        let heights = solve_tracks(
+            &self.rows,
+            n_rows,
+            &row_items,
+            row_spacing,
+            available.height,
+        );
+
+        let column_starts = track_starts(&widths, column_spacing);
+        let row_starts = track_starts(&heights, row_spacing);
+        let mut paint_rect = Rect::ZERO;
+        for child in &mut self.children {
+            let GridParams {
+                row,
+                col,
+                row_span,
+                col_span,
+            } = child.params;
+            let cell = Rect::from_origin_size(
+                (column_starts[col], row_starts[row]),
+                (
+                    span_size(&widths, col, col_span, column_spacing),
+                    span_size(&heights, row, row_span, row_spacing),
+                ),
+            );
+            let child_size = child.widget.layout_rect().size();
+            let extra_width = (cell.width() - child_size.width).max(0.);
+            let extra_height = (cell.height() - child_size.height).max(0.);
+            let offset = self
+                .cell_alignment
+                .resolve(Rect::new(0., 0., extra_width, extra_height))
+                .expand();
+            let origin = cell.origin() + offset.to_vec2();
+            child.widget.set_origin(ctx, data, env, origin);
+            paint_rect = paint_rect.union(child.widget.paint_rect());
+        }
+
+        let my_size = bc.constrain(Size::new(
+            span_size(&widths, 0, n_cols, column_spacing),
+            span_size(&heights, 0, n_rows, row_spacing),
+        ));
+        let insets = paint_rect - my_size.to_rect();
+        ctx.set_paint_insets(insets);
+        trace!("Computed layout: size={}, insets={:?}", my_size, insets);
+        my_size
+    }
+
+    #[instrument(name = "Grid", level = "trace", skip(self, ctx, data, env))]
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        for child in &mut self.children {
+            child.widget.paint(ctx, data, env);
+        }
+    }
+}
+
+/// Work out the size of each track along one axis.
+///
+/// `items` has the first track, the number of tracks and the size along this axis
+/// of the children that were measured, and `available` is the space the grid has
+/// along this axis.
+fn solve_tracks(
+    tracks: &[GridTrack],
+    count: usize,
+    items: &[(usize, usize, f64)],
+    spacing: f64,
+    available: f64,
+) -> Vec<f64> {
+    let track = |i: usize| tracks.get(i).copied().unwrap_or(GridTrack::Fit);
+    let mut sizes: Vec<f64> = (0..count)
+        .map(|i| match track(i) {
+            GridTrack::Fixed(size) => size.max(0.),
+            _ => 0.,
+        })
+        .collect();
+
+    // tracks that fit their content are as large as the largest child only in them,
+    // and then grow for the children that span several tracks
+    for &(start, _, size) in items.iter().filter(|(_, span, _)| *span == 1) {
+        if track(start).fits_content(available) {
+            sizes[start] = sizes[start].max(size);
+        }
+    }
+    for &(start, span, size) in items.iter().filter(|(_, span, _)| *span > 1) {
+        // flex tracks will make room for the child
+        let spans_flex = (start..start + span)
+            .any(|i| matches!(track(i), GridTrack::Flex(_)) && available.is_finite());
+        if spans_flex {
+            continue;
+        }
+        let current = span_size(&sizes, start, span, spacing);
+        let fitting: Vec<usize> = (start..start + span)
+            .filter(|i| track(*i).fits_content(available))
+            .collect();
+        if size > current && !fitting.is_empty() {
+            let extra = (size - current) / fitting.len() as f64;
+            for i in fitting {
+                sizes[i] += extra;
+            }
+        }
+    }
+
+    // flex tracks share what is left
+    if available.is_finite() {
+        let total_flex: f64 = (0..count)
+            .filter_map(|i| match track(i) {
+                GridTrack::Flex(flex) => Some(flex.max(0.)),
+                _ => None,
+            })
+            .sum();
+        if total_flex > 0. {
+            let remaining = (available - span_size(&sizes, 0, count, spacing)).max(0.);
+            for (i, size) in sizes.iter_mut().enumerate() {
+                if let GridTrack::Flex(flex) = track(i) {
+                    *size = remaining * flex.max(0.) / total_flex;
+                }
+            }
+        }
+    }
+    sizes
+}
+
+/// The size of `span` tracks from `start`, including the spacing between them.
+fn span_size(sizes: &[f64], start: usize, span: usize, spacing: f64) -> f64 {
+    let tracks = &sizes[start..start + span];
+    tracks.iter().sum::<f64>() + spacing * tracks.len().saturating_sub(1) as f64
+}
+
+/// Where each track starts.
+fn track_starts(sizes: &[f64], spacing: f64) -> Vec<f64> {
+    let mut pos = 0.;
+    sizes
+        .iter()
+        .map(|size| {
+            let start = pos;
+            pos += size + spacing;
+            start
+        })
+        .collect()
+}
@@ -15,6 +15,8 @@
 //! An Image widget.
 //! Please consider using SVG and the SVG widget as it scales much better.
 
+use std::ops::{Deref, DerefMut};
+
 use crate::{
     kurbo::Rect,
     piet::{Image as _, ImageBuf, InterpolationMode, PietImage},
@@ -201,7 +203,11 @@ impl<T: Data> Widget<T> for Image {
             let ratio = max.height / image_size.height;
             Size::new(ratio * image_size.width, max.height)
         } else {
-            bc.constrain(self.image_data.size())
+            // scale the image down to fit, keeping its aspect ratio
+            let scale = (max.width / image_size.width)
+                .min(max.height / image_size.height)
+                .min(1.0);
+            bc.constrain(image_size * scale)
         };
         trace!("Computed size: {}", size);
         size
@@ -253,6 +259,119 @@ impl<T: Data> Widget<T> for Image {
     }
 }
 
+/// A widget that renders the bitmap image in its [`Data`].
+///
+/// This is an [`Image`] that takes its [`ImageBuf`] from the data, so that
+/// the image can be changed at runtime; it derefs to the [`Image`] for
+/// configuration.
+///
+/// # Example
+///
+/// ```
+/// use druid::{
+///     widget::{FillStrat, RawImage},
+///     piet::{ImageBuf, InterpolationMode},
+///     Widget,
+/// };
+///
+/// let image_widget = RawImage::new()
+///     .fill_mode(FillStrat::Cover)
+///     .interpolation_mode(InterpolationMode::NearestNeighbor);
+/// # let _: &dyn Widget<ImageBuf> = &image_widget;
+/// ```
+///
+/// [`Data`]: crate::Data
+pub struct RawImage {
+    image: Image,
+}
+
+impl RawImage {
+    /// Create a new `RawImage`.
+    pub fn new() -> Self {
+        RawImage {
+            image: Image::new(ImageBuf::empty()),
+        }
+    }
+
+    /// Builder-style method for specifying the fill strategy.
+    pub fn fill_mode(mut self, mode: FillStrat) -> Self {
+        self.image.set_fill_mode(mode);
+        self
+    }
+
+    /// Builder-style method for specifying the interpolation strategy.
+    pub fn interpolation_mode(mut self, interpolation: InterpolationMode) -> Self {
+        self.image.set_interpolation_mode(interpolation);
+        self
+    }
+}
+
+impl Default for RawImage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for RawImage {
+    type Target = Image;
+    fn deref(&self) -> &Self::Target {
+        &self.image
+    }
+}
+
+impl DerefMut for RawImage {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.image
+    }
+}
+
+impl Widget<ImageBuf> for RawImage {
+    #[instrument(name = "RawImage", level = "trace", skip(self, ctx, event, data, env))]
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut ImageBuf, env: &Env) {
+        self.image.event(ctx, event, data, env)
+    }
+
+    #[instrument(name = "RawImage", level = "trace", skip(self, ctx, event, data, env))]
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &ImageBuf, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            self.image.set_image_data(data.clone());
+        }
+        self.image.lifecycle(ctx, event, data, env)
+    }
+
+    #[instrument(
+        name = "RawImage",
+        level = "trace",
+        skip(self, ctx, old_data, data, _env)
+    )]
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &ImageBuf, data: &ImageBuf, _env: &Env) {
+        if !old_data.same(data) {
+            self.image.set_image_data(data.clone());
+            if old_data.size() != data.size() {
+                ctx.request_layout();
+            } else {
+                ctx.request_paint();
+            }
+        }
+    }
+
+    #[instrument(name = "RawImage", level = "trace", skip(self, ctx, bc, data, env))]
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &ImageBuf,
+        env: &Env,
+    ) -> Size {
+        self.image.layout(ctx, bc, data, env)
+    }
+
+    #[instrument(name = "RawImage", level = "trace", skip(self, ctx, data, env))]
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &ImageBuf, env: &Env) {
+        self.image.paint(ctx, data, env)
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 #[cfg(test)]
 mod tests {
@@ -477,6 +596,42 @@ mod tests {
         })
     }
 
+    #[test]
+    fn loose_layout_keeps_aspect_ratio() {
+        use crate::{tests::harness::Harness, widget::Align, WidgetExt, WidgetId};
+        use float_cmp::approx_eq;
+
+        let id_1 = WidgetId::next();
+        let image_data = ImageBuf::from_raw(vec![0; 800 * 400], ImageFormat::Grayscale, 800, 400);
+        let image_widget = Align::centered(Image::new(image_data).with_id(id_1));
+
+        Harness::create_simple(true, image_widget, |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            let state = harness.get_state(id_1);
+            assert!(approx_eq!(f64, state.layout_rect().width(), 400.0));
+            assert!(approx_eq!(f64, state.layout_rect().height(), 200.0));
+        })
+    }
+
+    #[test]
+    fn raw_image_follows_data() {
+        use crate::{tests::harness::Harness, widget::Align, WidgetExt, WidgetId};
+        use float_cmp::approx_eq;
+
+        let id_1 = WidgetId::next();
+        let image_data = ImageBuf::from_raw(vec![0; 20 * 10], ImageFormat::Grayscale, 20, 10);
+        let image_widget = Align::centered(RawImage::new().with_id(id_1));
+
+        Harness::create_simple(image_data, image_widget, |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            let state = harness.get_state(id_1);
+            assert!(approx_eq!(f64, state.layout_rect().width(), 20.0));
+            assert!(approx_eq!(f64, state.layout_rect().height(), 10.0));
+        })
+    }
+
     #[test]
     fn image_clip_area() {
         use crate::{tests::harness::Harness, WidgetId};
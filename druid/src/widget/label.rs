@@ -14,16 +14,17 @@
 
 //! A label widget.
 
-use std::ops::{Deref, DerefMut};
+use std::ops::{Deref, DerefMut, Range};
 
 use druid_shell::Cursor;
 
-use crate::kurbo::Vec2;
-use crate::text::TextStorage;
+use crate::kurbo::{Line, Rect, Vec2};
+use crate::piet::TextLayout as _;
+use crate::text::{word_range_for_pos, Selection, TextStorage};
 use crate::widget::prelude::*;
 use crate::{
-    ArcStr, Color, Data, FontDescriptor, KeyOrValue, LocalizedString, Point, TextAlignment,
-    TextLayout,
+    theme, ArcStr, Color, Data, FontDescriptor, HotKey, KbKey, KeyOrValue, LocalizedString, Point,
+    SysMods, TextAlignment, TextLayout,
 };
 use tracing::{instrument, trace};
 
@@ -92,13 +93,37 @@ pub struct Label<T> {
 /// This requires the `Data` to be `ArcStr`; to handle static, dynamic, or
 /// localized text, use [`Label`].
 ///
+/// If the text contains [`Link`]s (for instance a [`RichText`] built with
+/// [`AttributesAdder::link`]) they are underlined while hovered, and clicking
+/// one submits its command. Labels whose initial text has links also take
+/// part in keyboard focus: `Tab` cycles through the links and `Enter` or
+/// `Space` activates the focused one.
+///
+/// [`Link`]: crate::text::Link
+/// [`RichText`]: crate::text::RichText
+/// [`AttributesAdder::link`]: crate::text::AttributesAdder::link
 /// [`Label`]: struct.Label.html
 pub struct RawLabel<T> {
     layout: TextLayout<T>,
     line_break_mode: LineBreaking,
+    max_lines: Option<usize>,
+    /// Drawn at the end of the last visible line when lines are cut off.
+    ellipsis: TextLayout<ArcStr>,
+    /// If lines are cut off, the visible rect of the last visible line and
+    /// the origin of the ellipsis, relative to the text.
+    truncation: Option<(Rect, Point)>,
 
     disabled: bool,
     default_text_color: KeyOrValue<Color>,
+
+    selectable: bool,
+    selection: Option<Selection>,
+    /// The region selected by the initial click of a drag, and the click count.
+    drag_origin: (Range<usize>, u8),
+    /// The index of the link under the mouse, if any.
+    hovered_link: Option<usize>,
+    /// The index of the link that has keyboard focus, if any.
+    focused_link: Option<usize>,
 }
 
 /// Options for handling lines that are too wide for the label.
@@ -155,11 +180,28 @@ impl<T: TextStorage> RawLabel<T> {
         Self {
             layout: TextLayout::new(),
             line_break_mode: LineBreaking::Overflow,
+            max_lines: None,
+            ellipsis: TextLayout::from_text("\u{2026}"),
+            truncation: None,
             disabled: false,
             default_text_color: crate::theme::TEXT_COLOR.into(),
+            selectable: false,
+            selection: None,
+            drag_origin: (0..0, 1),
+            hovered_link: None,
+            focused_link: None,
         }
     }
 
+    /// Builder-style method to make the label's text selectable.
+    ///
+    /// A selectable label can be selected with the mouse, and the selection
+    /// can be copied to the clipboard.
+    pub fn selectable(mut self) -> Self {
+        self.set_selectable(true);
+        self
+    }
+
     /// Builder-style method for setting the text color.
     ///
     /// The argument can be either a `Color` or a [`Key<Color>`].
@@ -201,6 +243,16 @@ impl<T: TextStorage> RawLabel<T> {
         self
     }
 
+    /// Builder-style method to set the maximum number of lines to show.
+    ///
+    /// See [`set_max_lines`] for details.
+    ///
+    /// [`set_max_lines`]: #method.set_max_lines
+    pub fn with_max_lines(mut self, max_lines: usize) -> Self {
+        self.set_max_lines(Some(max_lines));
+        self
+    }
+
     /// Builder-style method to set the [`TextAlignment`].
     ///
     /// [`TextAlignment`]: enum.TextAlignment.html
@@ -222,6 +274,7 @@ impl<T: TextStorage> RawLabel<T> {
         let color = color.into();
         if !self.disabled {
             self.layout.set_text_color(color.clone());
+            self.ellipsis.set_text_color(color.clone());
         }
         self.default_text_color = color;
     }
@@ -236,6 +289,8 @@ impl<T: TextStorage> RawLabel<T> {
     /// [`request_layout`]: ../struct.EventCtx.html#method.request_layout
     /// [`Key<f64>`]: ../struct.Key.html
     pub fn set_text_size(&mut self, size: impl Into<KeyOrValue<f64>>) {
+        let size = size.into();
+        self.ellipsis.set_text_size(size.clone());
         self.layout.set_text_size(size);
     }
 
@@ -252,6 +307,8 @@ impl<T: TextStorage> RawLabel<T> {
     /// [`FontDescriptor`]: ../struct.FontDescriptor.html
     /// [`Key<FontDescriptor>`]: ../struct.Key.html
     pub fn set_font(&mut self, font: impl Into<KeyOrValue<FontDescriptor>>) {
+        let font = font.into();
+        self.ellipsis.set_font(font.clone());
         self.layout.set_font(font);
     }
 
@@ -266,6 +323,19 @@ impl<T: TextStorage> RawLabel<T> {
         self.line_break_mode = mode;
     }
 
+    /// Set the maximum number of lines to show, or `None` for no limit.
+    ///
+    /// If the text has more lines than this, the label is only as tall as
+    /// the visible lines, and the last of them ends with an ellipsis.
+    ///
+    /// If you change this property, you are responsible for calling
+    /// [`request_layout`] to ensure the label is updated.
+    ///
+    /// [`request_layout`]: ../struct.EventCtx.html#method.request_layout
+    pub fn set_max_lines(&mut self, max_lines: Option<usize>) {
+        self.max_lines = max_lines.filter(|n| *n > 0);
+    }
+
     /// Set the [`TextAlignment`] for this layout.
     ///
     /// [`TextAlignment`]: enum.TextAlignment.html
@@ -273,6 +343,67 @@ impl<T: TextStorage> RawLabel<T> {
         self.layout.set_text_alignment(alignment);
     }
 
+    /// Set whether the label's text can be selected and copied.
+    ///
+    /// If you change this property, you are responsible for calling
+    /// [`request_paint`] to ensure the label is updated.
+    ///
+    /// [`request_paint`]: ../struct.EventCtx.html#method.request_paint
+    pub fn set_selectable(&mut self, selectable: bool) {
+        self.selectable = selectable;
+        if !selectable {
+            self.selection = None;
+        }
+    }
+
+    /// The currently selected range of the text, if any.
+    pub fn selection(&self) -> Option<Selection> {
+        self.selection
+    }
+
+    /// Put the selected text on the clipboard.
+    ///
+    /// Returns `false` if there was no selection to copy.
+    fn copy_selection(&self) -> bool {
+        let text = match (self.selection, self.layout.text()) {
+            (Some(sel), Some(text)) if !sel.is_caret() => text.as_str().get(sel.range()),
+            _ => None,
+        };
+        if let Some(text) = text {
+            crate::Application::global().clipboard().put_string(text);
+        }
+        text.is_some()
+    }
+
+    /// Submit the command of the link at `index`.
+    fn activate_link(&self, ctx: &mut EventCtx, index: usize) {
+        if let Some(link) = self.layout.text().and_then(|t| t.links().get(index)) {
+            ctx.submit_command(link.command.clone());
+        }
+    }
+
+    /// The range that a click with the given count selects at `pos`.
+    fn region_for_pos(&self, pos: usize, click_count: u8) -> Range<usize> {
+        match click_count {
+            1 => pos..pos,
+            2 => {
+                let layout = match self.layout.layout() {
+                    Some(layout) => layout,
+                    None => return pos..pos,
+                };
+                let line_n = layout.hit_test_text_position(pos).line;
+                let (line_start, text) =
+                    match (layout.line_metric(line_n), layout.line_text(line_n)) {
+                        (Some(metric), Some(text)) => (metric.start_offset, text),
+                        _ => return pos..pos,
+                    };
+                let range = word_range_for_pos(text, pos - line_start);
+                range.start + line_start..range.end + line_start
+            }
+            _ => 0..self.layout.text().map(|t| t.as_str().len()).unwrap_or(0),
+        }
+    }
+
     /// Draw this label's text at the provided `Point`, without internal padding.
     ///
     /// This is a convenience for widgets that want to use Label as a way
@@ -371,6 +502,16 @@ impl<T: Data> Label<T> {
         self.text_should_be_updated = true;
     }
 
+    /// Builder-style method to make the label's text selectable.
+    ///
+    /// A selectable label can be selected with the mouse; double-clicking
+    /// selects a word and triple-clicking selects all of the text. The
+    /// selection can be copied to the clipboard.
+    pub fn selectable(mut self) -> Self {
+        self.label.set_selectable(true);
+        self
+    }
+
     /// Builder-style method for setting the text color.
     ///
     /// The argument can be either a `Color` or a [`Key<Color>`].
@@ -412,6 +553,15 @@ impl<T: Data> Label<T> {
         self
     }
 
+    /// Builder-style method to set the maximum number of lines to show.
+    ///
+    /// If the text has more lines than this, the last visible line ends
+    /// with an ellipsis.
+    pub fn with_max_lines(mut self, max_lines: usize) -> Self {
+        self.label.set_max_lines(Some(max_lines));
+        self
+    }
+
     /// Builder-style method to set the [`TextAlignment`].
     ///
     /// [`TextAlignment`]: enum.TextAlignment.html
@@ -487,8 +637,13 @@ impl<T: Data> LabelText<T> {
 }
 
 impl<T: Data> Widget<T> for Label<T> {
-    #[instrument(name = "Label", level = "trace", skip(self, _ctx, _event, _data, _env))]
-    fn event(&mut self, _ctx: &mut EventCtx, _event: &Event, _data: &mut T, _env: &Env) {}
+    #[instrument(name = "Label", level = "trace", skip(self, ctx, event, _data, env))]
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, _data: &mut T, env: &Env) {
+        if self.label.selectable {
+            let mut text = self.current_text.clone();
+            self.label.event(ctx, event, &mut text, env);
+        }
+    }
 
     #[instrument(name = "Label", level = "trace", skip(self, ctx, event, data, env))]
     fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
@@ -536,7 +691,25 @@ impl<T: TextStorage> Widget<T> for RawLabel<T> {
     )]
     fn event(&mut self, ctx: &mut EventCtx, event: &Event, _data: &mut T, _env: &Env) {
         match event {
+            Event::MouseDown(mouse) if self.selectable && mouse.button.is_left() => {
+                let pos = mouse.pos - Vec2::new(LABEL_X_PADDING, 0.0);
+                let pos = self.layout.text_position_for_point(pos);
+                let region = self.region_for_pos(pos, mouse.count);
+                self.selection = Some(Selection::new(region.start, region.end));
+                self.drag_origin = (region, mouse.count);
+                ctx.request_focus();
+                ctx.set_active(true);
+                ctx.request_paint();
+                ctx.set_handled();
+            }
             Event::MouseUp(event) => {
+                if ctx.is_active() {
+                    ctx.set_active(false);
+                }
+                // A drag that selected some text is not a click on a link.
+                if self.selection.map(|sel| !sel.is_caret()).unwrap_or(false) {
+                    return;
+                }
                 // Account for the padding
                 let pos = event.pos - Vec2::new(LABEL_X_PADDING, 0.0);
                 if let Some(link) = self.layout.link_for_pos(pos) {
@@ -547,12 +720,67 @@ impl<T: TextStorage> Widget<T> for RawLabel<T> {
                 // Account for the padding
                 let pos = event.pos - Vec2::new(LABEL_X_PADDING, 0.0);
 
-                if self.layout.link_for_pos(pos).is_some() {
+                if ctx.is_active() && self.selectable {
+                    let (origin, count) = self.drag_origin.clone();
+                    let pos = self.layout.text_position_for_point(pos);
+                    let region = self.region_for_pos(pos, count);
+                    let selection = if region.start < origin.start {
+                        Selection::new(origin.end, region.start)
+                    } else {
+                        Selection::new(origin.start, region.end)
+                    };
+                    self.selection = Some(selection);
+                    ctx.request_paint();
+                }
+
+                let hovered = self.layout.link_index_for_pos(pos);
+                if hovered != self.hovered_link {
+                    self.hovered_link = hovered;
+                    ctx.request_paint();
+                }
+                if hovered.is_some() {
                     ctx.set_cursor(&Cursor::Pointer);
+                } else if self.selectable {
+                    ctx.set_cursor(&Cursor::IBeam);
                 } else {
                     ctx.clear_cursor();
                 }
             }
+            Event::KeyDown(key)
+                if ctx.is_focused() && HotKey::new(SysMods::Cmd, "c").matches(key) =>
+            {
+                self.copy_selection();
+                ctx.set_handled();
+            }
+            Event::Command(cmd) if ctx.is_focused() && cmd.is(crate::commands::COPY) => {
+                self.copy_selection();
+                ctx.set_handled();
+            }
+            Event::KeyDown(key) if ctx.is_focused() && self.focused_link.is_some() => {
+                let n_links = self.layout.text().map(|t| t.links().len()).unwrap_or(0);
+                let current = self.focused_link.unwrap_or(0);
+                match &key.key {
+                    KbKey::Tab if key.mods.shift() => {
+                        if current == 0 {
+                            ctx.focus_prev();
+                        } else {
+                            self.focused_link = Some(current - 1);
+                        }
+                    }
+                    KbKey::Tab => {
+                        if current + 1 >= n_links {
+                            ctx.focus_next();
+                        } else {
+                            self.focused_link = Some(current + 1);
+                        }
+                    }
+                    KbKey::Enter => self.activate_link(ctx, current),
+                    KbKey::Character(c) if c == " " => self.activate_link(ctx, current),
+                    _ => return,
+                }
+                ctx.request_paint();
+                ctx.set_handled();
+            }
             _ => {}
         }
     }
@@ -562,6 +790,21 @@ impl<T: TextStorage> Widget<T> for RawLabel<T> {
         match event {
             LifeCycle::WidgetAdded => {
                 self.layout.set_text(data.to_owned());
+                if !data.links().is_empty() {
+                    ctx.register_for_focus();
+                }
+            }
+            LifeCycle::HotChanged(false) if self.hovered_link.is_some() => {
+                self.hovered_link = None;
+                ctx.request_paint();
+            }
+            // Focus that arrives from a click is for selection, not for links.
+            LifeCycle::FocusChanged(true) if !ctx.is_active() => {
+                let has_links = self.layout.text().map(|t| !t.links().is_empty());
+                if has_links.unwrap_or(false) {
+                    self.focused_link = Some(0);
+                    ctx.request_paint();
+                }
             }
             LifeCycle::DisabledChanged(disabled) => {
                 let color = if *disabled {
@@ -569,9 +812,15 @@ impl<T: TextStorage> Widget<T> for RawLabel<T> {
                 } else {
                     self.default_text_color.clone()
                 };
-                self.layout.set_text_color(color);
+                self.layout.set_text_color(color.clone());
+                self.ellipsis.set_text_color(color);
                 ctx.request_layout();
             }
+            LifeCycle::FocusChanged(false) => {
+                self.selection = None;
+                self.focused_link = None;
+                ctx.request_paint();
+            }
             _ => {}
         }
     }
@@ -584,9 +833,13 @@ impl<T: TextStorage> Widget<T> for RawLabel<T> {
     fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, _env: &Env) {
         if !old_data.same(data) {
             self.layout.set_text(data.clone());
+            self.selection = None;
+            self.hovered_link = None;
+            self.focused_link = self.focused_link.filter(|i| *i < data.links().len());
             ctx.request_layout();
         }
-        if self.layout.needs_rebuild_after_update(ctx) {
+        let ellipsis_changed = self.ellipsis.needs_rebuild_after_update(ctx);
+        if self.layout.needs_rebuild_after_update(ctx) || ellipsis_changed {
             ctx.request_layout();
         }
     }
@@ -604,24 +857,101 @@ impl<T: TextStorage> Widget<T> for RawLabel<T> {
         self.layout.rebuild_if_needed(ctx.text(), env);
 
         let text_metrics = self.layout.layout_metrics();
-        ctx.set_baseline_offset(text_metrics.size.height - text_metrics.first_baseline);
+        let mut text_height = text_metrics.size.height;
+        self.truncation = None;
+        if let (Some(max_lines), Some(layout)) = (self.max_lines, self.layout.layout()) {
+            if let Some(last) = layout
+                .line_metric(max_lines - 1)
+                .filter(|_| layout.line_count() > max_lines)
+            {
+                self.ellipsis.rebuild_if_needed(ctx.text(), env);
+                let ellipsis_width = self.ellipsis.size().width;
+                let max_x = if width.is_finite() {
+                    width
+                } else {
+                    text_metrics.size.width
+                };
+
+                let line_end = last.end_offset - last.trailing_whitespace;
+                let end_x = layout.hit_test_text_position(line_end).point.x;
+                let cutoff = if end_x + ellipsis_width <= max_x {
+                    end_x
+                } else {
+                    // cut at the grapheme boundary closest to where the ellipsis must start
+                    let target = (max_x - ellipsis_width).max(0.0);
+                    let point = Point::new(target, last.y_offset + last.baseline);
+                    let idx = layout.hit_test_point(point).idx;
+                    layout.hit_test_text_position(idx).point.x.min(target)
+                };
+
+                text_height = last.y_offset + last.height;
+                let visible = Rect::new(0.0, last.y_offset, cutoff, text_height);
+                let ellipsis_baseline = self.ellipsis.layout_metrics().first_baseline;
+                let ellipsis_origin =
+                    Point::new(cutoff, last.y_offset + last.baseline - ellipsis_baseline);
+                self.truncation = Some((visible, ellipsis_origin));
+            }
+        }
+
+        ctx.set_baseline_offset(text_height - text_metrics.first_baseline);
         let size = bc.constrain(Size::new(
             text_metrics.size.width + 2. * LABEL_X_PADDING,
-            text_metrics.size.height,
+            text_height,
         ));
         trace!("Computed size: {}", size);
         size
     }
 
-    #[instrument(name = "RawLabel", level = "trace", skip(self, ctx, _data, _env))]
-    fn paint(&mut self, ctx: &mut PaintCtx, _data: &T, _env: &Env) {
+    #[instrument(name = "RawLabel", level = "trace", skip(self, ctx, _data, env))]
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &T, env: &Env) {
         let origin = Point::new(LABEL_X_PADDING, 0.0);
         let label_size = ctx.size();
 
         if self.line_break_mode == LineBreaking::Clip {
             ctx.clip(label_size.to_rect());
         }
-        self.draw_at(ctx, origin)
+        if let Some(selection) = self.selection.filter(|sel| !sel.is_caret()) {
+            let color = env.get(theme::SELECTED_TEXT_BACKGROUND_COLOR);
+            for rect in self.layout.rects_for_range(selection.range()) {
+                ctx.fill(rect + origin.to_vec2(), &color);
+            }
+        }
+        if let Some((visible, ellipsis_origin)) = self.truncation {
+            let layout = &self.layout;
+            let above = Rect::new(0.0, 0.0, label_size.width, visible.y0);
+            ctx.with_save(|ctx| {
+                ctx.clip(above);
+                layout.draw(ctx, origin);
+            });
+            ctx.with_save(|ctx| {
+                ctx.clip(visible + origin.to_vec2());
+                layout.draw(ctx, origin);
+            });
+            self.ellipsis.draw(ctx, ellipsis_origin + origin.to_vec2());
+        } else {
+            self.draw_at(ctx, origin);
+        }
+
+        if let (Some(link), Some(layout)) = (self.hovered_link, self.layout.layout()) {
+            let color = self.default_text_color.resolve(env);
+            for rect in self.layout.link_rects(link) {
+                // underline each rect just below the baseline of its line
+                let idx = layout.hit_test_point(rect.center()).idx;
+                let line = layout.hit_test_text_position(idx).line;
+                let y = layout
+                    .line_metric(line)
+                    .map(|metric| metric.y_offset + metric.baseline + 1.5)
+                    .unwrap_or(rect.y1 - 0.5);
+                let line = Line::new((rect.x0, y), (rect.x1, y));
+                ctx.stroke(line + origin.to_vec2(), &color, 1.0);
+            }
+        }
+        if let Some(link) = self.focused_link {
+            let color = env.get(theme::PRIMARY_LIGHT);
+            for rect in self.layout.link_rects(link) {
+                ctx.stroke(rect + origin.to_vec2(), &color, 1.0);
+            }
+        }
     }
 }
 
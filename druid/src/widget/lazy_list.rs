@@ -0,0 +1,220 @@
+// Copyright 2021 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A list view over items that are produced on demand.
+
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+
+use tracing::{instrument, trace};
+
+use super::list::materialized_range;
+use crate::commands::VIEWPORT_CHANGED;
+use crate::widget::prelude::*;
+use crate::widget::Axis;
+use crate::{KeyOrValue, Point, Rect, Selector, WidgetPod};
+
+/// A virtualized list whose items don't live in the [`Data`] tree.
+///
+/// Instead of a collection, a `LazyList` gets a closure that returns the number of
+/// items, and one that produces the data of the item with a given index. Only the
+/// rows that are visible in an enclosing [`Scroll`] are built, and their data is
+/// produced when they are. Every item has the same size along the list's axis.
+///
+/// The closures are only called again when the list is sent the [`INVALIDATE`]
+/// command, which should be done whenever the items change. The rows are
+/// read-only: changes they make to their data are discarded.
+///
+/// [`Scroll`]: crate::widget::Scroll
+/// [`INVALIDATE`]: LazyList::INVALIDATE
+pub struct LazyList<T> {
+    len_fn: Box<dyn Fn() -> usize>,
+    item_fn: Box<dyn Fn(usize) -> T>,
+    closure: Box<dyn Fn() -> Box<dyn Widget<T>>>,
+    item_extent: f64,
+    axis: Axis,
+    spacing: KeyOrValue<f64>,
+    /// The number of items, as of the last time it was asked for.
+    len: usize,
+    /// The part of the list that is visible in a scrolling ancestor.
+    visible: Option<Rect>,
+    /// The rows that are built, by the index of their item.
+    rows: BTreeMap<usize, Row<T>>,
+}
+
+/// A built row of a [`LazyList`], with the data of its item.
+struct Row<T> {
+    data: T,
+    child: WidgetPod<T, Box<dyn Widget<T>>>,
+}
+
+impl LazyList<()> {
+    /// Get the number of items again, and produce the data of every built row again.
+    ///
+    /// This can be submitted to a `LazyList`, or to every widget to have all lazy
+    /// lists refresh.
+    pub const INVALIDATE: Selector = Selector::new("druid-builtin.lazy-list-invalidate");
+}
+
+impl<T: Data> LazyList<T> {
+    /// Create a new lazy list.
+    ///
+    /// `len` returns the number of items and `item` the data of the item with the
+    /// given index. Every row has the size `item_extent` along the list's axis, and
+    /// the widget of each row is built by `closure`.
+    pub fn new<W: Widget<T> + 'static>(
+        len: impl Fn() -> usize + 'static,
+        item: impl Fn(usize) -> T + 'static,
+        item_extent: f64,
+        closure: impl Fn() -> W + 'static,
+    ) -> Self {
+        LazyList {
+            len_fn: Box::new(len),
+            item_fn: Box::new(item),
+            closure: Box::new(move || Box::new(closure())),
+            item_extent,
+            axis: Axis::Vertical,
+            spacing: KeyOrValue::Concrete(0.),
+            len: 0,
+            visible: None,
+            rows: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the widget to display the list horizontally, not vertically.
+    pub fn horizontal(mut self) -> Self {
+        self.axis = Axis::Horizontal;
+        self
+    }
+
+    /// Set the spacing between elements.
+    pub fn with_spacing(mut self, spacing: impl Into<KeyOrValue<f64>>) -> Self {
+        self.spacing = spacing.into();
+        self
+    }
+
+    /// Build the rows that became visible, and drop the ones that aren't anymore,
+    /// unless they have focus or are active.
+    ///
+    /// Returns `true` if rows were added or removed.
+    fn update_rows(&mut self, env: &Env) -> bool {
+        let stride = self.item_extent + self.spacing.resolve(env);
+        let range = materialized_range(self.axis, self.visible, stride, self.len);
+        let len = self.len;
+        let old_count = self.rows.len();
+        self.rows.retain(|i, row| {
+            *i < len && (range.contains(i) || row.child.has_focus() || row.child.is_active())
+        });
+        let removed = self.rows.len() != old_count;
+
+        let mut added = false;
+        for i in range {
+            if let Entry::Vacant(entry) = self.rows.entry(i) {
+                entry.insert(Row {
+                    data: (self.item_fn)(i),
+                    child: WidgetPod::new((self.closure)()),
+                });
+                added = true;
+            }
+        }
+        added || removed
+    }
+}
+
+impl<T: Data, D: Data> Widget<D> for LazyList<T> {
+    #[instrument(name = "LazyList", level = "trace", skip(self, ctx, event, _data, env))]
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, _data: &mut D, env: &Env) {
+        for row in self.rows.values_mut() {
+            let mut data = row.data.clone();
+            row.child.event(ctx, event, &mut data, env);
+        }
+
+        if let Event::Command(cmd) = event {
+            if let Some(visible) = cmd.get(VIEWPORT_CHANGED) {
+                self.visible = Some(*visible);
+                if self.update_rows(env) {
+                    ctx.children_changed();
+                }
+            } else if cmd.is(LazyList::INVALIDATE) {
+                self.len = (self.len_fn)();
+                if self.update_rows(env) {
+                    ctx.children_changed();
+                }
+                for (i, row) in &mut self.rows {
+                    row.data = (self.item_fn)(*i);
+                }
+                ctx.request_update();
+                ctx.request_layout();
+            }
+        }
+    }
+
+    #[instrument(name = "LazyList", level = "trace", skip(self, ctx, event, _data, env))]
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, _data: &D, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            self.len = (self.len_fn)();
+            if self.update_rows(env) {
+                ctx.children_changed();
+            }
+        }
+
+        for row in self.rows.values_mut() {
+            row.child.lifecycle(ctx, event, &row.data, env);
+        }
+    }
+
+    #[instrument(
+        name = "LazyList",
+        level = "trace",
+        skip(self, ctx, _old_data, _data, env)
+    )]
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &D, _data: &D, env: &Env) {
+        // rows that were just built get their data with `WidgetAdded`
+        for row in self.rows.values_mut() {
+            if row.child.is_initialized() {
+                row.child.update(ctx, &row.data, env);
+            }
+        }
+    }
+
+    #[instrument(name = "LazyList", level = "trace", skip(self, ctx, bc, _data, env))]
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &D, env: &Env) -> Size {
+        let axis = self.axis;
+        let stride = self.item_extent + self.spacing.resolve(env);
+        let child_bc = axis.constraints(bc, self.item_extent, self.item_extent);
+        let mut minor = axis.minor(bc.min());
+        let mut paint_rect = Rect::ZERO;
+        for (i, row) in &mut self.rows {
+            let child_size = row.child.layout(ctx, &child_bc, &row.data, env);
+            minor = minor.max(axis.minor(child_size));
+            let child_pos: Point = axis.pack(*i as f64 * stride, 0.).into();
+            row.child.set_origin(ctx, &row.data, env, child_pos);
+            paint_rect = paint_rect.union(row.child.paint_rect());
+        }
+
+        let major = (self.len as f64 * stride - self.spacing.resolve(env)).max(0.);
+        let my_size = bc.constrain(Size::from(axis.pack(major, minor)));
+        let insets = paint_rect - my_size.to_rect();
+        ctx.set_paint_insets(insets);
+        trace!("Computed layout: size={}, insets={:?}", my_size, insets);
+        my_size
+    }
+
+    #[instrument(name = "LazyList", level = "trace", skip(self, ctx, _data, env))]
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &D, env: &Env) {
+        for row in self.rows.values_mut() {
+            row.child.paint(ctx, &row.data, env);
+        }
+    }
+}
@@ -14,49 +14,403 @@
 
 //! Simple list view widget.
 
-use std::cmp::Ordering;
-use std::collections::VecDeque;
+use std::collections::btree_map::Entry;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 use std::f64;
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::ops::Range;
+use std::rc::Rc;
 use std::sync::Arc;
 
-use tracing::{instrument, trace};
+use instant::Duration;
+use tracing::{instrument, trace, warn};
 
 #[cfg(feature = "im")]
-use crate::im::{OrdMap, Vector};
+use crate::im::{HashMap as ImHashMap, OrdMap, Vector};
 
-use crate::kurbo::{Point, Rect, Size};
+use crate::kurbo::{Point, Rect, Size, Vec2};
 
+use crate::commands::{SCROLL_BY, VIEWPORT_CHANGED};
+use crate::lens::Map;
 use crate::{
-    widget::Axis, BoxConstraints, Data, Env, Event, EventCtx, KeyOrValue, LayoutCtx, LifeCycle,
-    LifeCycleCtx, PaintCtx, UpdateCtx, Widget, WidgetPod,
+    theme,
+    widget::{Axis, CrossAxisAlignment},
+    BoxConstraints, Color, Data, Env, Event, EventCtx, KbKey, KeyOrValue, LayoutCtx, LifeCycle,
+    LifeCycleCtx, PaintCtx, RenderContext, Selector, UpdateCtx, Widget, WidgetExt, WidgetPod,
 };
 
+/// The number of items beyond each end of the visible region that a virtualized
+/// `List` builds, so that small scrolls don't have to wait for new children.
+const VIRTUAL_OVERSCAN: usize = 4;
+
+/// The number of scrolled out children a virtualized `List` keeps, so that they
+/// have the same state when they are scrolled back. The ones farthest from the
+/// visible region are dropped first.
+const MAX_PARKED: usize = 64;
+
+/// How far the pointer has to move after pressing on an item of a reorderable
+/// `List` before the item is picked up.
+const REORDER_THRESHOLD: f64 = 4.0;
+
+/// How close to the edge of the visible region a dragged item has to be for the
+/// enclosing `Scroll` to start scrolling.
+const AUTOSCROLL_MARGIN: f64 = 24.0;
+
+/// How fast, in pixels per second, the enclosing `Scroll` scrolls when a dragged
+/// item is right at the edge of the visible region.
+const AUTOSCROLL_SPEED: f64 = 800.0;
+
+/// How quickly items move out of the way of a dragged item. The distance left to
+/// go decays as `exp(-GAP_SPEED * t)`, with `t` in seconds.
+const GAP_SPEED: f64 = 20.0;
+
 /// A list widget for a variable-size collection of items.
 pub struct List<T> {
-    closure: Box<dyn Fn() -> Box<dyn Widget<T>>>,
-    children: Vec<WidgetPod<T, Box<dyn Widget<T>>>>,
+    closure: Box<dyn Fn() -> Box<dyn ListChild<T>>>,
+    /// The children, by the index of their item.
+    children: BTreeMap<usize, Box<dyn ListChild<T>>>,
+    /// The children of a virtualized list that are scrolled out of view, by the
+    /// index of their item. Up to `MAX_PARKED` of them are kept so that they have
+    /// the same state when they come back.
+    parked: BTreeMap<usize, Box<dyn ListChild<T>>>,
+    /// The children that came back into view, and may have missed changes to their
+    /// items while they were parked.
+    restored: Vec<usize>,
     axis: Axis,
     spacing: KeyOrValue<f64>,
+    /// The size of every item along the axis, if the list is virtualized.
+    item_extent: Option<f64>,
+    /// The part of the list that is visible in a scrolling ancestor.
+    visible: Option<Rect>,
+    /// Whether children are placed in lines across the axis, wrapping as needed.
+    wrap: bool,
+    /// How children are aligned within their line, when wrapping.
+    line_alignment: CrossAxisAlignment,
+    separator_closure: Option<Box<dyn Fn() -> Box<dyn Widget<()>>>>,
+    /// The separators, by the index of the item they follow.
+    separators: BTreeMap<usize, WidgetPod<(), Box<dyn Widget<()>>>>,
+    placeholder_closure: Option<Box<dyn Fn() -> Box<dyn Widget<()>>>>,
+    /// The widget shown instead of the children while there are no items.
+    placeholder: Option<WidgetPod<(), Box<dyn Widget<()>>>>,
+    /// Picks the background of a row from its index and whether it is hovered.
+    row_background: Option<Box<dyn Fn(usize, bool, &Env) -> Option<Color>>>,
+    /// The index of the item under the pointer.
+    hovered: Option<usize>,
+    sections: Option<Sections<T>>,
+    /// The section headers, by the index of the first item of their section.
+    headers: BTreeMap<usize, WidgetPod<T, Box<dyn Widget<T>>>>,
+    /// Hashes the key of an item, if children are matched to items by key.
+    key_fn: Option<Box<dyn Fn(&T) -> u64>>,
+    /// The hashed keys of the items, as of the last time children were matched to them.
+    keys: Vec<u64>,
+    reorderable: bool,
+    /// The item that is pressed or being dragged, in a reorderable list.
+    drag: Option<ReorderDrag>,
+    /// How far items are moved to make room for a dragged item, by index.
+    gaps: BTreeMap<usize, Gap>,
+    /// How long items take to appear and disappear, if that is animated.
+    animation: Option<Duration>,
+    /// How far along appearing children are, from 0 to 1, by index.
+    appearing: BTreeMap<usize, f64>,
+    /// The children of removed items, while they disappear.
+    leaving: Vec<Leaving<T>>,
+}
+
+/// How the items of a [`List`] are grouped into sections with headers.
+struct Sections<T> {
+    /// Whether two consecutive items are in the same section.
+    same: Box<dyn Fn(&T, &T) -> bool>,
+    /// Builds a header, which gets the first item of its section.
+    header: Box<dyn Fn() -> Box<dyn Widget<T>>>,
+}
+
+/// The child of an item that has been removed from a [`List`] with animations.
+struct Leaving<T> {
+    child: Box<dyn ListChild<T>>,
+    /// The item as it was when it was removed.
+    data: T,
+    /// The index the item had when it was removed.
+    index: usize,
+    /// The index of the item this child is shown in front of.
+    before: usize,
+    /// The hashed key of the item, in a keyed list.
+    key: Option<u64>,
+    /// How much of the child is still shown, from 1 down to 0.
+    progress: f64,
+}
+
+/// An item of a reorderable [`List`] that has been pressed, and may be dragged.
+struct ReorderDrag {
+    /// The index of the item.
+    index: usize,
+    /// Where the item was pressed, in the list's coordinates.
+    start: Point,
+    /// Where the pointer is now, in the list's coordinates.
+    pos: Point,
+    /// `true` once the pointer has moved far enough to pick up the item.
+    dragging: bool,
+    /// The index the item would get if it was dropped now.
+    target: usize,
+}
+
+/// How far an item is moved along the list's axis to make room for a dragged item.
+#[derive(Default)]
+struct Gap {
+    current: f64,
+    target: f64,
+}
+
+/// Where an item, and the separator after it, go in a `List` that isn't being
+/// reordered.
+struct Slot {
+    pos: f64,
+    extent: f64,
+    separator_pos: Option<f64>,
+}
+
+impl List<()> {
+    /// Scroll the enclosing [`Scroll`] the minimal distance to show the item with the
+    /// given index, or the last item if the index is out of range.
+    ///
+    /// The list must have been laid out in the `Scroll` for this to have an effect.
+    ///
+    /// [`Scroll`]: crate::widget::Scroll
+    pub const SCROLL_TO_ITEM: Selector<usize> = Selector::new("druid-builtin.list-scroll-to-item");
 }
 
 impl<T: Data> List<T> {
     /// Create a new list widget. Closure will be called every time when a new child
     /// needs to be constructed.
     pub fn new<W: Widget<T> + 'static>(closure: impl Fn() -> W + 'static) -> Self {
+        List::with_child_closure(Box::new(move || {
+            Box::new(WidgetPod::new(Box::new(closure()) as Box<dyn Widget<T>>))
+        }))
+    }
+
+    /// Create a new list widget whose children also know the index of their item.
+    ///
+    /// Each child's data is an `(index, item)` pair. The list keeps the index up
+    /// to date as items are inserted or removed, and only the item is written back
+    /// when a child changes its data.
+    pub fn new_indexed<W: Widget<(usize, T)> + 'static>(closure: impl Fn() -> W + 'static) -> Self {
+        List::with_child_closure(Box::new(move || {
+            Box::new(WidgetPod::new(
+                Box::new(closure()) as Box<dyn Widget<(usize, T)>>
+            ))
+        }))
+    }
+
+    fn with_child_closure(closure: Box<dyn Fn() -> Box<dyn ListChild<T>>>) -> Self {
         List {
-            closure: Box::new(move || Box::new(closure())),
-            children: Vec::new(),
+            closure,
+            children: BTreeMap::new(),
+            parked: BTreeMap::new(),
+            restored: Vec::new(),
             axis: Axis::Vertical,
             spacing: KeyOrValue::Concrete(0.),
+            item_extent: None,
+            visible: None,
+            wrap: false,
+            line_alignment: CrossAxisAlignment::Start,
+            separator_closure: None,
+            separators: BTreeMap::new(),
+            placeholder_closure: None,
+            placeholder: None,
+            row_background: None,
+            hovered: None,
+            sections: None,
+            headers: BTreeMap::new(),
+            key_fn: None,
+            keys: Vec::new(),
+            reorderable: false,
+            drag: None,
+            gaps: BTreeMap::new(),
+            animation: None,
+            appearing: BTreeMap::new(),
+            leaving: Vec::new(),
         }
     }
 
+    /// Show a widget instead of the children while there are no items. The closure
+    /// will be called every time the list becomes empty.
+    ///
+    /// The placeholder gets the list's constraints, and the list takes its size. It
+    /// is dropped as soon as there are items again. To change the data from the
+    /// placeholder, for instance with a "create the first item" button, submit a
+    /// command.
+    pub fn with_placeholder<W: Widget<()> + 'static>(
+        mut self,
+        closure: impl Fn() -> W + 'static,
+    ) -> Self {
+        self.placeholder_closure = Some(Box::new(move || Box::new(closure())));
+        self
+    }
+
+    /// Place a separator between each pair of consecutive items. The closure will be
+    /// called every time a new separator needs to be constructed.
+    ///
+    /// Separators are laid out with the list's width (or height, for a horizontal
+    /// list), and take up space between the items, with the list's spacing on either
+    /// side. In a [virtualized] list they are centered in the spacing instead, so the
+    /// spacing should leave room for them.
+    ///
+    /// [virtualized]: List::virtual_fixed_extent
+    pub fn with_separator<W: Widget<()> + 'static>(
+        mut self,
+        closure: impl Fn() -> W + 'static,
+    ) -> Self {
+        self.separator_closure = Some(Box::new(move || Box::new(closure())));
+        self
+    }
+
+    /// Paint a background behind each row, for instance to stripe the rows or to
+    /// highlight the row under the pointer.
+    ///
+    /// The closure gets the index of the item and whether the pointer is over its
+    /// row, and returns the color to fill the row with, or `None` to leave it
+    /// transparent. Rows span the whole list.
+    pub fn with_row_background(
+        mut self,
+        background: impl Fn(usize, bool, &Env) -> Option<Color> + 'static,
+    ) -> Self {
+        self.row_background = Some(Box::new(background));
+        self
+    }
+
+    /// Group consecutive items into sections, with a header in front of each one.
+    ///
+    /// Consecutive items for which `section` returns the same value are in the same
+    /// section, and the header closure is called to build the header of every new
+    /// section, which gets that value as its data. Headers span the whole list, like
+    /// separators.
+    ///
+    /// When the list is the content of a [`Scroll`], the header of the section at the
+    /// top of the visible region sticks there until the next header pushes it out.
+    /// Headers are drawn on top of the items and get events before them; an event
+    /// handled by a header isn't passed on to the items.
+    ///
+    /// This has no effect on a [virtualized] list.
+    ///
+    /// [`Scroll`]: crate::widget::Scroll
+    /// [virtualized]: List::virtual_fixed_extent
+    pub fn with_sections<K: Data, W: Widget<K> + 'static>(
+        mut self,
+        section: impl Fn(&T) -> K + 'static,
+        header: impl Fn() -> W + 'static,
+    ) -> Self {
+        let section = Rc::new(section);
+        let section_clone = section.clone();
+        self.sections = Some(Sections {
+            same: Box::new(move |a: &T, b: &T| section(a).same(&section(b))),
+            header: Box::new(move || {
+                let section = section_clone.clone();
+                let lens = Map::new(move |item: &T| section(item), |_: &mut T, _| {});
+                Box::new(header().lens(lens))
+            }),
+        });
+        self
+    }
+
+    /// Only build and lay out the children that are visible, with every item having
+    /// the given size along the list's axis: the height of a vertical list, or the
+    /// width of a horizontal one.
+    ///
+    /// This makes lists with a huge number of items cheap, as long as the list is the
+    /// content of a [`Scroll`], which tells it about the visible region with the
+    /// [`VIEWPORT_CHANGED`] command. The list only visits the visible items, and those
+    /// whose children have focus or are active. Other children are set aside when they
+    /// are scrolled out of view, and come back with the same state when they are
+    /// scrolled into view again; in between, they get no events and no updates. Only
+    /// the children of the items nearest to the visible region are set aside, the
+    /// others are dropped and built again if they come back.
+    ///
+    /// [`Scroll`]: crate::widget::Scroll
+    /// [`VIEWPORT_CHANGED`]: crate::commands::VIEWPORT_CHANGED
+    pub fn virtual_fixed_extent(mut self, item_extent: f64) -> Self {
+        self.item_extent = Some(item_extent);
+        self
+    }
+
+    /// Match children to items by a key, instead of by position.
+    ///
+    /// Whenever the data changes, each child follows the item with the same key, so
+    /// that widget state such as focus or the contents of a text box stay with their
+    /// item when other items are inserted, removed or moved. Keys are compared by
+    /// their hash; items whose key is not unique are matched by position.
+    pub fn with_key<K: Hash>(mut self, key: impl Fn(&T) -> K + 'static) -> Self {
+        self.key_fn = Some(Box::new(move |item| {
+            let mut hasher = DefaultHasher::new();
+            key(item).hash(&mut hasher);
+            hasher.finish()
+        }));
+        self
+    }
+
+    /// Animate items appearing and disappearing, over the given duration.
+    ///
+    /// The children of new items are revealed as they grow from nothing to their
+    /// full size along the list's axis, and the children of removed items shrink
+    /// away before they are dropped. In a [keyed] list, an item that comes back while its
+    /// child is still disappearing gets that child back, and the animation reverses.
+    ///
+    /// This has no effect on a [virtualized] list.
+    ///
+    /// [keyed]: List::with_key
+    /// [virtualized]: List::virtual_fixed_extent
+    pub fn with_animation(mut self, duration: Duration) -> Self {
+        self.animation = Some(duration);
+        self
+    }
+
+    /// Let the user reorder the items by dragging them.
+    ///
+    /// Once the pointer has moved a few pixels after pressing on an item, the item
+    /// follows the pointer along the list's axis, drawn on top of the others with a
+    /// [`BORDER_LIGHT`] border, and the other items make room for it. Releasing the pointer moves the item in the data, and pressing Escape
+    /// puts it back. When the list is the content of a [`Scroll`], dragging an item
+    /// near the edge of the visible region scrolls it.
+    ///
+    /// [`Scroll`]: crate::widget::Scroll
+    /// [`BORDER_LIGHT`]: theme::BORDER_LIGHT
+    pub fn reorderable(mut self, reorderable: bool) -> Self {
+        self.reorderable = reorderable;
+        self
+    }
+
     /// Sets the widget to display the list horizontally, not vertically.
     pub fn horizontal(mut self) -> Self {
         self.axis = Axis::Horizontal;
         self
     }
 
+    /// Place the items next to each other across the list's axis, and start a new
+    /// line whenever the next item doesn't fit, like words in a paragraph.
+    ///
+    /// Children get their natural size, with the list's spacing between them and
+    /// between the lines. A vertical list fills rows from left to right, and a
+    /// horizontal list fills columns from top to bottom.
+    ///
+    /// Separators, section headers and reordering are not supported in a wrapping
+    /// list, and this has no effect on a [virtualized] list.
+    ///
+    /// [virtualized]: List::virtual_fixed_extent
+    pub fn wrap(mut self) -> Self {
+        self.wrap = true;
+        self
+    }
+
+    /// Set how children are aligned within their line, in a [wrapping] list.
+    ///
+    /// The default is [`CrossAxisAlignment::Start`].
+    ///
+    /// [wrapping]: List::wrap
+    pub fn with_line_alignment(mut self, alignment: CrossAxisAlignment) -> Self {
+        self.line_alignment = alignment;
+        self
+    }
+
     /// Set the spacing between elements.
     pub fn with_spacing(mut self, spacing: impl Into<KeyOrValue<f64>>) -> Self {
         self.spacing = spacing.into();
@@ -69,26 +423,1004 @@ impl<T: Data> List<T> {
         self
     }
 
-    /// When the widget is created or the data changes, create or remove children as needed
+    /// The indices of the items that should have a child in a virtualized list.
+    fn materialized_range(&self, item_extent: f64, len: usize, env: &Env) -> Range<usize> {
+        let stride = item_extent + self.spacing.resolve(env);
+        materialized_range(self.axis, self.visible, stride, len)
+    }
+
+    /// When the widget is created, the data changes, or different items become visible,
+    /// create or remove children as needed
     ///
     /// Returns `true` if children were added or removed.
-    fn update_child_count(&mut self, data: &impl ListIter<T>, _env: &Env) -> bool {
-        let len = self.children.len();
-        match len.cmp(&data.data_len()) {
-            Ordering::Greater => self.children.truncate(data.data_len()),
-            Ordering::Less => data.for_each(|_, i| {
-                if i >= len {
-                    let child = WidgetPod::new((self.closure)());
-                    self.children.push(child);
+    fn update_child_count(&mut self, data: &impl ListIter<T>, env: &Env) -> bool {
+        let len = data.data_len();
+        let children_changed = self.update_children(data, env);
+        let separators_changed = self.update_separators(len);
+        let headers_changed = self.update_headers(data);
+        self.update_placeholder(len) || separators_changed || headers_changed || children_changed
+    }
+
+    /// Create the placeholder when the list becomes empty, and drop it when items
+    /// appear.
+    fn update_placeholder(&mut self, len: usize) -> bool {
+        let closure = match &self.placeholder_closure {
+            Some(closure) => closure,
+            None => return false,
+        };
+        match (len, self.placeholder.is_some()) {
+            (0, false) => {
+                self.placeholder = Some(WidgetPod::new(closure()));
+                // the placeholder takes over right away
+                self.leaving.clear();
+                true
+            }
+            (0, true) | (_, false) => false,
+            (_, true) => {
+                self.placeholder = None;
+                true
+            }
+        }
+    }
+
+    /// The hashed keys of the items, if children are matched to items by key.
+    fn item_keys(&self, data: &impl ListIter<T>) -> Option<Vec<u64>> {
+        let key_fn = self.key_fn.as_ref()?;
+        let mut keys = Vec::with_capacity(data.data_len());
+        data.for_each(|item, _| keys.push(key_fn(item)));
+        Some(keys)
+    }
+
+    /// Whether items appear and disappear gradually.
+    fn animates(&self) -> bool {
+        self.animation.is_some() && self.item_extent.is_none() && !self.wrap
+    }
+
+    /// Whether children are placed in lines across the axis.
+    fn wraps(&self) -> bool {
+        self.wrap && self.item_extent.is_none()
+    }
+
+    /// The area of the row of a child, for its background.
+    fn row_rect(&self, child_rect: Rect, minor: f64) -> Rect {
+        if self.wraps() {
+            return child_rect;
+        }
+        let (start, end) = self.axis.major_span(child_rect);
+        Rect::from_points(self.axis.pack(start, 0.), self.axis.pack(end, minor))
+    }
+
+    /// Move the children of a keyed list to the new indices of their items, and drop
+    /// the children whose items are gone, or let them disappear gradually.
+    ///
+    /// This leaves holes for new items, which are filled by [`update_child_count`].
+    /// Returns `true` if children were removed.
+    ///
+    /// [`update_child_count`]: List::update_child_count
+    fn match_keys(&mut self, data: &impl ListIter<T>, old_data: &impl ListIter<T>) -> bool {
+        let keys = match self.item_keys(data) {
+            Some(keys) => keys,
+            None => return false,
+        };
+        if keys == self.keys {
+            return false;
+        }
+
+        // the old index of each key, or `None` if the key isn't unique
+        let mut old_indices: HashMap<u64, Option<usize>> = HashMap::new();
+        for (i, key) in self.keys.iter().enumerate() {
+            old_indices
+                .entry(*key)
+                .and_modify(|index| *index = None)
+                .or_insert(Some(i));
+        }
+        let mut new_counts: HashMap<u64, usize> = HashMap::new();
+        for key in &keys {
+            *new_counts.entry(*key).or_default() += 1;
+        }
+        if new_counts.len() < keys.len() {
+            warn!("List: duplicate keys, matching those items by position");
+        }
+
+        let mut old_children = mem::take(&mut self.children);
+        let mut old_parked = mem::take(&mut self.parked);
+        let mut old_appearing = mem::take(&mut self.appearing);
+        let mut moves = Vec::new();
+        let mut duplicates = Vec::new();
+        for (i, key) in keys.iter().enumerate() {
+            match old_indices.get(key) {
+                Some(Some(old_index)) if new_counts[key] == 1 => moves.push((*old_index, i)),
+                Some(_) => duplicates.push(i),
+                None if new_counts[key] == 1 => {
+                    // the item came back before its child was done disappearing
+                    let leaving = self.leaving.iter().position(|l| l.key == Some(*key));
+                    if let Some(leaving) = leaving.map(|pos| self.leaving.remove(pos)) {
+                        self.children.insert(i, leaving.child);
+                        self.appearing.insert(i, leaving.progress);
+                    }
                 }
-            }),
-            Ordering::Equal => (),
+                None => (),
+            }
+        }
+        moves.extend(duplicates.into_iter().map(|i| (i, i)));
+        for (old_index, i) in moves {
+            if let Some(child) = old_children.remove(&old_index) {
+                self.children.insert(i, child);
+                if let Some(progress) = old_appearing.remove(&old_index) {
+                    self.appearing.insert(i, progress);
+                }
+            } else if let Some(child) = old_parked.remove(&old_index) {
+                self.parked.insert(i, child);
+            }
+        }
+
+        if self.animates() {
+            // removed children are shown in front of the next item that is still there
+            let new_indices: HashMap<u64, usize> = keys
+                .iter()
+                .enumerate()
+                .filter(|(_, key)| new_counts[*key] == 1)
+                .map(|(i, key)| (*key, i))
+                .collect();
+            let old_keys = &self.keys;
+            let next_kept = |from: usize| {
+                old_keys[from.min(old_keys.len())..]
+                    .iter()
+                    .find_map(|key| new_indices.get(key).copied())
+                    .unwrap_or(keys.len())
+            };
+            for leaving in &mut self.leaving {
+                leaving.before = next_kept(leaving.before);
+            }
+            retire(
+                &mut self.leaving,
+                &mut old_children,
+                &mut old_appearing,
+                old_data,
+                |index| next_kept(index + 1),
+                |index| old_keys.get(index).copied(),
+            );
+        }
+        self.keys = keys;
+        !old_children.is_empty()
+    }
+
+    /// Create or remove item children as needed.
+    fn update_children(&mut self, data: &impl ListIter<T>, env: &Env) -> bool {
+        let len = data.data_len();
+        let contiguous = self
+            .children
+            .keys()
+            .next_back()
+            .map_or(true, |last| last + 1 == self.children.len());
+        let range = match self.item_extent {
+            Some(item_extent) => self.materialized_range(item_extent, len, env),
+            // every item has a child, so unless a keyed list has moved its children
+            // around, only the end of the list can change
+            None if contiguous => {
+                let old_len = self.children.len();
+                for i in len..old_len {
+                    self.children.remove(&i);
+                }
+                for i in old_len..len {
+                    self.children.insert(i, (self.closure)());
+                }
+                return old_len != len;
+            }
+            None => 0..len,
+        };
+        let grabbed = self.drag.as_ref().map(|drag| drag.index);
+        let stale: Vec<usize> = self
+            .children
+            .iter()
+            .filter(|(i, child)| {
+                **i >= len
+                    || !(range.contains(i)
+                        || Some(**i) == grabbed
+                        || child.has_focus()
+                        || child.has_active())
+            })
+            .map(|(i, _)| *i)
+            .collect();
+        for i in &stale {
+            if let Some(child) = self.children.remove(i) {
+                if *i < len && self.item_extent.is_some() {
+                    self.parked.insert(*i, child);
+                }
+            }
+        }
+        // the items past the end are gone
+        drop(self.parked.split_off(&len));
+        while self.parked.len() > MAX_PARKED {
+            let first = *self.parked.keys().next().unwrap();
+            let last = *self.parked.keys().next_back().unwrap();
+            if range.start.saturating_sub(first) >= last.saturating_sub(range.end) {
+                self.parked.remove(&first);
+            } else {
+                self.parked.remove(&last);
+            }
+        }
+
+        let mut added = false;
+        for i in range {
+            if let Entry::Vacant(entry) = self.children.entry(i) {
+                match self.parked.remove(&i) {
+                    Some(child) => {
+                        entry.insert(child);
+                        self.restored.push(i);
+                    }
+                    None => {
+                        entry.insert((self.closure)());
+                    }
+                }
+                added = true;
+            }
+        }
+        added || !stale.is_empty()
+    }
+
+    /// The runs of indices of the items that have a child, in a virtualized list.
+    ///
+    /// Other lists have a child for (nearly) every item, so the passes visit them all.
+    fn child_ranges(&self) -> Option<Vec<Range<usize>>> {
+        self.item_extent?;
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        for &i in self.children.keys() {
+            match ranges.last_mut() {
+                Some(range) if range.end == i => range.end += 1,
+                _ => ranges.push(i..i + 1),
+            }
+        }
+        Some(ranges)
+    }
+
+    /// Make sure there is a separator after every item that has a child, except
+    /// for the last item.
+    fn update_separators(&mut self, len: usize) -> bool {
+        let closure = match &self.separator_closure {
+            Some(closure) if !self.wraps() => closure,
+            _ => return false,
+        };
+        let children = &self.children;
+        let stale: Vec<usize> = self
+            .separators
+            .keys()
+            .filter(|i| **i + 1 >= len || !children.contains_key(*i))
+            .copied()
+            .collect();
+        for i in &stale {
+            self.separators.remove(i);
+        }
+
+        let mut added = false;
+        for i in children.keys().filter(|i| **i + 1 < len) {
+            if let Entry::Vacant(entry) = self.separators.entry(*i) {
+                entry.insert(WidgetPod::new(closure()));
+                added = true;
+            }
+        }
+        added || !stale.is_empty()
+    }
+
+    /// Keep track of the row under the pointer, if rows have backgrounds.
+    fn track_hover(&mut self, ctx: &mut EventCtx, event: &Event) {
+        if self.row_background.is_none() {
+            return;
         }
-        len != data.data_len()
+        let hovered = match event {
+            Event::MouseMove(mouse) if ctx.is_hot() => {
+                let minor = self.axis.minor(ctx.size());
+                self.children
+                    .iter()
+                    .find(|(_, child)| {
+                        self.row_rect(child.layout_rect(), minor)
+                            .contains(mouse.pos)
+                    })
+                    .map(|(i, _)| *i)
+            }
+            Event::MouseMove(_) => None,
+            _ => return,
+        };
+        if hovered != self.hovered {
+            self.hovered = hovered;
+            ctx.request_paint();
+        }
+    }
+
+    /// Make sure there is a header in front of the first item of every section.
+    fn update_headers(&mut self, data: &impl ListIter<T>) -> bool {
+        let sections = match &self.sections {
+            Some(sections) if self.item_extent.is_none() && !self.wrap => sections,
+            _ => return false,
+        };
+        let mut starts = BTreeSet::new();
+        let mut previous: Option<T> = None;
+        data.for_each(|item, i| {
+            if !matches!(&previous, Some(previous) if (sections.same)(previous, item)) {
+                starts.insert(i);
+            }
+            previous = Some(item.clone());
+        });
+
+        let stale: Vec<usize> = self
+            .headers
+            .keys()
+            .filter(|i| !starts.contains(i))
+            .copied()
+            .collect();
+        for i in &stale {
+            self.headers.remove(i);
+        }
+
+        let mut added = false;
+        for i in starts {
+            if let Entry::Vacant(entry) = self.headers.entry(i) {
+                entry.insert(WidgetPod::new((sections.header)()));
+                added = true;
+            }
+        }
+        added || !stale.is_empty()
+    }
+
+    /// The index of the item being dragged, if any.
+    fn dragged_index(&self) -> Option<usize> {
+        self.drag
+            .as_ref()
+            .filter(|drag| drag.dragging)
+            .map(|drag| drag.index)
+    }
+
+    /// Pick up, move, and put back items of a reorderable list.
+    ///
+    /// Returns `true` if the event is part of a drag, and shouldn't go to the children.
+    fn reorder_event(&mut self, ctx: &mut EventCtx, event: &Event) -> bool {
+        match event {
+            Event::MouseDown(mouse) if mouse.button.is_left() && self.drag.is_none() => {
+                let pressed = self
+                    .children
+                    .iter()
+                    .find(|(_, child)| child.layout_rect().contains(mouse.pos));
+                if let Some((&index, _)) = pressed {
+                    self.drag = Some(ReorderDrag {
+                        index,
+                        start: mouse.pos,
+                        pos: mouse.pos,
+                        dragging: false,
+                        target: index,
+                    });
+                }
+                false
+            }
+            Event::MouseMove(mouse) => {
+                let drag = match &mut self.drag {
+                    Some(drag) => drag,
+                    None => return false,
+                };
+                if !drag.dragging {
+                    if !mouse.buttons.has_left() {
+                        self.drag = None;
+                        return false;
+                    }
+                    if mouse.pos.distance(drag.start) < REORDER_THRESHOLD {
+                        return false;
+                    }
+                    drag.dragging = true;
+                    ctx.set_active(true);
+                    ctx.request_focus();
+                }
+                drag.pos = mouse.pos;
+                ctx.request_layout();
+                ctx.request_anim_frame();
+                true
+            }
+            Event::KeyDown(key) if key.key == KbKey::Escape && self.dragged_index().is_some() => {
+                self.drag = None;
+                for gap in self.gaps.values_mut() {
+                    gap.target = 0.0;
+                }
+                ctx.set_active(false);
+                ctx.request_layout();
+                ctx.request_anim_frame();
+                ctx.set_handled();
+                true
+            }
+            Event::AnimFrame(interval) => {
+                self.animate_reorder(ctx, *interval as f64 * 1e-9);
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// Move items towards where they should be while reordering, and scroll the
+    /// enclosing `Scroll` if an item is dragged near the edge of the visible region.
+    fn animate_reorder(&mut self, ctx: &mut EventCtx, seconds: f64) {
+        let axis = self.axis;
+        let list_major = axis.major(ctx.size());
+        if let (Some(drag), Some(visible)) = (&mut self.drag, self.visible) {
+            let (start, end) = axis.major_span(visible);
+            let pos = axis.major_pos(drag.pos);
+            let depth = if pos < start + AUTOSCROLL_MARGIN {
+                (pos - start - AUTOSCROLL_MARGIN).max(-AUTOSCROLL_MARGIN)
+            } else if pos > end - AUTOSCROLL_MARGIN {
+                (pos - end + AUTOSCROLL_MARGIN).min(AUTOSCROLL_MARGIN)
+            } else {
+                0.0
+            };
+            // don't scroll past either end of the list
+            let delta = (depth / AUTOSCROLL_MARGIN * AUTOSCROLL_SPEED * seconds)
+                .max(-(start.max(0.0)))
+                .min((list_major - end).max(0.0));
+            if drag.dragging && delta != 0.0 {
+                let delta = Vec2::from(axis.pack(delta, 0.0));
+                ctx.submit_notification(SCROLL_BY.with(delta));
+                // the content moves under the pointer
+                drag.pos += delta;
+                ctx.request_layout();
+                ctx.request_anim_frame();
+            }
+        }
+
+        let progress = 1.0 - (-GAP_SPEED * seconds).exp();
+        let mut moving = false;
+        for gap in self.gaps.values_mut() {
+            let remaining = gap.target - gap.current;
+            if remaining.abs() < 0.5 {
+                gap.current = gap.target;
+            } else {
+                gap.current += remaining * progress;
+                moving = true;
+            }
+        }
+        if moving {
+            ctx.request_layout();
+            ctx.request_anim_frame();
+        } else if self.drag.is_none() && !self.gaps.is_empty() {
+            self.gaps.clear();
+            ctx.request_layout();
+        }
+    }
+
+    /// Advance the animations of appearing and disappearing children.
+    fn animate_presence(&mut self, ctx: &mut EventCtx, interval: u64) {
+        let duration = match self.animation {
+            Some(duration) => duration.as_nanos() as f64,
+            None => return,
+        };
+        if self.appearing.is_empty() && self.leaving.is_empty() {
+            return;
+        }
+        let step = if duration > 0.0 {
+            interval as f64 / duration
+        } else {
+            1.0
+        };
+
+        for progress in self.appearing.values_mut() {
+            *progress = (*progress + step).min(1.0);
+        }
+        let appeared: Vec<usize> = self
+            .appearing
+            .iter()
+            .filter(|(_, progress)| **progress >= 1.0)
+            .map(|(i, _)| *i)
+            .collect();
+        for i in appeared {
+            self.appearing.remove(&i);
+        }
+
+        let leaving = self.leaving.len();
+        for child in &mut self.leaving {
+            child.progress -= step;
+        }
+        self.leaving.retain(|child| child.progress > 0.0);
+        if self.leaving.len() != leaving {
+            ctx.children_changed();
+        }
+
+        ctx.request_layout();
+        if !(self.appearing.is_empty() && self.leaving.is_empty()) {
+            ctx.request_anim_frame();
+        }
+    }
+
+    /// Lay out the children in lines across the axis, wrapping to a new line when
+    /// the next child doesn't fit.
+    fn layout_wrapped(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &impl ListIter<T>,
+        env: &Env,
+    ) -> Size {
+        let axis = self.axis;
+        let spacing = self.spacing.resolve(env);
+        let max_minor = axis.minor(bc.max());
+        let child_bc = bc.loosen();
+        let children = &mut self.children;
+
+        // break the children into lines, by index
+        let mut lines: Vec<Vec<usize>> = Vec::new();
+        let mut line_minor = 0.0;
+        let mut minor = axis.minor(bc.min());
+        data.for_each(|child_data, i| {
+            let child = match children.get_mut(&i) {
+                Some(child) => child,
+                None => return,
+            };
+            let child_minor = axis.minor(child.layout(ctx, &child_bc, child_data, i, env));
+            match lines.last_mut() {
+                Some(line) if line_minor + spacing + child_minor <= max_minor => {
+                    line_minor += spacing + child_minor;
+                    line.push(i);
+                }
+                _ => {
+                    lines.push(vec![i]);
+                    line_minor = child_minor;
+                }
+            }
+            minor = minor.max(line_minor);
+        });
+
+        // where each child goes, and the size it gets if it fills its line
+        let alignment = self.line_alignment;
+        let mut placements = BTreeMap::new();
+        let mut major_pos = 0.0;
+        for line in &lines {
+            let line_children = line.iter().map(|i| &children[i]);
+            let line_major = line_children
+                .clone()
+                .map(|child| axis.major(child.layout_rect().size()))
+                .fold(0.0, f64::max);
+            // baselines only line up across a row
+            let baseline = alignment == CrossAxisAlignment::Baseline && axis == Axis::Vertical;
+            let max_above_baseline = line_children
+                .map(|child| child.layout_rect().height() - child.baseline_offset())
+                .fold(0.0, f64::max);
+
+            let mut minor_pos = 0.0;
+            for i in line {
+                let child_size = children[i].layout_rect().size();
+                let child_major = axis.major(child_size);
+                let (offset, fill) = match alignment {
+                    _ if baseline => {
+                        let above_baseline = child_size.height - children[i].baseline_offset();
+                        (max_above_baseline - above_baseline, None)
+                    }
+                    CrossAxisAlignment::Fill => {
+                        let fill_size = Size::from(axis.pack(line_major, axis.minor(child_size)));
+                        (0.0, Some(fill_size))
+                    }
+                    _ => (alignment.align(line_major - child_major), None),
+                };
+                let child_pos = Point::from(axis.pack(major_pos + offset, minor_pos));
+                placements.insert(*i, (child_pos, fill));
+                minor_pos += axis.minor(child_size) + spacing;
+            }
+            major_pos += line_major + spacing;
+        }
+
+        let mut paint_rect = Rect::ZERO;
+        data.for_each(|child_data, i| {
+            let (child, (child_pos, fill)) = match (children.get_mut(&i), placements.get(&i)) {
+                (Some(child), Some(placement)) => (child, placement),
+                _ => return,
+            };
+            if let Some(fill_size) = fill {
+                child.layout(ctx, &BoxConstraints::tight(*fill_size), child_data, i, env);
+            }
+            child.set_origin(ctx, child_data, i, env, *child_pos);
+            paint_rect = paint_rect.union(child.paint_rect());
+        });
+
+        // correct overshoot at end.
+        let major = (major_pos - spacing).max(0.0);
+        let my_size = bc.constrain(Size::from(axis.pack(major, minor)));
+        let insets = paint_rect - my_size.to_rect();
+        ctx.set_paint_insets(insets);
+        trace!("Computed layout: size={}, insets={:?}", my_size, insets);
+        my_size
+    }
+
+    /// Ask the enclosing `Scroll` to show the item with the given index.
+    fn scroll_to_item(&self, ctx: &mut EventCtx, index: usize, len: usize, env: &Env) {
+        let visible = match self.visible {
+            Some(visible) if len > 0 && ctx.size() != Size::ZERO => visible,
+            _ => return,
+        };
+        let axis = self.axis;
+        let index = index.min(len - 1);
+        let (item_start, item_end) = match self.item_extent {
+            Some(item_extent) => {
+                let start = index as f64 * (item_extent + self.spacing.resolve(env));
+                (start, start + item_extent)
+            }
+            None => match self.children.get(&index) {
+                Some(child) => axis.major_span(child.layout_rect()),
+                None => return,
+            },
+        };
+
+        let (start, end) = axis.major_span(visible);
+        let delta = if item_start < start {
+            item_start - start
+        } else if item_end > end {
+            (item_end - end).min(item_start - start)
+        } else {
+            return;
+        };
+        ctx.submit_notification(SCROLL_BY.with(axis.pack(delta, 0.).into()));
+    }
+
+    /// Move the dragged item to where it was dropped.
+    fn drop_item(&mut self, ctx: &mut EventCtx, data: &mut impl ListIter<T>) {
+        if let Some(drag) = self.drag.take() {
+            if drag.dragging {
+                move_item(data, drag.index, drag.target);
+                self.gaps.clear();
+                ctx.set_active(false);
+                ctx.request_layout();
+                ctx.set_handled();
+            }
+        }
+    }
+}
+
+/// Move the children of removed items from `removed` to `leaving`, so that they
+/// can disappear gradually.
+///
+/// `before` gives the index of the item to show a child in front of, and `key` the
+/// hashed key of its item, from the item's old index.
+fn retire<T: Data>(
+    leaving: &mut Vec<Leaving<T>>,
+    removed: &mut BTreeMap<usize, Box<dyn ListChild<T>>>,
+    appearing: &mut BTreeMap<usize, f64>,
+    old_data: &impl ListIter<T>,
+    before: impl Fn(usize) -> usize,
+    key: impl Fn(usize) -> Option<u64>,
+) {
+    old_data.for_each(|item, index| {
+        if let Some(child) = removed.remove(&index) {
+            leaving.push(Leaving {
+                child,
+                data: item.clone(),
+                index,
+                before: before(index),
+                key: key(index),
+                progress: appearing.remove(&index).unwrap_or(1.0),
+            });
+        }
+    });
+}
+
+/// The indices of the items of a virtualized list that should have a child, when
+/// each item takes up `stride` along the axis and `visible` is the visible region.
+pub(crate) fn materialized_range(
+    axis: Axis,
+    visible: Option<Rect>,
+    stride: f64,
+    len: usize,
+) -> Range<usize> {
+    if stride <= 0.0 {
+        return 0..len;
+    }
+    let (start, end) = axis.major_span(visible.unwrap_or(Rect::ZERO));
+    let first = ((start / stride).floor().max(0.0) as usize).saturating_sub(VIRTUAL_OVERSCAN);
+    let last = ((end / stride).ceil().max(0.0) as usize).saturating_add(VIRTUAL_OVERSCAN);
+    first.min(len)..last.min(len)
+}
+
+/// Visit the items in `ranges`, or every item if there are no ranges.
+fn for_each_in_ranges<C, T: ListIter<C>>(
+    data: &T,
+    ranges: Option<&[Range<usize>]>,
+    mut cb: impl FnMut(&C, usize),
+) {
+    match ranges {
+        Some(ranges) => {
+            for range in ranges {
+                data.for_each_in(range.clone(), &mut cb);
+            }
+        }
+        None => data.for_each(cb),
+    }
+}
+
+/// Visit the items in `ranges` mutably, or every item if there are no ranges.
+fn for_each_mut_in_ranges<C, T: ListIter<C>>(
+    data: &mut T,
+    ranges: Option<&[Range<usize>]>,
+    mut cb: impl FnMut(&mut C, usize),
+) {
+    match ranges {
+        Some(ranges) => {
+            for range in ranges {
+                data.for_each_mut_in(range.clone(), &mut cb);
+            }
+        }
+        None => data.for_each_mut(cb),
+    }
+}
+
+/// Ease an animation's progress in and out.
+fn smoothstep(t: f64) -> f64 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Paint a child that is appearing or disappearing, cut off at `scale` of its size
+/// along the axis.
+fn paint_partial<T>(
+    child: &mut dyn ListChild<T>,
+    ctx: &mut PaintCtx,
+    data: &T,
+    index: usize,
+    axis: Axis,
+    scale: f64,
+    env: &Env,
+) {
+    let rect = child.layout_rect();
+    let size = axis.pack(axis.major(rect.size()) * scale, axis.minor(rect.size()));
+    let shown = Rect::from_origin_size(rect.origin(), size);
+    ctx.with_save(|ctx| {
+        ctx.clip(shown);
+        child.paint(ctx, data, index, env);
+    });
+}
+
+/// Move the item at `from` to `to`, shifting the items in between.
+fn move_item<T: Data>(data: &mut impl ListIter<T>, from: usize, to: usize) {
+    let len = data.data_len();
+    if from == to || from >= len || to >= len {
+        return;
+    }
+    // only the items from one index to the other move
+    let range = from.min(to)..from.max(to) + 1;
+    let start = range.start;
+    let mut items = Vec::with_capacity(range.len());
+    data.for_each_in(range.clone(), |item, _| items.push(item.clone()));
+    let item = items.remove(from - start);
+    items.insert(to - start, item);
+    data.for_each_mut_in(range, |item, i| {
+        if !item.same(&items[i - start]) {
+            *item = items[i - start].clone();
+        }
+    });
+}
+
+/// Work out where the dragged item would land, and how far the other items have
+/// to move to make room for it.
+fn update_gaps(
+    drag: &mut ReorderDrag,
+    slots: &BTreeMap<usize, Slot>,
+    gaps: &mut BTreeMap<usize, Gap>,
+    axis: Axis,
+    stride: Option<f64>,
+    len: usize,
+) {
+    let grabbed = match slots.get(&drag.index) {
+        Some(slot) => slot,
+        None => return,
+    };
+    let offset = axis.major_pos(drag.pos) - axis.major_pos(drag.start);
+    // the room the dragged item takes up, including what follows it
+    let next = slots.range(drag.index + 1..).next();
+    let previous = slots.range(..drag.index).next_back();
+    let room = match (stride, next, previous) {
+        (Some(stride), _, _) => stride,
+        (None, Some((_, next)), _) => next.pos - grabbed.pos,
+        (None, None, Some((_, previous))) => grabbed.pos - previous.pos,
+        (None, None, None) => grabbed.extent,
+    };
+
+    drag.target = match stride {
+        Some(stride) if stride > 0.0 => {
+            let target = ((grabbed.pos + offset) / stride).round();
+            target.max(0.0).min(len.saturating_sub(1) as f64) as usize
+        }
+        _ => {
+            let center = grabbed.pos + offset + grabbed.extent / 2.0;
+            let before = slots
+                .range(..drag.index)
+                .filter(|(_, slot)| slot.pos + slot.extent / 2.0 > center)
+                .count();
+            let after = slots
+                .range(drag.index + 1..)
+                .filter(|(_, slot)| slot.pos + slot.extent / 2.0 < center)
+                .count();
+            drag.index + after - before
+        }
+    };
+
+    for &i in slots.keys() {
+        let target = if i > drag.index && i <= drag.target {
+            -room
+        } else if i < drag.index && i >= drag.target {
+            room
+        } else {
+            0.0
+        };
+        gaps.entry(i).or_default().target = target;
+    }
+}
+
+/// A child of a [`List`], which may or may not be told the index of its item.
+trait ListChild<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, index: usize, env: &Env);
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &T,
+        index: usize,
+        env: &Env,
+    );
+    fn update(&mut self, ctx: &mut UpdateCtx, data: &T, index: usize, env: &Env);
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &T,
+        index: usize,
+        env: &Env,
+    ) -> Size;
+    fn set_origin(&mut self, ctx: &mut LayoutCtx, data: &T, index: usize, env: &Env, origin: Point);
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, index: usize, env: &Env);
+    fn layout_rect(&self) -> Rect;
+    fn paint_rect(&self) -> Rect;
+    fn baseline_offset(&self) -> f64;
+    fn has_focus(&self) -> bool;
+    fn has_active(&self) -> bool;
+}
+
+impl<T: Data> ListChild<T> for WidgetPod<T, Box<dyn Widget<T>>> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, _: usize, env: &Env) {
+        WidgetPod::event(self, ctx, event, data, env)
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &T,
+        _: usize,
+        env: &Env,
+    ) {
+        WidgetPod::lifecycle(self, ctx, event, data, env)
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, data: &T, _: usize, env: &Env) {
+        WidgetPod::update(self, ctx, data, env)
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &T,
+        _: usize,
+        env: &Env,
+    ) -> Size {
+        WidgetPod::layout(self, ctx, bc, data, env)
+    }
+
+    fn set_origin(&mut self, ctx: &mut LayoutCtx, data: &T, _: usize, env: &Env, origin: Point) {
+        WidgetPod::set_origin(self, ctx, data, env, origin)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, _: usize, env: &Env) {
+        WidgetPod::paint(self, ctx, data, env)
+    }
+
+    fn layout_rect(&self) -> Rect {
+        WidgetPod::layout_rect(self)
+    }
+
+    fn paint_rect(&self) -> Rect {
+        WidgetPod::paint_rect(self)
+    }
+
+    fn baseline_offset(&self) -> f64 {
+        WidgetPod::baseline_offset(self)
+    }
+
+    fn has_focus(&self) -> bool {
+        WidgetPod::has_focus(self)
+    }
+
+    fn has_active(&self) -> bool {
+        WidgetPod::has_active(self)
+    }
+}
+
+impl<T: Data> ListChild<T> for WidgetPod<(usize, T), Box<dyn Widget<(usize, T)>>> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, index: usize, env: &Env) {
+        let mut entry = (index, data.clone());
+        WidgetPod::event(self, ctx, event, &mut entry, env);
+        if !data.same(&entry.1) {
+            *data = entry.1;
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &T,
+        index: usize,
+        env: &Env,
+    ) {
+        WidgetPod::lifecycle(self, ctx, event, &(index, data.clone()), env)
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, data: &T, index: usize, env: &Env) {
+        WidgetPod::update(self, ctx, &(index, data.clone()), env)
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &T,
+        index: usize,
+        env: &Env,
+    ) -> Size {
+        WidgetPod::layout(self, ctx, bc, &(index, data.clone()), env)
+    }
+
+    fn set_origin(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        data: &T,
+        index: usize,
+        env: &Env,
+        origin: Point,
+    ) {
+        WidgetPod::set_origin(self, ctx, &(index, data.clone()), env, origin)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, index: usize, env: &Env) {
+        WidgetPod::paint(self, ctx, &(index, data.clone()), env)
+    }
+
+    fn layout_rect(&self) -> Rect {
+        WidgetPod::layout_rect(self)
+    }
+
+    fn paint_rect(&self) -> Rect {
+        WidgetPod::paint_rect(self)
+    }
+
+    fn baseline_offset(&self) -> f64 {
+        WidgetPod::baseline_offset(self)
+    }
+
+    fn has_focus(&self) -> bool {
+        WidgetPod::has_focus(self)
+    }
+
+    fn has_active(&self) -> bool {
+        WidgetPod::has_active(self)
     }
 }
 
 /// This iterator enables writing List widget for any `Data`.
+///
+/// `T` is the data of each child. Implement this for your own collection to show it
+/// in a [`List`] without copying it into a `Vector` first. `for_each` and
+/// `for_each_mut` should visit the items in the same order every time, and pass
+/// the index of each item along with it.
+///
+/// A [virtualized] `List` only visits the items it shows, with `for_each_in` and
+/// `for_each_mut_in`. By default these go through every item and skip the ones
+/// outside the range; collections that can get at an item by its index should
+/// implement them directly.
+///
+/// Besides the sequences, this is implemented for `im::OrdMap<K, V>` and
+/// `im::HashMap<K, V>`, with each child getting a `(K, V)` pair. Their items are in
+/// the order of their keys, so inserting an item moves the ones after it; use
+/// [`List::with_key`] to keep each child with its entry.
+///
+/// [virtualized]: List::virtual_fixed_extent
 pub trait ListIter<T>: Data {
     /// Iterate over each data child.
     fn for_each(&self, cb: impl FnMut(&T, usize));
@@ -96,9 +1428,33 @@ pub trait ListIter<T>: Data {
     /// Iterate over each data child. Keep track of changed data and update self.
     fn for_each_mut(&mut self, cb: impl FnMut(&mut T, usize));
 
+    /// Iterate over the data children with an index in `range`.
+    fn for_each_in(&self, range: Range<usize>, mut cb: impl FnMut(&T, usize)) {
+        self.for_each(|item, i| {
+            if range.contains(&i) {
+                cb(item, i);
+            }
+        });
+    }
+
+    /// Iterate over the data children with an index in `range`. Keep track of
+    /// changed data and update self.
+    fn for_each_mut_in(&mut self, range: Range<usize>, mut cb: impl FnMut(&mut T, usize)) {
+        self.for_each_mut(|item, i| {
+            if range.contains(&i) {
+                cb(item, i);
+            }
+        });
+    }
+
     /// Return data length.
     fn data_len(&self) -> usize;
 }
+
+/// The part of `range` that is within a collection of `len` items.
+fn clamp_range(range: Range<usize>, len: usize) -> Range<usize> {
+    range.start.min(len)..range.end.min(len)
+}
 #[cfg(feature = "im")]
 impl<T: Data> ListIter<T> for Vector<T> {
     fn for_each(&self, mut cb: impl FnMut(&T, usize)) {
@@ -113,13 +1469,34 @@ impl<T: Data> ListIter<T> for Vector<T> {
         }
     }
 
+    fn for_each_in(&self, range: Range<usize>, mut cb: impl FnMut(&T, usize)) {
+        let range = clamp_range(range, self.len());
+        if range.is_empty() {
+            // narrowing a focus to nothing panics
+            return;
+        }
+        let start = range.start;
+        for (i, item) in self.focus().narrow(range).into_iter().enumerate() {
+            cb(item, start + i);
+        }
+    }
+
+    fn for_each_mut_in(&mut self, range: Range<usize>, mut cb: impl FnMut(&mut T, usize)) {
+        // only copy the parts of the vector whose items changed
+        for i in clamp_range(range, self.len()) {
+            let mut item = self[i].clone();
+            cb(&mut item, i);
+            if !self[i].same(&item) {
+                self[i] = item;
+            }
+        }
+    }
+
     fn data_len(&self) -> usize {
         self.len()
     }
 }
 
-//An implementation for ListIter<(K, V)> has been ommitted due to problems
-//with how the List Widget handles the reordering of its data.
 #[cfg(feature = "im")]
 impl<K, V> ListIter<V> for OrdMap<K, V>
 where
@@ -149,6 +1526,70 @@ where
     }
 }
 
+/// Changes to the key are ignored; changes to the value are written back to the map.
+#[cfg(feature = "im")]
+impl<K, V> ListIter<(K, V)> for OrdMap<K, V>
+where
+    K: Data + Ord,
+    V: Data,
+{
+    fn for_each(&self, mut cb: impl FnMut(&(K, V), usize)) {
+        for (i, (key, value)) in self.iter().enumerate() {
+            cb(&(key.clone(), value.clone()), i);
+        }
+    }
+
+    fn for_each_mut(&mut self, mut cb: impl FnMut(&mut (K, V), usize)) {
+        for (i, (key, value)) in self.clone().iter().enumerate() {
+            let mut entry = (key.clone(), value.clone());
+            cb(&mut entry, i);
+
+            if !value.same(&entry.1) {
+                self[key] = entry.1;
+            }
+        }
+    }
+
+    fn data_len(&self) -> usize {
+        self.len()
+    }
+}
+
+/// The entries are visited in the order of their keys, so that they don't move
+/// around when the map changes. Changes to the key are ignored; changes to the value
+/// are written back to the map.
+#[cfg(feature = "im")]
+impl<K, V> ListIter<(K, V)> for ImHashMap<K, V>
+where
+    K: Data + Ord + Hash,
+    V: Data,
+{
+    fn for_each(&self, mut cb: impl FnMut(&(K, V), usize)) {
+        let mut entries: Vec<_> = self.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        for (i, (key, value)) in entries.into_iter().enumerate() {
+            cb(&(key.clone(), value.clone()), i);
+        }
+    }
+
+    fn for_each_mut(&mut self, mut cb: impl FnMut(&mut (K, V), usize)) {
+        let mut keys: Vec<K> = self.keys().cloned().collect();
+        keys.sort();
+        for (i, key) in keys.into_iter().enumerate() {
+            let mut entry = (key.clone(), self[&key].clone());
+            cb(&mut entry, i);
+
+            if !self[&key].same(&entry.1) {
+                self[&key] = entry.1;
+            }
+        }
+    }
+
+    fn data_len(&self) -> usize {
+        self.len()
+    }
+}
+
 // S == shared data type
 #[cfg(feature = "im")]
 impl<S: Data, T: Data> ListIter<(S, T)> for (S, Vector<T>) {
@@ -173,6 +1614,33 @@ impl<S: Data, T: Data> ListIter<(S, T)> for (S, Vector<T>) {
         }
     }
 
+    fn for_each_in(&self, range: Range<usize>, mut cb: impl FnMut(&(S, T), usize)) {
+        let range = clamp_range(range, self.1.len());
+        if range.is_empty() {
+            // narrowing a focus to nothing panics
+            return;
+        }
+        let start = range.start;
+        for (i, item) in self.1.focus().narrow(range).into_iter().enumerate() {
+            let d = (self.0.to_owned(), item.to_owned());
+            cb(&d, start + i);
+        }
+    }
+
+    fn for_each_mut_in(&mut self, range: Range<usize>, mut cb: impl FnMut(&mut (S, T), usize)) {
+        for i in clamp_range(range, self.1.len()) {
+            let mut d = (self.0.clone(), self.1[i].clone());
+            cb(&mut d, i);
+
+            if !self.0.same(&d.0) {
+                self.0 = d.0;
+            }
+            if !self.1[i].same(&d.1) {
+                self.1[i] = d.1;
+            }
+        }
+    }
+
     fn data_len(&self) -> usize {
         self.1.len()
     }
@@ -204,6 +1672,25 @@ impl<T: Data> ListIter<T> for Arc<Vec<T>> {
         }
     }
 
+    fn for_each_in(&self, range: Range<usize>, mut cb: impl FnMut(&T, usize)) {
+        let range = clamp_range(range, self.len());
+        let start = range.start;
+        for (i, item) in self[range].iter().enumerate() {
+            cb(item, start + i);
+        }
+    }
+
+    fn for_each_mut_in(&mut self, range: Range<usize>, mut cb: impl FnMut(&mut T, usize)) {
+        for i in clamp_range(range, self.len()) {
+            let mut d = self[i].to_owned();
+            cb(&mut d, i);
+
+            if !self[i].same(&d) {
+                Arc::make_mut(self)[i] = d;
+            }
+        }
+    }
+
     fn data_len(&self) -> usize {
         self.len()
     }
@@ -244,6 +1731,27 @@ impl<S: Data, T: Data> ListIter<(S, T)> for (S, Arc<Vec<T>>) {
         }
     }
 
+    fn for_each_in(&self, range: Range<usize>, mut cb: impl FnMut(&(S, T), usize)) {
+        for i in clamp_range(range, self.1.len()) {
+            let d = (self.0.clone(), self.1[i].to_owned());
+            cb(&d, i);
+        }
+    }
+
+    fn for_each_mut_in(&mut self, range: Range<usize>, mut cb: impl FnMut(&mut (S, T), usize)) {
+        for i in clamp_range(range, self.1.len()) {
+            let mut d = (self.0.clone(), self.1[i].to_owned());
+            cb(&mut d, i);
+
+            if !self.0.same(&d.0) {
+                self.0 = d.0;
+            }
+            if !self.1[i].same(&d.1) {
+                Arc::make_mut(&mut self.1)[i] = d.1;
+            }
+        }
+    }
+
     fn data_len(&self) -> usize {
         self.1.len()
     }
@@ -275,6 +1783,23 @@ impl<T: Data> ListIter<T> for Arc<VecDeque<T>> {
         }
     }
 
+    fn for_each_in(&self, range: Range<usize>, mut cb: impl FnMut(&T, usize)) {
+        for i in clamp_range(range, self.len()) {
+            cb(&self[i], i);
+        }
+    }
+
+    fn for_each_mut_in(&mut self, range: Range<usize>, mut cb: impl FnMut(&mut T, usize)) {
+        for i in clamp_range(range, self.len()) {
+            let mut d = self[i].to_owned();
+            cb(&mut d, i);
+
+            if !self[i].same(&d) {
+                Arc::make_mut(self)[i] = d;
+            }
+        }
+    }
+
     fn data_len(&self) -> usize {
         self.len()
     }
@@ -315,6 +1840,27 @@ impl<S: Data, T: Data> ListIter<(S, T)> for (S, Arc<VecDeque<T>>) {
         }
     }
 
+    fn for_each_in(&self, range: Range<usize>, mut cb: impl FnMut(&(S, T), usize)) {
+        for i in clamp_range(range, self.1.len()) {
+            let d = (self.0.clone(), self.1[i].to_owned());
+            cb(&d, i);
+        }
+    }
+
+    fn for_each_mut_in(&mut self, range: Range<usize>, mut cb: impl FnMut(&mut (S, T), usize)) {
+        for i in clamp_range(range, self.1.len()) {
+            let mut d = (self.0.clone(), self.1[i].to_owned());
+            cb(&mut d, i);
+
+            if !self.0.same(&d.0) {
+                self.0 = d.0;
+            }
+            if !self.1[i].same(&d.1) {
+                Arc::make_mut(&mut self.1)[i] = d.1;
+            }
+        }
+    }
+
     fn data_len(&self) -> usize {
         self.1.len()
     }
@@ -323,74 +1869,351 @@ impl<S: Data, T: Data> ListIter<(S, T)> for (S, Arc<VecDeque<T>>) {
 impl<C: Data, T: ListIter<C>> Widget<T> for List<C> {
     #[instrument(name = "List", level = "trace", skip(self, ctx, event, data, env))]
     fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
-        let mut children = self.children.iter_mut();
-        data.for_each_mut(|child_data, _| {
-            if let Some(child) = children.next() {
-                child.event(ctx, event, child_data, env);
+        if self.reorderable && !self.wraps() && self.reorder_event(ctx, event) {
+            return;
+        }
+        if let Event::AnimFrame(interval) = event {
+            self.animate_presence(ctx, *interval);
+        }
+        self.track_hover(ctx, event);
+
+        // headers are on top of the items, so they get the first look at events
+        let ranges = self.child_ranges();
+        let headers = &mut self.headers;
+        for_each_in_ranges(data, ranges.as_deref(), |item, i| {
+            if let Some(header) = headers.get_mut(&i) {
+                header.event(ctx, event, &mut item.clone(), env);
             }
         });
+        if !ctx.is_handled() {
+            let children = &mut self.children;
+            for_each_mut_in_ranges(data, ranges.as_deref(), |child_data, i| {
+                if let Some(child) = children.get_mut(&i) {
+                    child.event(ctx, event, child_data, i, env);
+                }
+            });
+        }
+        for separator in self.separators.values_mut() {
+            separator.event(ctx, event, &mut (), env);
+        }
+        if let Some(placeholder) = &mut self.placeholder {
+            placeholder.event(ctx, event, &mut (), env);
+        }
+
+        // the children get the release first, so that any pressed child is released
+        if let Event::MouseUp(mouse) = event {
+            if mouse.button.is_left() {
+                self.drop_item(ctx, data);
+            }
+        }
+
+        if let Event::Command(cmd) = event {
+            if let Some(visible) = cmd.get(VIEWPORT_CHANGED) {
+                self.visible = Some(*visible);
+                if self.item_extent.is_some() && self.update_child_count(data, env) {
+                    ctx.children_changed();
+                }
+                if !self.restored.is_empty() {
+                    // catch the restored children up with their items
+                    ctx.request_update();
+                }
+                if !self.headers.is_empty() {
+                    // the sticky header moves
+                    ctx.request_layout();
+                }
+            } else if let Some(index) = cmd.get(List::SCROLL_TO_ITEM) {
+                self.scroll_to_item(ctx, *index, data.data_len(), env);
+                ctx.set_handled();
+            }
+        }
     }
 
     #[instrument(name = "List", level = "trace", skip(self, ctx, event, data, env))]
     fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
         if let LifeCycle::WidgetAdded = event {
+            self.keys = self.item_keys(data).unwrap_or_default();
             if self.update_child_count(data, env) {
                 ctx.children_changed();
             }
         }
+        if let LifeCycle::HotChanged(false) = event {
+            if self.hovered.take().is_some() {
+                ctx.request_paint();
+            }
+        }
 
-        let mut children = self.children.iter_mut();
-        data.for_each(|child_data, _| {
-            if let Some(child) = children.next() {
-                child.lifecycle(ctx, event, child_data, env);
+        let ranges = self.child_ranges();
+        let children = &mut self.children;
+        let headers = &mut self.headers;
+        for_each_in_ranges(data, ranges.as_deref(), |child_data, i| {
+            if let Some(child) = children.get_mut(&i) {
+                child.lifecycle(ctx, event, child_data, i, env);
+            }
+            if let Some(header) = headers.get_mut(&i) {
+                header.lifecycle(ctx, event, child_data, env);
             }
         });
+        for separator in self.separators.values_mut() {
+            separator.lifecycle(ctx, event, &(), env);
+        }
+        if let Some(placeholder) = &mut self.placeholder {
+            placeholder.lifecycle(ctx, event, &(), env);
+        }
+        for leaving in &mut self.leaving {
+            leaving
+                .child
+                .lifecycle(ctx, event, &leaving.data, leaving.index, env);
+        }
     }
 
-    #[instrument(name = "List", level = "trace", skip(self, ctx, _old_data, data, env))]
-    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+    #[instrument(name = "List", level = "trace", skip(self, ctx, old_data, data, env))]
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        // Children skip updates for items that are the same, but with many items even
+        // asking them is slow, so don't when nothing can have changed, unless a
+        // row asked for an update itself.
+        if old_data.same(data) && !ctx.env_changed() && !ctx.has_requested_update() {
+            return;
+        }
+
+        let children_removed = self.match_keys(data, old_data);
+        let animates = self.animates();
+        if animates && self.key_fn.is_none() {
+            // the items at the end are gone
+            let len = data.data_len();
+            let mut removed = self.children.split_off(&len);
+            retire(
+                &mut self.leaving,
+                &mut removed,
+                &mut self.appearing,
+                old_data,
+                |_| len,
+                |_| None,
+            );
+        }
+        let old_children: BTreeSet<usize> = if animates {
+            self.children.keys().copied().collect()
+        } else {
+            BTreeSet::new()
+        };
+
         // we send update to children first, before adding or removing children;
         // this way we avoid sending update to newly added children, at the cost
         // of potentially updating children that are going to be removed.
-        let mut children = self.children.iter_mut();
-        data.for_each(|child_data, _| {
-            if let Some(child) = children.next() {
-                child.update(ctx, child_data, env);
+        let ranges = self.child_ranges();
+        let children = &mut self.children;
+        let headers = &mut self.headers;
+        for_each_in_ranges(data, ranges.as_deref(), |child_data, i| {
+            if let Some(child) = children.get_mut(&i) {
+                child.update(ctx, child_data, i, env);
+            }
+            if let Some(header) = headers.get_mut(&i) {
+                header.update(ctx, child_data, env);
             }
         });
+        for separator in self.separators.values_mut() {
+            separator.update(ctx, &(), env);
+        }
+        if let Some(placeholder) = &mut self.placeholder {
+            placeholder.update(ctx, &(), env);
+        }
 
-        if self.update_child_count(data, env) {
+        if self.update_child_count(data, env) || children_removed {
             ctx.children_changed();
         }
+        // children that were parked may have missed changes to their items
+        for i in mem::take(&mut self.restored) {
+            if let Some(child) = self.children.get_mut(&i) {
+                data.for_each_in(i..i + 1, |child_data, i| {
+                    child.update(ctx, child_data, i, env)
+                });
+            }
+        }
+
+        if animates {
+            for i in self.children.keys() {
+                if !old_children.contains(i) {
+                    self.appearing.insert(*i, 0.0);
+                }
+            }
+            if !(self.appearing.is_empty() && self.leaving.is_empty()) {
+                ctx.request_layout();
+                ctx.request_anim_frame();
+            }
+        }
     }
 
     #[instrument(name = "List", level = "trace", skip(self, ctx, bc, data, env))]
     fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        if let Some(placeholder) = &mut self.placeholder {
+            let size = placeholder.layout(ctx, bc, &(), env);
+            placeholder.set_origin(ctx, &(), env, Point::ORIGIN);
+            ctx.set_paint_insets(placeholder.paint_insets());
+            return size;
+        }
+        if self.wraps() {
+            return self.layout_wrapped(ctx, bc, data, env);
+        }
+
         let axis = self.axis;
         let spacing = self.spacing.resolve(env);
+        let item_extent = self.item_extent;
         let mut minor = axis.minor(bc.min());
         let mut major_pos = 0.0;
         let mut paint_rect = Rect::ZERO;
-        let mut children = self.children.iter_mut();
-        let child_bc = axis.constraints(bc, 0., f64::INFINITY);
-        data.for_each(|child_data, _| {
-            let child = match children.next() {
-                Some(child) => child,
-                None => {
-                    return;
+        let ranges = self.child_ranges();
+        let children = &mut self.children;
+        let separators = &mut self.separators;
+        let child_bc = match item_extent {
+            Some(item_extent) => axis.constraints(bc, item_extent, item_extent),
+            None => axis.constraints(bc, 0., f64::INFINITY),
+        };
+        for_each_in_ranges(data, ranges.as_deref(), |child_data, i| {
+            if let Some(child) = children.get_mut(&i) {
+                let child_size = child.layout(ctx, &child_bc, child_data, i, env);
+                minor = minor.max(axis.minor(child_size));
+            }
+        });
+        let leaving = &mut self.leaving;
+        for leaving in leaving.iter_mut() {
+            let child_size =
+                leaving
+                    .child
+                    .layout(ctx, &child_bc, &leaving.data, leaving.index, env);
+            minor = minor.max(axis.minor(child_size));
+        }
+
+        // separators span the whole list
+        let separator_bc = BoxConstraints::new(
+            Size::from(axis.pack(0., minor)),
+            Size::from(axis.pack(f64::INFINITY, minor)),
+        );
+        for separator in separators.values_mut() {
+            separator.layout(ctx, &separator_bc, &(), env);
+        }
+        let headers = &mut self.headers;
+        for_each_in_ranges(data, ranges.as_deref(), |item, i| {
+            if let Some(header) = headers.get_mut(&i) {
+                header.layout(ctx, &separator_bc, item, env);
+            }
+        });
+
+        // where everything goes, before making room for a dragged item
+        let mut slots = BTreeMap::new();
+        let mut header_positions = Vec::new();
+        let appearing = &self.appearing;
+        leaving.sort_by_key(|leaving| leaving.before);
+        let mut remaining = leaving.iter_mut().peekable();
+        // lay out the leaving children in front of the item at `before`
+        let mut place_leaving = |ctx: &mut LayoutCtx, major_pos: &mut f64, before: usize| {
+            while let Some(leaving) = remaining.next_if(|leaving| leaving.before <= before) {
+                let child = &mut leaving.child;
+                let child_pos = axis.pack(*major_pos, 0.).into();
+                child.set_origin(ctx, &leaving.data, leaving.index, env, child_pos);
+                paint_rect = paint_rect.union(child.paint_rect());
+                let child_major = axis.major(child.layout_rect().size());
+                *major_pos += (child_major + spacing) * smoothstep(leaving.progress);
+            }
+        };
+        for (&i, child) in children.iter() {
+            if let Some(item_extent) = item_extent {
+                major_pos = i as f64 * (item_extent + spacing);
+            }
+            place_leaving(ctx, &mut major_pos, i);
+            if let Some(header) = headers.get(&i) {
+                header_positions.push((i, major_pos));
+                major_pos += axis.major(header.layout_rect().size()) + spacing;
+            }
+            let scale = appearing
+                .get(&i)
+                .map_or(1.0, |progress| smoothstep(*progress));
+            let child_pos = major_pos;
+            let child_major = axis.major(child.layout_rect().size()) * scale;
+            major_pos += child_major + spacing * scale;
+
+            let mut separator_pos = None;
+            if let Some(separator) = separators.get(&i) {
+                let separator_major = axis.major(separator.layout_rect().size());
+                if item_extent.is_some() {
+                    separator_pos = Some(major_pos - (spacing + separator_major) / 2.);
+                } else {
+                    separator_pos = Some(major_pos);
+                    major_pos += separator_major + spacing;
                 }
+            }
+            slots.insert(
+                i,
+                Slot {
+                    pos: child_pos,
+                    extent: child_major,
+                    separator_pos,
+                },
+            );
+        }
+
+        place_leaving(ctx, &mut major_pos, usize::MAX);
+
+        let gaps = &mut self.gaps;
+        let mut dragged = None;
+        if let Some(drag) = self.drag.as_mut().filter(|drag| drag.dragging) {
+            let stride = item_extent.map(|item_extent| item_extent + spacing);
+            update_gaps(drag, &slots, gaps, axis, stride, data.data_len());
+            dragged = Some((
+                drag.index,
+                axis.major_pos(drag.pos) - axis.major_pos(drag.start),
+            ));
+        }
+
+        for_each_in_ranges(data, ranges.as_deref(), |child_data, i| {
+            let (child, slot) = match (children.get_mut(&i), slots.get(&i)) {
+                (Some(child), Some(slot)) => (child, slot),
+                _ => return,
             };
-            let child_size = child.layout(ctx, &child_bc, child_data, env);
-            let child_pos: Point = axis.pack(major_pos, 0.).into();
-            child.set_origin(ctx, child_data, env, child_pos);
+            let shift = match dragged {
+                Some((index, offset)) if index == i => offset,
+                _ => gaps.get(&i).map_or(0., |gap| gap.current),
+            };
+            let child_pos: Point = axis.pack(slot.pos + shift, 0.).into();
+            child.set_origin(ctx, child_data, i, env, child_pos);
             paint_rect = paint_rect.union(child.paint_rect());
-            minor = minor.max(axis.minor(child_size));
-            major_pos += axis.major(child_size) + spacing;
+
+            if let (Some(separator), Some(separator_pos)) =
+                (separators.get_mut(&i), slot.separator_pos)
+            {
+                separator.set_origin(ctx, &(), env, axis.pack(separator_pos + shift, 0.).into());
+                paint_rect = paint_rect.union(separator.paint_rect());
+            }
         });
+        if let Some(item_extent) = item_extent {
+            major_pos = data.data_len() as f64 * (item_extent + spacing);
+        }
 
         // correct overshoot at end.
         major_pos -= spacing;
 
+        // the header of the section at the top of the visible region sticks there,
+        // until the end of its section pushes it out
+        let view_start = self.visible.map(|visible| axis.major_span(visible).0);
+        for_each_in_ranges(data, ranges.as_deref(), |item, i| {
+            let index = match header_positions.binary_search_by_key(&i, |(i, _)| *i) {
+                Ok(index) => index,
+                Err(_) => return,
+            };
+            let header = match headers.get_mut(&i) {
+                Some(header) => header,
+                None => return,
+            };
+            let natural_pos = header_positions[index].1;
+            let section_end = header_positions
+                .get(index + 1)
+                .map_or(major_pos, |(_, next_pos)| next_pos - spacing);
+            let extent = axis.major(header.layout_rect().size());
+            let header_pos = view_start.map_or(natural_pos, |view_start| {
+                view_start.min(section_end - extent).max(natural_pos)
+            });
+            header.set_origin(ctx, item, env, axis.pack(header_pos, 0.).into());
+            paint_rect = paint_rect.union(header.paint_rect());
+        });
+
         let my_size = bc.constrain(Size::from(axis.pack(major_pos, minor)));
         let insets = paint_rect - my_size.to_rect();
         ctx.set_paint_insets(insets);
@@ -400,11 +2223,79 @@ impl<C: Data, T: ListIter<C>> Widget<T> for List<C> {
 
     #[instrument(name = "List", level = "trace", skip(self, ctx, data, env))]
     fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
-        let mut children = self.children.iter_mut();
-        data.for_each(|child_data, _| {
-            if let Some(child) = children.next() {
-                child.paint(ctx, child_data, env);
+        let axis = self.axis;
+        let dragged = self.dragged_index();
+        let ranges = self.child_ranges();
+        let children = &mut self.children;
+        let appearing = &self.appearing;
+        let row_background = &self.row_background;
+        let hovered = self.hovered;
+        let wraps = self.wraps();
+        let minor = axis.minor(ctx.size());
+        for_each_in_ranges(data, ranges.as_deref(), |child_data, i| {
+            if Some(i) == dragged {
+                return;
+            }
+            if let Some(child) = children.get_mut(&i) {
+                let background = row_background
+                    .as_ref()
+                    .and_then(|background| background(i, hovered == Some(i), env));
+                if let Some(color) = background {
+                    let row = if wraps {
+                        child.layout_rect()
+                    } else {
+                        let scale = appearing
+                            .get(&i)
+                            .map_or(1.0, |progress| smoothstep(*progress));
+                        let (start, end) = axis.major_span(child.layout_rect());
+                        let end = start + (end - start) * scale;
+                        Rect::from_points(axis.pack(start, 0.), axis.pack(end, minor))
+                    };
+                    ctx.fill(row, &color);
+                }
+                match appearing.get(&i) {
+                    Some(progress) => {
+                        let scale = smoothstep(*progress);
+                        paint_partial(child.as_mut(), ctx, child_data, i, axis, scale, env);
+                    }
+                    None => child.paint(ctx, child_data, i, env),
+                }
             }
         });
+        for leaving in &mut self.leaving {
+            let scale = smoothstep(leaving.progress);
+            let child = leaving.child.as_mut();
+            paint_partial(child, ctx, &leaving.data, leaving.index, axis, scale, env);
+        }
+        for separator in self.separators.values_mut() {
+            separator.paint(ctx, &(), env);
+        }
+        let headers = &mut self.headers;
+        for_each_in_ranges(data, ranges.as_deref(), |item, i| {
+            if let Some(header) = headers.get_mut(&i) {
+                header.paint(ctx, item, env);
+            }
+        });
+        if let Some(placeholder) = &mut self.placeholder {
+            placeholder.paint(ctx, &(), env);
+        }
+
+        // the dragged item goes on top, on its row background, with a border
+        if let Some(dragged) = dragged {
+            data.for_each_in(dragged..dragged + 1, |child_data, i| {
+                if let Some(child) = children.get_mut(&i) {
+                    let (start, end) = axis.major_span(child.layout_rect());
+                    let row = Rect::from_points(axis.pack(start, 0.), axis.pack(end, minor));
+                    let background = row_background
+                        .as_ref()
+                        .and_then(|background| background(i, true, env));
+                    if let Some(color) = background {
+                        ctx.fill(row, &color);
+                    }
+                    child.paint(ctx, child_data, i, env);
+                    ctx.stroke(row.inset(-0.5), &env.get(theme::BORDER_LIGHT), 1.0);
+                }
+            });
+        }
     }
 }
@@ -21,24 +21,33 @@ mod widget_wrapper;
 mod added;
 mod align;
 mod aspect_ratio_box;
+#[cfg(feature = "image")]
+#[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+mod async_image;
 mod button;
 mod checkbox;
 mod click;
 mod clip_box;
 mod common;
 mod container;
+mod context_menu;
 mod controller;
+mod controllers;
 mod disable_if;
 mod either;
 mod env_scope;
+mod file_drop;
 mod flex;
+mod grid;
 mod identity_wrapper;
 mod image;
 mod invalidation;
 mod label;
+mod lazy_list;
 mod lens_wrap;
 mod list;
 mod maybe;
+mod modal_host;
 mod padding;
 mod painter;
 mod parse;
@@ -55,51 +64,65 @@ mod stepper;
 #[cfg_attr(docsrs, doc(cfg(feature = "svg")))]
 mod svg;
 mod switch;
+mod tab_index;
 mod tabs;
 mod textbox;
+mod tooltip;
 mod value_textbox;
 mod view_switcher;
 #[allow(clippy::module_inception)]
 mod widget;
 mod widget_ext;
+mod wrap;
+mod z_stack;
 
-pub use self::image::Image;
+pub use self::image::{Image, RawImage};
 pub use added::Added;
 pub use align::Align;
 pub use aspect_ratio_box::AspectRatioBox;
+#[cfg(feature = "image")]
+pub use async_image::AsyncImage;
 pub use button::Button;
 pub use checkbox::Checkbox;
 pub use click::Click;
 pub use clip_box::{ClipBox, Viewport};
 pub use common::FillStrat;
-pub use container::Container;
+pub use container::{BorderStyle, Container};
+pub use context_menu::ContextMenuController;
 pub use controller::{Controller, ControllerHost};
+pub use controllers::{DoubleClick, Drag, DragEvent, LongPress};
 pub use disable_if::DisabledIf;
-pub use either::Either;
+pub use either::{Either, Transition};
 pub use env_scope::EnvScope;
+pub use file_drop::FileDrop;
 pub use flex::{Axis, CrossAxisAlignment, Flex, FlexParams, MainAxisAlignment};
+pub use grid::{Grid, GridParams, GridTrack};
 pub use identity_wrapper::IdentityWrapper;
 pub use label::{Label, LabelText, LineBreaking, RawLabel};
+pub use lazy_list::LazyList;
 pub use lens_wrap::LensWrap;
 pub use list::{List, ListIter};
 pub use maybe::Maybe;
+pub use modal_host::ModalHost;
 pub use padding::Padding;
 pub use painter::{BackgroundBrush, Painter};
 pub use parse::Parse;
 pub use progress_bar::ProgressBar;
 pub use radio::{Radio, RadioGroup};
 pub use scope::{DefaultScopePolicy, LensScopeTransfer, Scope, ScopePolicy, ScopeTransfer};
-pub use scroll::Scroll;
+pub use scroll::{Scroll, SnapBehavior};
 pub use sized_box::SizedBox;
-pub use slider::Slider;
+pub use slider::{RangeSlider, Slider};
 pub use spinner::Spinner;
 pub use split::Split;
 pub use stepper::Stepper;
 #[cfg(feature = "svg")]
 pub use svg::{Svg, SvgData};
 pub use switch::Switch;
+pub use tab_index::TabIndex;
 pub use tabs::{TabInfo, Tabs, TabsEdge, TabsPolicy, TabsState, TabsTransition};
 pub use textbox::TextBox;
+pub use tooltip::Tooltip;
 pub use value_textbox::{TextBoxEvent, ValidationDelegate, ValueTextBox};
 pub use view_switcher::ViewSwitcher;
 #[doc(hidden)]
@@ -107,6 +130,8 @@ pub use widget::{Widget, WidgetId};
 #[doc(hidden)]
 pub use widget_ext::WidgetExt;
 pub use widget_wrapper::WidgetWrapper;
+pub use wrap::Wrap;
+pub use z_stack::ZStack;
 
 /// The types required to implement a `Widget`.
 ///
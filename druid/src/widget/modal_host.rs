@@ -0,0 +1,244 @@
+// Copyright 2021 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A widget that shows modal widgets on top of its content.
+
+use std::any::Any;
+
+use tracing::{instrument, trace, warn};
+
+use crate::widget::prelude::*;
+use crate::{theme, Command, KbKey, Point, Rect, Selector, SingleUse, WidgetPod};
+
+/// Move the focus into the top modal, once it has been added to the tree.
+const FOCUS_MODAL: Selector = Selector::new("druid-builtin.modal-host-focus-modal");
+
+/// A widget that shows modal widgets on top of its content, within the window.
+///
+/// A `ModalHost` is meant to wrap the root widget of a window. Submitting
+/// [`SHOW_MODAL`] shows a widget centered on top of the content, which is dimmed
+/// with [`theme::MODAL_SCRIM_COLOR`]. While a modal is shown, the mouse and the
+/// keyboard only reach the modal; commands, timers and the like still reach the
+/// content. The focus moves into the modal, and goes back to where it was when
+/// the modal is dismissed.
+///
+/// A modal is dismissed by submitting [`DISMISS_MODAL`], or when Escape is
+/// pressed and the modal doesn't handle it. Modals can be shown on top of other
+/// modals; they are dismissed in the reverse order.
+///
+/// # Examples
+///
+/// ```
+/// use druid::widget::{Button, Flex, Label, ModalHost};
+/// use druid::{Widget, WidgetExt};
+///
+/// fn confirm_dialog() -> impl Widget<()> {
+///     Flex::column()
+///         .with_child(Label::new("Discard unsaved changes?"))
+///         .with_child(Button::new("Discard").on_click(|ctx, _, _| {
+///             ctx.submit_command(ModalHost::DISMISS_MODAL);
+///         }))
+///         .padding(20.)
+/// }
+///
+/// let root = ModalHost::new(Button::new("Close").on_click(|ctx, _, _| {
+///     ctx.submit_command(ModalHost::show_modal(confirm_dialog()));
+/// }));
+/// # let _: ModalHost<()> = root;
+/// ```
+///
+/// [`SHOW_MODAL`]: ModalHost::SHOW_MODAL
+/// [`DISMISS_MODAL`]: ModalHost::DISMISS_MODAL
+pub struct ModalHost<T> {
+    child: WidgetPod<T, Box<dyn Widget<T>>>,
+    /// The modals that are shown, from the bottom to the top.
+    modals: Vec<Modal<T>>,
+}
+
+struct Modal<T> {
+    widget: WidgetPod<T, Box<dyn Widget<T>>>,
+    /// The widget that had focus when the modal was shown.
+    previous_focus: Option<WidgetId>,
+}
+
+impl ModalHost<()> {
+    /// Show a modal widget on top of the content of the nearest `ModalHost`.
+    ///
+    /// The payload is a `Box<dyn Widget<T>>`, where `T` is the data of the
+    /// `ModalHost`; [`show_modal`] makes this command from a widget.
+    ///
+    /// [`show_modal`]: ModalHost::show_modal
+    pub const SHOW_MODAL: Selector<SingleUse<Box<dyn Any>>> =
+        Selector::new("druid-builtin.show-modal");
+
+    /// Dismiss the modal widget on top.
+    pub const DISMISS_MODAL: Selector = Selector::new("druid-builtin.dismiss-modal");
+
+    /// Make a [`SHOW_MODAL`] command that shows `widget`.
+    ///
+    /// [`SHOW_MODAL`]: ModalHost::SHOW_MODAL
+    pub fn show_modal<T: Data>(widget: impl Widget<T> + 'static) -> Command {
+        let widget: Box<dyn Widget<T>> = Box::new(widget);
+        ModalHost::SHOW_MODAL.with(SingleUse::new(Box::new(widget)))
+    }
+}
+
+impl<T: Data> ModalHost<T> {
+    /// Create a new `ModalHost` around the content of a window.
+    pub fn new(child: impl Widget<T> + 'static) -> Self {
+        ModalHost {
+            child: WidgetPod::new(Box::new(child)),
+            modals: Vec::new(),
+        }
+    }
+
+    fn push_modal(&mut self, ctx: &mut EventCtx, widget: Box<dyn Widget<T>>) {
+        self.modals.push(Modal {
+            widget: WidgetPod::new(widget),
+            previous_focus: ctx.state.focus_widget,
+        });
+        ctx.children_changed();
+        ctx.submit_command(FOCUS_MODAL.to(ctx.widget_id()));
+    }
+
+    fn pop_modal(&mut self, ctx: &mut EventCtx) {
+        if let Some(modal) = self.modals.pop() {
+            match modal.previous_focus {
+                Some(id) => ctx.set_focus(id),
+                None if ctx.has_focus() => ctx.resign_focus(),
+                None => {}
+            }
+            ctx.children_changed();
+        }
+    }
+}
+
+impl<T: Data> Widget<T> for ModalHost<T> {
+    #[instrument(name = "ModalHost", level = "trace", skip(self, ctx, event, data, env))]
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if let Event::Command(cmd) = event {
+            if let Some(payload) = cmd.get(ModalHost::SHOW_MODAL) {
+                match payload
+                    .take()
+                    .map(|widget| widget.downcast::<Box<dyn Widget<T>>>())
+                {
+                    Some(Ok(widget)) => {
+                        trace!("Showing modal");
+                        self.push_modal(ctx, *widget);
+                    }
+                    Some(Err(_)) => warn!("SHOW_MODAL carries a widget for the wrong data type"),
+                    None => warn!("SHOW_MODAL payload was already taken"),
+                }
+                ctx.set_handled();
+                return;
+            } else if cmd.is(ModalHost::DISMISS_MODAL) && !self.modals.is_empty() {
+                trace!("Dismissing modal");
+                self.pop_modal(ctx);
+                ctx.set_handled();
+                return;
+            } else if cmd.is(FOCUS_MODAL) {
+                if let Some(modal) = self.modals.last() {
+                    // focus ourselves if the modal has nothing to focus, so that
+                    // we still get Escape
+                    let own_id = ctx.widget_id();
+                    let target = modal.widget.state().focus_chain.first().copied();
+                    ctx.set_focus(target.unwrap_or(own_id));
+                }
+                ctx.set_handled();
+                return;
+            }
+        }
+
+        if self.modals.is_empty() {
+            self.child.event(ctx, event, data, env);
+            return;
+        }
+
+        let propagate_to_hidden = event.should_propagate_to_hidden();
+        if propagate_to_hidden {
+            self.child.event(ctx, event, data, env);
+        }
+        let count = self.modals.len();
+        for (i, modal) in self.modals.iter_mut().enumerate() {
+            if propagate_to_hidden || i == count - 1 {
+                modal.widget.event(ctx, event, data, env);
+            }
+        }
+
+        if let Event::KeyDown(key) = event {
+            if key.key == KbKey::Escape && !ctx.is_handled() {
+                self.pop_modal(ctx);
+                ctx.set_handled();
+            }
+        }
+    }
+
+    #[instrument(name = "ModalHost", level = "trace", skip(self, ctx, event, data, env))]
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        // only the top modal takes part in the focus chain
+        let focus_chain = matches!(event, LifeCycle::BuildFocusChain);
+        if !focus_chain || self.modals.is_empty() {
+            self.child.lifecycle(ctx, event, data, env);
+        }
+        let count = self.modals.len();
+        for (i, modal) in self.modals.iter_mut().enumerate() {
+            if !focus_chain || i == count - 1 {
+                modal.widget.lifecycle(ctx, event, data, env);
+            }
+        }
+    }
+
+    #[instrument(
+        name = "ModalHost",
+        level = "trace",
+        skip(self, ctx, _old_data, data, env)
+    )]
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        self.child.update(ctx, data, env);
+        for modal in &mut self.modals {
+            // a modal that was just shown gets its data with `WidgetAdded`
+            if modal.widget.is_initialized() {
+                modal.widget.update(ctx, data, env);
+            }
+        }
+    }
+
+    #[instrument(name = "ModalHost", level = "trace", skip(self, ctx, bc, data, env))]
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        bc.debug_check("ModalHost");
+        let size = self.child.layout(ctx, bc, data, env);
+        self.child.set_origin(ctx, data, env, Point::ORIGIN);
+
+        let modal_bc = BoxConstraints::new(Size::ZERO, size);
+        for modal in &mut self.modals {
+            let modal_size = modal.widget.layout(ctx, &modal_bc, data, env);
+            let origin = ((size - modal_size) / 2.0).to_vec2().to_point();
+            modal.widget.set_origin(ctx, data, env, origin.round());
+        }
+
+        ctx.set_paint_insets(self.child.paint_insets());
+        trace!("Computed layout: size={}", size);
+        size
+    }
+
+    #[instrument(name = "ModalHost", level = "trace", skip(self, ctx, data, env))]
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.child.paint(ctx, data, env);
+        let scrim = Rect::from_origin_size(Point::ORIGIN, ctx.size());
+        for modal in &mut self.modals {
+            ctx.fill(scrim, &env.get(theme::MODAL_SCRIM_COLOR));
+            modal.widget.paint(ctx, data, env);
+        }
+    }
+}
@@ -33,6 +33,9 @@ impl<T, W: Widget<T>> Padding<T, W> {
     /// an `f64` for uniform padding, an `(f64, f64)` for axis-uniform padding,
     /// or `(f64, f64, f64, f64)` (left, top, right, bottom) values.
     ///
+    /// Insets can be negative, which lets the child overlap its surroundings
+    /// on that side by the given amount.
+    ///
     /// # Examples
     ///
     /// Uniform padding:
@@ -102,9 +105,11 @@ impl<T: Data, W: Widget<T>> Widget<T> for Padding<T, W> {
         let origin = Point::new(insets.x0, insets.y0);
         self.child.set_origin(ctx, data, env, origin);
 
-        let my_size = Size::new(size.width + hpad, size.height + vpad);
+        // with negative insets the child may be smaller than the overlap.
+        let my_size = Size::new((size.width + hpad).max(0.0), (size.height + vpad).max(0.0));
         let my_insets = self.child.compute_parent_paint_insets(my_size);
         ctx.set_paint_insets(my_insets);
+        ctx.set_baseline_offset(self.child.baseline_offset() + insets.y1);
         trace!("Computed layout: size={}, insets={:?}", my_size, my_insets);
         my_size
     }
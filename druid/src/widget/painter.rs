@@ -128,7 +128,7 @@ impl<T: Data> Widget<T> for Painter<T> {
     fn lifecycle(&mut self, _: &mut LifeCycleCtx, _: &LifeCycle, _: &T, _: &Env) {}
     #[instrument(name = "Painter", level = "trace", skip(self, ctx, old_data, data))]
     fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, _: &Env) {
-        if !old_data.same(data) {
+        if !old_data.same(data) || ctx.env_changed() {
             ctx.request_paint();
         }
     }
@@ -14,44 +14,125 @@
 
 //! A progress bar widget.
 
+use std::fmt;
+use std::rc::Rc;
+
 use crate::widget::prelude::*;
 use crate::{theme, LinearGradient, Point, Rect, UnitPoint};
 use tracing::instrument;
 
+// the time, in seconds, for the indeterminate highlight to cross the bar.
+const INDETERMINATE_PERIOD: f64 = 1.5;
+// the width of the indeterminate highlight, as a fraction of the bar.
+const INDETERMINATE_WIDTH: f64 = 0.3;
+
 /// A progress bar, displaying a numeric progress value.
 ///
 /// This type impls `Widget<f64>`, expecting a float in the range `0.0..1.0`.
-#[derive(Debug, Clone, Default)]
-pub struct ProgressBar;
+///
+/// When the amount of work isn't known, an [`indeterminate`] progress bar
+/// shows a moving highlight instead.
+///
+/// [`indeterminate`]: #method.indeterminate
+#[derive(Clone, Default)]
+pub struct ProgressBar {
+    indeterminate: bool,
+    /// How far the indeterminate highlight has moved across the bar, from 0 to 1.
+    phase: f64,
+    animating: bool,
+    /// Whether we were painted since the last animation frame; when hidden
+    /// we aren't, and the animation stops.
+    painted: bool,
+    on_complete: Option<Rc<dyn Fn(&mut UpdateCtx, &Env)>>,
+    complete: bool,
+}
 
 impl ProgressBar {
     /// Return a new `ProgressBar`.
     pub fn new() -> ProgressBar {
         Self::default()
     }
+
+    /// Return a new indeterminate `ProgressBar`.
+    ///
+    /// This ignores its data, and animates a highlight moving along the bar.
+    /// The animation only runs while the bar is being painted.
+    pub fn indeterminate() -> ProgressBar {
+        ProgressBar {
+            indeterminate: true,
+            ..Self::default()
+        }
+    }
+
+    /// Builder-style method to provide a closure that is called when the
+    /// progress reaches `1.0`.
+    ///
+    /// The closure is called once each time the value goes from below `1.0`
+    /// to `1.0` or above.
+    pub fn on_complete(mut self, f: impl Fn(&mut UpdateCtx, &Env) + 'static) -> Self {
+        self.on_complete = Some(Rc::new(f));
+        self
+    }
+
+    fn start_animation(&mut self, ctx: &mut LifeCycleCtx) {
+        if self.indeterminate && !self.animating {
+            self.animating = true;
+            self.painted = true;
+            ctx.request_anim_frame();
+        }
+    }
 }
 
 impl Widget<f64> for ProgressBar {
     #[instrument(
         name = "ProgressBar",
         level = "trace",
-        skip(self, _ctx, _event, _data, _env)
+        skip(self, ctx, event, _data, _env)
     )]
-    fn event(&mut self, _ctx: &mut EventCtx, _event: &Event, _data: &mut f64, _env: &Env) {}
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, _data: &mut f64, _env: &Env) {
+        if let Event::AnimFrame(interval) = event {
+            if self.animating && self.painted {
+                let elapsed = *interval as f64 / 1e9;
+                self.phase = (self.phase + elapsed / INDETERMINATE_PERIOD) % 1.0;
+                self.painted = false;
+                ctx.request_paint();
+                ctx.request_anim_frame();
+            } else {
+                self.animating = false;
+            }
+        }
+    }
 
     #[instrument(
         name = "ProgressBar",
         level = "trace",
-        skip(self, _ctx, _event, _data, _env)
+        skip(self, ctx, event, data, _env)
     )]
-    fn lifecycle(&mut self, _ctx: &mut LifeCycleCtx, _event: &LifeCycle, _data: &f64, _env: &Env) {}
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &f64, _env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            self.complete = *data >= 1.0;
+        }
+        // hidden widgets don't get most lifecycle events, so getting one is a
+        // good sign that we may be visible again.
+        self.start_animation(ctx);
+    }
 
     #[instrument(
         name = "ProgressBar",
         level = "trace",
-        skip(self, ctx, _old_data, _data, _env)
+        skip(self, ctx, _old_data, data, env)
     )]
-    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &f64, _data: &f64, _env: &Env) {
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &f64, data: &f64, env: &Env) {
+        if !self.indeterminate {
+            if *data < 1.0 {
+                self.complete = false;
+            } else if !self.complete {
+                self.complete = true;
+                if let Some(on_complete) = &self.on_complete {
+                    on_complete(ctx, env);
+                }
+            }
+        }
         ctx.request_paint();
     }
 
@@ -101,6 +182,31 @@ impl Widget<f64> for ProgressBar {
         );
         ctx.fill(rounded_rect, &background_gradient);
 
+        let bar_gradient = LinearGradient::new(
+            UnitPoint::TOP,
+            UnitPoint::BOTTOM,
+            (env.get(theme::PRIMARY_LIGHT), env.get(theme::PRIMARY_DARK)),
+        );
+
+        if self.indeterminate {
+            self.painted = true;
+            // the highlight enters from the left and leaves on the right
+            let width = rounded_rect.width();
+            let highlight_width = width * INDETERMINATE_WIDTH;
+            let x = self.phase * (width + highlight_width) - highlight_width;
+            let highlight = Rect::from_origin_size(
+                Point::new(x - inset, 0.),
+                Size::new(highlight_width, height),
+            )
+            .inset((0.0, inset))
+            .to_rounded_rect(corner_radius);
+            ctx.with_save(|ctx| {
+                ctx.clip(rounded_rect);
+                ctx.fill(highlight, &bar_gradient);
+            });
+            return;
+        }
+
         // Paint the bar
         let calculated_bar_width = clamped * rounded_rect.width();
 
@@ -111,11 +217,19 @@ impl Widget<f64> for ProgressBar {
         .inset((0.0, inset))
         .to_rounded_rect(corner_radius);
 
-        let bar_gradient = LinearGradient::new(
-            UnitPoint::TOP,
-            UnitPoint::BOTTOM,
-            (env.get(theme::PRIMARY_LIGHT), env.get(theme::PRIMARY_DARK)),
-        );
         ctx.fill(rounded_rect, &bar_gradient);
     }
 }
+
+impl fmt::Debug for ProgressBar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ProgressBar")
+            .field("indeterminate", &self.indeterminate)
+            .field("phase", &self.phase)
+            .field(
+                "on_complete",
+                &self.on_complete.as_ref().map(|_| "<closure>"),
+            )
+            .finish()
+    }
+}
@@ -130,10 +130,17 @@ impl<T: Data + PartialEq> Widget<T> for Radio<T> {
         let background_gradient = LinearGradient::new(
             UnitPoint::TOP,
             UnitPoint::BOTTOM,
-            (
-                env.get(theme::BACKGROUND_LIGHT),
-                env.get(theme::BACKGROUND_DARK),
-            ),
+            if ctx.is_disabled() {
+                (
+                    env.get(theme::DISABLED_BACKGROUND_LIGHT),
+                    env.get(theme::DISABLED_BACKGROUND_DARK),
+                )
+            } else {
+                (
+                    env.get(theme::BACKGROUND_LIGHT),
+                    env.get(theme::BACKGROUND_DARK),
+                )
+            },
         );
 
         ctx.fill(circle, &background_gradient);
@@ -273,9 +273,19 @@ impl<In: Data, State: Data, F: Fn(In) -> State, L: Lens<State, In>, W: Widget<St
 impl<SP: ScopePolicy, W: Widget<SP::State>> Widget<SP::In> for Scope<SP, W> {
     #[instrument(name = "Scope", level = "trace", skip(self, ctx, event, data, env))]
     fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut SP::In, env: &Env) {
-        self.with_state(data, |state, inner| inner.event(ctx, event, state, env));
-        self.write_back_input(data);
-        ctx.request_update()
+        let changed = self.with_state(data, |state, inner| {
+            let old_state = state.clone();
+            inner.event(ctx, event, state, env);
+            !old_state.same(state)
+        });
+        // Changes to the state are written back to the input straight away, and
+        // an update is requested so that the inner widget sees changes to the
+        // parts of the state that the input doesn't cover. Reading the input
+        // back in during that update is then a no-op.
+        if changed {
+            self.write_back_input(data);
+            ctx.request_update();
+        }
     }
 
     #[instrument(name = "Scope", level = "trace", skip(self, ctx, event, data, env))]
@@ -14,11 +14,39 @@
 
 //! A container that scrolls its contents.
 
-use crate::widget::prelude::*;
-use crate::widget::{Axis, ClipBox};
-use crate::{scroll_component::*, Data, Rect, Vec2};
+use std::collections::VecDeque;
+
+use instant::{Duration, Instant};
 use tracing::{instrument, trace};
 
+use crate::commands::{SCROLLED, SCROLL_BY, VIEWPORT_CHANGED};
+use crate::widget::prelude::*;
+use crate::widget::{Axis, ClipBox, Viewport};
+use crate::{scroll_component::*, Affine, Data, Point, Rect, Selector, TimerToken, Vec2};
+
+/// Sent by a `Scroll` to itself when its offset changed during layout, or when its
+/// viewport changed, so that the change can be reported during the next event pass.
+const REPORT_SCROLL: Selector = Selector::new("druid-builtin.scroll-report-offset");
+
+/// How far back wheel input is considered when estimating the velocity of a fling.
+const MOMENTUM_SAMPLE_WINDOW: Duration = Duration::from_millis(100);
+/// The shortest time the wheel input of a fling is taken to span, so that events
+/// that arrive in a burst don't make for an absurd velocity.
+const MOMENTUM_MIN_SPAN: Duration = Duration::from_millis(16);
+/// How long wheel input has to pause before a fling starts.
+const MOMENTUM_PAUSE: Duration = Duration::from_millis(50);
+/// The number of recent wheel events needed to start a fling, so that single
+/// clicks of a mouse wheel don't.
+const MOMENTUM_MIN_SAMPLES: usize = 3;
+/// The fraction of a fling's velocity that remains after one second.
+const MOMENTUM_DECAY: f64 = 0.05;
+/// Flings stop once they are slower than this, in pixels per second.
+const MOMENTUM_MIN_VELOCITY: f64 = 20.0;
+/// How long wheel input has to pause before the offset settles on a snap point.
+const SNAP_IDLE_DELAY: Duration = Duration::from_millis(150);
+/// The fraction of the distance to a snap point that remains after one second.
+const SNAP_DECAY: f64 = 1e-4;
+
 /// A container that scrolls its contents.
 ///
 /// This container holds a single child, and uses the wheel to scroll it
@@ -29,11 +57,67 @@ use tracing::{instrument, trace};
 /// When restricted to scrolling on a specific axis the child's size is
 /// locked on the opposite axis.
 ///
+/// Whenever the scroll offset changes, the `Scroll` submits a [`SCROLLED`]
+/// notification to its ancestors. The same information can be observed
+/// directly with [`on_scroll`]. Descendants are told about the visible part of
+/// the content with the [`VIEWPORT_CHANGED`] command.
+///
 /// [`vertical`]: struct.Scroll.html#method.vertical
 /// [`horizontal`]: struct.Scroll.html#method.horizontal
+/// [`SCROLLED`]: crate::commands::SCROLLED
+/// [`VIEWPORT_CHANGED`]: crate::commands::VIEWPORT_CHANGED
+/// [`on_scroll`]: Scroll::on_scroll
 pub struct Scroll<T, W> {
     clip: ClipBox<T, W>,
     scroll_component: ScrollComponent,
+    /// Where the clip box is placed, which is not the origin when inlay scrollbar
+    /// tracks are placed along the left or top edges.
+    clip_origin: Point,
+    /// The offset set before the first layout, which is applied once the size of the
+    /// child is known.
+    pending_offset: Option<Vec2>,
+    laid_out: bool,
+    anchor_content: bool,
+    stick_to_bottom: bool,
+    momentum: Option<Momentum>,
+    reported_origin: Point,
+    reported_view: Rect,
+    on_scroll: Option<Box<dyn Fn(&mut EventCtx, &Viewport, &mut T, &Env)>>,
+    on_zoom: Option<Box<dyn Fn(&mut EventCtx, &mut T, Point, Vec2, &Env)>>,
+    snap: Option<Snap>,
+}
+
+/// Where a [`Scroll`] settles after the user has scrolled it.
+///
+/// Snap points are offsets along the vertical axis, or along the horizontal axis
+/// for a [`Scroll`] that only scrolls horizontally.
+pub enum SnapBehavior {
+    /// Snap to multiples of this distance, for example to the pages of a
+    /// carousel whose pages all have this size.
+    Interval(f64),
+    /// Snap to the offsets returned by the closure, which receives the size
+    /// of the child.
+    Points(Box<dyn Fn(Size) -> Vec<f64>>),
+}
+
+/// The snapping state of a `Scroll`.
+struct Snap {
+    behavior: SnapBehavior,
+    /// Fires when wheel input has paused for long enough to settle.
+    timer: TimerToken,
+    /// The offset we are currently animating towards.
+    target: Option<f64>,
+}
+
+/// Tracks recent wheel input, and the velocity of an ongoing fling.
+#[derive(Debug)]
+struct Momentum {
+    /// Recent wheel deltas that we applied, and when they arrived.
+    samples: VecDeque<(Instant, Vec2)>,
+    /// The velocity of the current fling, in pixels per second.
+    velocity: Option<Vec2>,
+    /// Fires when wheel input has paused for long enough to start a fling.
+    timer: TimerToken,
 }
 
 impl<T, W: Widget<T>> Scroll<T, W> {
@@ -46,6 +130,17 @@ impl<T, W: Widget<T>> Scroll<T, W> {
         Scroll {
             clip: ClipBox::new(child),
             scroll_component: ScrollComponent::new(),
+            clip_origin: Point::ORIGIN,
+            pending_offset: None,
+            laid_out: false,
+            anchor_content: false,
+            stick_to_bottom: false,
+            momentum: None,
+            reported_origin: Point::ORIGIN,
+            reported_view: Rect::ZERO,
+            on_scroll: None,
+            on_zoom: None,
+            snap: None,
         }
     }
 
@@ -53,14 +148,30 @@ impl<T, W: Widget<T>> Scroll<T, W> {
     ///
     /// Returns `true` if the scroll offset has changed.
     pub fn scroll_by(&mut self, delta: Vec2) -> bool {
+        self.pending_offset = None;
         self.clip.pan_by(delta)
     }
 
+    /// Set the scroll offset.
+    ///
+    /// The offset is clamped so that the viewport stays within the child. An offset
+    /// set before the first layout, when the child's size is not known yet, is
+    /// applied during that layout.
+    ///
+    /// Returns `true` if the scroll offset has changed.
+    pub fn set_offset(&mut self, offset: Vec2) -> bool {
+        self.cancel_snap();
+        self.pending_offset = if self.laid_out { None } else { Some(offset) };
+        self.clip.pan_to(offset.to_point())
+    }
+
     /// Scroll the minimal distance to show the target rect.
     ///
     /// If the target region is larger than the viewport, we will display the
     /// portion that fits, prioritizing the portion closest to the origin.
     pub fn scroll_to(&mut self, region: Rect) -> bool {
+        self.cancel_snap();
+        self.pending_offset = None;
         self.clip.pan_to_visible(region)
     }
 
@@ -68,8 +179,88 @@ impl<T, W: Widget<T>> Scroll<T, W> {
     ///
     /// Returns `true` if the scroll offset has changed.
     pub fn scroll_to_on_axis(&mut self, axis: Axis, position: f64) -> bool {
+        self.cancel_snap();
+        self.pending_offset = None;
         self.clip.pan_to_on_axis(axis, position)
     }
+
+    /// Adjust the vertical offset after the child changed size, according to
+    /// the anchoring settings.
+    fn anchor_content(&mut self, old_port: Viewport) {
+        let new_port = self.clip.viewport();
+        if old_port.content_size.height == new_port.content_size.height {
+            return;
+        }
+
+        let old_max_y = old_port.content_size.height - old_port.view_size.height;
+        let new_y = if self.stick_to_bottom && old_port.view_origin.y >= old_max_y {
+            new_port.content_size.height - new_port.view_size.height
+        } else if self.anchor_content && old_port.view_origin.y > 0.0 {
+            old_port.view_origin.y + new_port.content_size.height - old_port.content_size.height
+        } else {
+            return;
+        };
+        self.clip.pan_to(Point::new(new_port.view_origin.x, new_y));
+    }
+
+    /// Settle on the nearest snap point once a wheel gesture or scrollbar drag
+    /// has ended, and drive the animation towards it.
+    fn snap_event(&mut self, ctx: &mut EventCtx, event: &Event, scrolled: bool, released: bool) {
+        let axis = self.snap_axis();
+        let flinging = matches!(&self.momentum, Some(momentum) if momentum.velocity.is_some());
+        let snap = match &mut self.snap {
+            Some(snap) => snap,
+            None => return,
+        };
+
+        let mut settle = released;
+        match event {
+            Event::Wheel(_) if scrolled => {
+                snap.target = None;
+                snap.timer = ctx.request_timer(SNAP_IDLE_DELAY);
+            }
+            Event::MouseDown(_) => snap.target = None,
+            Event::Timer(token) if *token == snap.timer => {
+                ctx.set_handled();
+                if flinging {
+                    // wait for the fling to end
+                    snap.timer = ctx.request_timer(SNAP_IDLE_DELAY);
+                } else {
+                    snap.timer = TimerToken::INVALID;
+                    settle = true;
+                }
+            }
+            Event::AnimFrame(interval) => {
+                if let Some(target) = snap.target {
+                    let offset = axis.major_pos(self.clip.viewport_origin());
+                    let progress = 1.0 - SNAP_DECAY.powf(*interval as f64 * 1e-9);
+                    let mut next = offset + (target - offset) * progress;
+                    if (target - next).abs() < 0.5 {
+                        next = target;
+                        snap.target = None;
+                    } else {
+                        ctx.request_anim_frame();
+                    }
+                    self.clip.pan_to_on_axis(axis, next);
+                    ctx.request_paint();
+                }
+            }
+            _ => (),
+        }
+
+        if settle {
+            let port = self.clip.viewport();
+            let offset = axis.major_pos(port.view_origin);
+            let max_offset = axis.major_pos(port.max_view_origin());
+            if let Some((_, target)) = snap.behavior.nearest(offset, max_offset, port.content_size)
+            {
+                if (target - offset).abs() > f64::EPSILON {
+                    snap.target = Some(target);
+                    ctx.request_anim_frame();
+                }
+            }
+        }
+    }
 }
 
 impl<T, W> Scroll<T, W> {
@@ -99,7 +290,174 @@ impl<T, W> Scroll<T, W> {
         self
     }
 
-    /// Disable both scrollbars
+    /// Builder-style method to set whether the visible content should be kept
+    /// in place when the size of the child changes.
+    ///
+    /// If `true`, and the view is not at the very top of the child, a change in
+    /// the child's height is assumed to have happened above the view, and the
+    /// offset is adjusted so that the distance to the bottom of the child is
+    /// preserved. This is useful when content is prepended, such as when older
+    /// messages are loaded at the top of a chat log.
+    ///
+    /// The default is `false`.
+    pub fn with_content_anchoring(mut self, anchor: bool) -> Self {
+        self.anchor_content = anchor;
+        self
+    }
+
+    /// Builder-style method to set whether the view should stay at the bottom
+    /// of the child when the child's height changes.
+    ///
+    /// If `true`, and the view was at the bottom of the child before it
+    /// changed size, the view is moved to the new bottom. This takes precedence
+    /// over [`with_content_anchoring`].
+    ///
+    /// The default is `false`.
+    ///
+    /// [`with_content_anchoring`]: Scroll::with_content_anchoring
+    pub fn with_stick_to_bottom(mut self, stick: bool) -> Self {
+        self.stick_to_bottom = stick;
+        self
+    }
+
+    /// Builder-style method to set whether scrolling continues with decaying
+    /// velocity after wheel or trackpad input stops.
+    ///
+    /// Any new input immediately stops such a fling. The default is `false`.
+    ///
+    /// Some platforms, such as macOS, already deliver synthesized momentum
+    /// wheel events; enabling this there would apply the inertia twice.
+    pub fn with_momentum(mut self, momentum: bool) -> Self {
+        self.momentum = if momentum {
+            Some(Momentum::default())
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Builder-style method to provide a callback that is called whenever the
+    /// scroll offset changes.
+    ///
+    /// The callback receives the [`Viewport`] after the change. It is called at
+    /// the same time as the [`SCROLLED`] notification is submitted.
+    ///
+    /// [`SCROLLED`]: crate::commands::SCROLLED
+    pub fn on_scroll(
+        mut self,
+        f: impl Fn(&mut EventCtx, &Viewport, &mut T, &Env) + 'static,
+    ) -> Self {
+        self.on_scroll = Some(Box::new(f));
+        self
+    }
+
+    /// Builder-style method to provide a callback that handles zooming.
+    ///
+    /// When this is set, wheel events with the ctrl key held are passed to the
+    /// callback instead of scrolling. The callback receives the mouse position in
+    /// the child's coordinate space, which already accounts for the scroll offset,
+    /// and the wheel delta. Without a callback, ctrl+wheel scrolls as usual.
+    pub fn on_zoom(
+        mut self,
+        f: impl Fn(&mut EventCtx, &mut T, Point, Vec2, &Env) + 'static,
+    ) -> Self {
+        self.on_zoom = Some(Box::new(f));
+        self
+    }
+
+    /// Builder-style method to set the edge the vertical scrollbar is placed along.
+    ///
+    /// The default is [`VerticalBarPosition::Right`]; right-to-left interfaces
+    /// will usually want [`VerticalBarPosition::Left`].
+    pub fn vertical_scrollbar_position(mut self, position: VerticalBarPosition) -> Self {
+        self.scroll_component.vertical_bar_position = position;
+        self
+    }
+
+    /// Builder-style method to set the edge the horizontal scrollbar is placed along.
+    ///
+    /// The default is [`HorizontalBarPosition::Bottom`].
+    pub fn horizontal_scrollbar_position(mut self, position: HorizontalBarPosition) -> Self {
+        self.scroll_component.horizontal_bar_position = position;
+        self
+    }
+
+    /// Builder-style method to set whether the scrollbars are drawn over the content,
+    /// or placed in tracks next to it.
+    ///
+    /// The default is [`ScrollbarStyle::Overlay`].
+    pub fn scrollbar_style(mut self, style: ScrollbarStyle) -> Self {
+        self.scroll_component.scrollbar_style = style;
+        self
+    }
+
+    /// Builder-style method to set when the scrollbars are shown.
+    ///
+    /// The default is [`ScrollbarVisibility::Auto`].
+    pub fn scrollbar_visibility(mut self, visibility: ScrollbarVisibility) -> Self {
+        self.scroll_component.scrollbar_visibility = visibility;
+        self
+    }
+
+    /// Builder-style method to set whether the vertical scrollbar is shown.
+    ///
+    /// A hidden scrollbar is neither drawn nor hit tested, so mouse events over it
+    /// reach the child. Scrolling vertically still works, for instance with the
+    /// mouse wheel.
+    pub fn show_vertical_scrollbar(mut self, show: bool) -> Self {
+        self.scroll_component.show_vertical_bar = show;
+        self
+    }
+
+    /// Builder-style method to set whether the horizontal scrollbar is shown.
+    ///
+    /// A hidden scrollbar is neither drawn nor hit tested, so mouse events over it
+    /// reach the child. Scrolling horizontally still works, for instance with the
+    /// mouse wheel.
+    pub fn show_horizontal_scrollbar(mut self, show: bool) -> Self {
+        self.scroll_component.show_horizontal_bar = show;
+        self
+    }
+
+    /// Builder-style method to make the offset settle on snap points.
+    ///
+    /// Once a wheel gesture or a scrollbar drag ends, the offset animates to the
+    /// nearest snap point. Offsets set programmatically, such as with
+    /// [`scroll_to`], are not snapped.
+    ///
+    /// [`scroll_to`]: Scroll::scroll_to
+    pub fn with_snap_points(mut self, behavior: SnapBehavior) -> Self {
+        self.snap = Some(Snap {
+            behavior,
+            timer: TimerToken::INVALID,
+            target: None,
+        });
+        self
+    }
+
+    /// Builder-style method to set the initial scroll offset.
+    ///
+    /// The offset is applied during the first layout, once the size of the child
+    /// is known, and is clamped so that the viewport stays within the child. On an
+    /// axis that can't be scrolled (see [`vertical`] and [`horizontal`]) the
+    /// corresponding component of the offset is ignored.
+    ///
+    /// [`vertical`]: Scroll::vertical
+    /// [`horizontal`]: Scroll::horizontal
+    pub fn with_initial_offset(mut self, offset: Vec2) -> Self {
+        self.pending_offset = Some(offset);
+        self
+    }
+
+    /// Builder-style method to disable both scrollbars.
+    ///
+    /// The content can still be scrolled with a trackpad or scroll wheel, or
+    /// programmatically, but no scrollbars are painted or hit tested, and no
+    /// fade animations are scheduled.
+    ///
+    /// Scrollbars can be turned back on at runtime with [`set_enabled_scrollbars`].
+    ///
+    /// [`set_enabled_scrollbars`]: Scroll::set_enabled_scrollbars
     pub fn disable_scrollbars(mut self) -> Self {
         self.scroll_component.enabled = ScrollbarsEnabled::None;
         self
@@ -166,15 +524,110 @@ impl<T, W> Scroll<T, W> {
         self.clip.viewport().view_rect()
     }
 
+    /// Returns the largest scroll offset possible with the current child and viewport sizes.
+    ///
+    /// This is updated during layout, and is valid after the first layout pass.
+    pub fn max_scroll_offset(&self) -> Vec2 {
+        self.clip.viewport().max_view_origin().to_vec2()
+    }
+
     /// Return the scroll offset on a particular axis
     pub fn offset_for_axis(&self, axis: Axis) -> f64 {
         axis.major_pos(self.clip.viewport_origin())
     }
+
+    /// Returns the index of the snap point nearest to the current offset, such as
+    /// the current page of a carousel, or `None` if no snap points are set.
+    pub fn snap_index(&self) -> Option<usize> {
+        let axis = self.snap_axis();
+        let port = self.clip.viewport();
+        let snap = self.snap.as_ref()?;
+        let offset = axis.major_pos(port.view_origin);
+        let max_offset = axis.major_pos(port.max_view_origin());
+        snap.behavior
+            .nearest(offset, max_offset, port.content_size)
+            .map(|(index, _)| index)
+    }
+
+    /// The axis along which snap points are placed.
+    fn snap_axis(&self) -> Axis {
+        match self.scroll_component.enabled {
+            ScrollbarsEnabled::Horizontal => Axis::Horizontal,
+            _ => Axis::Vertical,
+        }
+    }
+
+    /// Stop settling on a snap point.
+    fn cancel_snap(&mut self) {
+        if let Some(snap) = &mut self.snap {
+            snap.target = None;
+            snap.timer = TimerToken::INVALID;
+        }
+    }
+
+    /// Notify observers if the offset has changed since we last did so, and schedule
+    /// telling our descendants if the visible part of the content has changed.
+    fn report_scroll(&mut self, ctx: &mut EventCtx, data: &mut T, env: &Env) {
+        let port = self.clip.viewport();
+        if port.view_origin != self.reported_origin {
+            self.reported_origin = port.view_origin;
+            if let Some(on_scroll) = &self.on_scroll {
+                on_scroll(ctx, &port, data, env);
+            }
+            ctx.submit_notification(SCROLLED.with(port));
+        }
+        if port.view_rect() != self.reported_view {
+            ctx.submit_command(REPORT_SCROLL.to(ctx.widget_id()));
+        }
+    }
 }
 
 impl<T: Data, W: Widget<T>> Widget<T> for Scroll<T, W> {
     #[instrument(name = "Scroll", level = "trace", skip(self, ctx, event, data, env))]
     fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if let Event::Command(cmd) = event {
+            if cmd.is(REPORT_SCROLL) {
+                let view = self.clip.viewport().view_rect();
+                if view != self.reported_view {
+                    // Tell our descendants which part of the content is visible.
+                    self.reported_view = view;
+                    let event = Event::Command(VIEWPORT_CHANGED.with(view));
+                    self.clip.event(ctx, &event, data, env);
+                }
+                self.report_scroll(ctx, data, env);
+                ctx.set_handled();
+                return;
+            }
+            if cmd.is(VIEWPORT_CHANGED) {
+                // This comes from a scrolling ancestor, and doesn't apply to our content.
+                ctx.set_handled();
+                return;
+            }
+        }
+
+        if let Event::Notification(notification) = event {
+            if let Some(delta) = notification.get(SCROLL_BY) {
+                self.cancel_snap();
+                if self.scroll_by(*delta) {
+                    self.scroll_component
+                        .reset_scrollbar_fade(|d| ctx.request_timer(d), env);
+                    ctx.request_paint();
+                }
+                self.report_scroll(ctx, data, env);
+                ctx.set_handled();
+                return;
+            }
+        }
+
+        // The scroll component and the clip box expect events relative to the clip box.
+        let translated = if self.clip_origin != Point::ORIGIN {
+            event.transform_scroll(-self.clip_origin.to_vec2(), Rect::ZERO, true)
+        } else {
+            None
+        };
+        let event = translated.as_ref().unwrap_or(event);
+
+        let bars_were_held = self.scroll_component.are_bars_held();
         let scroll_component = &mut self.scroll_component;
         self.clip.with_port(|port| {
             scroll_component.event(port, ctx, event, env);
@@ -182,10 +635,25 @@ impl<T: Data, W: Widget<T>> Widget<T> for Scroll<T, W> {
         if !ctx.is_handled() {
             self.clip.event(ctx, event, data, env);
         }
+        if let (Some(on_zoom), Event::Wheel(mouse)) = (&self.on_zoom, event) {
+            if mouse.mods.ctrl() && !ctx.is_handled() {
+                let pos = mouse.pos + self.clip.viewport_origin().to_vec2();
+                on_zoom(ctx, data, pos, mouse.wheel_delta, env);
+                ctx.set_handled();
+            }
+        }
 
         self.clip.with_port(|port| {
             scroll_component.handle_scroll(port, ctx, event, env);
         });
+        let scrolled = self.scroll_component.take_scrolled();
+        if let Some(momentum) = &mut self.momentum {
+            let clip = &mut self.clip;
+            momentum.event(ctx, event, scrolled, |delta| clip.pan_by(delta));
+        }
+        let released_bar = bars_were_held && !self.scroll_component.are_bars_held();
+        self.snap_event(ctx, event, scrolled, released_bar);
+        self.report_scroll(ctx, data, env);
     }
 
     #[instrument(name = "Scroll", level = "trace", skip(self, ctx, event, data, env))]
@@ -203,18 +671,47 @@ impl<T: Data, W: Widget<T>> Widget<T> for Scroll<T, W> {
     fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
         bc.debug_check("Scroll");
 
-        let old_size = self.clip.viewport().view_size;
-        let child_size = self.clip.layout(ctx, bc, data, env);
+        let old_port = self.clip.viewport();
+        let mut child_size = self.clip.layout(ctx, bc, data, env);
+        let content_size = self.clip.viewport().content_size;
+        let track_eat = self
+            .scroll_component
+            .calc_track_eat(child_size, content_size, env);
+        if track_eat != Size::ZERO {
+            // Lay out again, leaving room for the scrollbar tracks.
+            child_size = self.clip.layout(ctx, &bc.shrink(track_eat), data, env);
+        }
         log_size_warnings(child_size);
 
-        let self_size = bc.constrain(child_size);
+        let self_size = bc.constrain(child_size + track_eat);
+        self.clip_origin = Point::new(
+            match self.scroll_component.vertical_bar_position {
+                VerticalBarPosition::Left => track_eat.width,
+                VerticalBarPosition::Right => 0.0,
+            },
+            match self.scroll_component.horizontal_bar_position {
+                HorizontalBarPosition::Top => track_eat.height,
+                HorizontalBarPosition::Bottom => 0.0,
+            },
+        );
+        self.anchor_content(old_port);
+        if let Some(offset) = self.pending_offset.take() {
+            self.clip.pan_to(offset.to_point());
+        }
+        self.laid_out = true;
         // The new size might have made the current scroll offset invalid. This makes it valid
         // again.
         let _ = self.scroll_by(Vec2::ZERO);
-        if old_size != self_size {
+        if old_port.view_size != self.clip.viewport().view_size {
             self.scroll_component
                 .reset_scrollbar_fade(|d| ctx.request_timer(d), env);
         }
+        // We can't submit notifications from layout, so ask for an event pass instead.
+        if self.clip.viewport_origin() != self.reported_origin
+            || self.clip.viewport().view_rect() != self.reported_view
+        {
+            ctx.submit_command(REPORT_SCROLL.to(ctx.widget_id()));
+        }
 
         trace!("Computed size: {}", self_size);
         self_size
@@ -222,9 +719,120 @@ impl<T: Data, W: Widget<T>> Widget<T> for Scroll<T, W> {
 
     #[instrument(name = "Scroll", level = "trace", skip(self, ctx, data, env))]
     fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
-        self.clip.paint(ctx, data, env);
-        self.scroll_component
-            .draw_bars(ctx, &self.clip.viewport(), env);
+        let clip_offset = self.clip_origin.to_vec2();
+        let mut region = ctx.region().clone();
+        region -= clip_offset;
+        ctx.with_save(|ctx| {
+            ctx.transform(Affine::translate(clip_offset));
+            ctx.with_child_ctx(region, |ctx| {
+                let port = self.clip.viewport();
+                self.scroll_component
+                    .draw_scrollbar_background(ctx, &port, env);
+                self.clip.paint(ctx, data, env);
+                self.scroll_component.draw_bars(ctx, &port, env);
+            });
+        });
+    }
+}
+
+impl SnapBehavior {
+    /// Returns the index of the snap point closest to `offset`, and that snap point
+    /// clamped to the scrollable range.
+    fn nearest(&self, offset: f64, max_offset: f64, child_size: Size) -> Option<(usize, f64)> {
+        let (index, point) = match self {
+            SnapBehavior::Interval(interval) => {
+                if *interval <= 0.0 {
+                    return None;
+                }
+                let index = (offset.min(max_offset) / interval).round().max(0.0);
+                (index as usize, index * interval)
+            }
+            SnapBehavior::Points(points) => {
+                points(child_size)
+                    .into_iter()
+                    .enumerate()
+                    .min_by(|(_, a), (_, b)| {
+                        let a = (a - offset).abs();
+                        let b = (b - offset).abs();
+                        a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+                    })?
+            }
+        };
+        Some((index, point.max(0.0).min(max_offset)))
+    }
+}
+
+impl Default for Momentum {
+    fn default() -> Self {
+        Momentum {
+            samples: VecDeque::new(),
+            velocity: None,
+            timer: TimerToken::INVALID,
+        }
+    }
+}
+
+impl Momentum {
+    /// Track wheel input and drive any fling.
+    ///
+    /// `scrolled` is whether the event scrolled our content, and `pan_by` should move
+    /// the viewport, returning `false` if it couldn't.
+    fn event(
+        &mut self,
+        ctx: &mut EventCtx,
+        event: &Event,
+        scrolled: bool,
+        mut pan_by: impl FnMut(Vec2) -> bool,
+    ) {
+        match event {
+            Event::Wheel(mouse) => {
+                // new input always stops a fling
+                self.velocity = None;
+                if scrolled {
+                    let now = Instant::now();
+                    self.samples
+                        .retain(|(time, _)| now.duration_since(*time) <= MOMENTUM_SAMPLE_WINDOW);
+                    self.samples.push_back((now, mouse.wheel_delta));
+                    self.timer = ctx.request_timer(MOMENTUM_PAUSE);
+                }
+            }
+            Event::MouseDown(_) => {
+                self.velocity = None;
+                self.samples.clear();
+                self.timer = TimerToken::INVALID;
+            }
+            Event::Timer(token) if *token == self.timer => {
+                self.timer = TimerToken::INVALID;
+                if self.samples.len() >= MOMENTUM_MIN_SAMPLES {
+                    let total = self
+                        .samples
+                        .iter()
+                        .fold(Vec2::ZERO, |total, (_, delta)| total + *delta);
+                    let first = self.samples.front().unwrap().0;
+                    let last = self.samples.back().unwrap().0;
+                    let span = last.duration_since(first).max(MOMENTUM_MIN_SPAN);
+                    self.velocity = Some(total / span.as_secs_f64());
+                    ctx.request_anim_frame();
+                }
+                self.samples.clear();
+                ctx.set_handled();
+            }
+            Event::AnimFrame(interval) => {
+                if let Some(velocity) = self.velocity {
+                    // avoid huge jumps if we missed some frames
+                    let elapsed = (*interval as f64 * 1e-9).min(0.1);
+                    let velocity = velocity * MOMENTUM_DECAY.powf(elapsed);
+                    if velocity.hypot() < MOMENTUM_MIN_VELOCITY || !pan_by(velocity * elapsed) {
+                        self.velocity = None;
+                    } else {
+                        self.velocity = Some(velocity);
+                        ctx.request_anim_frame();
+                        ctx.request_paint();
+                    }
+                }
+            }
+            _ => (),
+        }
     }
 }
 
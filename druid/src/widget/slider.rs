@@ -14,26 +14,48 @@
 
 //! A slider widget.
 
-use crate::kurbo::{Circle, Shape};
+use crate::kurbo::{Circle, Line, Shape};
 use crate::widget::prelude::*;
-use crate::{theme, LinearGradient, Point, Rect, UnitPoint};
+use crate::widget::Axis;
+use crate::{theme, ArcStr, KbKey, LinearGradient, Point, Rect, TextLayout, UnitPoint};
 use tracing::{instrument, trace};
 
 const TRACK_THICKNESS: f64 = 4.0;
 const BORDER_WIDTH: f64 = 2.0;
 const KNOB_STROKE_WIDTH: f64 = 2.0;
+const TICK_LENGTH: f64 = 4.0;
+// the space between the knob and the range labels.
+const LABEL_SPACING: f64 = 2.0;
+// the fraction of the range moved by a key press on a continuous slider.
+const CONTINUOUS_KEY_STEP: f64 = 0.01;
+// the number of steps moved by PageUp and PageDown.
+const PAGE_STEPS: f64 = 10.0;
 
 /// A slider, allowing interactive update of a numeric value.
 ///
 /// This slider implements `Widget<f64>`, and works on values clamped
-/// in the range `min..max`.
-#[derive(Debug, Clone, Default)]
+/// in the range `min..max`. If a step is set with [`with_step`], the value
+/// also snaps to multiples of the step from `min`.
+///
+/// When focused, the value can be changed with the arrow keys, by ten steps
+/// with PageUp and PageDown, and set to either end with Home and End.
+///
+/// [`with_step`]: #method.with_step
+#[derive(Debug, Clone)]
 pub struct Slider {
     min: f64,
     max: f64,
+    step: Option<f64>,
+    axis: Axis,
+    show_ticks: bool,
+    show_range_labels: bool,
+    min_label: TextLayout<ArcStr>,
+    max_label: TextLayout<ArcStr>,
     knob_pos: Point,
     knob_hovered: bool,
-    x_offset: f64,
+    /// The distance along the track from the mouse to the knob's center
+    /// when a drag started on the knob.
+    drag_offset: f64,
 }
 
 impl Slider {
@@ -42,9 +64,25 @@ impl Slider {
         Slider {
             min: 0.,
             max: 1.,
+            step: None,
+            axis: Axis::Horizontal,
+            show_ticks: false,
+            show_range_labels: false,
+            min_label: TextLayout::new(),
+            max_label: TextLayout::new(),
             knob_pos: Default::default(),
             knob_hovered: Default::default(),
-            x_offset: Default::default(),
+            drag_offset: Default::default(),
+        }
+    }
+
+    /// Create a new vertical `Slider`.
+    ///
+    /// A vertical slider has its minimum at the bottom.
+    pub fn vertical() -> Slider {
+        Slider {
+            axis: Axis::Vertical,
+            ..Slider::new()
         }
     }
 
@@ -56,6 +94,32 @@ impl Slider {
         self.max = max;
         self
     }
+
+    /// Builder-style method to make the value snap to multiples of `step`,
+    /// counted from the minimum of the range.
+    ///
+    /// This also sets how much the arrow keys change the value.
+    pub fn with_step(mut self, step: f64) -> Self {
+        self.step = Some(step).filter(|step| *step > 0.0);
+        self
+    }
+
+    /// Builder-style method to paint a tick mark at each step.
+    ///
+    /// This has no effect if there is no step; see [`with_step`].
+    ///
+    /// [`with_step`]: #method.with_step
+    pub fn with_ticks(mut self, show_ticks: bool) -> Self {
+        self.show_ticks = show_ticks;
+        self
+    }
+
+    /// Builder-style method to show the minimum and maximum of the range
+    /// next to the ends of the track.
+    pub fn with_range_labels(mut self, show_range_labels: bool) -> Self {
+        self.show_range_labels = show_range_labels;
+        self
+    }
 }
 
 impl Slider {
@@ -64,40 +128,98 @@ impl Slider {
         knob_circle.winding(mouse_pos) > 0
     }
 
-    fn calculate_value(&self, mouse_x: f64, knob_width: f64, slider_width: f64) -> f64 {
-        let scalar = ((mouse_x + self.x_offset - knob_width / 2.) / (slider_width - knob_width))
+    /// The length of the track the knob's center can move along.
+    fn track_length(&self, size: Size, knob_size: f64) -> f64 {
+        (self.axis.major(size) - knob_size).max(0.0)
+    }
+
+    /// The position along the major axis of the knob's center for a value.
+    fn major_for_value(&self, value: f64, size: Size, knob_size: f64) -> f64 {
+        let scalar = match self.axis {
+            Axis::Horizontal => self.normalize(value),
+            Axis::Vertical => 1.0 - self.normalize(value),
+        };
+        knob_size / 2. + scalar * self.track_length(size, knob_size)
+    }
+
+    fn calculate_value(&self, mouse_pos: Point, knob_size: f64, size: Size) -> f64 {
+        let major = self.axis.major_pos(mouse_pos) + self.drag_offset;
+        let scalar = ((major - knob_size / 2.) / self.track_length(size, knob_size))
             .max(0.0)
             .min(1.0);
-        self.min + scalar * (self.max - self.min)
+        let scalar = match self.axis {
+            Axis::Horizontal => scalar,
+            Axis::Vertical => 1.0 - scalar,
+        };
+        self.snap(self.min + scalar * (self.max - self.min))
+    }
+
+    fn snap(&self, value: f64) -> f64 {
+        snap(value, self.min, self.max, self.step)
     }
 
     fn normalize(&self, data: f64) -> f64 {
         (data.max(self.min).min(self.max) - self.min) / (self.max - self.min)
     }
+
+    /// The new value for a key press, if the key adjusts the slider.
+    fn value_for_key(&self, key: &KbKey, value: f64) -> Option<f64> {
+        let step = self
+            .step
+            .unwrap_or((self.max - self.min) * CONTINUOUS_KEY_STEP);
+        let value = match key {
+            KbKey::ArrowRight | KbKey::ArrowUp => value + step,
+            KbKey::ArrowLeft | KbKey::ArrowDown => value - step,
+            KbKey::PageUp => value + step * PAGE_STEPS,
+            KbKey::PageDown => value - step * PAGE_STEPS,
+            KbKey::Home => self.min,
+            KbKey::End => self.max,
+            _ => return None,
+        };
+        Some(self.snap(value))
+    }
+
+    /// The size of the area taken by the range labels, across the track.
+    fn labels_minor_size(&self) -> f64 {
+        if !self.show_range_labels {
+            return 0.0;
+        }
+        let (min, max) = (self.min_label.size(), self.max_label.size());
+        let labels = self.axis.minor(min).max(self.axis.minor(max));
+        labels + LABEL_SPACING
+    }
+}
+
+impl Default for Slider {
+    fn default() -> Self {
+        Slider::new()
+    }
 }
 
 impl Widget<f64> for Slider {
     #[instrument(name = "Slider", level = "trace", skip(self, ctx, event, data, env))]
     fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut f64, env: &Env) {
         let knob_size = env.get(theme::BASIC_WIDGET_HEIGHT);
-        let slider_width = ctx.size().width;
+        let size = ctx.size();
 
         match event {
             Event::MouseDown(mouse) => {
                 if !ctx.is_disabled() {
                     ctx.set_active(true);
+                    ctx.request_focus();
                     if self.knob_hit_test(knob_size, mouse.pos) {
-                        self.x_offset = self.knob_pos.x - mouse.pos.x
+                        self.drag_offset =
+                            self.axis.major_pos(self.knob_pos) - self.axis.major_pos(mouse.pos);
                     } else {
-                        self.x_offset = 0.;
-                        *data = self.calculate_value(mouse.pos.x, knob_size, slider_width);
+                        self.drag_offset = 0.;
+                        *data = self.calculate_value(mouse.pos, knob_size, size);
                     }
                     ctx.request_paint();
                 }
             }
             Event::MouseUp(mouse) => {
                 if ctx.is_active() && !ctx.is_disabled() {
-                    *data = self.calculate_value(mouse.pos.x, knob_size, slider_width);
+                    *data = self.calculate_value(mouse.pos, knob_size, size);
                     ctx.request_paint();
                 }
                 ctx.set_active(false);
@@ -105,7 +227,7 @@ impl Widget<f64> for Slider {
             Event::MouseMove(mouse) => {
                 if !ctx.is_disabled() {
                     if ctx.is_active() {
-                        *data = self.calculate_value(mouse.pos.x, knob_size, slider_width);
+                        *data = self.calculate_value(mouse.pos, knob_size, size);
                         ctx.request_paint();
                     }
                     if ctx.is_hot() {
@@ -119,14 +241,31 @@ impl Widget<f64> for Slider {
                     ctx.set_active(false);
                 }
             }
+            Event::KeyDown(key) if ctx.is_focused() && !ctx.is_disabled() => {
+                if let Some(value) = self.value_for_key(&key.key, *data) {
+                    *data = value;
+                    ctx.request_paint();
+                    ctx.set_handled();
+                }
+            }
             _ => (),
         }
     }
 
     #[instrument(name = "Slider", level = "trace", skip(self, ctx, event, _data, _env))]
     fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, _data: &f64, _env: &Env) {
-        if let LifeCycle::DisabledChanged(_) = event {
-            ctx.request_paint();
+        match event {
+            LifeCycle::WidgetAdded => {
+                self.min_label.set_text(self.min.to_string().into());
+                self.max_label.set_text(self.max.to_string().into());
+                ctx.register_for_focus();
+            }
+            LifeCycle::DisabledChanged(_) | LifeCycle::FocusChanged(_) => ctx.request_paint(),
+            LifeCycle::HotChanged(false) if self.knob_hovered => {
+                self.knob_hovered = false;
+                ctx.request_paint();
+            }
+            _ => (),
         }
     }
 
@@ -136,17 +275,31 @@ impl Widget<f64> for Slider {
         skip(self, ctx, _old_data, _data, _env)
     )]
     fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &f64, _data: &f64, _env: &Env) {
+        let labels_changed = self.min_label.needs_rebuild_after_update(ctx);
+        if self.max_label.needs_rebuild_after_update(ctx) || labels_changed {
+            ctx.request_layout();
+        }
         ctx.request_paint();
     }
 
     #[instrument(name = "Slider", level = "trace", skip(self, ctx, bc, _data, env))]
     fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &f64, env: &Env) -> Size {
         bc.debug_check("Slider");
-        let height = env.get(theme::BASIC_WIDGET_HEIGHT);
-        let width = env.get(theme::WIDE_WIDGET_WIDTH);
-        let baseline_offset = (height / 2.0) - TRACK_THICKNESS;
+        if self.show_range_labels {
+            self.min_label.rebuild_if_needed(ctx.text(), env);
+            self.max_label.rebuild_if_needed(ctx.text(), env);
+        }
+
+        let knob_size = env.get(theme::BASIC_WIDGET_HEIGHT);
+        let length = env.get(theme::WIDE_WIDGET_WIDTH);
+        let thickness = knob_size + self.labels_minor_size();
+        let size = bc.constrain(self.axis.pack(length, thickness));
+
+        let baseline_offset = match self.axis {
+            Axis::Horizontal => size.height - knob_size / 2.0 - TRACK_THICKNESS,
+            Axis::Vertical => 0.0,
+        };
         ctx.set_baseline_offset(baseline_offset);
-        let size = bc.constrain((width, height));
         trace!(
             "Computed layout: size={}, baseline_offset={:?}",
             size,
@@ -157,21 +310,25 @@ impl Widget<f64> for Slider {
 
     #[instrument(name = "Slider", level = "trace", skip(self, ctx, data, env))]
     fn paint(&mut self, ctx: &mut PaintCtx, data: &f64, env: &Env) {
-        let clamped = self.normalize(*data);
-        let rect = ctx.size().to_rect();
+        let size = ctx.size();
         let knob_size = env.get(theme::BASIC_WIDGET_HEIGHT);
+        let axis = self.axis;
 
         //Paint the background
-        let background_width = rect.width() - knob_size;
-        let background_origin = Point::new(knob_size / 2., (knob_size - TRACK_THICKNESS) / 2.);
-        let background_size = Size::new(background_width, TRACK_THICKNESS);
-        let background_rect = Rect::from_origin_size(background_origin, background_size)
+        let track_start =
+            Point::from(axis.pack(knob_size / 2., (knob_size - TRACK_THICKNESS) / 2.));
+        let track_size = Size::from(axis.pack(self.track_length(size, knob_size), TRACK_THICKNESS));
+        let background_rect = Rect::from_origin_size(track_start, track_size)
             .inset(-BORDER_WIDTH / 2.)
             .to_rounded_rect(2.);
 
+        let (gradient_start, gradient_end) = match axis {
+            Axis::Horizontal => (UnitPoint::TOP, UnitPoint::BOTTOM),
+            Axis::Vertical => (UnitPoint::LEFT, UnitPoint::RIGHT),
+        };
         let background_gradient = LinearGradient::new(
-            UnitPoint::TOP,
-            UnitPoint::BOTTOM,
+            gradient_start,
+            gradient_end,
             (
                 env.get(theme::BACKGROUND_LIGHT),
                 env.get(theme::BACKGROUND_DARK),
@@ -182,53 +339,460 @@ impl Widget<f64> for Slider {
 
         ctx.fill(background_rect, &background_gradient);
 
-        //Get ready to paint the knob
-        let is_active = ctx.is_active();
-        let is_hovered = self.knob_hovered;
-
-        let knob_position = (rect.width() - knob_size) * clamped + knob_size / 2.;
-        self.knob_pos = Point::new(knob_position, knob_size / 2.);
-        let knob_circle = Circle::new(self.knob_pos, (knob_size - KNOB_STROKE_WIDTH) / 2.);
-
-        let knob_gradient = if ctx.is_disabled() {
-            LinearGradient::new(
-                UnitPoint::TOP,
-                UnitPoint::BOTTOM,
-                (
-                    env.get(theme::DISABLED_FOREGROUND_LIGHT),
-                    env.get(theme::DISABLED_FOREGROUND_DARK),
-                ),
-            )
-        } else if ctx.is_active() {
-            LinearGradient::new(
-                UnitPoint::TOP,
-                UnitPoint::BOTTOM,
-                (
-                    env.get(theme::FOREGROUND_DARK),
-                    env.get(theme::FOREGROUND_LIGHT),
+        //Paint the tick marks, on the far side of the track from the labels
+        if let (true, Some(step)) = (self.show_ticks, self.step) {
+            let tick_color = env.get(theme::BORDER_LIGHT);
+            let tick_start = (knob_size + TRACK_THICKNESS) / 2. + BORDER_WIDTH;
+            let n_steps = ((self.max - self.min) / step).floor() as usize;
+            for i in 0..=n_steps {
+                let value = self.min + i as f64 * step;
+                let major = self.major_for_value(value, size, knob_size);
+                let line = Line::new(
+                    axis.pack(major, tick_start),
+                    axis.pack(major, tick_start + TICK_LENGTH),
+                );
+                ctx.stroke(line, &tick_color, 1.0);
+            }
+        }
+
+        //Paint the range labels
+        if self.show_range_labels {
+            let minor = knob_size + LABEL_SPACING;
+            let (min_size, max_size) = (self.min_label.size(), self.max_label.size());
+            let (min_origin, max_origin) = match axis {
+                // the labels are centered under the ends of the track, but kept inside the widget
+                Axis::Horizontal => (
+                    Point::new((knob_size / 2. - min_size.width / 2.).max(0.0), minor),
+                    Point::new(
+                        (size.width - knob_size / 2. - max_size.width / 2.)
+                            .min(size.width - max_size.width),
+                        minor,
+                    ),
                 ),
-            )
-        } else {
-            LinearGradient::new(
-                UnitPoint::TOP,
-                UnitPoint::BOTTOM,
-                (
-                    env.get(theme::FOREGROUND_LIGHT),
-                    env.get(theme::FOREGROUND_DARK),
+                Axis::Vertical => (
+                    Point::new(minor, size.height - min_size.height),
+                    Point::new(minor, 0.0),
                 ),
-            )
+            };
+            self.min_label.draw(ctx, min_origin);
+            self.max_label.draw(ctx, max_origin);
+        }
+
+        //Paint the knob
+        let knob_position = self.major_for_value(*data, size, knob_size);
+        self.knob_pos = Point::from(axis.pack(knob_position, knob_size / 2.));
+        let state = KnobState {
+            active: ctx.is_active(),
+            hovered: self.knob_hovered,
+            focused: ctx.is_focused(),
         };
+        paint_knob(ctx, env, self.knob_pos, knob_size, state);
+    }
+}
+
+/// Clamp a value to a range, and snap it to multiples of `step` from `min`
+/// if there is a step.
+fn snap(value: f64, min: f64, max: f64, step: Option<f64>) -> f64 {
+    let value = value.max(min).min(max);
+    match step {
+        Some(step) => {
+            let steps = ((value - min) / step).round();
+            (min + steps * step).min(max)
+        }
+        None => value,
+    }
+}
+
+/// The interaction state of a slider knob, for painting.
+#[derive(Debug, Clone, Copy)]
+struct KnobState {
+    active: bool,
+    hovered: bool,
+    focused: bool,
+}
+
+fn paint_knob(ctx: &mut PaintCtx, env: &Env, center: Point, knob_size: f64, state: KnobState) {
+    let knob_circle = Circle::new(center, (knob_size - KNOB_STROKE_WIDTH) / 2.);
+    let disabled = ctx.is_disabled();
+
+    let knob_gradient = if disabled {
+        LinearGradient::new(
+            UnitPoint::TOP,
+            UnitPoint::BOTTOM,
+            (
+                env.get(theme::DISABLED_FOREGROUND_LIGHT),
+                env.get(theme::DISABLED_FOREGROUND_DARK),
+            ),
+        )
+    } else if state.active {
+        LinearGradient::new(
+            UnitPoint::TOP,
+            UnitPoint::BOTTOM,
+            (
+                env.get(theme::FOREGROUND_DARK),
+                env.get(theme::FOREGROUND_LIGHT),
+            ),
+        )
+    } else {
+        LinearGradient::new(
+            UnitPoint::TOP,
+            UnitPoint::BOTTOM,
+            (
+                env.get(theme::FOREGROUND_LIGHT),
+                env.get(theme::FOREGROUND_DARK),
+            ),
+        )
+    };
 
-        //Paint the border
-        let border_color = if (is_hovered || is_active) && !ctx.is_disabled() {
-            env.get(theme::FOREGROUND_LIGHT)
-        } else {
-            env.get(theme::FOREGROUND_DARK)
+    //Paint the border
+    let border_color = if state.focused && !disabled {
+        env.get(theme::PRIMARY_LIGHT)
+    } else if (state.hovered || state.active) && !disabled {
+        env.get(theme::FOREGROUND_LIGHT)
+    } else {
+        env.get(theme::FOREGROUND_DARK)
+    };
+
+    ctx.stroke(knob_circle, &border_color, KNOB_STROKE_WIDTH);
+
+    //Actually paint the knob
+    ctx.fill(knob_circle, &knob_gradient);
+}
+
+/// A slider with two knobs, for choosing a range of values.
+///
+/// This implements `Widget<(f64, f64)>`, where the first value is the lower
+/// end of the chosen range and the second the upper end. Both are clamped
+/// to the range `min..max`, and the knobs cannot cross; by default a knob
+/// stops at the other one, but it can push the other one along instead with
+/// [`with_push`].
+///
+/// When focused, Tab moves between the knobs, and the arrow keys, Home and
+/// End adjust the focused knob.
+///
+/// [`with_push`]: #method.with_push
+#[derive(Debug, Clone)]
+pub struct RangeSlider {
+    min: f64,
+    max: f64,
+    step: Option<f64>,
+    push: bool,
+    knob_pos: [Point; 2],
+    hovered_knob: Option<usize>,
+    /// The knob being dragged, or `None` while a drag that started on two
+    /// overlapping knobs hasn't picked one yet.
+    dragged_knob: Option<usize>,
+    drag_start: f64,
+    drag_offset: f64,
+    focused_knob: usize,
+}
+
+impl RangeSlider {
+    /// Create a new `RangeSlider`.
+    pub fn new() -> RangeSlider {
+        RangeSlider {
+            min: 0.,
+            max: 1.,
+            step: None,
+            push: false,
+            knob_pos: Default::default(),
+            hovered_knob: None,
+            dragged_knob: None,
+            drag_start: 0.,
+            drag_offset: 0.,
+            focused_knob: 0,
+        }
+    }
+
+    /// Builder-style method to set the range covered by this slider.
+    ///
+    /// The default range is `0.0..1.0`.
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    /// Builder-style method to make the values snap to multiples of `step`,
+    /// counted from the minimum of the range.
+    pub fn with_step(mut self, step: f64) -> Self {
+        self.step = Some(step).filter(|step| *step > 0.0);
+        self
+    }
+
+    /// Builder-style method to set whether a knob dragged into the other one
+    /// pushes it along, instead of stopping.
+    pub fn with_push(mut self, push: bool) -> Self {
+        self.push = push;
+        self
+    }
+}
+
+impl RangeSlider {
+    fn knob_hit_test(&self, knob_width: f64, mouse_pos: Point) -> Vec<usize> {
+        (0..2)
+            .filter(|i| Circle::new(self.knob_pos[*i], knob_width / 2.).winding(mouse_pos) > 0)
+            .collect()
+    }
+
+    fn x_for_value(&self, value: f64, width: f64, knob_size: f64) -> f64 {
+        let scalar = (value.max(self.min).min(self.max) - self.min) / (self.max - self.min);
+        knob_size / 2. + scalar * (width - knob_size).max(0.0)
+    }
+
+    fn value_for_x(&self, x: f64, width: f64, knob_size: f64) -> f64 {
+        let scalar = ((x - knob_size / 2.) / (width - knob_size))
+            .max(0.0)
+            .min(1.0);
+        snap(
+            self.min + scalar * (self.max - self.min),
+            self.min,
+            self.max,
+            self.step,
+        )
+    }
+
+    /// Move one knob to `value`, keeping the knobs from crossing.
+    fn set_knob(&self, knob: usize, value: f64, data: &mut (f64, f64)) {
+        let value = snap(value, self.min, self.max, self.step);
+        match (knob, self.push) {
+            (0, true) => *data = (value, data.1.max(value)),
+            (0, false) => data.0 = value.min(data.1),
+            (_, true) => *data = (data.0.min(value), value),
+            (_, false) => data.1 = value.max(data.0),
+        }
+    }
+
+    fn drag_to(&mut self, mouse_x: f64, width: f64, knob_size: f64, data: &mut (f64, f64)) {
+        let knob = match self.dragged_knob {
+            Some(knob) => knob,
+            // the knobs overlap; pick the one that can move the way we're going.
+            None if mouse_x > self.drag_start => 1,
+            None if mouse_x < self.drag_start => 0,
+            None => return,
         };
+        self.dragged_knob = Some(knob);
+        let value = self.value_for_x(mouse_x + self.drag_offset, width, knob_size);
+        self.set_knob(knob, value, data);
+    }
+}
+
+impl Default for RangeSlider {
+    fn default() -> Self {
+        RangeSlider::new()
+    }
+}
+
+impl Widget<(f64, f64)> for RangeSlider {
+    #[instrument(
+        name = "RangeSlider",
+        level = "trace",
+        skip(self, ctx, event, data, env)
+    )]
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut (f64, f64), env: &Env) {
+        let knob_size = env.get(theme::BASIC_WIDGET_HEIGHT);
+        let width = ctx.size().width;
+
+        match event {
+            Event::MouseDown(mouse) => {
+                if !ctx.is_disabled() {
+                    ctx.set_active(true);
+                    ctx.request_focus();
+                    let hits = self.knob_hit_test(knob_size, mouse.pos);
+                    self.drag_start = mouse.pos.x;
+                    match hits.as_slice() {
+                        [knob] => {
+                            self.dragged_knob = Some(*knob);
+                            self.drag_offset = self.knob_pos[*knob].x - mouse.pos.x;
+                        }
+                        [] => {
+                            // move the nearest knob to the click
+                            let to_knob = |i: usize| (self.knob_pos[i].x - mouse.pos.x).abs();
+                            let knob = if to_knob(1) < to_knob(0) { 1 } else { 0 };
+                            self.dragged_knob = Some(knob);
+                            self.drag_offset = 0.;
+                            let value = self.value_for_x(mouse.pos.x, width, knob_size);
+                            self.set_knob(knob, value, data);
+                        }
+                        _ => {
+                            let offset = self.knob_pos[0].x - mouse.pos.x;
+                            self.dragged_knob = None;
+                            self.drag_offset = offset;
+                        }
+                    }
+                    if let Some(knob) = self.dragged_knob {
+                        self.focused_knob = knob;
+                    }
+                    ctx.request_paint();
+                }
+            }
+            Event::MouseUp(mouse) => {
+                if ctx.is_active() && !ctx.is_disabled() {
+                    self.drag_to(mouse.pos.x, width, knob_size, data);
+                    if let Some(knob) = self.dragged_knob {
+                        self.focused_knob = knob;
+                    }
+                    ctx.request_paint();
+                }
+                ctx.set_active(false);
+            }
+            Event::MouseMove(mouse) => {
+                if !ctx.is_disabled() {
+                    if ctx.is_active() {
+                        self.drag_to(mouse.pos.x, width, knob_size, data);
+                        ctx.request_paint();
+                    }
+                    if ctx.is_hot() {
+                        let hovered = self.knob_hit_test(knob_size, mouse.pos).first().copied();
+                        if hovered != self.hovered_knob {
+                            self.hovered_knob = hovered;
+                            ctx.request_paint();
+                        }
+                    }
+                } else {
+                    ctx.set_active(false);
+                }
+            }
+            Event::KeyDown(key) if ctx.is_focused() && !ctx.is_disabled() => {
+                let knob = self.focused_knob;
+                let value = if knob == 0 { data.0 } else { data.1 };
+                let step = self
+                    .step
+                    .unwrap_or((self.max - self.min) * CONTINUOUS_KEY_STEP);
+                match &key.key {
+                    KbKey::Tab if key.mods.shift() && knob == 1 => self.focused_knob = 0,
+                    KbKey::Tab if key.mods.shift() => ctx.focus_prev(),
+                    KbKey::Tab if knob == 0 => self.focused_knob = 1,
+                    KbKey::Tab => ctx.focus_next(),
+                    KbKey::ArrowRight | KbKey::ArrowUp => self.set_knob(knob, value + step, data),
+                    KbKey::ArrowLeft | KbKey::ArrowDown => self.set_knob(knob, value - step, data),
+                    KbKey::PageUp => self.set_knob(knob, value + step * PAGE_STEPS, data),
+                    KbKey::PageDown => self.set_knob(knob, value - step * PAGE_STEPS, data),
+                    KbKey::Home => self.set_knob(knob, self.min, data),
+                    KbKey::End => self.set_knob(knob, self.max, data),
+                    _ => return,
+                }
+                ctx.request_paint();
+                ctx.set_handled();
+            }
+            _ => (),
+        }
+    }
+
+    #[instrument(
+        name = "RangeSlider",
+        level = "trace",
+        skip(self, ctx, event, _data, _env)
+    )]
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        _data: &(f64, f64),
+        _env: &Env,
+    ) {
+        match event {
+            LifeCycle::WidgetAdded => ctx.register_for_focus(),
+            LifeCycle::DisabledChanged(_) | LifeCycle::FocusChanged(_) => ctx.request_paint(),
+            LifeCycle::HotChanged(false) if self.hovered_knob.is_some() => {
+                self.hovered_knob = None;
+                ctx.request_paint();
+            }
+            _ => (),
+        }
+    }
+
+    #[instrument(
+        name = "RangeSlider",
+        level = "trace",
+        skip(self, ctx, _old_data, _data, _env)
+    )]
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        _old_data: &(f64, f64),
+        _data: &(f64, f64),
+        _env: &Env,
+    ) {
+        ctx.request_paint();
+    }
+
+    #[instrument(name = "RangeSlider", level = "trace", skip(self, ctx, bc, _data, env))]
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &(f64, f64),
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("RangeSlider");
+        let height = env.get(theme::BASIC_WIDGET_HEIGHT);
+        let width = env.get(theme::WIDE_WIDGET_WIDTH);
+        let baseline_offset = (height / 2.0) - TRACK_THICKNESS;
+        ctx.set_baseline_offset(baseline_offset);
+        let size = bc.constrain((width, height));
+        trace!(
+            "Computed layout: size={}, baseline_offset={:?}",
+            size,
+            baseline_offset
+        );
+        size
+    }
+
+    #[instrument(name = "RangeSlider", level = "trace", skip(self, ctx, data, env))]
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &(f64, f64), env: &Env) {
+        let width = ctx.size().width;
+        let knob_size = env.get(theme::BASIC_WIDGET_HEIGHT);
+
+        //Paint the background
+        let track_y = (knob_size - TRACK_THICKNESS) / 2.;
+        let background_rect = Rect::new(
+            knob_size / 2.,
+            track_y,
+            width - knob_size / 2.,
+            track_y + TRACK_THICKNESS,
+        )
+        .inset(-BORDER_WIDTH / 2.)
+        .to_rounded_rect(2.);
+
+        let background_gradient = LinearGradient::new(
+            UnitPoint::TOP,
+            UnitPoint::BOTTOM,
+            (
+                env.get(theme::BACKGROUND_LIGHT),
+                env.get(theme::BACKGROUND_DARK),
+            ),
+        );
 
-        ctx.stroke(knob_circle, &border_color, KNOB_STROKE_WIDTH);
+        ctx.stroke(background_rect, &env.get(theme::BORDER_DARK), BORDER_WIDTH);
+        ctx.fill(background_rect, &background_gradient);
 
-        //Actually paint the knob
-        ctx.fill(knob_circle, &knob_gradient);
+        //Paint the chosen range
+        let low_x = self.x_for_value(data.0, width, knob_size);
+        let high_x = self.x_for_value(data.1, width, knob_size);
+        self.knob_pos = [
+            Point::new(low_x, knob_size / 2.),
+            Point::new(high_x, knob_size / 2.),
+        ];
+        if !ctx.is_disabled() {
+            let range_rect = Rect::new(low_x, track_y, high_x, track_y + TRACK_THICKNESS);
+            ctx.fill(range_rect, &env.get(theme::PRIMARY_LIGHT));
+        }
+
+        //Paint the knobs, the one being dragged on top
+        let is_active = ctx.is_active();
+        let is_focused = ctx.is_focused();
+        let top = self
+            .dragged_knob
+            .filter(|_| is_active)
+            .unwrap_or(self.focused_knob);
+        for knob in [1 - top, top].iter().copied() {
+            let state = KnobState {
+                active: is_active && self.dragged_knob == Some(knob),
+                hovered: self.hovered_knob == Some(knob),
+                focused: is_focused && self.focused_knob == knob,
+            };
+            paint_knob(ctx, env, self.knob_pos[knob], knob_size, state);
+        }
     }
 }
@@ -20,6 +20,9 @@ use crate::widget::prelude::*;
 use crate::{theme, Color, Cursor, Data, Point, Rect, WidgetPod};
 use tracing::{instrument, trace, warn};
 
+/// How long collapsing or restoring the first pane takes, in seconds.
+const COLLAPSE_DURATION: f64 = 0.15;
+
 /// A container containing two other widgets, splitting the area either horizontally or vertically.
 pub struct Split<T> {
     split_axis: Axis,
@@ -30,6 +33,13 @@ pub struct Split<T> {
     min_bar_area: f64,    // Integers only
     solid: bool,
     draggable: bool,
+    collapsible: bool,
+    /// The first pane is collapsed, or is being collapsed.
+    collapsed: bool,
+    /// The split point to go back to when the first pane is restored.
+    restore_point: f64,
+    /// The transition of the split point while collapsing or restoring.
+    collapse_animation: Option<CollapseAnimation>,
     /// The split bar is hovered by the mouse. This state is locked to `true` if the
     /// widget is active (the bar is being dragged) to avoid cursor and painting jitter
     /// if the mouse moves faster than the layout and temporarily gets outside of the
@@ -43,6 +53,14 @@ pub struct Split<T> {
     child2: WidgetPod<T, Box<dyn Widget<T>>>,
 }
 
+/// A transition of the chosen split point.
+struct CollapseAnimation {
+    from: f64,
+    to: f64,
+    /// How far along the transition is, from `0.0` to `1.0`.
+    progress: f64,
+}
+
 impl<T> Split<T> {
     /// Create a new split panel, with the specified axis being split in two.
     ///
@@ -62,6 +80,10 @@ impl<T> Split<T> {
             min_bar_area: 6.0,
             solid: false,
             draggable: false,
+            collapsible: false,
+            collapsed: false,
+            restore_point: 0.5,
+            collapse_animation: None,
             is_bar_hover: false,
             click_offset: 0.0,
             child1: WidgetPod::new(child1).boxed(),
@@ -96,8 +118,13 @@ impl<T> Split<T> {
 
     /// Builder-style method to set the minimum size for both sides of the split axis.
     ///
+    /// The sizes are in pixels, and are kept both in layout and while dragging
+    /// the bar, except that a [`collapsible`] split can collapse its first pane.
+    ///
     /// The value must be greater than or equal to `0.0`.
     /// The value will be rounded up to the nearest integer.
+    ///
+    /// [`collapsible`]: Split::collapsible
     pub fn min_size(mut self, first: f64, second: f64) -> Self {
         assert!(first >= 0.0);
         assert!(second >= 0.0);
@@ -140,6 +167,18 @@ impl<T> Split<T> {
         self
     }
 
+    /// Builder-style method to set whether the first pane can be collapsed.
+    ///
+    /// If this is `true`, double-clicking the splitter bar collapses the first
+    /// pane, and double-clicking it again restores the previous split point.
+    /// Dragging the bar of a collapsed split also restores the first pane.
+    ///
+    /// The default is `false`.
+    pub fn collapsible(mut self, collapsible: bool) -> Self {
+        self.collapsible = collapsible;
+        self
+    }
+
     /// Builder-style method to set whether the splitter bar is drawn as a solid rectangle.
     ///
     /// If this is `false` (the default), the bar will be drawn as two parallel lines.
@@ -211,6 +250,9 @@ impl<T> Split<T> {
         let split_axis_size = self.split_axis.major(size);
 
         let (mut min_limit, min_second) = self.min_size;
+        if self.collapsed || self.collapse_animation.is_some() {
+            min_limit = 0.0;
+        }
         let mut max_limit = (split_axis_size - min_second).max(0.0);
 
         if min_limit > max_limit {
@@ -230,6 +272,40 @@ impl<T> Split<T> {
         }
     }
 
+    /// Collapse the first pane, or restore it if it is collapsed.
+    fn toggle_collapsed(&mut self, ctx: &mut EventCtx) {
+        let to = if self.collapsed {
+            self.restore_point
+        } else {
+            self.restore_point = self.split_point_chosen;
+            0.0
+        };
+        self.collapsed = !self.collapsed;
+        self.collapse_animation = Some(CollapseAnimation {
+            from: self.split_point_chosen,
+            to,
+            progress: 0.0,
+        });
+        ctx.request_anim_frame();
+    }
+
+    /// Move the split point along the collapse transition.
+    fn animate_collapse(&mut self, ctx: &mut EventCtx, seconds: f64) {
+        if let Some(animation) = &mut self.collapse_animation {
+            animation.progress = (animation.progress + seconds / COLLAPSE_DURATION).min(1.0);
+            // ease in and out
+            let t = animation.progress;
+            let eased = t * t * (3.0 - 2.0 * t);
+            self.split_point_chosen = animation.from + (animation.to - animation.from) * eased;
+            if animation.progress < 1.0 {
+                ctx.request_anim_frame();
+            } else {
+                self.collapse_animation = None;
+            }
+            ctx.request_layout();
+        }
+    }
+
     /// Returns the color of the splitter bar.
     fn bar_color(&self, env: &Env) -> Color {
         if self.draggable {
@@ -310,6 +386,15 @@ impl<T: Data> Widget<T> for Split<T> {
         }
         if self.draggable {
             match event {
+                Event::MouseDown(mouse)
+                    if self.collapsible
+                        && mouse.button.is_left()
+                        && mouse.count == 2
+                        && self.bar_hit_test(ctx.size(), mouse.pos) =>
+                {
+                    ctx.set_handled();
+                    self.toggle_collapsed(ctx);
+                }
                 Event::MouseDown(mouse) => {
                     if mouse.button.is_left() && self.bar_hit_test(ctx.size(), mouse.pos) {
                         ctx.set_handled();
@@ -353,6 +438,9 @@ impl<T: Data> Widget<T> for Split<T> {
                                 Point::new(mouse.pos.x, mouse.pos.y - self.click_offset)
                             }
                         };
+                        // dragging takes over from collapsing
+                        self.collapsed = false;
+                        self.collapse_animation = None;
                         self.update_split_point(ctx.size(), effective_pos);
                         ctx.request_layout();
                     } else {
@@ -371,6 +459,9 @@ impl<T: Data> Widget<T> for Split<T> {
                         }
                     }
                 }
+                Event::AnimFrame(interval) => {
+                    self.animate_collapse(ctx, *interval as f64 * 1e-9);
+                }
                 _ => {}
             }
         }
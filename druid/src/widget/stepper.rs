@@ -21,14 +21,25 @@ use tracing::{instrument, trace};
 use crate::kurbo::BezPath;
 use crate::piet::{LinearGradient, RenderContext, UnitPoint};
 use crate::widget::prelude::*;
-use crate::{theme, Point, Rect, TimerToken};
+use crate::{theme, KbKey, Point, Rect, TimerToken};
 
 // Delay until stepper starts automatically changing valued when one of the button is held down.
 const STEPPER_REPEAT_DELAY: Duration = Duration::from_millis(500);
 // Delay between value changes when one of the button is held down.
 const STEPPER_REPEAT: Duration = Duration::from_millis(200);
+// The number of repeats after which the repeat delay halves, down to a quarter.
+const STEPPER_REPEAT_ACCELERATION: u32 = 5;
 
 /// A stepper widget for step-wise increasing and decreasing a value.
+///
+/// The value changes with the two buttons, which repeat faster and faster when
+/// held down, with the Up and Down arrow keys when the stepper has focus, and with
+/// the mouse wheel when it is hovered. The value stays within the range; without
+/// wraparound, the button for a direction that has reached the end of the range
+/// is painted as disabled.
+///
+/// A `Stepper` works on `f64`; for integers, map the data with a lens, for example
+/// `Stepper::new().lens(lens::Identity.map(|n: &i32| *n as f64, |n, x| *n = x as i32))`.
 pub struct Stepper {
     max: f64,
     min: f64,
@@ -38,6 +49,8 @@ pub struct Stepper {
     increase_active: bool,
     decrease_active: bool,
     timer_id: TimerToken,
+    /// How many times the value changed since the button was pressed.
+    repeat_count: u32,
 }
 
 impl Stepper {
@@ -51,6 +64,7 @@ impl Stepper {
             increase_active: false,
             decrease_active: false,
             timer_id: TimerToken::INVALID,
+            repeat_count: 0,
         }
     }
 
@@ -92,7 +106,7 @@ impl Stepper {
             (true, true, true) => self.min,
             (true, false, true) => self.max,
             (false, _, true) => self.max,
-            _ => next,
+            _ => next.max(self.min),
         }
     }
 
@@ -104,9 +118,23 @@ impl Stepper {
             (true, true, true) => self.max,
             (true, false, true) => self.min,
             (false, _, true) => self.min,
-            _ => next,
+            _ => next.min(self.max),
         }
     }
+
+    fn can_increment(&self, data: f64) -> bool {
+        self.wrap || data + EPSILON < self.max
+    }
+
+    fn can_decrement(&self, data: f64) -> bool {
+        self.wrap || data - EPSILON > self.min
+    }
+
+    /// The delay until the next change while a button is held down, which gets
+    /// shorter the longer it is held.
+    fn repeat_delay(&self) -> Duration {
+        STEPPER_REPEAT / 2u32.pow((self.repeat_count / STEPPER_REPEAT_ACCELERATION).min(2))
+    }
 }
 
 impl Default for Stepper {
@@ -116,8 +144,8 @@ impl Default for Stepper {
 }
 
 impl Widget<f64> for Stepper {
-    #[instrument(name = "Stepper", level = "trace", skip(self, ctx, _data, env))]
-    fn paint(&mut self, ctx: &mut PaintCtx, _data: &f64, env: &Env) {
+    #[instrument(name = "Stepper", level = "trace", skip(self, ctx, data, env))]
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &f64, env: &Env) {
         let stroke_width = 2.0;
         let rounded_rect = ctx
             .size()
@@ -129,7 +157,12 @@ impl Widget<f64> for Stepper {
         let width = env.get(theme::BASIC_WIDGET_HEIGHT);
         let button_size = Size::new(width, height / 2.);
 
-        ctx.stroke(rounded_rect, &env.get(theme::BORDER_DARK), stroke_width);
+        let border_color = if ctx.is_focused() {
+            env.get(theme::PRIMARY_LIGHT)
+        } else {
+            env.get(theme::BORDER_DARK)
+        };
+        ctx.stroke(rounded_rect, &border_color, stroke_width);
         ctx.clip(rounded_rect);
 
         // draw buttons for increase/decrease
@@ -160,8 +193,9 @@ impl Widget<f64> for Stepper {
             (env.get(theme::BUTTON_DARK), env.get(theme::BUTTON_LIGHT)),
         );
 
-        // draw buttons that are currently triggered as active
-        if ctx.is_disabled() {
+        // draw buttons that are currently triggered as active, and the ones that
+        // can't change the value any further as disabled
+        if ctx.is_disabled() || !self.can_increment(*data) {
             ctx.fill(increase_button_rect, &disabled_gradient);
         } else if self.increase_active {
             ctx.fill(increase_button_rect, &active_gradient);
@@ -169,7 +203,7 @@ impl Widget<f64> for Stepper {
             ctx.fill(increase_button_rect, &inactive_gradient);
         };
 
-        if ctx.is_disabled() {
+        if ctx.is_disabled() || !self.can_decrement(*data) {
             ctx.fill(decrease_button_rect, &disabled_gradient);
         } else if self.decrease_active {
             ctx.fill(decrease_button_rect, &active_gradient);
@@ -235,6 +269,7 @@ impl Widget<f64> for Stepper {
                         self.increment(data);
                     }
 
+                    self.repeat_count = 0;
                     self.timer_id = ctx.request_timer(STEPPER_REPEAT_DELAY);
 
                     ctx.request_paint();
@@ -257,18 +292,40 @@ impl Widget<f64> for Stepper {
                     if self.decrease_active {
                         self.decrement(data);
                     }
-                    self.timer_id = ctx.request_timer(STEPPER_REPEAT);
+                    self.repeat_count += 1;
+                    self.timer_id = ctx.request_timer(self.repeat_delay());
                 } else {
                     ctx.set_active(false);
                 }
             }
+            Event::KeyDown(key) if !ctx.is_disabled() => match key.key {
+                KbKey::ArrowUp => {
+                    self.increment(data);
+                    ctx.set_handled();
+                }
+                KbKey::ArrowDown => {
+                    self.decrement(data);
+                    ctx.set_handled();
+                }
+                _ => (),
+            },
+            Event::Wheel(mouse) if ctx.is_hot() && !ctx.is_handled() && !ctx.is_disabled() => {
+                if mouse.wheel_delta.y < 0. {
+                    self.increment(data);
+                } else if mouse.wheel_delta.y > 0. {
+                    self.decrement(data);
+                }
+                ctx.set_handled();
+            }
             _ => (),
         }
     }
 
     fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, _data: &f64, _env: &Env) {
-        if let LifeCycle::DisabledChanged(_) = event {
-            ctx.request_paint();
+        match event {
+            LifeCycle::WidgetAdded => ctx.register_for_focus(),
+            LifeCycle::DisabledChanged(_) | LifeCycle::FocusChanged(_) => ctx.request_paint(),
+            _ => (),
         }
     }
 
@@ -20,7 +20,7 @@ use tracing::{instrument, trace};
 use crate::kurbo::{Circle, Shape};
 use crate::piet::{LinearGradient, RenderContext, UnitPoint};
 use crate::widget::prelude::*;
-use crate::{theme, ArcStr, Point, TextLayout};
+use crate::{theme, AnimationCurve, AnimationToken, ArcStr, Point, TextLayout};
 
 const SWITCH_CHANGE_TIME: f64 = 0.2;
 const SWITCH_PADDING: f64 = 3.;
@@ -32,7 +32,8 @@ pub struct Switch {
     knob_pos: Point,
     knob_hovered: bool,
     knob_dragged: bool,
-    animation_in_progress: bool,
+    /// The running knob animation, and the knob position it started from.
+    knob_animation: Option<(AnimationToken, f64)>,
     on_text: TextLayout<ArcStr>,
     off_text: TextLayout<ArcStr>,
 }
@@ -43,7 +44,7 @@ impl Default for Switch {
             knob_pos: Point::ZERO,
             knob_hovered: false,
             knob_dragged: false,
-            animation_in_progress: false,
+            knob_animation: None,
             //TODO: use localized strings, also probably make these configurable?
             on_text: TextLayout::from_text("ON"),
             off_text: TextLayout::from_text("OFF"),
@@ -62,6 +63,25 @@ impl Switch {
         knob_circle.winding(mouse_pos) > 0
     }
 
+    /// The x position of the knob when the switch is set to `on`.
+    fn knob_target(on: bool, env: &Env) -> f64 {
+        let switch_height = env.get(theme::BORDERED_WIDGET_HEIGHT);
+        let switch_width = switch_height * SWITCH_WIDTH_RATIO;
+        let knob_size = switch_height - 2. * SWITCH_PADDING;
+        if on {
+            switch_width - knob_size / 2. - SWITCH_PADDING
+        } else {
+            knob_size / 2. + SWITCH_PADDING
+        }
+    }
+
+    /// How long the knob takes to move from where it is to its position for `on`.
+    fn knob_travel_time(&self, on: bool, env: &Env) -> Duration {
+        let switch_width = env.get(theme::BORDERED_WIDGET_HEIGHT) * SWITCH_WIDTH_RATIO;
+        let distance = (Self::knob_target(on, env) - self.knob_pos.x).abs();
+        Duration::from_secs_f64(SWITCH_CHANGE_TIME * distance / switch_width)
+    }
+
     fn paint_labels(&mut self, ctx: &mut PaintCtx, env: &Env, switch_width: f64) {
         self.on_text.rebuild_if_needed(ctx.text(), env);
         self.off_text.rebuild_if_needed(ctx.text(), env);
@@ -120,47 +140,44 @@ impl Widget<bool> for Switch {
                 ctx.set_active(false);
 
                 self.knob_dragged = false;
-                self.animation_in_progress = true;
-                ctx.request_anim_frame();
+                if let Some((token, _)) = self.knob_animation.take() {
+                    ctx.cancel_animation(token);
+                }
+                let duration = self.knob_travel_time(*data, env);
+                let token = ctx.request_animation(duration, AnimationCurve::Linear);
+                self.knob_animation = Some((token, self.knob_pos.x));
             }
             Event::MouseMove(mouse) => {
                 if !ctx.is_disabled() {
                     if ctx.is_active() {
                         self.knob_pos.x = mouse.pos.x.min(on_pos).max(off_pos);
                         self.knob_dragged = true;
+                        ctx.request_paint();
                     }
                     if ctx.is_hot() {
-                        self.knob_hovered = self.knob_hit_test(knob_size, mouse.pos)
+                        let knob_hover = self.knob_hit_test(knob_size, mouse.pos);
+                        if knob_hover != self.knob_hovered {
+                            self.knob_hovered = knob_hover;
+                            ctx.request_paint();
+                        }
                     }
-                    ctx.request_paint();
                 } else {
                     ctx.set_active(false);
                 }
             }
-            Event::AnimFrame(interval) => {
-                let delta = Duration::from_nanos(*interval).as_secs_f64();
-                let switch_height = env.get(theme::BORDERED_WIDGET_HEIGHT);
-                let switch_width = switch_height * SWITCH_WIDTH_RATIO;
-                let knob_size = switch_height - 2. * SWITCH_PADDING;
-                let on_pos = switch_width - knob_size / 2. - SWITCH_PADDING;
-                let off_pos = knob_size / 2. + SWITCH_PADDING;
-
+            Event::AnimProgress(token, t) => {
                 // move knob to right position depending on the value
-                if self.animation_in_progress {
-                    let change_time = if *data {
-                        SWITCH_CHANGE_TIME
-                    } else {
-                        -SWITCH_CHANGE_TIME
-                    };
-                    let change = (switch_width / change_time) * delta;
-                    self.knob_pos.x = (self.knob_pos.x + change).min(on_pos).max(off_pos);
-
-                    if (self.knob_pos.x > off_pos && !*data) || (self.knob_pos.x < on_pos && *data)
-                    {
-                        ctx.request_anim_frame();
-                    } else {
-                        self.animation_in_progress = false;
+                if let Some((knob_token, start)) = self.knob_animation {
+                    if knob_token == *token {
+                        let target = Self::knob_target(*data, env);
+                        self.knob_pos.x = start + (target - start) * t;
+                        ctx.request_paint();
                     }
+                }
+            }
+            Event::AnimEnded(token) => {
+                if matches!(self.knob_animation, Some((knob_token, _)) if knob_token == *token) {
+                    self.knob_animation = None;
                     ctx.request_paint();
                 }
             }
@@ -168,8 +185,8 @@ impl Widget<bool> for Switch {
         }
     }
 
-    #[instrument(name = "Switch", level = "trace", skip(self, ctx, event, _data, env))]
-    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, _data: &bool, env: &Env) {
+    #[instrument(name = "Switch", level = "trace", skip(self, ctx, event, data, env))]
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &bool, env: &Env) {
         match event {
             LifeCycle::WidgetAdded => {
                 self.on_text.rebuild_if_needed(ctx.text(), env);
@@ -177,8 +194,12 @@ impl Widget<bool> for Switch {
             }
             LifeCycle::DisabledChanged(true) if self.knob_dragged => {
                 self.knob_dragged = false;
-                self.animation_in_progress = true;
-                ctx.request_anim_frame();
+                if let Some((token, _)) = self.knob_animation.take() {
+                    ctx.cancel_animation(token);
+                }
+                let duration = self.knob_travel_time(*data, env);
+                let token = ctx.request_animation(duration, AnimationCurve::Linear);
+                self.knob_animation = Some((token, self.knob_pos.x));
             }
             LifeCycle::DisabledChanged(disabled) => {
                 ctx.request_paint();
@@ -191,19 +212,23 @@ impl Widget<bool> for Switch {
                 self.on_text.set_text_color(color);
                 ctx.request_paint();
             }
+            LifeCycle::HotChanged(false) if self.knob_hovered => {
+                self.knob_hovered = false;
+                ctx.request_paint();
+            }
             _ => {}
         }
     }
 
-    #[instrument(
-        name = "Switch",
-        level = "trace",
-        skip(self, ctx, old_data, data, _env)
-    )]
-    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &bool, data: &bool, _env: &Env) {
+    #[instrument(name = "Switch", level = "trace", skip(self, ctx, old_data, data, env))]
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &bool, data: &bool, env: &Env) {
         if old_data != data {
-            self.animation_in_progress = true;
-            ctx.request_anim_frame();
+            if let Some((token, _)) = self.knob_animation.take() {
+                ctx.cancel_animation(token);
+            }
+            let duration = self.knob_travel_time(*data, env);
+            let token = ctx.request_animation(duration, AnimationCurve::Linear);
+            self.knob_animation = Some((token, self.knob_pos.x));
         }
     }
 
@@ -246,7 +271,7 @@ impl Widget<bool> for Switch {
             .to_rounded_rect(switch_height / 2.);
 
         // position knob
-        if !self.animation_in_progress && !self.knob_dragged {
+        if self.knob_animation.is_none() && !self.knob_dragged {
             if *data {
                 self.knob_pos.x = on_pos;
             } else {
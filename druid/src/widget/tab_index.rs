@@ -0,0 +1,81 @@
+// Copyright 2021 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A widget that sets the tab index of its child.
+
+use crate::kurbo::Size;
+use crate::widget::prelude::*;
+use crate::widget::WidgetWrapper;
+use crate::Data;
+use tracing::instrument;
+
+/// A wrapper that changes where a widget, and any focusable widgets inside
+/// it, come in the order in which Tab and Shift+Tab move focus.
+///
+/// See [`LifeCycleCtx::set_tab_index`] for how the order is determined.
+///
+/// [`LifeCycleCtx::set_tab_index`]: crate::LifeCycleCtx::set_tab_index
+pub struct TabIndex<W> {
+    index: i32,
+    inner: W,
+}
+
+impl<W> TabIndex<W> {
+    /// Give a widget a tab index.
+    pub fn new(inner: W, index: i32) -> TabIndex<W> {
+        TabIndex { index, inner }
+    }
+}
+
+impl<T: Data, W: Widget<T>> Widget<T> for TabIndex<W> {
+    #[instrument(name = "TabIndex", level = "trace", skip(self, ctx, event, data, env))]
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        self.inner.event(ctx, event, data, env);
+    }
+
+    #[instrument(name = "TabIndex", level = "trace", skip(self, ctx, event, data, env))]
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            ctx.set_tab_index(self.index);
+        }
+        self.inner.lifecycle(ctx, event, data, env)
+    }
+
+    #[instrument(
+        name = "TabIndex",
+        level = "trace",
+        skip(self, ctx, old_data, data, env)
+    )]
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        self.inner.update(ctx, old_data, data, env);
+    }
+
+    #[instrument(name = "TabIndex", level = "trace", skip(self, ctx, bc, data, env))]
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        self.inner.layout(ctx, bc, data, env)
+    }
+
+    #[instrument(name = "TabIndex", level = "trace", skip(self, ctx, data, env))]
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.inner.paint(ctx, data, env);
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        self.inner.id()
+    }
+}
+
+impl<W> WidgetWrapper for TabIndex<W> {
+    widget_wrapper_body!(W, inner);
+}
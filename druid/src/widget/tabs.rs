@@ -25,7 +25,7 @@ use tracing::{instrument, trace};
 use crate::kurbo::{Circle, Line};
 use crate::widget::prelude::*;
 use crate::widget::{Axis, Flex, Label, LabelText, LensScopeTransfer, Painter, Scope, ScopePolicy};
-use crate::{theme, Affine, Data, Insets, Lens, Point, SingleUse, WidgetExt, WidgetPod};
+use crate::{theme, Affine, Data, Insets, KbKey, Lens, Point, SingleUse, WidgetExt, WidgetPod};
 
 type TabsScope<TP> = Scope<TabsScopePolicy<TP>, Box<dyn Widget<TabsState<TP>>>>;
 type TabBodyPod<TP> = WidgetPod<<TP as TabsPolicy>::Input, <TP as TabsPolicy>::BodyWidget>;
@@ -428,7 +428,7 @@ impl<TP: TabsPolicy> Widget<TabsState<TP>> for TabBar<TP> {
     #[instrument(name = "TabBar", level = "trace", skip(self, ctx, data, env))]
     fn paint(&mut self, ctx: &mut PaintCtx, data: &TabsState<TP>, env: &Env) {
         let hl_thickness = 2.;
-        let highlight = env.get(theme::PRIMARY_LIGHT);
+        let highlight = env.get(theme::TAB_INDICATOR_COLOR);
         for (idx, (_, tab)) in self.tabs.iter_mut().enumerate() {
             let layout_rect = tab.layout_rect();
             let expanded_size = self.axis.pack(
@@ -437,9 +437,9 @@ impl<TP: TabsPolicy> Widget<TabsState<TP>> for TabBar<TP> {
             );
             let rect = layout_rect.with_size(expanded_size);
             let bg = match (idx == data.selected, Some(idx) == self.hot) {
-                (_, true) => env.get(theme::BUTTON_DARK),
-                (true, false) => env.get(theme::BACKGROUND_LIGHT),
-                _ => env.get(theme::BACKGROUND_DARK),
+                (_, true) => env.get(theme::TAB_HOT_BACKGROUND),
+                (true, false) => env.get(theme::TAB_SELECTED_BACKGROUND),
+                _ => env.get(theme::TAB_BACKGROUND),
             };
             ctx.fill(rect, &bg);
 
@@ -534,18 +534,22 @@ fn ensure_for_tabs<Content, TP: TabsPolicy + ?Sized>(
 /// This widget is the tabs body. It shows the active tab, keeps other tabs hidden, and can
 /// animate transitions between them.
 struct TabsBody<TP: TabsPolicy> {
-    children: Vec<(TP::Key, TabBodyPod<TP>)>,
+    /// The bodies of the tabs; with lazy bodies, the ones that haven't been
+    /// shown yet are `None`.
+    children: Vec<(TP::Key, Option<TabBodyPod<TP>>)>,
     axis: Axis,
+    lazy: bool,
     transition: TabsTransition,
     transition_state: Option<TabsTransitionState>,
     phantom_tp: PhantomData<TP>,
 }
 
 impl<TP: TabsPolicy> TabsBody<TP> {
-    fn new(axis: Axis, transition: TabsTransition) -> TabsBody<TP> {
+    fn new(axis: Axis, lazy: bool, transition: TabsTransition) -> TabsBody<TP> {
         TabsBody {
             children: vec![],
             axis,
+            lazy,
             transition,
             transition_state: None,
             phantom_tp: Default::default(),
@@ -553,12 +557,48 @@ impl<TP: TabsPolicy> TabsBody<TP> {
     }
 
     fn make_tabs(&mut self, data: &TabsState<TP>) -> Vec<usize> {
-        ensure_for_tabs(
+        let lazy = self.lazy;
+        let existing = ensure_for_tabs(
             &mut self.children,
             &data.policy,
             &data.inner,
-            |policy, key| WidgetPod::new(policy.tab_body(key, &data.inner)),
-        )
+            |policy, key| {
+                if lazy {
+                    None
+                } else {
+                    Some(WidgetPod::new(policy.tab_body(key, &data.inner)))
+                }
+            },
+        );
+        self.ensure_selected(data);
+        existing
+    }
+
+    /// Create the body of the selected tab, if it hasn't been created yet.
+    ///
+    /// Returns `true` if a body was created.
+    fn ensure_selected(&mut self, data: &TabsState<TP>) -> bool {
+        match self.children.get_mut(data.selected) {
+            Some((key, child @ None)) => {
+                *child = Some(WidgetPod::new(
+                    data.policy.tab_body(key.clone(), &data.inner),
+                ));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Switch to the next tab, or the previous one if `backwards` is `true`.
+    fn cycle_selected(&self, data: &mut TabsState<TP>, backwards: bool) {
+        let count = self.children.len();
+        if count > 0 {
+            data.selected = if backwards {
+                (data.selected + count - 1) % count
+            } else {
+                (data.selected + 1) % count
+            };
+        }
     }
 
     fn active_child(&mut self, state: &TabsState<TP>) -> Option<&mut TabBodyPod<TP>> {
@@ -567,14 +607,14 @@ impl<TP: TabsPolicy> TabsBody<TP> {
 
     // Doesn't take self to allow separate borrowing
     fn child(
-        children: &mut Vec<(TP::Key, TabBodyPod<TP>)>,
+        children: &mut Vec<(TP::Key, Option<TabBodyPod<TP>>)>,
         idx: usize,
     ) -> Option<&mut TabBodyPod<TP>> {
-        children.get_mut(idx).map(|x| &mut x.1)
+        children.get_mut(idx).and_then(|x| x.1.as_mut())
     }
 
     fn child_pods(&mut self) -> impl Iterator<Item = &mut TabBodyPod<TP>> {
-        self.children.iter_mut().map(|x| &mut x.1)
+        self.children.iter_mut().filter_map(|x| x.1.as_mut())
     }
 }
 
@@ -589,6 +629,13 @@ impl<TP: TabsPolicy> Widget<TabsState<TP>> for TabsBody<TP> {
             child.event(ctx, event, &mut data.inner, env);
         }
 
+        if let Event::KeyDown(key) = event {
+            if !ctx.is_handled() && key.key == KbKey::Tab && key.mods.ctrl() {
+                self.cycle_selected(data, key.mods.shift());
+                ctx.set_handled();
+            }
+        }
+
         if let (Some(t_state), Event::AnimFrame(interval)) = (&mut self.transition_state, event) {
             t_state.current_time += *interval;
             if t_state.live() {
@@ -645,6 +692,9 @@ impl<TP: TabsPolicy> Widget<TabsState<TP>> for TabsBody<TP> {
         };
 
         if old_data.selected != data.selected {
+            if self.ensure_selected(data) {
+                ctx.children_changed();
+            }
             self.transition_state = self
                 .transition
                 .tab_changed(old_data.selected, data.selected);
@@ -663,8 +713,11 @@ impl<TP: TabsPolicy> Widget<TabsState<TP>> for TabsBody<TP> {
                 }
             }
         } else {
+            // a body that was just created gets its data with `WidgetAdded`
             for child in self.child_pods() {
-                child.update(ctx, &data.inner, env);
+                if child.is_initialized() {
+                    child.update(ctx, &data.inner, env);
+                }
             }
         }
     }
@@ -815,6 +868,10 @@ enum TabsContent<TP: TabsPolicy> {
 /// Or, the tabs can be derived from the input data by implementing TabsPolicy, and providing it to
 /// Tabs::from_policy()
 ///
+/// While a widget in the body of a tab has focus, Ctrl+Tab switches to the next tab and
+/// Ctrl+Shift+Tab to the previous one. The colors of the tab bar are set with the `TAB_*`
+/// keys in [`theme`].
+///
 /// ```
 /// use druid::widget::{Tabs, Label, WidgetExt};
 /// use druid::{Data, Lens};
@@ -832,9 +889,11 @@ enum TabsContent<TP: TabsPolicy> {
 ///
 /// ```
 ///
+/// [`theme`]: crate::theme
 pub struct Tabs<TP: TabsPolicy> {
     axis: Axis,
     edge: TabsEdge,
+    lazy: bool,
     transition: TabsTransition,
     content: TabsContent<TP>,
 }
@@ -858,6 +917,7 @@ impl<TP: TabsPolicy> Tabs<TP> {
         Tabs {
             axis: Axis::Horizontal,
             edge: Default::default(),
+            lazy: false,
             transition: Default::default(),
             content,
         }
@@ -893,6 +953,15 @@ impl<TP: TabsPolicy> Tabs<TP> {
         self
     }
 
+    /// Only create the body of a tab when it is first shown.
+    ///
+    /// By default the bodies of all tabs are created up front. Either way, the
+    /// bodies of hidden tabs are kept alive, so that their state is preserved.
+    pub fn with_lazy_bodies(mut self, lazy: bool) -> Self {
+        self.lazy = lazy;
+        self
+    }
+
     /// Use the provided transition when tabs change
     pub fn with_transition(mut self, transition: TabsTransition) -> Self {
         self.transition = transition;
@@ -931,7 +1000,7 @@ impl<TP: TabsPolicy> Tabs<TP> {
 
     fn make_scope(&self, tabs_from_data: TP) -> WidgetPod<TP::Input, TabsScope<TP>> {
         let tabs_bar = TabBar::new(self.axis, self.edge);
-        let tabs_body = TabsBody::new(self.axis, self.transition)
+        let tabs_body = TabsBody::new(self.axis, self.lazy, self.transition)
             .padding(5.)
             .border(theme::BORDER_DARK, 0.5);
         let mut layout: Flex<TabsState<TP>> = Flex::for_axis(self.axis.cross());
@@ -23,10 +23,10 @@ use crate::text::{
     EditableText, ImeInvalidation, Selection, TextComponent, TextLayout, TextStorage,
 };
 use crate::widget::prelude::*;
-use crate::widget::{Padding, Scroll, WidgetWrapper};
+use crate::widget::{LabelText, Padding, Scroll, WidgetWrapper};
 use crate::{
-    theme, Color, Command, FontDescriptor, HotKey, KeyEvent, KeyOrValue, Point, Rect, SysMods,
-    TextAlignment, TimerToken, Vec2,
+    theme, ArcStr, Color, Command, FontDescriptor, HotKey, KbKey, KeyEvent, KeyOrValue, Point,
+    Rect, SysMods, TextAlignment, TimerToken, Vec2,
 };
 
 const CURSOR_BLINK_DURATION: Duration = Duration::from_millis(500);
@@ -47,10 +47,16 @@ const SCROLL_TO_INSETS: Insets = Insets::uniform_xy(40.0, 0.0);
 /// [`Formatter`]: crate::text::format::Formatter
 /// [`ValueTextBox`]: super::ValueTextBox
 pub struct TextBox<T> {
-    placeholder: TextLayout<String>,
+    placeholder_text: LabelText<T>,
+    placeholder_layout: TextLayout<ArcStr>,
+    /// `true` if the placeholder was replaced since it was last resolved.
+    placeholder_should_be_updated: bool,
     inner: Scroll<T, Padding<T, TextComponent<T>>>,
     scroll_to_selection_after_layout: bool,
     multiline: bool,
+    /// The key that submits a [`TextComponent::RETURN`] notification in a
+    /// multi-line `TextBox`, where Enter inserts a newline.
+    submit_hotkey: Option<HotKey>,
     /// true if a click event caused us to gain focus.
     ///
     /// On macOS, if focus happens via click then we set the selection based
@@ -70,8 +76,8 @@ pub struct TextBox<T> {
 impl<T: EditableText + TextStorage> TextBox<T> {
     /// Create a new TextBox widget.
     pub fn new() -> Self {
-        let mut placeholder = TextLayout::from_text("");
-        placeholder.set_text_color(theme::PLACEHOLDER_COLOR);
+        let mut placeholder_layout = TextLayout::new();
+        placeholder_layout.set_text_color(theme::PLACEHOLDER_COLOR);
         let mut scroll = Scroll::new(Padding::new(
             theme::TEXTBOX_INSETS,
             TextComponent::default(),
@@ -81,8 +87,11 @@ impl<T: EditableText + TextStorage> TextBox<T> {
         Self {
             inner: scroll,
             scroll_to_selection_after_layout: false,
-            placeholder,
+            placeholder_text: "".into(),
+            placeholder_layout,
+            placeholder_should_be_updated: false,
             multiline: false,
+            submit_hotkey: None,
             was_focused_from_click: false,
             cursor_on: false,
             cursor_timer: TimerToken::INVALID,
@@ -92,6 +101,15 @@ impl<T: EditableText + TextStorage> TextBox<T> {
     }
 
     /// Create a new multi-line `TextBox`.
+    ///
+    /// Enter inserts a newline; Ctrl+Enter (Cmd+Enter on macOS) submits a
+    /// [`TextComponent::RETURN`] notification instead, which a parent can
+    /// handle to act on the text. The submit key can be changed with
+    /// [`with_submit_hotkey`]. When the text is taller than the `TextBox`,
+    /// it scrolls vertically.
+    ///
+    /// [`TextComponent::RETURN`]: crate::text::TextComponent::RETURN
+    /// [`with_submit_hotkey`]: TextBox::with_submit_hotkey
     pub fn multiline() -> Self {
         let mut this = TextBox::new();
         this.inner
@@ -99,9 +117,20 @@ impl<T: EditableText + TextStorage> TextBox<T> {
         this.text_mut().borrow_mut().set_accepts_newlines(true);
         this.inner.set_horizontal_scroll_enabled(false);
         this.multiline = true;
+        this.submit_hotkey = Some(HotKey::new(SysMods::Cmd, KbKey::Enter));
         this
     }
 
+    /// Create a new `TextBox` for passwords, which shows a bullet in place of
+    /// each character.
+    ///
+    /// See [`with_text_obscuring`] for details.
+    ///
+    /// [`with_text_obscuring`]: TextBox::with_text_obscuring
+    pub fn protected() -> Self {
+        TextBox::new().with_text_obscuring('\u{2022}')
+    }
+
     /// If `true` (and this is a [`multiline`] text box) lines will be wrapped
     /// at the maximum layout width.
     ///
@@ -113,12 +142,42 @@ impl<T: EditableText + TextStorage> TextBox<T> {
         self.inner.set_horizontal_scroll_enabled(!wrap_lines);
         self
     }
+
+    /// Builder-style method to set the key that submits a [`multiline`]
+    /// text box, or `None` to only submit it from code.
+    ///
+    /// Submitting sends a [`TextComponent::RETURN`] notification, like Enter
+    /// does in a single-line `TextBox`. The default is Ctrl+Enter (Cmd+Enter
+    /// on macOS).
+    ///
+    /// [`multiline`]: TextBox::multiline
+    /// [`TextComponent::RETURN`]: crate::text::TextComponent::RETURN
+    pub fn with_submit_hotkey(mut self, hotkey: impl Into<Option<HotKey>>) -> Self {
+        self.submit_hotkey = hotkey.into();
+        self
+    }
 }
 
-impl<T> TextBox<T> {
+impl<T: Data> TextBox<T> {
     /// Builder-style method to set the `TextBox`'s placeholder text.
-    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
-        self.placeholder.set_text(placeholder.into());
+    ///
+    /// The placeholder is shown in [`theme::PLACEHOLDER_COLOR`] while the
+    /// `TextBox` is empty. It can be anything that converts into [`LabelText`],
+    /// including a closure over the data and the [`Env`].
+    ///
+    /// [`LabelText`]: crate::widget::LabelText
+    pub fn with_placeholder(mut self, placeholder: impl Into<LabelText<T>>) -> Self {
+        self.set_placeholder(placeholder);
+        self
+    }
+
+    /// Builder-style method to show `mask` in place of each character.
+    ///
+    /// The data still holds the real text; only what is shown changes. The
+    /// text can't be copied or cut, and double-clicking selects all of it
+    /// rather than a word.
+    pub fn with_text_obscuring(mut self, mask: char) -> Self {
+        self.set_text_obscuring(Some(mask));
         self
     }
 
@@ -179,8 +238,40 @@ impl<T> TextBox<T> {
     }
 
     /// Set the `TextBox`'s placeholder text.
-    pub fn set_placeholder(&mut self, placeholder: impl Into<String>) {
-        self.placeholder.set_text(placeholder.into());
+    ///
+    /// # Note
+    ///
+    /// If you change this property at runtime, call [`request_layout`] or
+    /// [`request_update`], so that the new placeholder is resolved with the
+    /// current data and shown.
+    ///
+    /// [`request_layout`]: crate::EventCtx::request_layout
+    /// [`request_update`]: crate::EventCtx::request_update
+    pub fn set_placeholder(&mut self, placeholder: impl Into<LabelText<T>>) {
+        self.placeholder_text = placeholder.into();
+        self.placeholder_should_be_updated = true;
+    }
+
+    /// Resolve the placeholder, returning `true` if its text changed.
+    fn update_placeholder(&mut self, data: &T, env: &Env) -> bool {
+        let changed = self.placeholder_text.resolve(data, env);
+        if changed || self.placeholder_should_be_updated {
+            self.placeholder_layout
+                .set_text(self.placeholder_text.display_text());
+            self.placeholder_should_be_updated = false;
+            return true;
+        }
+        false
+    }
+
+    /// Set the character shown in place of each character of the text, or
+    /// `None` to show the text itself.
+    pub fn set_text_obscuring(&mut self, mask: Option<char>) {
+        if !self.text().can_write() {
+            tracing::warn!("set_text_obscuring called with IME lock held.");
+            return;
+        }
+        self.text_mut().borrow_mut().set_text_obscuring(mask);
     }
 
     /// Set the text size.
@@ -199,7 +290,7 @@ impl<T> TextBox<T> {
             .borrow_mut()
             .layout
             .set_text_size(size.clone());
-        self.placeholder.set_text_size(size);
+        self.placeholder_layout.set_text_size(size);
     }
 
     /// Set the font.
@@ -217,7 +308,7 @@ impl<T> TextBox<T> {
         }
         let font = font.into();
         self.text_mut().borrow_mut().layout.set_font(font.clone());
-        self.placeholder.set_font(font);
+        self.placeholder_layout.set_font(font);
     }
 
     /// Set the [`TextAlignment`] for this `TextBox``.
@@ -309,9 +400,9 @@ impl<T> TextBox<T> {
 impl<T: TextStorage + EditableText> TextBox<T> {
     fn rect_for_selection_end(&self) -> Rect {
         let text = self.text().borrow();
-        let layout = text.layout.layout().unwrap();
+        let layout = text.display_layout().layout().unwrap();
 
-        let hit = layout.hit_test_text_position(text.selection().active);
+        let hit = layout.hit_test_text_position(text.to_display(text.selection().active));
         let line = layout.line_metric(hit.line).unwrap();
         let y0 = line.y_offset;
         let y1 = y0 + line.height;
@@ -393,6 +484,17 @@ impl<T: TextStorage + EditableText> Widget<T> for TextBox<T> {
                 }
                 _ => (),
             },
+            Event::KeyDown(key)
+                if self.multiline
+                    && !self.text().is_composing()
+                    && self
+                        .submit_hotkey
+                        .as_ref()
+                        .map_or(false, |hk| hk.matches(key)) =>
+            {
+                ctx.submit_notification(TextComponent::RETURN);
+                ctx.set_handled();
+            }
             Event::KeyDown(key) if !self.text().is_composing() => {
                 if let Some(cmd) = self.fallback_do_builtin_command(ctx, key) {
                     ctx.submit_command(cmd);
@@ -467,6 +569,7 @@ impl<T: TextStorage + EditableText> Widget<T> for TextBox<T> {
         match event {
             LifeCycle::WidgetAdded => {
                 ctx.register_text_input(self.text().input_handler());
+                self.update_placeholder(data, env);
             }
             LifeCycle::BuildFocusChain => {
                 //TODO: make this a configurable option? maybe?
@@ -498,6 +601,7 @@ impl<T: TextStorage + EditableText> Widget<T> for TextBox<T> {
                 self.was_focused_from_click = false;
                 ctx.request_paint();
             }
+            LifeCycle::DisabledChanged(_) => ctx.request_paint(),
             _ => (),
         }
         self.inner.lifecycle(ctx, event, data, env);
@@ -506,7 +610,10 @@ impl<T: TextStorage + EditableText> Widget<T> for TextBox<T> {
     #[instrument(name = "TextBox", level = "trace", skip(self, ctx, old, data, env))]
     fn update(&mut self, ctx: &mut UpdateCtx, old: &T, data: &T, env: &Env) {
         self.inner.update(ctx, old, data, env);
-        if ctx.env_changed() && self.placeholder.needs_rebuild_after_update(ctx) {
+        if self.update_placeholder(data, env) {
+            ctx.request_layout();
+        }
+        if ctx.env_changed() && self.placeholder_layout.needs_rebuild_after_update(ctx) {
             ctx.request_layout();
         }
         if self.text().can_write() {
@@ -525,16 +632,21 @@ impl<T: TextStorage + EditableText> Widget<T> for TextBox<T> {
         let min_width = env.get(theme::WIDE_WIDGET_WIDTH);
         let textbox_insets = env.get(theme::TEXTBOX_INSETS);
 
-        self.placeholder.rebuild_if_needed(ctx.text(), env);
+        if self.placeholder_should_be_updated {
+            self.update_placeholder(data, env);
+        }
+        self.placeholder_layout.rebuild_if_needed(ctx.text(), env);
         let min_size = bc.constrain((min_width, 0.0));
         let child_bc = BoxConstraints::new(min_size, bc.max());
 
         let size = self.inner.layout(ctx, &child_bc, data, env);
 
-        let text_metrics = if !self.text().can_read() || data.is_empty() {
-            self.placeholder.layout_metrics()
+        // the placeholder doesn't change the metrics, so that the baseline stays
+        // put when the first character is typed
+        let text_metrics = if self.text().can_read() {
+            self.text().borrow().display_layout().layout_metrics()
         } else {
-            self.text().borrow().layout.layout_metrics()
+            self.placeholder_layout.layout_metrics()
         };
 
         let layout_baseline = text_metrics.size.height - text_metrics.first_baseline;
@@ -562,7 +674,11 @@ impl<T: TextStorage + EditableText> Widget<T> for TextBox<T> {
             return;
         }
         let size = ctx.size();
-        let background_color = env.get(theme::BACKGROUND_LIGHT);
+        let background_color = if ctx.is_disabled() {
+            env.get(theme::DISABLED_BACKGROUND_LIGHT)
+        } else {
+            env.get(theme::BACKGROUND_LIGHT)
+        };
         let cursor_color = env.get(theme::CURSOR_COLOR);
         let border_width = env.get(theme::TEXTBOX_BORDER_WIDTH);
         let textbox_insets = env.get(theme::TEXTBOX_INSETS);
@@ -583,19 +699,20 @@ impl<T: TextStorage + EditableText> Widget<T> for TextBox<T> {
 
         ctx.fill(clip_rect, &background_color);
 
-        if !data.is_empty() {
+        if !data.is_empty() || self.text().is_composing() {
             self.inner.paint(ctx, data, env);
         } else {
             // clip when we draw the placeholder, since it isn't in a clipbox
+            let text_rect = size.to_rect() - textbox_insets;
+            let placeholder = &mut self.placeholder_layout;
             ctx.with_save(|ctx| {
-                ctx.clip(clip_rect);
-                self.placeholder
-                    .draw(ctx, (textbox_insets.x0, textbox_insets.y0));
+                ctx.clip(text_rect);
+                placeholder.draw(ctx, (textbox_insets.x0, textbox_insets.y0));
             })
         }
 
         // Paint the cursor if focused and there's no selection
-        if is_focused && self.should_draw_cursor() {
+        if is_focused && !ctx.is_disabled() && self.should_draw_cursor() {
             // if there's no data, we always draw the cursor based on
             // our alignment.
             let cursor_pos = self.text().borrow().selection().active;
@@ -628,3 +745,64 @@ impl<T: TextStorage + EditableText> Default for TextBox<T> {
         TextBox::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::harness::Harness;
+    use crate::widget::Controller;
+    use crate::{Selector, WidgetExt};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    const SET_PLACEHOLDER: Selector = Selector::new("druid-tests.set-placeholder");
+
+    /// Replaces the placeholder with a dynamic one on a command, and records
+    /// the placeholder text after every update.
+    struct DynamicPlaceholder(Rc<RefCell<Option<ArcStr>>>);
+
+    impl Controller<String, TextBox<String>> for DynamicPlaceholder {
+        fn event(
+            &mut self,
+            child: &mut TextBox<String>,
+            ctx: &mut EventCtx,
+            event: &Event,
+            data: &mut String,
+            env: &Env,
+        ) {
+            if matches!(event, Event::Command(cmd) if cmd.is(SET_PLACEHOLDER)) {
+                child
+                    .set_placeholder(|data: &String, _: &Env| format!("{} characters", data.len()));
+                ctx.request_update();
+            }
+            child.event(ctx, event, data, env)
+        }
+
+        fn update(
+            &mut self,
+            child: &mut TextBox<String>,
+            ctx: &mut UpdateCtx,
+            old_data: &String,
+            data: &String,
+            env: &Env,
+        ) {
+            child.update(ctx, old_data, data, env);
+            *self.0.borrow_mut() = child.placeholder_layout.text().cloned();
+        }
+    }
+
+    #[test]
+    fn dynamic_placeholder_set_at_runtime() {
+        let placeholder = Rc::new(RefCell::new(None));
+        let textbox = TextBox::new()
+            .with_placeholder("static")
+            .controller(DynamicPlaceholder(placeholder.clone()));
+
+        Harness::create_simple(String::new(), textbox, |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            harness.submit_command(SET_PLACEHOLDER);
+            assert_eq!(placeholder.borrow().as_deref(), Some("0 characters"));
+        });
+    }
+}
@@ -0,0 +1,183 @@
+// Copyright 2021 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A [`Controller`] that shows a tooltip when the mouse rests on its child.
+//!
+//! [`Controller`]: struct.Controller.html
+
+use instant::Duration;
+use tracing::{instrument, trace};
+
+use crate::commands::CLOSE_WINDOW;
+use crate::text::TextLayout;
+use crate::widget::{Controller, Label, LabelText};
+use crate::{
+    theme, ArcStr, Data, Env, Event, EventCtx, LifeCycle, LifeCycleCtx, Point, Size, TimerToken,
+    Widget, WidgetExt, WindowConfig, WindowId, WindowLevel, WindowSizePolicy,
+};
+
+/// The space between the text of a tooltip and its border.
+const TOOLTIP_PADDING: Size = Size::new(6.0, 3.0);
+/// The offset of a tooltip from the mouse, so that the cursor doesn't cover it.
+const CURSOR_OFFSET: Size = Size::new(12.0, 16.0);
+
+/// A [`Controller`] that shows a tooltip when the mouse rests on its child.
+///
+/// When the mouse has stayed still over the child for [`theme::TOOLTIP_DELAY`],
+/// the text is shown in a small borderless window next to the cursor, inside the
+/// bounds of the child's window where possible. Moving the mouse hides the
+/// tooltip and starts the delay again. The tooltip is a separate window, so it
+/// never gets the mouse events meant for the widgets under it.
+///
+/// This is more conveniently available as the `tooltip` method on [`WidgetExt`].
+///
+/// [`Controller`]: struct.Controller.html
+/// [`WidgetExt`]: ../trait.WidgetExt.html
+pub struct Tooltip<T> {
+    text: LabelText<T>,
+    state: TooltipState,
+}
+
+enum TooltipState {
+    /// The mouse isn't over the child.
+    Fresh,
+    /// The mouse moved over the child, and the tooltip is shown if the timer
+    /// fires before it moves again.
+    Waiting {
+        token: TimerToken,
+        window_pos: Point,
+    },
+    /// The tooltip is shown in a window.
+    Showing(WindowId),
+}
+
+impl<T: Data> Tooltip<T> {
+    /// Create a new tooltip [`Controller`] with the given text.
+    ///
+    /// The text can be anything that converts into [`LabelText`], including a
+    /// closure over the data and the [`Env`].
+    ///
+    /// [`Controller`]: struct.Controller.html
+    /// [`Env`]: ../struct.Env.html
+    pub fn new(text: impl Into<LabelText<T>>) -> Self {
+        Tooltip {
+            text: text.into(),
+            state: TooltipState::Fresh,
+        }
+    }
+
+    /// Close the tooltip's window, if it is showing.
+    fn hide(&mut self, ctx: &mut EventCtx) {
+        if let TooltipState::Showing(window_id) = self.state {
+            ctx.submit_command(CLOSE_WINDOW.to(window_id));
+        }
+        self.state = TooltipState::Fresh;
+    }
+
+    /// Open the tooltip's window next to the mouse.
+    fn show(&mut self, ctx: &mut EventCtx, window_pos: Point, data: &T, env: &Env) -> WindowId {
+        self.text.resolve(data, env);
+        let text = self.text.display_text();
+
+        // measure the tooltip, to keep it inside the window
+        let mut layout = TextLayout::<ArcStr>::from_text(text.clone());
+        layout.rebuild_if_needed(ctx.text(), env);
+        let size = layout.size() + TOOLTIP_PADDING * 2.0 + Size::new(2.0, 2.0);
+        let window_size = ctx.window().get_size();
+        let mut pos = window_pos + CURSOR_OFFSET.to_vec2();
+        if pos.y + size.height > window_size.height {
+            // above the cursor instead
+            pos.y = window_pos.y - size.height - 4.0;
+        }
+        pos.x = pos.x.min(window_size.width - size.width).max(0.0);
+        pos.y = pos.y.max(0.0);
+
+        let label = Label::<()>::new(text)
+            .with_text_color(theme::TOOLTIP_TEXT_COLOR)
+            .padding((TOOLTIP_PADDING.width, TOOLTIP_PADDING.height))
+            .background(theme::TOOLTIP_BACKGROUND_COLOR)
+            .border(theme::TOOLTIP_BORDER_COLOR, 1.0);
+        ctx.new_sub_window(
+            WindowConfig::default()
+                .show_titlebar(false)
+                .resizable(false)
+                .window_size_policy(WindowSizePolicy::Content)
+                .set_level(WindowLevel::Tooltip)
+                .set_position(ctx.window().get_position() + pos.to_vec2()),
+            label,
+            (),
+            env.clone(),
+        )
+    }
+}
+
+impl<T: Data, W: Widget<T>> Controller<T, W> for Tooltip<T> {
+    #[instrument(
+        name = "Tooltip",
+        level = "trace",
+        skip(self, child, ctx, event, data, env)
+    )]
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::MouseMove(mouse) if ctx.is_hot() => {
+                self.hide(ctx);
+                let delay = Duration::from_millis(env.get(theme::TOOLTIP_DELAY));
+                self.state = TooltipState::Waiting {
+                    token: ctx.request_timer(delay),
+                    window_pos: mouse.window_pos,
+                };
+            }
+            Event::MouseMove(_) | Event::MouseDown(_) | Event::Wheel(_) => self.hide(ctx),
+            Event::Timer(token) => {
+                if let TooltipState::Waiting {
+                    token: waiting_for,
+                    window_pos,
+                } = self.state
+                {
+                    if *token == waiting_for {
+                        trace!("Showing tooltip");
+                        let window_id = self.show(ctx, window_pos, data, env);
+                        self.state = TooltipState::Showing(window_id);
+                        ctx.set_handled();
+                        return;
+                    }
+                }
+            }
+            _ => {}
+        }
+        child.event(ctx, event, data, env);
+    }
+
+    #[instrument(
+        name = "Tooltip",
+        level = "trace",
+        skip(self, child, ctx, event, data, env)
+    )]
+    fn lifecycle(
+        &mut self,
+        child: &mut W,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &T,
+        env: &Env,
+    ) {
+        if let LifeCycle::HotChanged(false) = event {
+            if let TooltipState::Showing(window_id) = self.state {
+                ctx.submit_command(CLOSE_WINDOW.to(window_id));
+            }
+            self.state = TooltipState::Fresh;
+        }
+        child.lifecycle(ctx, event, data, env)
+    }
+}
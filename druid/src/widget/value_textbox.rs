@@ -19,7 +19,7 @@ use tracing::instrument;
 use super::TextBox;
 use crate::text::{Formatter, Selection, TextComponent, ValidationError};
 use crate::widget::prelude::*;
-use crate::{Data, Selector};
+use crate::{theme, Data, Selector};
 
 const BEGIN_EDITING: Selector = Selector::new("druid.builtin.textbox-begin-editing");
 const COMPLETE_EDITING: Selector = Selector::new("druid.builtin.textbox-complete-editing");
@@ -38,9 +38,16 @@ const COMPLETE_EDITING: Selector = Selector::new("druid.builtin.textbox-complete
 /// - [`ValueTextBox::update_data_while_editing`] takes a flag that determines
 /// whether the output value is updated during editing, when possible.
 ///
+/// - [`ValueTextBox::commit_on_focus_loss`] takes a flag that determines
+/// whether editing completes when the text box loses focus, or is cancelled.
+/// Editing always completes when Enter is pressed.
+///
 /// - [`ValueTextBox::delegate`] allows you to provide some implementation of
 /// the [`ValidationDelegate`] trait, which receives a callback during editing;
 /// this can be used to report errors further back up the tree.
+///
+/// While the text is not valid, the border is painted in
+/// [`theme::TEXTBOX_ERROR_BORDER_COLOR`].
 pub struct ValueTextBox<T> {
     inner: TextBox<String>,
     formatter: Box<dyn Formatter<T>>,
@@ -48,6 +55,9 @@ pub struct ValueTextBox<T> {
     is_editing: bool,
     validate_while_editing: bool,
     update_data_while_editing: bool,
+    commit_on_focus_loss: bool,
+    /// `true` if the text is not valid, and the error border is shown.
+    is_invalid: bool,
     /// the last data that this textbox saw or created.
     /// This is used to determine when a change to the data is originating
     /// elsewhere in the application, which we need to special-case
@@ -115,6 +125,8 @@ impl<T: Data> ValueTextBox<T> {
             last_known_data: None,
             validate_while_editing: true,
             update_data_while_editing: false,
+            commit_on_focus_loss: true,
+            is_invalid: false,
             old_buffer: String::new(),
             buffer: String::new(),
             force_selection: None,
@@ -148,9 +160,28 @@ impl<T: Data> ValueTextBox<T> {
         self
     }
 
+    /// Builder-style method to set whether editing completes when this text
+    /// box loses focus.
+    ///
+    /// If `true` (the default) the data is updated when focus moves elsewhere,
+    /// if the text is valid. If `false`, the edit is cancelled instead, and the
+    /// data is only updated when Enter or Tab is pressed.
+    pub fn commit_on_focus_loss(mut self, flag: bool) -> Self {
+        self.commit_on_focus_loss = flag;
+        self
+    }
+
+    fn set_invalid(&mut self, ctx: &mut EventCtx, invalid: bool) {
+        if self.is_invalid != invalid {
+            self.is_invalid = invalid;
+            ctx.request_paint();
+        }
+    }
+
     fn complete(&mut self, ctx: &mut EventCtx, data: &mut T) -> bool {
         match self.formatter.value(&self.buffer) {
             Ok(new_data) => {
+                self.set_invalid(ctx, false);
                 *data = new_data;
                 self.buffer = self.formatter.format(data);
                 self.is_editing = false;
@@ -169,9 +200,8 @@ impl<T: Data> ValueTextBox<T> {
                         ctx.invalidate_text_input(inval);
                     }
                 }
+                self.set_invalid(ctx, true);
                 self.send_event(ctx, TextBoxEvent::Invalid(err));
-                // our content isn't valid
-                // ideally we would flash the background or something
                 false
             }
         }
@@ -179,6 +209,7 @@ impl<T: Data> ValueTextBox<T> {
 
     fn cancel(&mut self, ctx: &mut EventCtx, data: &T) {
         self.is_editing = false;
+        self.set_invalid(ctx, false);
         self.buffer = self.formatter.format(data);
         ctx.request_update();
         ctx.resign_focus();
@@ -222,7 +253,7 @@ impl<T: Data + std::fmt::Debug> Widget<T> for ValueTextBox<T> {
                 // this is caused by an external focus change, like the mouse being clicked
                 // elsewhere.
                 Event::Command(cmd) if cmd.is(COMPLETE_EDITING) => {
-                    if !self.complete(ctx, data) {
+                    if !self.commit_on_focus_loss || !self.complete(ctx, data) {
                         self.cancel(ctx, data);
                     }
                     return;
@@ -308,6 +339,9 @@ impl<T: Data + std::fmt::Debug> Widget<T> for ValueTextBox<T> {
                     }
                 }
 
+                // rejected edits never make it into the buffer
+                let invalid = validation.is_err() && !self.validate_while_editing;
+                self.set_invalid(ctx, invalid);
                 match validation.error() {
                     Some(err) => {
                         self.send_event(ctx, TextBoxEvent::PartiallyInvalid(err.to_owned()))
@@ -413,5 +447,18 @@ impl<T: Data + std::fmt::Debug> Widget<T> for ValueTextBox<T> {
     #[instrument(name = "ValueTextBox", level = "trace", skip(self, ctx, _data, env))]
     fn paint(&mut self, ctx: &mut PaintCtx, _data: &T, env: &Env) {
         self.inner.paint(ctx, &self.buffer, env);
+        if self.is_invalid {
+            let border_width = env.get(theme::TEXTBOX_BORDER_WIDTH);
+            let border_rect = ctx
+                .size()
+                .to_rect()
+                .inset(-border_width / 2.0)
+                .to_rounded_rect(env.get(theme::TEXTBOX_BORDER_RADIUS));
+            ctx.stroke(
+                border_rect,
+                &env.get(theme::TEXTBOX_ERROR_BORDER_COLOR),
+                border_width,
+            );
+        }
     }
 }
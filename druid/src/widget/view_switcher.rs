@@ -14,9 +14,11 @@
 
 //! A widget that can dynamically switch between one of many views.
 
+use crate::widget::either::TransitionProgress;
 use crate::widget::prelude::*;
+use crate::widget::Transition;
 use crate::{Data, Point, WidgetPod};
-use tracing::instrument;
+use tracing::{instrument, trace};
 
 type ChildPicker<T, U> = dyn Fn(&T, &Env) -> U;
 type ChildBuilder<T, U> = dyn Fn(&U, &T, &Env) -> Box<dyn Widget<T>>;
@@ -27,6 +29,10 @@ pub struct ViewSwitcher<T, U> {
     child_builder: Box<ChildBuilder<T, U>>,
     active_child: Option<WidgetPod<T, Box<dyn Widget<T>>>>,
     active_child_id: Option<U>,
+    /// The child that is going out, while a transition runs.
+    outgoing_child: Option<(WidgetPod<T, Box<dyn Widget<T>>>, U)>,
+    transition: Transition,
+    progress: TransitionProgress,
 }
 
 impl<T: Data, U: Data> ViewSwitcher<T, U> {
@@ -48,8 +54,24 @@ impl<T: Data, U: Data> ViewSwitcher<T, U> {
             child_builder: Box::new(child_builder),
             active_child: None,
             active_child_id: None,
+            outgoing_child: None,
+            transition: Transition::Instant,
+            progress: TransitionProgress::new(),
         }
     }
+
+    /// Builder-style method for setting how the change between views is animated.
+    ///
+    /// While the transition runs, the old child is kept alive, laid out and
+    /// painted, but only the new child gets mouse and keyboard events. If the
+    /// `child_picker` goes back to the old child during the transition, the old
+    /// child is kept and the transition is reversed from where it was.
+    ///
+    /// The default is [`Transition::Instant`].
+    pub fn with_transition(mut self, transition: Transition) -> Self {
+        self.transition = transition;
+        self
+    }
 }
 
 impl<T: Data, U: Data> Widget<T> for ViewSwitcher<T, U> {
@@ -62,6 +84,24 @@ impl<T: Data, U: Data> Widget<T> for ViewSwitcher<T, U> {
         if let Some(child) = self.active_child.as_mut() {
             child.event(ctx, event, data, env);
         }
+        if event.should_propagate_to_hidden() {
+            if let Some((child, _)) = self.outgoing_child.as_mut() {
+                child.event(ctx, event, data, env);
+            }
+        }
+
+        if let Event::AnimFrame(interval) = event {
+            if self.outgoing_child.is_some() {
+                if self.progress.advance(self.transition, *interval) {
+                    ctx.request_anim_frame();
+                } else {
+                    trace!("Transition done");
+                    self.outgoing_child = None;
+                    ctx.children_changed();
+                }
+                ctx.request_paint();
+            }
+        }
     }
 
     #[instrument(
@@ -78,6 +118,11 @@ impl<T: Data, U: Data> Widget<T> for ViewSwitcher<T, U> {
         if let Some(child) = self.active_child.as_mut() {
             child.lifecycle(ctx, event, data, env);
         }
+        if event.should_propagate_to_hidden() {
+            if let Some((child, _)) = self.outgoing_child.as_mut() {
+                child.lifecycle(ctx, event, data, env);
+            }
+        }
     }
 
     #[instrument(
@@ -89,17 +134,46 @@ impl<T: Data, U: Data> Widget<T> for ViewSwitcher<T, U> {
         let child_id = (self.child_picker)(data, env);
         // Safe to unwrap because self.active_child_id should not be empty
         if !child_id.same(self.active_child_id.as_ref().unwrap()) {
-            self.active_child = Some(WidgetPod::new((self.child_builder)(&child_id, data, env)));
+            // going back to the child that is going out reverses the transition
+            let reversing = self.progress.is_animating()
+                && matches!(&self.outgoing_child, Some((_, id)) if id.same(&child_id));
+            if !reversing {
+                self.progress = TransitionProgress::new();
+            }
+            let animating = self.progress.start(self.transition);
+            if animating {
+                ctx.request_anim_frame();
+            }
+
+            let previous = self.active_child.take().zip(self.active_child_id.take());
+            let (child, child_id) = match self.outgoing_child.take() {
+                Some(outgoing) if reversing => outgoing,
+                _ => (
+                    WidgetPod::new((self.child_builder)(&child_id, data, env)),
+                    child_id,
+                ),
+            };
+            self.active_child = Some(child);
             self.active_child_id = Some(child_id);
+            self.outgoing_child = if animating { previous } else { None };
             ctx.children_changed();
-        // Because the new child has not yet been initialized, we have to skip the update after switching.
-        } else if let Some(child) = self.active_child.as_mut() {
-            child.update(ctx, data, env);
+        }
+
+        // Because a new child has not yet been initialized, we have to skip its update.
+        let outgoing = self.outgoing_child.as_mut().map(|(child, _)| child);
+        for child in self.active_child.iter_mut().chain(outgoing) {
+            if child.is_initialized() {
+                child.update(ctx, data, env);
+            }
         }
     }
 
     #[instrument(name = "ViewSwitcher", level = "trace", skip(self, ctx, bc, data, env))]
     fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        if let Some((child, _)) = self.outgoing_child.as_mut() {
+            child.layout(ctx, bc, data, env);
+            child.set_origin(ctx, data, env, Point::ORIGIN);
+        }
         match self.active_child {
             Some(ref mut child) => {
                 let size = child.layout(ctx, bc, data, env);
@@ -112,8 +186,13 @@ impl<T: Data, U: Data> Widget<T> for ViewSwitcher<T, U> {
 
     #[instrument(name = "ViewSwitcher", level = "trace", skip(self, ctx, data, env))]
     fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
-        if let Some(ref mut child) = self.active_child {
-            child.paint_raw(ctx, data, env);
+        match (&mut self.outgoing_child, &mut self.active_child) {
+            (Some((outgoing, _)), Some(child)) => {
+                self.progress
+                    .paint(self.transition, ctx, outgoing, child, data, env);
+            }
+            (_, Some(child)) => child.paint_raw(ctx, data, env),
+            _ => {}
         }
     }
 }
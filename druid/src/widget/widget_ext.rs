@@ -14,14 +14,19 @@
 
 //! Convenience methods for widgets.
 
+use std::path::PathBuf;
+use std::time::Duration;
+
 use super::invalidation::DebugInvalidation;
 use super::{
-    Added, Align, BackgroundBrush, Click, Container, Controller, ControllerHost, EnvScope,
-    IdentityWrapper, LensWrap, Padding, Parse, SizedBox, WidgetId,
+    Added, Align, AspectRatioBox, BackgroundBrush, Click, Container, ContextMenuController,
+    Controller, ControllerHost, DoubleClick, Drag, DragEvent, EnvScope, FileDrop, IdentityWrapper,
+    LabelText, LensWrap, LongPress, Padding, Parse, SizedBox, TabIndex, Tooltip, WidgetId,
 };
 use crate::widget::{DisabledIf, Scroll};
 use crate::{
-    Color, Data, Env, EventCtx, Insets, KeyOrValue, Lens, LifeCycleCtx, UnitPoint, Widget,
+    Color, Data, Env, EventCtx, Insets, KeyOrValue, Lens, LifeCycleCtx, Menu, UnitPoint, Vec2,
+    Widget,
 };
 
 /// A trait that provides extra methods for combining `Widget`s.
@@ -93,6 +98,18 @@ pub trait WidgetExt<T: Data>: Widget<T> + Sized + 'static {
         SizedBox::new(self).width(width).height(height)
     }
 
+    /// Wrap this widget in an [`AspectRatioBox`] with the given ratio of width
+    /// to height.
+    ///
+    /// The widget is as large as the constraints allow while keeping the ratio;
+    /// for example `aspect_ratio(16.0 / 9.0)` makes a widget as wide as it can
+    /// be, and 9/16 as tall.
+    ///
+    /// [`AspectRatioBox`]: widget/struct.AspectRatioBox.html
+    fn aspect_ratio(self, ratio: f64) -> AspectRatioBox<T> {
+        AspectRatioBox::new(self, ratio)
+    }
+
     /// Wrap this widget in a [`SizedBox`] with an infinite width and height.
     ///
     /// Only call this method if you want your widget to occupy all available
@@ -149,9 +166,27 @@ pub trait WidgetExt<T: Data>: Widget<T> + Sized + 'static {
         Container::new(self).border(color, width)
     }
 
+    /// Wrap this widget in a [`Container`] that paints a drop shadow behind it.
+    ///
+    /// See [`Container::shadow`] for details.
+    ///
+    /// [`Container`]: crate::widget::Container
+    /// [`Container::shadow`]: crate::widget::Container::shadow
+    fn shadow(
+        self,
+        radius: f64,
+        offset: impl Into<Vec2>,
+        color: impl Into<KeyOrValue<Color>>,
+    ) -> Container<T> {
+        Container::new(self).shadow(radius, offset, color)
+    }
+
     /// Wrap this widget in a [`EnvScope`] widget, modifying the parent
     /// [`Env`] with the provided closure.
     ///
+    /// The closure also gets the data, and runs again whenever the data
+    /// changes, so the environment of this widget can depend on its data.
+    ///
     /// [`EnvScope`]: widget/struct.EnvScope.html
     /// [`Env`]: struct.Env.html
     fn env_scope(self, f: impl Fn(&mut Env, &T) + 'static) -> EnvScope<T, Self> {
@@ -165,6 +200,32 @@ pub trait WidgetExt<T: Data>: Widget<T> + Sized + 'static {
         ControllerHost::new(self, controller)
     }
 
+    /// Show a context menu when this widget is right-clicked.
+    ///
+    /// The closure builds the menu from this widget's data each time the menu is
+    /// opened. The menu's data type must be the application's data type. See
+    /// [`ContextMenuController`] for details.
+    ///
+    /// [`ContextMenuController`]: widget/struct.ContextMenuController.html
+    fn context_menu<U: Data>(
+        self,
+        make_menu: impl Fn(&T, &Env) -> Menu<U> + 'static,
+    ) -> ControllerHost<Self, ContextMenuController<T, U>> {
+        ControllerHost::new(self, ContextMenuController::new(make_menu))
+    }
+
+    /// Show a tooltip when the mouse rests on this widget.
+    ///
+    /// The text can be a string, a [`LocalizedString`], or a closure over the
+    /// data and the [`Env`]. See [`Tooltip`] for details.
+    ///
+    /// [`LocalizedString`]: struct.LocalizedString.html
+    /// [`Env`]: struct.Env.html
+    /// [`Tooltip`]: widget/struct.Tooltip.html
+    fn tooltip(self, text: impl Into<LabelText<T>>) -> ControllerHost<Self, Tooltip<T>> {
+        ControllerHost::new(self, Tooltip::new(text))
+    }
+
     /// Provide a closure that will be called when this widget is added to the widget tree.
     ///
     /// You can use this to perform any initial setup.
@@ -197,6 +258,56 @@ pub trait WidgetExt<T: Data>: Widget<T> + Sized + 'static {
         ControllerHost::new(self, Click::new(f))
     }
 
+    /// Control the events of this widget with a [`FileDrop`] controller. The
+    /// closure provided will be called with the paths of the files dropped on
+    /// this widget, unless a descendant handles the drop first.
+    ///
+    /// Dropping files is not supported on every platform; see
+    /// [`Event::DropHover`].
+    ///
+    /// [`FileDrop`]: crate::widget::FileDrop
+    /// [`Event::DropHover`]: crate::Event::DropHover
+    fn on_file_drop(
+        self,
+        f: impl Fn(&mut EventCtx, &[PathBuf], &mut T, &Env) + 'static,
+    ) -> ControllerHost<Self, FileDrop<T>> {
+        ControllerHost::new(self, FileDrop::new(f))
+    }
+
+    /// Provide a closure to be called when this widget is double clicked.
+    ///
+    /// See [`DoubleClick`] for how a double click is recognized.
+    fn on_double_click(
+        self,
+        f: impl Fn(&mut EventCtx, &mut T, &Env) + 'static,
+    ) -> ControllerHost<Self, DoubleClick<T>> {
+        ControllerHost::new(self, DoubleClick::new(f))
+    }
+
+    /// Provide a closure to be called when the mouse is held down on this
+    /// widget for `duration`.
+    ///
+    /// See [`LongPress`] for what cancels a long press.
+    fn on_long_press(
+        self,
+        duration: Duration,
+        f: impl Fn(&mut EventCtx, &mut T, &Env) + 'static,
+    ) -> ControllerHost<Self, LongPress<T>> {
+        ControllerHost::new(self, LongPress::new(duration, f))
+    }
+
+    /// Provide a closure to be called as this widget is dragged with the
+    /// mouse.
+    ///
+    /// The closure gets a [`DragEvent`] for the start, each move and the end
+    /// of the drag; see [`Drag`] for details.
+    fn on_drag(
+        self,
+        f: impl Fn(&mut EventCtx, DragEvent, &mut T, &Env) + 'static,
+    ) -> ControllerHost<Self, Drag<T>> {
+        ControllerHost::new(self, Drag::new(f))
+    }
+
     /// Draw the [`layout`] `Rect`s of  this widget and its children.
     ///
     /// [`layout`]: trait.Widget.html#tymethod.layout
@@ -285,6 +396,17 @@ pub trait WidgetExt<T: Data>: Widget<T> + Sized + 'static {
     fn disabled_if(self, disabled_if: impl Fn(&T, &Env) -> bool + 'static) -> DisabledIf<T, Self> {
         DisabledIf::new(self, disabled_if)
     }
+
+    /// Wrap this widget in a [`TabIndex`], changing where it and the focusable
+    /// widgets inside it come in the order Tab moves focus in.
+    ///
+    /// See [`LifeCycleCtx::set_tab_index`] for how the order is determined.
+    ///
+    /// [`TabIndex`]: crate::widget::TabIndex
+    /// [`LifeCycleCtx::set_tab_index`]: crate::LifeCycleCtx::set_tab_index
+    fn tab_index(self, index: i32) -> TabIndex<Self> {
+        TabIndex::new(self, index)
+    }
 }
 
 impl<T: Data, W: Widget<T> + 'static> WidgetExt<T> for W {}
@@ -0,0 +1,278 @@
+// Copyright 2021 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A widget that places its children in lines, wrapping them as needed.
+
+use tracing::{instrument, trace};
+
+use crate::widget::prelude::*;
+use crate::widget::{Axis, CrossAxisAlignment};
+use crate::{Insets, KeyOrValue, Point, Rect, WidgetPod};
+
+/// A container that places its children next to each other, and starts a new
+/// line whenever the next child doesn't fit, like words in a paragraph.
+///
+/// Children are laid out at their natural size. A [`row`] fills lines from left
+/// to right, and stacks them from top to bottom; a [`column`] fills lines from
+/// top to bottom, and stacks them from left to right.
+///
+/// A child that is larger than the available space gets a line of its own. By
+/// default it overflows the container; use [`clip_overflow`] to cut it off
+/// instead.
+///
+/// # Examples
+///
+/// ```
+/// use druid::widget::{CrossAxisAlignment, Label, Wrap};
+///
+/// let tags = Wrap::row()
+///     .spacing(4.0)
+///     .run_spacing(4.0)
+///     .cross_axis_alignment(CrossAxisAlignment::Center)
+///     .with_child(Label::new("rust"))
+///     .with_child(Label::new("gui"))
+///     .with_child(Label::new("druid"));
+/// # let _: Wrap<()> = tags;
+/// ```
+///
+/// [`row`]: Wrap::row
+/// [`column`]: Wrap::column
+/// [`clip_overflow`]: Wrap::clip_overflow
+pub struct Wrap<T> {
+    direction: Axis,
+    spacing: KeyOrValue<f64>,
+    run_spacing: KeyOrValue<f64>,
+    cross_alignment: CrossAxisAlignment,
+    clip_overflow: bool,
+    children: Vec<WidgetPod<T, Box<dyn Widget<T>>>>,
+}
+
+impl<T: Data> Wrap<T> {
+    /// Create a new `Wrap` whose lines run along the provided axis.
+    pub fn for_axis(axis: Axis) -> Self {
+        Wrap {
+            direction: axis,
+            spacing: KeyOrValue::Concrete(0.0),
+            run_spacing: KeyOrValue::Concrete(0.0),
+            cross_alignment: CrossAxisAlignment::Start,
+            clip_overflow: false,
+            children: Vec::new(),
+        }
+    }
+
+    /// Create a new `Wrap` with horizontal lines.
+    ///
+    /// The children are laid out from left to right, and lines from top to bottom.
+    pub fn row() -> Self {
+        Self::for_axis(Axis::Horizontal)
+    }
+
+    /// Create a new `Wrap` with vertical lines.
+    ///
+    /// The children are laid out from top to bottom, and lines from left to right.
+    pub fn column() -> Self {
+        Self::for_axis(Axis::Vertical)
+    }
+
+    /// Builder-style method for setting the space between adjacent children in
+    /// a line.
+    ///
+    /// The default value is `0.0`.
+    pub fn spacing(mut self, spacing: impl Into<KeyOrValue<f64>>) -> Self {
+        self.set_spacing(spacing);
+        self
+    }
+
+    /// Builder-style method for setting the space between lines.
+    ///
+    /// The default value is `0.0`.
+    pub fn run_spacing(mut self, spacing: impl Into<KeyOrValue<f64>>) -> Self {
+        self.set_run_spacing(spacing);
+        self
+    }
+
+    /// Builder-style method for specifying how children are aligned within
+    /// their line.
+    ///
+    /// Lines are as thick as their thickest child. The default is
+    /// [`CrossAxisAlignment::Start`].
+    pub fn cross_axis_alignment(mut self, alignment: CrossAxisAlignment) -> Self {
+        self.cross_alignment = alignment;
+        self
+    }
+
+    /// Builder-style method for setting whether children that are larger than
+    /// the container are cut off at its edges.
+    ///
+    /// The default value is `false`.
+    pub fn clip_overflow(mut self, clip: bool) -> Self {
+        self.clip_overflow = clip;
+        self
+    }
+
+    /// Builder-style variant of `add_child`.
+    pub fn with_child(mut self, child: impl Widget<T> + 'static) -> Self {
+        self.add_child(child);
+        self
+    }
+
+    /// Set the space between adjacent children in a line.
+    pub fn set_spacing(&mut self, spacing: impl Into<KeyOrValue<f64>>) {
+        self.spacing = spacing.into();
+    }
+
+    /// Set the space between lines.
+    pub fn set_run_spacing(&mut self, spacing: impl Into<KeyOrValue<f64>>) {
+        self.run_spacing = spacing.into();
+    }
+
+    /// Set how children are aligned within their line.
+    pub fn set_cross_axis_alignment(&mut self, alignment: CrossAxisAlignment) {
+        self.cross_alignment = alignment;
+    }
+
+    /// Set whether children that are larger than the container are cut off at
+    /// its edges.
+    pub fn set_clip_overflow(&mut self, clip: bool) {
+        self.clip_overflow = clip;
+    }
+
+    /// Add a child widget.
+    ///
+    /// See also [`with_child`].
+    ///
+    /// [`with_child`]: Wrap::with_child
+    pub fn add_child(&mut self, child: impl Widget<T> + 'static) {
+        self.children.push(WidgetPod::new(Box::new(child)));
+    }
+}
+
+impl<T: Data> Widget<T> for Wrap<T> {
+    #[instrument(name = "Wrap", level = "trace", skip(self, ctx, event, data, env))]
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        for child in &mut self.children {
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    #[instrument(name = "Wrap", level = "trace", skip(self, ctx, event, data, env))]
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        for child in &mut self.children {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    #[instrument(name = "Wrap", level = "trace", skip(self, ctx, _old_data, data, env))]
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        for child in &mut self.children {
+            child.update(ctx, data, env);
+        }
+    }
+
+    #[instrument(name = "Wrap", level = "trace", skip(self, ctx, bc, data, env))]
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        bc.debug_check("Wrap");
+        let axis = self.direction;
+        let spacing = self.spacing.resolve(env);
+        let run_spacing = self.run_spacing.resolve(env);
+        let max_major = axis.major(bc.max());
+        let child_bc = axis.constraints(&bc.loosen(), 0.0, f64::INFINITY);
+
+        // measure the children, and break them into lines of indices
+        let mut lines: Vec<Vec<usize>> = Vec::new();
+        let mut line_major = 0.0;
+        let mut major = axis.major(bc.min());
+        for (i, child) in self.children.iter_mut().enumerate() {
+            let child_major = axis.major(child.layout(ctx, &child_bc, data, env));
+            match lines.last_mut() {
+                Some(line) if line_major + spacing + child_major <= max_major => {
+                    line_major += spacing + child_major;
+                    line.push(i);
+                }
+                _ => {
+                    lines.push(vec![i]);
+                    line_major = child_major;
+                }
+            }
+            major = major.max(line_major);
+        }
+
+        // baselines only line up across a row
+        let use_baseline =
+            self.cross_alignment == CrossAxisAlignment::Baseline && axis == Axis::Horizontal;
+        let mut minor = 0.0;
+        let mut paint_rect = Rect::ZERO;
+        for line in &lines {
+            let line_minor = line
+                .iter()
+                .map(|i| axis.minor(self.children[*i].layout_rect().size()))
+                .fold(0.0, f64::max);
+            let max_above_baseline = line
+                .iter()
+                .map(|i| {
+                    let child = &self.children[*i];
+                    child.layout_rect().height() - child.baseline_offset()
+                })
+                .fold(0.0, f64::max);
+
+            let mut major_pos = 0.0;
+            for i in line {
+                let child = &mut self.children[*i];
+                let child_size = child.layout_rect().size();
+                let child_minor_offset = match self.cross_alignment {
+                    _ if use_baseline => {
+                        max_above_baseline - (child_size.height - child.baseline_offset())
+                    }
+                    CrossAxisAlignment::Fill => {
+                        let fill_size: Size = axis.pack(axis.major(child_size), line_minor).into();
+                        child.layout(ctx, &BoxConstraints::tight(fill_size), data, env);
+                        0.0
+                    }
+                    alignment => alignment.align(line_minor - axis.minor(child_size)),
+                };
+                let child_pos: Point = axis.pack(major_pos, minor + child_minor_offset).into();
+                child.set_origin(ctx, data, env, child_pos);
+                paint_rect = paint_rect.union(child.paint_rect());
+                major_pos += axis.major(child_size) + spacing;
+            }
+            minor += line_minor + run_spacing;
+        }
+        // correct overshoot at end.
+        let minor = (minor - run_spacing).max(0.0);
+
+        let my_size = bc.constrain(Size::from(axis.pack(major, minor)));
+        if self.clip_overflow {
+            ctx.set_paint_insets(Insets::ZERO);
+        } else {
+            ctx.set_paint_insets(paint_rect - my_size.to_rect());
+        }
+        trace!("Computed layout: size={}", my_size);
+        my_size
+    }
+
+    #[instrument(name = "Wrap", level = "trace", skip(self, ctx, data, env))]
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let clip_rect = ctx.size().to_rect();
+        let clip_overflow = self.clip_overflow;
+        let children = &mut self.children;
+        ctx.with_save(|ctx| {
+            if clip_overflow {
+                ctx.clip(clip_rect);
+            }
+            for child in children {
+                child.paint(ctx, data, env);
+            }
+        });
+    }
+}
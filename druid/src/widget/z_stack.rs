@@ -0,0 +1,219 @@
+// Copyright 2021 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A widget that stacks its children on top of each other.
+
+use tracing::{instrument, trace};
+
+use crate::widget::prelude::*;
+use crate::{Data, InternalEvent, Point, Rect, UnitPoint, WidgetPod};
+
+/// A widget that stacks its children on top of each other.
+///
+/// The first child is the base layer: it gets the constraints of the `ZStack`
+/// and determines its size. Every other layer is laid out with loose
+/// constraints up to that size, and is positioned within it according to its
+/// alignment.
+///
+/// Layers are painted in order of increasing z-index, and mouse events are
+/// hit-tested in the reverse order: once a layer is under the mouse, the
+/// layers below it don't get the mouse, unless they are [active]. Layers with
+/// the same z-index are painted in the order they were added, so by default
+/// every layer covers the ones added before it.
+///
+/// Layers are still clipped by the ancestors of the `ZStack`, such as a
+/// [`Scroll`]. A layer that needs to draw outside of that clip has to opt out
+/// explicitly, using [`PaintCtx::paint_with_z_index`].
+///
+/// # Examples
+///
+/// ```
+/// use druid::widget::{Label, SizedBox, ZStack};
+/// use druid::{Color, UnitPoint, WidgetExt};
+///
+/// let icon = SizedBox::empty()
+///     .fix_size(48., 48.)
+///     .background(Color::grey(0.5));
+/// let badge = ZStack::new(icon).with_child(
+///     Label::new("3").background(Color::rgb8(0xd0, 0x30, 0x30)),
+///     UnitPoint::TOP_RIGHT,
+/// );
+/// # let _: ZStack<()> = badge;
+/// ```
+///
+/// [active]: crate::EventCtx::is_active
+/// [`Scroll`]: super::Scroll
+pub struct ZStack<T> {
+    layers: Vec<ZLayer<T>>,
+}
+
+struct ZLayer<T> {
+    child: WidgetPod<T, Box<dyn Widget<T>>>,
+    alignment: UnitPoint,
+    z_index: i32,
+}
+
+impl<T: Data> ZStack<T> {
+    /// Create a new `ZStack` with the given base layer.
+    ///
+    /// The base layer has a z-index of `0`.
+    pub fn new(base_layer: impl Widget<T> + 'static) -> Self {
+        ZStack {
+            layers: vec![ZLayer {
+                child: WidgetPod::new(Box::new(base_layer)),
+                alignment: UnitPoint::TOP_LEFT,
+                z_index: 0,
+            }],
+        }
+    }
+
+    /// Builder-style variant of [`add_child`].
+    ///
+    /// [`add_child`]: ZStack::add_child
+    pub fn with_child(mut self, child: impl Widget<T> + 'static, alignment: UnitPoint) -> Self {
+        self.add_child(child, alignment);
+        self
+    }
+
+    /// Builder-style method to add a layer centered on the base layer.
+    pub fn with_centered_child(self, child: impl Widget<T> + 'static) -> Self {
+        self.with_child(child, UnitPoint::CENTER)
+    }
+
+    /// Builder-style variant of [`add_z_indexed_child`].
+    ///
+    /// [`add_z_indexed_child`]: ZStack::add_z_indexed_child
+    pub fn with_z_indexed_child(
+        mut self,
+        child: impl Widget<T> + 'static,
+        alignment: UnitPoint,
+        z_index: i32,
+    ) -> Self {
+        self.add_z_indexed_child(child, alignment, z_index);
+        self
+    }
+
+    /// Add a layer with a z-index of `0`, positioned with `alignment`.
+    ///
+    /// It is painted above the layers that were added before it.
+    pub fn add_child(&mut self, child: impl Widget<T> + 'static, alignment: UnitPoint) {
+        self.add_z_indexed_child(child, alignment, 0);
+    }
+
+    /// Add a layer with the given z-index, positioned with `alignment`.
+    pub fn add_z_indexed_child(
+        &mut self,
+        child: impl Widget<T> + 'static,
+        alignment: UnitPoint,
+        z_index: i32,
+    ) {
+        self.layers.push(ZLayer {
+            child: WidgetPod::new(Box::new(child)),
+            alignment,
+            z_index,
+        });
+    }
+
+    /// Set the z-index of the layer at `idx`, where the base layer is `0` and
+    /// the other layers are numbered in the order they were added.
+    ///
+    /// This can be used to raise a layer above its siblings at runtime; the
+    /// caller is responsible for requesting a paint.
+    pub fn set_z_index(&mut self, idx: usize, z_index: i32) {
+        match self.layers.get_mut(idx) {
+            Some(layer) => layer.z_index = z_index,
+            None => debug_panic!("ZStack has no layer {}", idx),
+        }
+    }
+
+    /// The indices of the layers, from the bottom to the top.
+    fn paint_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.layers.len()).collect();
+        // a stable sort, so layers with equal z-index keep their order
+        order.sort_by_key(|&idx| self.layers[idx].z_index);
+        order
+    }
+}
+
+impl<T: Data> Widget<T> for ZStack<T> {
+    #[instrument(name = "ZStack", level = "trace", skip(self, ctx, event, data, env))]
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        let is_mouse_event = matches!(
+            event,
+            Event::MouseDown(_) | Event::MouseUp(_) | Event::MouseMove(_) | Event::Wheel(_)
+        );
+        let mut covered = false;
+        for idx in self.paint_order().into_iter().rev() {
+            let child = &mut self.layers[idx].child;
+            if covered && !child.is_active() {
+                // the mouse is over a layer above this one
+                child.event(ctx, &Event::Internal(InternalEvent::MouseLeave), data, env);
+            } else {
+                child.event(ctx, event, data, env);
+                covered |= is_mouse_event && child.is_hot();
+            }
+        }
+    }
+
+    #[instrument(name = "ZStack", level = "trace", skip(self, ctx, event, data, env))]
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        for layer in &mut self.layers {
+            layer.child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    #[instrument(
+        name = "ZStack",
+        level = "trace",
+        skip(self, ctx, _old_data, data, env)
+    )]
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        for layer in &mut self.layers {
+            layer.child.update(ctx, data, env);
+        }
+    }
+
+    #[instrument(name = "ZStack", level = "trace", skip(self, ctx, bc, data, env))]
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        bc.debug_check("ZStack");
+
+        let (base, layers) = self.layers.split_first_mut().unwrap();
+        let size = base.child.layout(ctx, bc, data, env);
+        base.child.set_origin(ctx, data, env, Point::ORIGIN);
+
+        let layer_bc = BoxConstraints::new(Size::ZERO, size);
+        let mut paint_rect = size.to_rect();
+        for layer in layers {
+            let child_size = layer.child.layout(ctx, &layer_bc, data, env);
+            let extra = Rect::from_origin_size(Point::ORIGIN, size - child_size);
+            let origin = layer.alignment.resolve(extra).expand();
+            layer.child.set_origin(ctx, data, env, origin);
+            paint_rect = paint_rect.union(layer.child.paint_rect());
+        }
+        paint_rect = paint_rect.union(base.child.paint_rect());
+
+        let insets = paint_rect - size.to_rect();
+        ctx.set_paint_insets(insets);
+        ctx.set_baseline_offset(base.child.baseline_offset());
+        trace!("Computed layout: size={}, insets={:?}", size, insets);
+        size
+    }
+
+    #[instrument(name = "ZStack", level = "trace", skip(self, ctx, data, env))]
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        for idx in self.paint_order() {
+            self.layers[idx].child.paint(ctx, data, env);
+        }
+    }
+}
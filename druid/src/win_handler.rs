@@ -17,13 +17,14 @@
 use std::any::{Any, TypeId};
 use std::cell::RefCell;
 use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
 use std::rc::Rc;
 
-use crate::kurbo::Size;
+use crate::kurbo::{Point, Size};
 use crate::piet::Piet;
 use crate::shell::{
-    text::InputHandler, Application, FileDialogToken, FileInfo, IdleToken, MouseEvent, Region,
-    Scale, TextFieldToken, WinHandler, WindowHandle,
+    text::InputHandler, Appearance, Application, FileDialogToken, FileInfo, IdleToken, MouseEvent,
+    Region, Scale, TextFieldToken, WinHandler, WindowHandle, WindowState,
 };
 
 use crate::app_delegate::{AppDelegate, DelegateCtx};
@@ -32,11 +33,11 @@ use crate::ext_event::{ExtEventHost, ExtEventSink};
 use crate::menu::{ContextMenu, MenuItemId, MenuManager};
 use crate::window::{ImeUpdateFn, Window};
 use crate::{
-    Command, Data, Env, Event, Handled, InternalEvent, KeyEvent, PlatformError, Selector, Target,
-    TimerToken, WidgetId, WindowDesc, WindowId,
+    Command, Data, Env, Event, Handled, InternalEvent, KeyEvent, Menu, PlatformError, Selector,
+    Target, TimerToken, WidgetId, WindowDesc, WindowId,
 };
 
-use crate::app::{PendingWindow, WindowConfig};
+use crate::app::{EnvBuilder, PendingWindow, WindowConfig};
 use crate::command::sys as sys_cmd;
 use druid_shell::WindowBuilder;
 
@@ -98,9 +99,12 @@ struct Inner<T> {
     /// is the window that's currently in charge of the app menu.
     #[allow(unused_variables)]
     menu_window: Option<WindowId>,
+    env_builder: EnvBuilder<T>,
     pub(crate) env: Env,
     pub(crate) data: T,
     ime_focus_change: Option<Box<dyn Fn()>>,
+    /// Whether to quit when the last window is closed.
+    quit_on_last_window_closed: bool,
 }
 
 /// All active windows.
@@ -154,9 +158,10 @@ impl<T> AppState<T> {
     pub(crate) fn new(
         app: Application,
         data: T,
-        env: Env,
+        env_builder: EnvBuilder<T>,
         delegate: Option<Box<dyn AppDelegate<T>>>,
         ext_event_host: ExtEventHost,
+        quit_on_last_window_closed: bool,
     ) -> Self {
         let inner = Rc::new(RefCell::new(Inner {
             app,
@@ -166,10 +171,12 @@ impl<T> AppState<T> {
             root_menu: None,
             menu_window: None,
             ext_event_host,
+            env: env_builder.build(&data),
+            env_builder,
             data,
-            env,
             windows: Windows::default(),
             ime_focus_change: None,
+            quit_on_last_window_closed,
         }));
 
         AppState { inner }
@@ -264,8 +271,7 @@ impl<T: Data> Inner<T> {
                 // on mac we need to keep the menu around
                 self.root_menu = win.menu.take();
                 // If there are even no pending windows, we quit the run loop.
-                if self.windows.count() == 0 {
-                    #[cfg(any(target_os = "windows", feature = "x11"))]
+                if self.windows.count() == 0 && self.quit_on_last_window_closed {
                     self.app.quit();
                 }
             }
@@ -328,6 +334,18 @@ impl<T: Data> Inner<T> {
         }
     }
 
+    fn set_position(&mut self, position: Point, id: WindowId) {
+        if let Some(win) = self.windows.get_mut(id) {
+            win.handle.set_position(position);
+        }
+    }
+
+    fn set_window_state(&mut self, state: WindowState, id: WindowId) {
+        if let Some(win) = self.windows.get_mut(id) {
+            win.handle.set_window_state(state);
+        }
+    }
+
     fn prepare_paint(&mut self, window_id: WindowId) {
         if let Some(win) = self.windows.get_mut(window_id) {
             win.prepare_paint(&mut self.command_queue, &mut self.data, &self.env);
@@ -360,23 +378,38 @@ impl<T: Data> Inner<T> {
                     self.show_context_menu(id, &cmd);
                     return Handled::Yes;
                 }
+                if cmd.is(sys_cmd::SET_MENU) {
+                    self.set_menu(id, &cmd);
+                    return Handled::Yes;
+                }
+                if cmd.is(sys_cmd::CLOSE_WINDOW) && self.windows.get_mut(id).is_some() {
+                    // the delegate can veto the close by swallowing the request
+                    let event = match self.delegate_event(id, Event::WindowCloseRequested) {
+                        Some(event) => event,
+                        None => return Handled::Yes,
+                    };
+                    let w = self.windows.get_mut(id).unwrap();
+                    let handled =
+                        w.event(&mut self.command_queue, event, &mut self.data, &self.env);
+                    if !handled.is_handled() {
+                        w.event(
+                            &mut self.command_queue,
+                            Event::WindowDisconnected,
+                            &mut self.data,
+                            &self.env,
+                        );
+                    }
+                    return handled;
+                }
                 if let Some(w) = self.windows.get_mut(id) {
-                    return if cmd.is(sys_cmd::CLOSE_WINDOW) {
-                        let handled = w.event(
+                    return if cmd.is(sys_cmd::FORCE_CLOSE_WINDOW) {
+                        w.event(
                             &mut self.command_queue,
-                            Event::WindowCloseRequested,
+                            Event::WindowDisconnected,
                             &mut self.data,
                             &self.env,
                         );
-                        if !handled.is_handled() {
-                            w.event(
-                                &mut self.command_queue,
-                                Event::WindowDisconnected,
-                                &mut self.data,
-                                &self.env,
-                            );
-                        }
-                        handled
+                        Handled::No
                     } else {
                         w.event(
                             &mut self.command_queue,
@@ -390,7 +423,9 @@ impl<T: Data> Inner<T> {
             // in this case we send it to every window that might contain
             // this widget, breaking if the event is handled.
             Target::Widget(id) => {
+                let mut any_window = false;
                 for w in self.windows.iter_mut().filter(|w| w.may_contain_widget(id)) {
+                    any_window = true;
                     let event = Event::Internal(InternalEvent::TargetedCommand(cmd.clone()));
                     if w.event(&mut self.command_queue, event, &mut self.data, &self.env)
                         .is_handled()
@@ -398,6 +433,9 @@ impl<T: Data> Inner<T> {
                         return Handled::Yes;
                     }
                 }
+                if !any_window {
+                    tracing::debug!("{:?} targets a widget that doesn't exist, dropping it", cmd);
+                }
             }
             Target::Global => {
                 for w in self.windows.iter_mut() {
@@ -455,6 +493,63 @@ impl<T: Data> Inner<T> {
         }
     }
 
+    fn set_menu(&mut self, window_id: WindowId, cmd: &Command) {
+        if let Some(win) = self.windows.get_mut(window_id) {
+            match cmd
+                .get_unchecked(sys_cmd::SET_MENU)
+                .take()
+                .and_then(|b| b.downcast::<Menu<T>>().ok())
+            {
+                Some(menu) => win.set_menu(*menu, &self.data, &self.env),
+                None => panic!(
+                    "{} command must carry a Menu<application state>.",
+                    sys_cmd::SET_MENU
+                ),
+            }
+
+            #[cfg(target_os = "macos")]
+            {
+                // a window that didn't have a menu may already be in front
+                if self.menu_window.is_none() || self.menu_window == Some(window_id) {
+                    self.menu_window = Some(window_id);
+                    win.macos_update_app_menu(&self.data, &self.env);
+                }
+            }
+        }
+    }
+
+    fn set_appearance(&mut self, cmd: &Command) {
+        let appearance = *cmd.get_unchecked(sys_cmd::SET_APPEARANCE);
+        self.env_builder.set_appearance(appearance);
+        self.rebuild_env();
+    }
+
+    fn set_locale(&mut self, cmd: &Command) {
+        let locale = cmd.get_unchecked(sys_cmd::SET_LOCALE);
+        if self.env_builder.set_locale(locale.as_deref()) {
+            self.rebuild_env();
+        }
+    }
+
+    /// Returns `true` if the environment was built again.
+    fn system_appearance_changed(&mut self, appearance: Appearance) -> bool {
+        let changed = self.env_builder.system_appearance_changed(appearance);
+        if changed {
+            self.rebuild_env();
+        }
+        changed
+    }
+
+    /// Builds the root `Env` again. The next `update` tells the widgets about
+    /// the changes.
+    fn rebuild_env(&mut self) {
+        self.env = self.env_builder.build(&self.data);
+        // most widgets read their colors from the `Env` when they paint
+        for window in self.windows.iter_mut() {
+            window.handle.invalidate();
+        }
+    }
+
     fn do_update(&mut self) {
         // we send `update` to all windows, not just the active one:
         for window in self.windows.iter_mut() {
@@ -571,6 +666,16 @@ impl<T: Data> AppState<T> {
         self.inner.borrow_mut().window_got_focus(window_id)
     }
 
+    fn appearance_changed(&mut self, appearance: Appearance) {
+        if self
+            .inner
+            .borrow_mut()
+            .system_appearance_changed(appearance)
+        {
+            self.inner.borrow_mut().do_update();
+        }
+    }
+
     /// Send an event to the widget hierarchy.
     ///
     /// Returns `true` if the event produced an action.
@@ -665,22 +770,39 @@ impl<T: Data> AppState<T> {
                 }
             }
             _ if cmd.is(sys_cmd::CLOSE_ALL_WINDOWS) => self.request_close_all_windows(),
+            _ if cmd.is(sys_cmd::SET_APPEARANCE) => self.inner.borrow_mut().set_appearance(&cmd),
+            _ if cmd.is(sys_cmd::SET_LOCALE) => self.inner.borrow_mut().set_locale(&cmd),
             T::Window(id) if cmd.is(sys_cmd::INVALIDATE_IME) => self.invalidate_ime(cmd, id),
             // these should come from a window
             // FIXME: we need to be able to open a file without a window handle
             T::Window(id) if cmd.is(sys_cmd::SHOW_OPEN_PANEL) => self.show_open_panel(cmd, id),
             T::Window(id) if cmd.is(sys_cmd::SHOW_SAVE_PANEL) => self.show_save_panel(cmd, id),
             T::Window(id) if cmd.is(sys_cmd::CONFIGURE_WINDOW) => self.configure_window(cmd, id),
+            T::Window(id) if cmd.is(sys_cmd::SET_POSITION) => self.set_position(cmd, id),
+            T::Window(id) if cmd.is(sys_cmd::SET_WINDOW_STATE) => self.set_window_state(cmd, id),
             T::Window(id) if cmd.is(sys_cmd::CLOSE_WINDOW) => {
                 if !self.inner.borrow_mut().dispatch_cmd(cmd).is_handled() {
                     self.request_close_window(id);
                 }
             }
+            T::Window(id) if cmd.is(sys_cmd::FORCE_CLOSE_WINDOW) => {
+                self.inner.borrow_mut().dispatch_cmd(cmd);
+                self.request_close_window(id);
+            }
             T::Window(id) if cmd.is(sys_cmd::SHOW_WINDOW) => self.show_window(id),
             T::Window(id) if cmd.is(sys_cmd::PASTE) => self.do_paste(id),
             _ if cmd.is(sys_cmd::CLOSE_WINDOW) => {
                 tracing::warn!("CLOSE_WINDOW command must target a window.")
             }
+            _ if cmd.is(sys_cmd::FORCE_CLOSE_WINDOW) => {
+                tracing::warn!("FORCE_CLOSE_WINDOW command must target a window.")
+            }
+            _ if cmd.is(sys_cmd::SET_POSITION) => {
+                tracing::warn!("SET_POSITION command must target a window.")
+            }
+            _ if cmd.is(sys_cmd::SET_WINDOW_STATE) => {
+                tracing::warn!("SET_WINDOW_STATE command must target a window.")
+            }
             _ if cmd.is(sys_cmd::SHOW_WINDOW) => {
                 tracing::warn!("SHOW_WINDOW command must target a window.")
             }
@@ -810,6 +932,18 @@ impl<T: Data> AppState<T> {
         }
     }
 
+    fn set_position(&mut self, cmd: Command, id: WindowId) {
+        if let Some(position) = cmd.get(sys_cmd::SET_POSITION) {
+            self.inner.borrow_mut().set_position(*position, id);
+        }
+    }
+
+    fn set_window_state(&mut self, cmd: Command, id: WindowId) {
+        if let Some(state) = cmd.get(sys_cmd::SET_WINDOW_STATE) {
+            self.inner.borrow_mut().set_window_state(*state, id);
+        }
+    }
+
     fn do_paste(&mut self, window_id: WindowId) {
         let event = Event::Paste(self.inner.borrow().app.clipboard());
         self.inner.borrow_mut().do_window_event(window_id, event);
@@ -905,8 +1039,13 @@ impl<T: Data> WinHandler for DruidHandler<T> {
         self.app_state.do_window_event(event, self.window_id);
     }
 
-    fn scale(&mut self, _scale: Scale) {
-        // TODO: Do something with the scale
+    fn scale(&mut self, scale: Scale) {
+        let event = Event::WindowScale(scale);
+        self.app_state.do_window_event(event, self.window_id);
+    }
+
+    fn appearance_changed(&mut self, appearance: Appearance) {
+        self.app_state.appearance_changed(appearance);
     }
 
     fn command(&mut self, id: u32) {
@@ -942,6 +1081,21 @@ impl<T: Data> WinHandler for DruidHandler<T> {
             .do_window_event(Event::Internal(InternalEvent::MouseLeave), self.window_id);
     }
 
+    fn file_drop_hover(&mut self, position: Point, paths: &[PathBuf]) {
+        let event = Event::DropHover(position, paths.to_vec());
+        self.app_state.do_window_event(event, self.window_id);
+    }
+
+    fn file_drop_cancelled(&mut self) {
+        self.app_state
+            .do_window_event(Event::DropCancelled, self.window_id);
+    }
+
+    fn file_dropped(&mut self, position: Point, paths: Vec<PathBuf>) {
+        let event = Event::Dropped(position, paths);
+        self.app_state.do_window_event(event, self.window_id);
+    }
+
     fn key_down(&mut self, event: KeyEvent) -> bool {
         self.app_state
             .do_window_event(Event::KeyDown(event), self.window_id)
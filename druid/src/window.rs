@@ -16,26 +16,29 @@
 
 use std::collections::{HashMap, VecDeque};
 use std::mem;
-use tracing::{error, info, info_span};
+use tracing::{debug, error, info, info_span};
 
 // Automatically defaults to std::time::Instant on non Wasm platforms
 use instant::Instant;
 
 use crate::piet::{Color, Piet, RenderContext};
-use crate::shell::{text::InputHandler, Counter, Cursor, Region, TextFieldToken, WindowHandle};
+use crate::shell::{
+    text::InputHandler, Counter, Cursor, HotKey, Region, TextFieldToken, WindowHandle,
+};
 
 use crate::app::{PendingWindow, WindowSizePolicy};
+use crate::command::sys as sys_cmd;
 use crate::contexts::ContextState;
-use crate::core::{CommandQueue, FocusChange, WidgetState};
+use crate::core::{CommandQueue, FocusChange, WidgetState, WidgetTimer};
 use crate::menu::{MenuItemId, MenuManager};
 use crate::text::TextFieldRegistration;
 use crate::util::ExtendDrain;
 use crate::widget::LabelText;
 use crate::win_handler::RUN_COMMANDS_TOKEN;
 use crate::{
-    BoxConstraints, Data, Env, Event, EventCtx, ExtEventSink, Handled, InternalEvent,
-    InternalLifeCycle, LayoutCtx, LifeCycle, LifeCycleCtx, Menu, PaintCtx, Point, Size, TimerToken,
-    UpdateCtx, Widget, WidgetId, WidgetPod,
+    BoxConstraints, Command, Data, Env, Event, EventCtx, ExtEventSink, Handled, InternalEvent,
+    InternalLifeCycle, LayoutCtx, LifeCycle, LifeCycleCtx, Menu, PaintCtx, Point, Size, Target,
+    TimerToken, UpdateCtx, Widget, WidgetId, WidgetPod,
 };
 
 pub type ImeUpdateFn = dyn FnOnce(crate::shell::text::Event);
@@ -57,9 +60,13 @@ pub struct Window<T> {
     // This will be `Some` whenever the most recently displayed frame was an animation frame.
     pub(crate) last_anim: Option<Instant>,
     pub(crate) last_mouse_pos: Option<Point>,
+    // The cursor that we last set on the window, if we know what it is.
+    cursor: Option<Cursor>,
     pub(crate) focus: Option<WidgetId>,
+    focus_wraps: bool,
+    pub(crate) hotkeys: Vec<(HotKey, Command)>,
     pub(crate) handle: WindowHandle,
-    pub(crate) timers: HashMap<TimerToken, WidgetId>,
+    pub(crate) timers: HashMap<TimerToken, WidgetTimer>,
     pub(crate) transparent: bool,
     pub(crate) ime_handlers: Vec<(TextFieldToken, TextFieldRegistration)>,
     ext_handle: ExtEventSink,
@@ -85,7 +92,10 @@ impl<T> Window<T> {
             context_menu: None,
             last_anim: None,
             last_mouse_pos: None,
+            cursor: None,
             focus: None,
+            focus_wraps: pending.focus_wraps,
+            hotkeys: pending.hotkeys,
             handle,
             timers: HashMap::new(),
             ext_handle,
@@ -135,6 +145,18 @@ impl<T: Data> Window<T> {
         self.context_menu = Some((manager, point));
     }
 
+    /// Replaces the menu of this window.
+    pub(crate) fn set_menu(&mut self, menu: Menu<T>, data: &T, env: &Env) {
+        let mut manager = MenuManager::new_static(menu);
+        let platform_menu = manager.initialize(Some(self.id), data, env);
+        self.menu = Some(manager);
+        // on macOS the menu belongs to the app, and the handler decides
+        // which window's menu is shown.
+        if cfg!(not(target_os = "macos")) {
+            self.handle.set_menu(platform_menu);
+        }
+    }
+
     /// On macos we need to update the global application menu to be the menu
     /// for the current window.
     #[cfg(target_os = "macos")]
@@ -152,9 +174,10 @@ impl<T: Data> Window<T> {
         env: &Env,
         process_commands: bool,
     ) {
+        let children_changed = widget_state.children_changed;
         // If children are changed during the handling of an event,
         // we need to send RouteWidgetAdded now, so that they are ready for update/layout.
-        if widget_state.children_changed {
+        if children_changed {
             // Anytime widgets are removed we check and see if any of those
             // widgets had IME sessions and unregister them if so.
             let Window {
@@ -201,8 +224,15 @@ impl<T: Data> Window<T> {
 
         self.update_focus(widget_state, queue, data, env);
 
-        // Add all the requested timers to the window's timers map.
+        // Add all the requested timers to the window's timers map, and remove
+        // the cancelled ones and those of widgets that were removed, so that
+        // intervals don't keep firing for them.
         self.timers.extend_drain(&mut widget_state.timers);
+        if children_changed || !widget_state.cancelled_timers.is_empty() {
+            let cancelled = mem::take(&mut widget_state.cancelled_timers);
+            self.timers
+                .retain(|_, timer| timer.is_alive() && !cancelled.contains(&timer.token));
+        }
 
         // If we need a new paint pass, make sure druid-shell knows it.
         if self.wants_animation_frame() {
@@ -243,15 +273,30 @@ impl<T: Data> Window<T> {
             _ => (),
         }
 
+        if let Event::KeyDown(key) = &event {
+            if let Some((_, cmd)) = self.hotkeys.iter().find(|(hotkey, _)| hotkey.matches(key)) {
+                queue.push_back(cmd.clone().default_to(Target::Window(self.id)));
+                return Handled::Yes;
+            }
+        }
+
         let event = match event {
-            Event::Timer(token) => {
-                if let Some(widget_id) = self.timers.get(&token) {
-                    Event::Internal(InternalEvent::RouteTimer(token, *widget_id))
-                } else {
-                    error!("No widget found for timer {:?}", token);
+            // Remove the timer before the event is handled, because the
+            // platform may reuse its token for a timer requested below.
+            Event::Timer(token) => match self.timers.remove(&token) {
+                Some(timer) if timer.is_alive() => {
+                    if let Some(period) = timer.interval {
+                        let next = self.handle.request_timer(period);
+                        self.timers.insert(next, timer);
+                    }
+                    Event::Internal(InternalEvent::RouteTimer(timer.token, timer.widget_id))
+                }
+                _ => {
+                    // the timer was cancelled, or its widget was removed.
+                    debug!("No widget found for timer {:?}", token);
                     return Handled::No;
                 }
-            }
+            },
             other => other,
         };
 
@@ -293,19 +338,21 @@ impl<T: Data> Window<T> {
             Handled::from(ctx.is_handled)
         };
 
-        // Clean up the timer token and do it immediately after the event handling
-        // because the token may be reused and re-added in a lifecycle pass below.
-        if let Event::Internal(InternalEvent::RouteTimer(token, _)) = event {
-            self.timers.remove(&token);
+        if let Event::Internal(InternalEvent::TargetedCommand(cmd)) = &event {
+            if let (true, Target::Widget(id)) = (cmd.is(sys_cmd::FOCUS_WIDGET), cmd.target()) {
+                widget_state.request_focus = Some(FocusChange::Focus(id));
+            }
         }
 
         if let Some(cursor) = &widget_state.cursor {
-            self.handle.set_cursor(cursor);
-        } else if matches!(
-            event,
-            Event::MouseMove(..) | Event::Internal(InternalEvent::MouseLeave)
-        ) {
+            self.set_cursor(cursor);
+        } else if matches!(event, Event::MouseMove(..)) {
+            self.set_cursor(&Cursor::Arrow);
+        } else if matches!(event, Event::Internal(InternalEvent::MouseLeave)) {
             self.handle.set_cursor(&Cursor::Arrow);
+            // Outside the window the cursor is up to the platform, so we
+            // can't know what it is when the mouse comes back.
+            self.cursor = None;
         }
 
         if matches!(
@@ -367,12 +414,23 @@ impl<T: Data> Window<T> {
         }
 
         if let Some(cursor) = &widget_state.cursor {
-            self.handle.set_cursor(cursor);
+            self.set_cursor(cursor);
         }
 
         self.post_event_processing(&mut widget_state, queue, data, env, false);
     }
 
+    /// Sets the cursor on the window, unless it is already showing it.
+    ///
+    /// Every mouse move recomputes the cursor, so setting it again each time
+    /// would cause needless work, and flicker on some platforms.
+    fn set_cursor(&mut self, cursor: &Cursor) {
+        if self.cursor.as_ref() != Some(cursor) {
+            self.handle.set_cursor(cursor);
+            self.cursor = Some(cursor.clone());
+        }
+    }
+
     pub(crate) fn invalidate_and_finalize(&mut self) {
         if self.root.state().needs_layout {
             self.handle.invalidate();
@@ -634,32 +692,45 @@ impl<T: Data> Window<T> {
         }
     }
 
+    /// The focus chain, ordered by tab index.
+    fn tab_order(&self) -> Vec<WidgetId> {
+        let tab_indices = &self.root.state().tab_indices;
+        let mut chain = self.focus_chain().to_vec();
+        // the sort is stable, so widgets with the same index stay in tree order.
+        chain.sort_by_key(|id| tab_indices.get(id).copied().unwrap_or(0));
+        chain
+    }
+
     fn widget_from_focus_chain(&self, forward: bool) -> Option<WidgetId> {
-        self.focus.and_then(|focus| {
-            self.focus_chain()
-                .iter()
-                // Find where the focused widget is in the focus chain
-                .position(|id| id == &focus)
-                .map(|idx| {
-                    // Return the id that's next to it in the focus chain
-                    let len = self.focus_chain().len();
-                    let new_idx = if forward {
-                        (idx + 1) % len
-                    } else {
-                        (idx + len - 1) % len
-                    };
-                    self.focus_chain()[new_idx]
-                })
-                .or_else(|| {
-                    // If the currently focused widget isn't in the focus chain,
-                    // then we'll just return the first/last entry of the chain, if any.
-                    if forward {
-                        self.focus_chain().first().copied()
-                    } else {
-                        self.focus_chain().last().copied()
-                    }
-                })
-        })
+        let chain = self.tab_order();
+        self.focus
+            .and_then(|focus| {
+                chain
+                    .iter()
+                    // Find where the focused widget is in the focus chain
+                    .position(|id| id == &focus)
+                    .map(|idx| {
+                        // Return the id that's next to it in the focus chain,
+                        // staying put at the ends if focus doesn't wrap.
+                        let len = chain.len();
+                        let new_idx = match (forward, self.focus_wraps) {
+                            (true, true) => (idx + 1) % len,
+                            (true, false) => (idx + 1).min(len - 1),
+                            (false, true) => (idx + len - 1) % len,
+                            (false, false) => idx.saturating_sub(1),
+                        };
+                        chain[new_idx]
+                    })
+            })
+            .or_else(|| {
+                // If no widget is focused, or the focused widget isn't in the focus
+                // chain, then we'll just return the first/last entry of the chain, if any.
+                if forward {
+                    chain.first().copied()
+                } else {
+                    chain.last().copied()
+                }
+            })
     }
 }
 